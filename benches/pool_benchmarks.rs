@@ -10,8 +10,8 @@ use web3_rpc_pool::{RpcPool, RpcPoolConfig};
 fn create_test_endpoints(count: usize) -> Vec<RpcEndpoint> {
     (0..count)
         .map(|i| {
-            RpcEndpoint::new(&format!("https://rpc{}.example.com", i))
-                .with_name(&format!("RPC {}", i))
+            RpcEndpoint::new(format!("https://rpc{}.example.com", i))
+                .with_name(format!("RPC {}", i))
                 .with_priority((i * 10) as u32)
                 .with_chain_id(chain_id::ETHEREUM)
         })
@@ -64,7 +64,7 @@ fn bench_pool_creation(c: &mut Criterion) {
                 b.iter(|| {
                     let config = RpcPoolConfig::new()
                         .with_endpoints(endpoints.clone())
-                        .with_strategy(Box::new(LatencyBasedStrategy))
+                        .with_strategy(Box::new(LatencyBasedStrategy::new()))
                         .with_health_check_interval(Duration::from_secs(60))
                         .with_max_consecutive_errors(3)
                         .with_retry_delay(Duration::from_secs(5));
@@ -2,18 +2,17 @@
 
 use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
 use std::collections::{HashMap, HashSet};
-use std::time::Instant;
 use web3_rpc_pool::endpoint::{EndpointStats, RpcEndpoint};
 use web3_rpc_pool::presets::chain_id;
 use web3_rpc_pool::strategies::{
-    FailoverStrategy, LatencyBasedStrategy, RoundRobinStrategy, SelectionStrategy,
+    FailoverStrategy, LatencyBasedStrategy, RoundRobinStrategy, SelectionContext, SelectionStrategy,
 };
 
 fn create_test_endpoints(count: usize) -> Vec<RpcEndpoint> {
     (0..count)
         .map(|i| {
-            RpcEndpoint::new(&format!("https://rpc{}.example.com", i))
-                .with_name(&format!("RPC {}", i))
+            RpcEndpoint::new(format!("https://rpc{}.example.com", i))
+                .with_name(format!("RPC {}", i))
                 .with_priority((i * 10) as u32)
                 .with_chain_id(chain_id::ETHEREUM)
         })
@@ -44,6 +43,7 @@ fn bench_failover_strategy(c: &mut Criterion) {
         let endpoints = create_test_endpoints(endpoint_count);
         let stats = create_test_stats(&endpoints, 0.8);
         let tried = HashSet::new();
+        let ctx = SelectionContext::default();
 
         group.throughput(Throughput::Elements(1));
         group.bench_with_input(
@@ -52,7 +52,7 @@ fn bench_failover_strategy(c: &mut Criterion) {
             |b, _| {
                 let mut strategy = FailoverStrategy;
                 b.iter(|| {
-                    black_box(strategy.select(&endpoints, &stats, &tried));
+                    black_box(strategy.select(&endpoints, &stats, &tried, &ctx));
                 });
             },
         );
@@ -68,6 +68,7 @@ fn bench_round_robin_strategy(c: &mut Criterion) {
         let endpoints = create_test_endpoints(endpoint_count);
         let stats = create_test_stats(&endpoints, 0.8);
         let tried = HashSet::new();
+        let ctx = SelectionContext::default();
 
         group.throughput(Throughput::Elements(1));
         group.bench_with_input(
@@ -76,7 +77,7 @@ fn bench_round_robin_strategy(c: &mut Criterion) {
             |b, _| {
                 let mut strategy = RoundRobinStrategy::new();
                 b.iter(|| {
-                    black_box(strategy.select(&endpoints, &stats, &tried));
+                    black_box(strategy.select(&endpoints, &stats, &tried, &ctx));
                 });
             },
         );
@@ -92,15 +93,16 @@ fn bench_latency_based_strategy(c: &mut Criterion) {
         let endpoints = create_test_endpoints(endpoint_count);
         let stats = create_test_stats(&endpoints, 0.8);
         let tried = HashSet::new();
+        let ctx = SelectionContext::default();
 
         group.throughput(Throughput::Elements(1));
         group.bench_with_input(
             BenchmarkId::new("select", endpoint_count),
             &endpoint_count,
             |b, _| {
-                let mut strategy = LatencyBasedStrategy;
+                let mut strategy = LatencyBasedStrategy::new();
                 b.iter(|| {
-                    black_box(strategy.select(&endpoints, &stats, &tried));
+                    black_box(strategy.select(&endpoints, &stats, &tried, &ctx));
                 });
             },
         );
@@ -113,6 +115,7 @@ fn bench_strategy_with_exclusions(c: &mut Criterion) {
     let mut group = c.benchmark_group("strategy_with_exclusions");
     let endpoints = create_test_endpoints(20);
     let stats = create_test_stats(&endpoints, 1.0);
+    let ctx = SelectionContext::default();
 
     for exclusion_count in [0, 5, 10, 15, 19] {
         let tried: HashSet<String> = endpoints
@@ -127,7 +130,7 @@ fn bench_strategy_with_exclusions(c: &mut Criterion) {
             |b, _| {
                 let mut strategy = FailoverStrategy;
                 b.iter(|| {
-                    black_box(strategy.select(&endpoints, &stats, &tried));
+                    black_box(strategy.select(&endpoints, &stats, &tried, &ctx));
                 });
             },
         );
@@ -136,9 +139,9 @@ fn bench_strategy_with_exclusions(c: &mut Criterion) {
             BenchmarkId::new("latency_based", exclusion_count),
             &exclusion_count,
             |b, _| {
-                let mut strategy = LatencyBasedStrategy;
+                let mut strategy = LatencyBasedStrategy::new();
                 b.iter(|| {
-                    black_box(strategy.select(&endpoints, &stats, &tried));
+                    black_box(strategy.select(&endpoints, &stats, &tried, &ctx));
                 });
             },
         );
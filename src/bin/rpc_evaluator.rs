@@ -275,13 +275,25 @@ async fn evaluate_endpoint(
         Some(0)
     };
 
+    // Step 5: eth_call with a state override set (balance override on a dummy account)
+    let supports_state_override = {
+        let params = serde_json::json!([
+            { "to": "0x00000000000000000000000000000000000000ff", "data": "0x" },
+            "latest",
+            { "0x00000000000000000000000000000000000000ff": { "balance": "0x1" } },
+        ]);
+        rpc_call(client, url, "eth_call", params).await.is_ok()
+    };
+
     let capabilities = EndpointCapabilities {
         supports_eth_get_logs: Some(supports_logs),
         max_batch_size,
         max_block_range,
         supports_debug_trace: None,
+        supports_eth_call_state_override: Some(supports_state_override),
         supports_websocket: false,
         rate_limit_rps: None,
+        is_starknet: false,
     };
 
     let grade = capabilities.grade();
@@ -300,10 +312,10 @@ async fn evaluate_endpoint(
 
 fn print_table(report: &EvaluationReport) {
     println!(
-        "\n{:<25} {:<6} {:<8} {:<10} {:<8} {:<10} {:<12}",
-        "Name", "Grade", "Reach", "Latency", "Logs", "Batch", "BlockRange"
+        "\n{:<25} {:<6} {:<8} {:<10} {:<8} {:<10} {:<12} {:<8}",
+        "Name", "Grade", "Reach", "Latency", "Logs", "Batch", "BlockRange", "Override"
     );
-    println!("{}", "-".repeat(85));
+    println!("{}", "-".repeat(94));
 
     let mut current_chain = 0u64;
     for ep in &report.endpoints {
@@ -347,17 +359,24 @@ fn print_table(report: &EvaluationReport) {
             })
             .unwrap_or_else(|| "?".to_string());
 
+        let state_override = ep
+            .capabilities
+            .supports_eth_call_state_override
+            .map(|v| if v { "yes" } else { "no" })
+            .unwrap_or("?");
+
         let reach = if ep.reachable { "OK" } else { "FAIL" };
 
         println!(
-            "{:<25} {:<6} {:<8} {:<10} {:<8} {:<10} {:<12}",
+            "{:<25} {:<6} {:<8} {:<10} {:<8} {:<10} {:<12} {:<8}",
             &ep.name[..ep.name.len().min(24)],
             ep.grade,
             reach,
             latency,
             logs,
             batch,
-            range
+            range,
+            state_override
         );
     }
 
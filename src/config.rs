@@ -0,0 +1,432 @@
+//! Loading pool configuration from TOML or JSON files.
+//!
+//! The file format mirrors [`RpcPoolConfig`], with the strategy given by name
+//! and all durations in milliseconds:
+//!
+//! ```toml
+//! strategy = "latency-based"
+//! request_timeout_ms = 10000
+//! max_consecutive_errors = 5
+//!
+//! [[endpoints]]
+//! url = "https://ethereum-rpc.publicnode.com"
+//! name = "PublicNode"
+//! priority = 10
+//! chain_id = 1
+//! ```
+//!
+//! [`TieredPoolConfigFile`] describes a [`TieredPool`](crate::TieredPool) the
+//! same way, with one array of endpoint tables per tier.
+
+use crate::endpoint::RpcEndpoint;
+use crate::error::RpcPoolError;
+use crate::pool::RpcPoolConfig;
+use crate::strategies::StrategyKind;
+use crate::tiered::TieredPoolConfig;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::time::Duration;
+use tracing::debug;
+
+/// Whether `path` names a TOML file; everything else is treated as JSON.
+fn is_toml(path: &Path) -> bool {
+    path.extension()
+        .map(|ext| ext.eq_ignore_ascii_case("toml"))
+        .unwrap_or(false)
+}
+
+/// Read and parse a config file, choosing the format by extension.
+fn read_config_file<T: DeserializeOwned>(path: &Path) -> Result<T, RpcPoolError> {
+    let contents = std::fs::read_to_string(path).map_err(|e| {
+        RpcPoolError::ConfigParseError(format!("Failed to read {}: {}", path.display(), e))
+    })?;
+
+    let parsed = if is_toml(path) {
+        toml::from_str(&contents).map_err(|e| e.to_string())
+    } else {
+        serde_json::from_str(&contents).map_err(|e| e.to_string())
+    };
+    parsed.map_err(|msg| RpcPoolError::ConfigParseError(format!("{}: {}", path.display(), msg)))
+}
+
+/// Serialize `config` and write it to `path`, choosing the format by extension.
+fn write_config_file<T: Serialize>(config: &T, path: &Path) -> Result<(), RpcPoolError> {
+    let contents = if is_toml(path) {
+        toml::to_string(config).map_err(|e| e.to_string())
+    } else {
+        serde_json::to_string_pretty(config).map_err(|e| e.to_string())
+    }
+    .map_err(RpcPoolError::ConfigWriteError)?;
+
+    std::fs::write(path, contents).map_err(|e| {
+        RpcPoolError::ConfigWriteError(format!("Failed to write {}: {}", path.display(), e))
+    })
+}
+
+/// Serializable form of [`RpcPoolConfig`].
+///
+/// Missing fields fall back to the [`RpcPoolConfig`] defaults.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RpcPoolFileConfig {
+    /// List of RPC endpoints.
+    pub endpoints: Vec<RpcEndpoint>,
+
+    /// Name of a built-in selection strategy.
+    pub strategy: StrategyKind,
+
+    /// Interval between health checks.
+    pub health_check_interval_ms: u64,
+
+    /// Number of consecutive errors before marking an endpoint unhealthy.
+    pub max_consecutive_errors: u32,
+
+    /// Delay before retrying an unhealthy endpoint.
+    pub retry_delay_ms: u64,
+
+    /// Timeout for individual RPC requests.
+    pub request_timeout_ms: u64,
+
+    /// Timeout for health check probes.
+    pub health_check_timeout_ms: u64,
+
+    /// Maximum tolerated block lag behind the best known block.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_block_lag: Option<u64>,
+}
+
+impl Default for RpcPoolFileConfig {
+    fn default() -> Self {
+        let defaults = RpcPoolConfig::default();
+        Self {
+            endpoints: vec![],
+            strategy: StrategyKind::default(),
+            health_check_interval_ms: defaults.health_check_interval.as_millis() as u64,
+            max_consecutive_errors: defaults.max_consecutive_errors,
+            retry_delay_ms: defaults.retry_delay.as_millis() as u64,
+            request_timeout_ms: defaults.request_timeout.as_millis() as u64,
+            health_check_timeout_ms: defaults.health_check_timeout.as_millis() as u64,
+            max_block_lag: defaults.max_block_lag,
+        }
+    }
+}
+
+impl RpcPoolFileConfig {
+    /// Read a config file, choosing the format by extension (`.toml`, otherwise JSON).
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, RpcPoolError> {
+        let path = path.as_ref();
+        let config: Self = read_config_file(path)?;
+
+        debug!(
+            path = %path.display(),
+            endpoints = config.endpoints.len(),
+            strategy = ?config.strategy,
+            "Loaded RPC pool config file"
+        );
+        Ok(config)
+    }
+
+    /// Parse a TOML document.
+    pub fn from_toml_str(s: &str) -> Result<Self, RpcPoolError> {
+        toml::from_str(s).map_err(|e| RpcPoolError::ConfigParseError(e.to_string()))
+    }
+
+    /// Parse a JSON document.
+    pub fn from_json_str(s: &str) -> Result<Self, RpcPoolError> {
+        serde_json::from_str(s).map_err(|e| RpcPoolError::ConfigParseError(e.to_string()))
+    }
+}
+
+impl From<RpcPoolFileConfig> for RpcPoolConfig {
+    fn from(file: RpcPoolFileConfig) -> Self {
+        let config = RpcPoolConfig::new()
+            .with_endpoints(file.endpoints)
+            .with_strategy_factory(move || file.strategy.build())
+            .with_health_check_interval(Duration::from_millis(file.health_check_interval_ms))
+            .with_max_consecutive_errors(file.max_consecutive_errors)
+            .with_retry_delay(Duration::from_millis(file.retry_delay_ms))
+            .with_request_timeout(Duration::from_millis(file.request_timeout_ms))
+            .with_health_check_timeout(Duration::from_millis(file.health_check_timeout_ms));
+
+        match file.max_block_lag {
+            Some(blocks) => config.with_max_block_lag(blocks),
+            None => config,
+        }
+    }
+}
+
+/// Endpoint table in one of the tier arrays of a [`TieredPoolConfigFile`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TieredEndpointFileConfig {
+    /// The endpoint, with the same fields as [`RpcPoolFileConfig::endpoints`].
+    #[serde(flatten)]
+    pub endpoint: RpcEndpoint,
+
+    /// Rate limit (requests per second), 0 = unlimited.
+    #[serde(default, skip_serializing_if = "is_unlimited")]
+    pub rate_limit: u32,
+}
+
+fn is_unlimited(rate_limit: &u32) -> bool {
+    *rate_limit == 0
+}
+
+/// Serializable form of a [`TieredPool`](crate::TieredPool)'s endpoints and settings.
+///
+/// Each tier is an array of endpoint tables, and durations are in
+/// milliseconds. Missing fields fall back to the [`TieredPoolConfig`] defaults.
+///
+/// ```toml
+/// max_consecutive_errors = 5
+///
+/// [[premium]]
+/// url = "https://eth-mainnet.g.alchemy.com/v2/<key>"
+/// name = "Alchemy"
+/// priority = 10
+/// rate_limit = 25
+///
+/// [[free]]
+/// url = "https://ethereum-rpc.publicnode.com"
+/// name = "PublicNode"
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TieredPoolConfigFile {
+    /// Interval between health checks.
+    pub health_check_interval_ms: u64,
+
+    /// Number of consecutive errors before marking an endpoint unhealthy.
+    pub max_consecutive_errors: u32,
+
+    /// Delay before retrying an unhealthy endpoint.
+    pub retry_delay_ms: u64,
+
+    /// Whether critical requests may fall back to lower tiers.
+    pub allow_critical_fallback: bool,
+
+    /// Whether low priority requests may escalate to higher tiers.
+    pub allow_low_escalation: bool,
+
+    /// Whether premium endpoints may use plain `http://`.
+    pub allow_insecure_premium: bool,
+
+    /// Premium tier endpoints.
+    pub premium: Vec<TieredEndpointFileConfig>,
+
+    /// Standard tier endpoints.
+    pub standard: Vec<TieredEndpointFileConfig>,
+
+    /// Free tier endpoints.
+    pub free: Vec<TieredEndpointFileConfig>,
+
+    /// Shadow tier endpoints.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub shadow: Vec<TieredEndpointFileConfig>,
+}
+
+impl Default for TieredPoolConfigFile {
+    fn default() -> Self {
+        let defaults = TieredPoolConfig::default();
+        Self {
+            health_check_interval_ms: defaults.health_check_interval.as_millis() as u64,
+            max_consecutive_errors: defaults.max_consecutive_errors,
+            retry_delay_ms: defaults.retry_delay.as_millis() as u64,
+            allow_critical_fallback: defaults.allow_critical_fallback,
+            allow_low_escalation: defaults.allow_low_escalation,
+            allow_insecure_premium: false,
+            premium: vec![],
+            standard: vec![],
+            free: vec![],
+            shadow: vec![],
+        }
+    }
+}
+
+impl TieredPoolConfigFile {
+    /// Read a config file, choosing the format by extension (`.toml`, otherwise JSON).
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, RpcPoolError> {
+        let path = path.as_ref();
+        let config: Self = read_config_file(path)?;
+
+        debug!(
+            path = %path.display(),
+            premium = config.premium.len(),
+            standard = config.standard.len(),
+            free = config.free.len(),
+            shadow = config.shadow.len(),
+            "Loaded tiered pool config file"
+        );
+        Ok(config)
+    }
+
+    /// Parse a TOML document.
+    pub fn from_toml_str(s: &str) -> Result<Self, RpcPoolError> {
+        toml::from_str(s).map_err(|e| RpcPoolError::ConfigParseError(e.to_string()))
+    }
+
+    /// Parse a JSON document.
+    pub fn from_json_str(s: &str) -> Result<Self, RpcPoolError> {
+        serde_json::from_str(s).map_err(|e| RpcPoolError::ConfigParseError(e.to_string()))
+    }
+
+    /// Write the config to a file, choosing the format by extension (`.toml`, otherwise JSON).
+    pub fn to_file(&self, path: impl AsRef<Path>) -> Result<(), RpcPoolError> {
+        write_config_file(self, path.as_ref())
+    }
+}
+
+impl RpcPoolConfig {
+    /// Load a configuration from a TOML or JSON file.
+    ///
+    /// Files ending in `.toml` are parsed as TOML; anything else as JSON.
+    /// See [`RpcPoolFileConfig`] for the accepted fields.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, RpcPoolError> {
+        RpcPoolFileConfig::from_file(path).map(Into::into)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::endpoint::EndpointCapabilities;
+
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("web3-rpc-pool-{}-{}", std::process::id(), name))
+    }
+
+    fn sample_file_config() -> RpcPoolFileConfig {
+        RpcPoolFileConfig {
+            endpoints: vec![
+                RpcEndpoint::new("https://rpc1.example.com")
+                    .with_name("Primary")
+                    .with_ws_url("wss://rpc1.example.com")
+                    .with_priority(10)
+                    .with_chain_id(1)
+                    .with_capabilities(EndpointCapabilities {
+                        supports_eth_get_logs: Some(true),
+                        max_batch_size: Some(100),
+                        max_block_range: Some(10_000),
+                        supports_debug_trace: Some(false),
+                        supports_eth_call_state_override: Some(true),
+                        supports_websocket: true,
+                        rate_limit_rps: Some(25),
+                        is_starknet: false,
+                    }),
+                RpcEndpoint::new("https://rpc2.example.com")
+                    .with_name("Backup")
+                    .with_priority(50)
+                    .with_chain_id(1),
+            ],
+            strategy: StrategyKind::LatencyBased,
+            health_check_interval_ms: 30_000,
+            max_consecutive_errors: 5,
+            retry_delay_ms: 2_500,
+            request_timeout_ms: 10_000,
+            health_check_timeout_ms: 4_000,
+            max_block_lag: Some(20),
+        }
+    }
+
+    fn assert_matches_sample(config: &RpcPoolConfig, expected: &RpcPoolFileConfig) {
+        assert_eq!(config.endpoints, expected.endpoints);
+        assert_eq!(config.strategy.read().name(), "latency-based");
+        assert_eq!(config.health_check_interval, Duration::from_secs(30));
+        assert_eq!(config.max_consecutive_errors, 5);
+        assert_eq!(config.retry_delay, Duration::from_millis(2_500));
+        assert_eq!(config.request_timeout, Duration::from_secs(10));
+        assert_eq!(config.health_check_timeout, Duration::from_secs(4));
+        assert_eq!(config.max_block_lag, Some(20));
+    }
+
+    #[test]
+    fn test_json_roundtrip() {
+        let expected = sample_file_config();
+        let path = temp_path("config.json");
+        std::fs::write(&path, serde_json::to_string_pretty(&expected).unwrap()).unwrap();
+
+        assert_eq!(RpcPoolFileConfig::from_file(&path).unwrap(), expected);
+        let config = RpcPoolConfig::from_file(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_matches_sample(&config, &expected);
+    }
+
+    #[test]
+    fn test_toml_roundtrip() {
+        let expected = sample_file_config();
+        let path = temp_path("config.toml");
+        std::fs::write(&path, toml::to_string(&expected).unwrap()).unwrap();
+
+        assert_eq!(RpcPoolFileConfig::from_file(&path).unwrap(), expected);
+        let config = RpcPoolConfig::from_file(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_matches_sample(&config, &expected);
+    }
+
+    #[test]
+    fn test_defaults_for_missing_fields() {
+        let config = RpcPoolFileConfig::from_toml_str(
+            r#"
+            [[endpoints]]
+            url = "https://rpc.example.com"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.endpoints.len(), 1);
+        assert_eq!(config.strategy, StrategyKind::Failover);
+        assert_eq!(config.max_consecutive_errors, 3);
+        assert_eq!(config.request_timeout_ms, 30_000);
+    }
+
+    #[test]
+    fn test_strategy_names_match_kinds() {
+        let config =
+            RpcPoolFileConfig::from_json_str(r#"{"strategy": "strict-priority"}"#).unwrap();
+        assert_eq!(config.strategy, StrategyKind::StrictPriority);
+        assert_eq!(config.strategy.build().name(), "strict-priority");
+    }
+
+    #[test]
+    fn test_unknown_strategy_is_rejected() {
+        let result = RpcPoolFileConfig::from_json_str(r#"{"strategy": "fastest"}"#);
+        assert!(matches!(result, Err(RpcPoolError::ConfigParseError(_))));
+    }
+
+    #[test]
+    fn test_tiered_config_from_toml() {
+        let config = TieredPoolConfigFile::from_toml_str(
+            r#"
+            retry_delay_ms = 1000
+
+            [[premium]]
+            url = "https://premium.example.com"
+            name = "Premium"
+            priority = 10
+            rate_limit = 25
+
+            [[free]]
+            url = "https://free.example.com"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.premium.len(), 1);
+        assert_eq!(config.premium[0].endpoint.priority, 10);
+        assert_eq!(config.premium[0].rate_limit, 25);
+        assert_eq!(config.free[0].rate_limit, 0);
+        assert!(config.standard.is_empty());
+        assert_eq!(config.retry_delay_ms, 1_000);
+        assert_eq!(config.max_consecutive_errors, 3);
+        assert!(config.allow_critical_fallback);
+    }
+
+    #[test]
+    fn test_missing_file() {
+        let result = RpcPoolConfig::from_file(temp_path("does-not-exist.json"));
+        assert!(matches!(result, Err(RpcPoolError::ConfigParseError(_))));
+    }
+}
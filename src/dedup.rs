@@ -0,0 +1,134 @@
+//! In-flight request coalescing for `RpcPool::execute_deduped`.
+
+use crate::error::RpcPoolError;
+
+use futures_util::future::{BoxFuture, Shared};
+use std::any::Any;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// A request future that several callers can await.
+pub(crate) type SharedRequest<T> = Shared<BoxFuture<'static, Result<T, RpcPoolError>>>;
+
+/// An in-flight request, type-erased so requests of any output type share one map.
+struct InflightRequest {
+    id: u64,
+    started: Instant,
+    future: Box<dyn Any + Send + Sync>,
+}
+
+/// In-flight requests keyed by caller-supplied request keys.
+///
+/// A request can be joined for `ttl` after it started; later callers with the
+/// same key start a fresh request.
+pub(crate) struct InflightRequests {
+    ttl: Duration,
+    next_id: u64,
+    requests: HashMap<u64, InflightRequest>,
+}
+
+impl InflightRequests {
+    /// Create an empty map whose requests can be joined for `ttl`.
+    pub(crate) fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            next_id: 0,
+            requests: HashMap::new(),
+        }
+    }
+
+    /// The request in flight for `key`, if it is still joinable and has output `T`.
+    pub(crate) fn join<T: Send + Sync + 'static>(&self, key: u64) -> Option<SharedRequest<T>> {
+        self.requests
+            .get(&key)
+            .filter(|request| request.started.elapsed() < self.ttl)
+            .and_then(|request| request.future.downcast_ref::<SharedRequest<T>>())
+            .cloned()
+    }
+
+    /// Reserve the id for the next [`insert`](Self::insert).
+    pub(crate) fn next_id(&mut self) -> u64 {
+        self.next_id += 1;
+        self.next_id
+    }
+
+    /// Register `future` as the request for `key`, dropping expired requests.
+    pub(crate) fn insert<T: Send + Sync + 'static>(
+        &mut self,
+        key: u64,
+        id: u64,
+        future: SharedRequest<T>,
+    ) {
+        let ttl = self.ttl;
+        self.requests
+            .retain(|_, request| request.started.elapsed() < ttl);
+        self.requests.insert(
+            key,
+            InflightRequest {
+                id,
+                started: Instant::now(),
+                future: Box::new(future),
+            },
+        );
+    }
+
+    /// Remove the request for `key` if it is still the one registered as `id`.
+    pub(crate) fn remove(&mut self, key: u64, id: u64) {
+        if self
+            .requests
+            .get(&key)
+            .is_some_and(|request| request.id == id)
+        {
+            self.requests.remove(&key);
+        }
+    }
+
+    /// Number of registered requests, including expired ones not yet dropped.
+    pub(crate) fn len(&self) -> usize {
+        self.requests.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::FutureExt;
+
+    fn ready<T: Send + Sync + Clone + 'static>(value: T) -> SharedRequest<T> {
+        async move { Ok(value) }.boxed().shared()
+    }
+
+    #[tokio::test]
+    async fn test_join_matches_key_and_type() {
+        let mut inflight = InflightRequests::new(Duration::from_secs(60));
+        let id = inflight.next_id();
+        inflight.insert(1, id, ready(42u64));
+
+        let joined = inflight.join::<u64>(1).unwrap();
+        assert_eq!(joined.await.unwrap(), 42);
+        assert!(inflight.join::<u64>(2).is_none());
+        assert!(inflight.join::<String>(1).is_none());
+
+        // A stale id does not remove a newer request
+        let newer = inflight.next_id();
+        inflight.insert(1, newer, ready(7u64));
+        inflight.remove(1, id);
+        assert_eq!(inflight.len(), 1);
+        inflight.remove(1, newer);
+        assert_eq!(inflight.len(), 0);
+    }
+
+    #[test]
+    fn test_request_expires() {
+        let mut inflight = InflightRequests::new(Duration::from_millis(20));
+        let id = inflight.next_id();
+        inflight.insert(1, id, ready(42u64));
+
+        std::thread::sleep(Duration::from_millis(30));
+        assert!(inflight.join::<u64>(1).is_none());
+
+        let id = inflight.next_id();
+        inflight.insert(2, id, ready(7u64));
+        assert_eq!(inflight.len(), 1);
+    }
+}
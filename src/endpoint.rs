@@ -1,14 +1,22 @@
 //! RPC endpoint definitions and statistics tracking.
 
+use crate::error::RpcPoolError;
+use alloy::transports::http::reqwest;
+use rand::rngs::SmallRng;
+use rand::{Rng, SeedableRng};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
 use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
 use std::fmt;
-use std::time::Instant;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::warn;
 
 /// Capability metadata for an RPC endpoint.
 ///
 /// Tracks what features an endpoint supports, enabling quality-based
 /// endpoint selection and grading.
-#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct EndpointCapabilities {
     /// Whether the endpoint supports `eth_getLogs`. `None` = untested.
     pub supports_eth_get_logs: Option<bool>,
@@ -22,12 +30,21 @@ pub struct EndpointCapabilities {
     /// Whether the endpoint supports `debug_traceTransaction`. `None` = untested.
     pub supports_debug_trace: Option<bool>,
 
+    /// Whether `eth_call` accepts a state override set, as used by transaction
+    /// simulators and bundle builders. `None` = untested.
+    pub supports_eth_call_state_override: Option<bool>,
+
     /// Whether the endpoint supports WebSocket connections (derived from ws_url).
     #[serde(default)]
     pub supports_websocket: bool,
 
     /// Known rate limit in requests per second. `None` = unknown.
     pub rate_limit_rps: Option<u32>,
+
+    /// Whether the endpoint serves the Starknet JSON-RPC API (`starknet_*`
+    /// methods) rather than the Ethereum one.
+    #[serde(default)]
+    pub is_starknet: bool,
 }
 
 impl EndpointCapabilities {
@@ -77,10 +94,19 @@ impl EndpointCapabilities {
     /// - Grade C: 0
     /// - Grade D: +10
     /// - Grade F: +50 (lowest priority)
+    ///
+    /// Grade A and B endpoints supporting `eth_call` state overrides are
+    /// promoted by half a grade (a further -5), so they rank between their
+    /// grade and the next one up.
     pub fn priority_adjustment(&self) -> i32 {
+        let state_override_bonus = if self.supports_eth_call_state_override == Some(true) {
+            -5
+        } else {
+            0
+        };
         match self.grade() {
-            EndpointGrade::A => -20,
-            EndpointGrade::B => -10,
+            EndpointGrade::A => -20 + state_override_bonus,
+            EndpointGrade::B => -10 + state_override_bonus,
             EndpointGrade::C => 0,
             EndpointGrade::D => {
                 // Only penalize if we actually tested and found lacking;
@@ -93,6 +119,122 @@ impl EndpointCapabilities {
             EndpointGrade::F => 50,
         }
     }
+
+    /// Return a weight bonus based on grade, the counterpart of
+    /// [`priority_adjustment`](Self::priority_adjustment) for weighted strategies.
+    ///
+    /// - Grade A: +3
+    /// - Grade B: +2
+    /// - Grade C: +1
+    /// - Grade D and F: 0
+    pub fn weight_adjustment(&self) -> u32 {
+        match self.grade() {
+            EndpointGrade::A => 3,
+            EndpointGrade::B => 2,
+            EndpointGrade::C => 1,
+            EndpointGrade::D | EndpointGrade::F => 0,
+        }
+    }
+}
+
+/// Minimum capability requirements used to filter endpoints.
+///
+/// Numeric limits are only enforced when the endpoint's limit is known; an
+/// unknown limit does not disqualify an endpoint unless the filter is
+/// [`strict`](Self::strict). A known limit of 0 means unlimited and always
+/// satisfies the requirement.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CapabilityFilter {
+    /// Minimum `eth_getLogs` block range.
+    pub min_block_range: Option<u64>,
+
+    /// Minimum batch request size.
+    pub min_batch_size: Option<u32>,
+
+    /// Require `supports_eth_get_logs` to be `Some(true)`.
+    pub require_eth_get_logs: bool,
+
+    /// Minimum [`EndpointCapabilities::grade`]. Endpoints without any
+    /// capability data are only graded when the filter is strict.
+    pub min_grade: Option<EndpointGrade>,
+
+    /// Reject endpoints whose capability is unknown instead of accepting them.
+    pub strict: bool,
+}
+
+impl CapabilityFilter {
+    /// Create a filter that accepts every endpoint.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builder: require a minimum `eth_getLogs` block range.
+    pub fn with_min_block_range(mut self, range: u64) -> Self {
+        self.min_block_range = Some(range);
+        self
+    }
+
+    /// Builder: require a minimum batch size.
+    pub fn with_min_batch_size(mut self, size: u32) -> Self {
+        self.min_batch_size = Some(size);
+        self
+    }
+
+    /// Builder: require confirmed `eth_getLogs` support.
+    pub fn with_eth_get_logs(mut self) -> Self {
+        self.require_eth_get_logs = true;
+        self
+    }
+
+    /// Builder: require a minimum capability grade.
+    pub fn with_min_grade(mut self, grade: EndpointGrade) -> Self {
+        self.min_grade = Some(grade);
+        self
+    }
+
+    /// Builder: reject endpoints whose required capabilities are unknown.
+    pub fn with_strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Check whether the given capabilities satisfy this filter.
+    pub fn matches(&self, caps: &EndpointCapabilities) -> bool {
+        if self.require_eth_get_logs && caps.supports_eth_get_logs != Some(true) {
+            return false;
+        }
+
+        if let Some(min) = self.min_block_range {
+            if !self.limit_satisfies(caps.max_block_range, min) {
+                return false;
+            }
+        }
+
+        if let Some(min) = self.min_batch_size {
+            if !self.limit_satisfies(caps.max_batch_size.map(u64::from), u64::from(min)) {
+                return false;
+            }
+        }
+
+        if let Some(min) = self.min_grade {
+            let known = caps.supports_eth_get_logs.is_some()
+                || caps.max_batch_size.is_some()
+                || caps.max_block_range.is_some();
+            if (known || self.strict) && caps.grade() < min {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    /// Whether a limit (`None` = unknown, 0 = unlimited) meets `min`.
+    fn limit_satisfies(&self, limit: Option<u64>, min: u64) -> bool {
+        match limit {
+            Some(max) => max == 0 || max >= min,
+            None => !self.strict,
+        }
+    }
 }
 
 /// Quality grade for an RPC endpoint (F < D < C < B < A).
@@ -122,8 +264,22 @@ impl fmt::Display for EndpointGrade {
     }
 }
 
+/// Geographic region an endpoint is served from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Region {
+    /// North America.
+    NorthAmerica,
+    /// Europe.
+    Europe,
+    /// Asia-Pacific.
+    AsiaPacific,
+    /// Any other region.
+    Other,
+}
+
 /// Configuration for a single RPC endpoint.
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct RpcEndpoint {
     /// HTTP/HTTPS RPC URL.
     pub url: String,
@@ -140,13 +296,28 @@ pub struct RpcEndpoint {
     #[serde(default = "default_priority")]
     pub priority: u32,
 
+    /// Relative share of traffic for weighted strategies (0 = never selected by weight).
+    #[serde(default = "default_weight")]
+    pub weight: u32,
+
     /// Chain ID this endpoint serves.
     #[serde(default)]
     pub chain_id: u64,
 
+    /// Region the endpoint is served from, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub region: Option<Region>,
+
     /// Capability metadata (supports backward-compatible deserialization).
     #[serde(default)]
     pub capabilities: EndpointCapabilities,
+
+    /// Extra HTTP headers sent with every request, e.g. `Authorization`.
+    ///
+    /// Applied by [`RpcPool::execute_with_client`](crate::RpcPool::execute_with_client),
+    /// health checks and capability probes.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub headers: Vec<(String, String)>,
 }
 
 fn default_name() -> String {
@@ -157,6 +328,10 @@ fn default_priority() -> u32 {
     100
 }
 
+fn default_weight() -> u32 {
+    1
+}
+
 impl RpcEndpoint {
     /// Create a new endpoint with minimal configuration.
     pub fn new(url: impl Into<String>) -> Self {
@@ -166,11 +341,55 @@ impl RpcEndpoint {
             url,
             ws_url: None,
             priority: 100,
+            weight: default_weight(),
             chain_id: 0,
+            region: None,
             capabilities: EndpointCapabilities::default(),
+            headers: Vec::new(),
         }
     }
 
+    /// Create an endpoint serving the Starknet JSON-RPC API.
+    ///
+    /// Sets [`EndpointCapabilities::is_starknet`] and the Starknet mainnet
+    /// chain ID. Selection strategies route Starknet endpoints like any
+    /// other, but the pool's default `eth_blockNumber` health check does not
+    /// work on them; see [`presets::starknet_endpoints`](crate::presets::starknet_endpoints).
+    pub fn new_starknet(url: impl Into<String>, name: impl Into<String>) -> Self {
+        let mut endpoint = Self::new(url)
+            .with_name(name)
+            .with_chain_id(crate::presets::chain_id::STARKNET);
+        endpoint.capabilities.is_starknet = true;
+        endpoint
+    }
+
+    /// Create an endpoint from an already parsed URL.
+    ///
+    /// Fails with [`RpcPoolError::InvalidUrl`] unless the scheme is `https`
+    /// or `http`; plain `http` is accepted with a warning, since requests and
+    /// any API key in the URL are sent unencrypted.
+    pub fn from_url(url: url::Url) -> Result<Self, RpcPoolError> {
+        match url.scheme() {
+            "https" => {}
+            "http" => warn!(
+                url = %url,
+                "Endpoint does not use TLS; requests and any API key are sent in plaintext"
+            ),
+            scheme => {
+                return Err(RpcPoolError::InvalidUrl(format!(
+                    "{}: unsupported scheme {}, expected http or https",
+                    url, scheme
+                )))
+            }
+        }
+        Ok(Self::new(url))
+    }
+
+    /// Parse the endpoint URL.
+    pub fn parsed_url(&self) -> Result<url::Url, url::ParseError> {
+        url::Url::parse(&self.url)
+    }
+
     /// Builder: set endpoint name.
     pub fn with_name(mut self, name: impl Into<String>) -> Self {
         self.name = name.into();
@@ -190,17 +409,150 @@ impl RpcEndpoint {
         self
     }
 
+    /// Builder: set the relative traffic share used by weighted strategies.
+    pub fn with_weight(mut self, weight: u32) -> Self {
+        self.weight = weight;
+        self
+    }
+
+    /// Weight including the capability bonus from [`EndpointCapabilities::weight_adjustment`].
+    ///
+    /// An endpoint with a base weight of 0 stays at 0.
+    pub fn effective_weight(&self) -> u32 {
+        if self.weight == 0 {
+            return 0;
+        }
+        self.weight
+            .saturating_add(self.capabilities.weight_adjustment())
+    }
+
     /// Builder: set chain ID.
     pub fn with_chain_id(mut self, chain_id: u64) -> Self {
         self.chain_id = chain_id;
         self
     }
 
+    /// Builder: tag the endpoint with the region it is served from.
+    pub fn with_region(mut self, region: Region) -> Self {
+        self.region = Some(region);
+        self
+    }
+
     /// Builder: set endpoint capabilities.
     pub fn with_capabilities(mut self, capabilities: EndpointCapabilities) -> Self {
         self.capabilities = capabilities;
         self
     }
+
+    /// Builder: add an HTTP header sent with every request to this endpoint.
+    pub fn with_header(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((key.into(), value.into()));
+        self
+    }
+
+    /// Build an HTTP client that sends this endpoint's headers by default, through `proxy` if set.
+    ///
+    /// Header values are marked sensitive so they are not printed by `Debug`.
+    pub(crate) fn http_client(
+        &self,
+        proxy: Option<&reqwest::Proxy>,
+    ) -> Result<reqwest::Client, RpcPoolError> {
+        let mut headers = HeaderMap::with_capacity(self.headers.len());
+        for (key, value) in &self.headers {
+            let invalid =
+                |e: &dyn fmt::Display| RpcPoolError::ClientCreationFailed(format!("{}: {}", key, e));
+            let name = HeaderName::from_bytes(key.as_bytes()).map_err(|e| invalid(&e))?;
+            let mut value = HeaderValue::from_str(value).map_err(|e| invalid(&e))?;
+            value.set_sensitive(true);
+            headers.append(name, value);
+        }
+        let mut builder = reqwest::Client::builder().default_headers(headers);
+        if let Some(proxy) = proxy {
+            builder = builder.proxy(proxy.clone());
+        }
+        builder
+            .build()
+            .map_err(|e| RpcPoolError::ClientCreationFailed(e.to_string()))
+    }
+}
+
+/// Upper bounds (inclusive, in milliseconds) of the latency histogram buckets.
+pub const LATENCY_BUCKETS_MS: [u64; 9] = [10, 25, 50, 100, 250, 500, 1000, 2500, 5000];
+
+/// Number of latency samples kept by a [`LatencyReservoir`].
+const RESERVOIR_SIZE: usize = 128;
+
+/// Fixed-size uniform sample of latencies (Vitter's Algorithm R).
+///
+/// Every recorded latency has the same chance of being in the sample, so
+/// percentiles stay representative of the whole history in constant memory.
+#[derive(Debug, Clone)]
+pub struct LatencyReservoir {
+    samples: [u64; RESERVOIR_SIZE],
+    count: u64,
+    rng: SmallRng,
+}
+
+impl Default for LatencyReservoir {
+    fn default() -> Self {
+        Self::from_rng(SmallRng::from_entropy())
+    }
+}
+
+impl LatencyReservoir {
+    /// Create an empty reservoir.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create an empty reservoir with a deterministic sampling sequence.
+    pub fn with_seed(seed: u64) -> Self {
+        Self::from_rng(SmallRng::seed_from_u64(seed))
+    }
+
+    fn from_rng(rng: SmallRng) -> Self {
+        Self {
+            samples: [0; RESERVOIR_SIZE],
+            count: 0,
+            rng,
+        }
+    }
+
+    /// Offer a latency to the sample.
+    pub fn record(&mut self, latency_ms: u64) {
+        if (self.count as usize) < RESERVOIR_SIZE {
+            self.samples[self.count as usize] = latency_ms;
+        } else {
+            let slot = self.rng.gen_range(0..=self.count);
+            if slot < RESERVOIR_SIZE as u64 {
+                self.samples[slot as usize] = latency_ms;
+            }
+        }
+        self.count += 1;
+    }
+
+    /// Number of latencies offered so far, including those not sampled.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Nearest-rank percentile (0-100) of the sampled latencies, or `None` if empty.
+    pub fn percentile(&self, percentile: f64) -> Option<u64> {
+        let len = (self.count as usize).min(RESERVOIR_SIZE);
+        if len == 0 {
+            return None;
+        }
+        let mut sorted = self.samples;
+        let sorted = &mut sorted[..len];
+        sorted.sort_unstable();
+        let rank = (percentile.clamp(0.0, 100.0) / 100.0 * len as f64).ceil() as usize;
+        Some(sorted[rank.saturating_sub(1)])
+    }
+
+    /// Drop all samples.
+    pub fn clear(&mut self) {
+        self.count = 0;
+    }
 }
 
 /// Runtime statistics for an RPC endpoint.
@@ -227,6 +579,17 @@ pub struct EndpointStats {
     /// Latency of the most recent request.
     pub last_latency_ms: u64,
 
+    /// Successful requests per [`LATENCY_BUCKETS_MS`] bucket (not cumulative).
+    ///
+    /// Requests slower than the last bucket are only counted in `successful_requests`.
+    pub latency_histogram: [u64; LATENCY_BUCKETS_MS.len()],
+
+    /// Sum of all successful request latencies in milliseconds.
+    pub latency_sum_ms: u64,
+
+    /// Sample of successful request latencies, for percentiles.
+    pub latency_reservoir: LatencyReservoir,
+
     /// Most recent error message (if any).
     pub last_error: Option<String>,
 
@@ -239,13 +602,50 @@ pub struct EndpointStats {
     /// Number of consecutive errors (resets on success).
     pub consecutive_errors: u32,
 
+    /// Number of consecutive successes (resets on failure and on recovery).
+    pub consecutive_successes: u32,
+
+    /// Whether the endpoint recovered from being unhealthy and has not yet
+    /// proven itself; see [`is_confident`](Self::is_confident).
+    pub recovering: bool,
+
     /// Number of consecutive recovery failures (for exponential backoff).
     pub recovery_attempts: u32,
+
+    /// Block number reported by the most recent health-check probe.
+    pub last_known_block: Option<u64>,
+
+    /// Timestamped outcomes of recent requests (`true` = success), oldest first.
+    ///
+    /// Holds at most [`RECENT_OUTCOMES_CAP`] entries from the last
+    /// [`RECENT_OUTCOMES_MAX_AGE`]; see [`success_rate_windowed`](Self::success_rate_windowed).
+    /// Shared, so cloning stats for every selection does not copy it; recording
+    /// an outcome copies it only while such a clone is alive.
+    pub recent_outcomes: Arc<VecDeque<(Instant, bool)>>,
+
+    /// Changes of [`is_healthy`](Self::is_healthy) (`true` = became healthy), oldest first.
+    ///
+    /// Holds at most [`HEALTH_TRANSITIONS_CAP`] entries; see
+    /// [`uptime_percentage`](Self::uptime_percentage). Shared across clones like
+    /// [`recent_outcomes`](Self::recent_outcomes).
+    pub health_transitions: Arc<VecDeque<(Instant, bool)>>,
+
+    /// When health tracking started: creation, or the last [`reset`](Self::reset).
+    pub tracked_since: Instant,
 }
 
 /// Maximum recovery backoff duration (5 minutes).
 const MAX_RECOVERY_BACKOFF_SECS: u64 = 300;
 
+/// Maximum number of outcomes kept for windowed success rates.
+pub const RECENT_OUTCOMES_CAP: usize = 1000;
+
+/// Outcomes older than this are evicted when a new one is recorded.
+pub const RECENT_OUTCOMES_MAX_AGE: Duration = Duration::from_secs(3600);
+
+/// Maximum number of health transitions kept for uptime.
+pub const HEALTH_TRANSITIONS_CAP: usize = 100;
+
 impl EndpointStats {
     /// Create new stats for an endpoint.
     pub fn new(endpoint: &RpcEndpoint) -> Self {
@@ -257,11 +657,20 @@ impl EndpointStats {
             failed_requests: 0,
             avg_latency_ms: 0.0,
             last_latency_ms: 0,
+            latency_histogram: [0; LATENCY_BUCKETS_MS.len()],
+            latency_sum_ms: 0,
+            latency_reservoir: LatencyReservoir::new(),
             last_error: None,
             last_error_time: None,
             is_healthy: true,
             consecutive_errors: 0,
+            consecutive_successes: 0,
+            recovering: false,
             recovery_attempts: 0,
+            last_known_block: None,
+            recent_outcomes: Arc::default(),
+            health_transitions: Arc::default(),
+            tracked_since: Instant::now(),
         }
     }
 
@@ -280,31 +689,63 @@ impl EndpointStats {
 
     /// Record a successful request.
     pub fn record_success(&mut self, latency_ms: u64) {
+        self.record_success_at(latency_ms, Instant::now());
+    }
+
+    fn record_success_at(&mut self, latency_ms: u64, now: Instant) {
         self.total_requests += 1;
         self.successful_requests += 1;
         self.update_latency(latency_ms);
+        self.latency_sum_ms += latency_ms;
+        if let Some(bucket) = LATENCY_BUCKETS_MS.iter().position(|&le| latency_ms <= le) {
+            self.latency_histogram[bucket] += 1;
+        }
+        self.latency_reservoir.record(latency_ms);
+        self.record_outcome(true, now);
+        if !self.is_healthy {
+            // A success on an unhealthy endpoint recovers it
+            self.recovering = true;
+            self.consecutive_successes = 0;
+        }
+        self.consecutive_successes = self.consecutive_successes.saturating_add(1);
         self.consecutive_errors = 0;
-        self.is_healthy = true;
+        self.set_healthy_at(true, now);
     }
 
     /// Record a failed request.
     ///
     /// Returns `true` if the endpoint should be marked unhealthy.
     pub fn record_failure(&mut self, error: String, max_consecutive: u32) -> bool {
+        self.record_failure_at(error, max_consecutive, Instant::now())
+    }
+
+    fn record_failure_at(&mut self, error: String, max_consecutive: u32, now: Instant) -> bool {
         self.total_requests += 1;
         self.failed_requests += 1;
         self.consecutive_errors += 1;
+        self.consecutive_successes = 0;
         self.last_error = Some(error);
-        self.last_error_time = Some(Instant::now());
+        self.last_error_time = Some(now);
+        self.record_outcome(false, now);
 
         if self.consecutive_errors >= max_consecutive {
-            self.is_healthy = false;
+            self.set_healthy_at(false, now);
             true
         } else {
             false
         }
     }
 
+    /// Median latency of successful requests, or `None` before the first success.
+    pub fn p50_latency_ms(&self) -> Option<u64> {
+        self.latency_reservoir.percentile(50.0)
+    }
+
+    /// 99th percentile latency of successful requests, or `None` before the first success.
+    pub fn p99_latency_ms(&self) -> Option<u64> {
+        self.latency_reservoir.percentile(99.0)
+    }
+
     /// Calculate success rate as a percentage.
     pub fn success_rate(&self) -> f64 {
         if self.total_requests == 0 {
@@ -313,6 +754,107 @@ impl EndpointStats {
         (self.successful_requests as f64 / self.total_requests as f64) * 100.0
     }
 
+    /// Success rate as a percentage over requests from the last `window`.
+    ///
+    /// Unlike [`success_rate`](Self::success_rate), old failures stop counting
+    /// once an endpoint recovers. Only the last [`RECENT_OUTCOMES_CAP`]
+    /// requests within [`RECENT_OUTCOMES_MAX_AGE`] are kept, so longer windows
+    /// see no further back. Returns 100.0 if no request falls in the window.
+    pub fn success_rate_windowed(&self, window: Duration) -> f64 {
+        self.success_rate_windowed_at(window, Instant::now())
+    }
+
+    fn success_rate_windowed_at(&self, window: Duration, now: Instant) -> f64 {
+        let (total, successes) = self
+            .recent_outcomes
+            .iter()
+            .rev()
+            .take_while(|(at, _)| now.duration_since(*at) <= window)
+            .fold((0u64, 0u64), |(total, successes), (_, ok)| {
+                (total + 1, successes + u64::from(*ok))
+            });
+        if total == 0 {
+            return 100.0;
+        }
+        (successes as f64 / total as f64) * 100.0
+    }
+
+    /// Append an outcome, evicting entries past the cap or maximum age.
+    fn record_outcome(&mut self, success: bool, now: Instant) {
+        let outcomes = Arc::make_mut(&mut self.recent_outcomes);
+        while outcomes.len() >= RECENT_OUTCOMES_CAP
+            || outcomes
+                .front()
+                .is_some_and(|(at, _)| now.duration_since(*at) > RECENT_OUTCOMES_MAX_AGE)
+        {
+            outcomes.pop_front();
+        }
+        outcomes.push_back((now, success));
+    }
+
+    /// Set [`is_healthy`](Self::is_healthy), logging the transition if it changes.
+    pub fn set_healthy(&mut self, healthy: bool) {
+        self.set_healthy_at(healthy, Instant::now());
+    }
+
+    fn set_healthy_at(&mut self, healthy: bool, now: Instant) {
+        if self.is_healthy == healthy {
+            return;
+        }
+        self.is_healthy = healthy;
+        let transitions = Arc::make_mut(&mut self.health_transitions);
+        if transitions.len() >= HEALTH_TRANSITIONS_CAP {
+            transitions.pop_front();
+        }
+        transitions.push_back((now, healthy));
+    }
+
+    /// Percentage of the last `window` during which the endpoint was healthy.
+    ///
+    /// Replays [`health_transitions`](Self::health_transitions), assuming the
+    /// endpoint was healthy before the first one. Time before
+    /// [`tracked_since`](Self::tracked_since) is not counted, and only the last
+    /// [`HEALTH_TRANSITIONS_CAP`] transitions are kept. Returns 100.0 or 0.0
+    /// for an empty window, by the current state.
+    pub fn uptime_percentage(&self, window: Duration) -> f64 {
+        self.uptime_percentage_at(window, Instant::now())
+    }
+
+    fn uptime_percentage_at(&self, window: Duration, now: Instant) -> f64 {
+        let start = now
+            .checked_sub(window)
+            .map_or(self.tracked_since, |start| start.max(self.tracked_since));
+        let total = now.duration_since(start);
+        if total.is_zero() {
+            return if self.is_healthy { 100.0 } else { 0.0 };
+        }
+
+        // Transitions alternate, so the state before one is its opposite
+        let mut healthy = self
+            .health_transitions
+            .iter()
+            .rev()
+            .find(|(at, _)| *at <= start)
+            .map(|(_, healthy)| *healthy)
+            .or_else(|| self.health_transitions.front().map(|(_, healthy)| !healthy))
+            .unwrap_or(true);
+
+        let mut since = start;
+        let mut uptime = Duration::ZERO;
+        for &(at, became_healthy) in self.health_transitions.iter().filter(|(at, _)| *at > start) {
+            if healthy {
+                uptime += at.duration_since(since);
+            }
+            healthy = became_healthy;
+            since = at;
+        }
+        if healthy {
+            uptime += now.duration_since(since);
+        }
+
+        uptime.as_secs_f64() / total.as_secs_f64() * 100.0
+    }
+
     /// Check if enough time has passed since the last error for a retry.
     ///
     /// Uses exponential backoff: base_delay * 2^recovery_attempts, capped at 5 minutes.
@@ -347,10 +889,115 @@ impl EndpointStats {
 
     /// Mark as recovered (healthy again).
     pub fn mark_recovered(&mut self) {
-        self.is_healthy = true;
+        self.set_healthy(true);
         self.consecutive_errors = 0;
+        self.consecutive_successes = 0;
+        self.recovering = true;
         self.recovery_attempts = 0; // Reset backoff on successful recovery
     }
+
+    /// Whether the endpoint can be trusted as a primary choice.
+    ///
+    /// `false` only while the endpoint is [`recovering`](Self::recovering) and
+    /// has fewer than `threshold` consecutive successes since it recovered.
+    pub fn is_confident(&self, threshold: u32) -> bool {
+        !self.recovering || self.consecutive_successes >= threshold
+    }
+
+    /// Clear request counters, latency history and errors, and mark healthy.
+    ///
+    /// The last known block is kept since it describes the chain, not the endpoint's record.
+    pub fn reset(&mut self) {
+        self.total_requests = 0;
+        self.successful_requests = 0;
+        self.failed_requests = 0;
+        self.avg_latency_ms = 0.0;
+        self.last_latency_ms = 0;
+        self.latency_histogram = [0; LATENCY_BUCKETS_MS.len()];
+        self.latency_sum_ms = 0;
+        self.latency_reservoir.clear();
+        self.recent_outcomes = Arc::default();
+        self.last_error = None;
+        self.last_error_time = None;
+        self.is_healthy = true;
+        self.health_transitions = Arc::default();
+        self.tracked_since = Instant::now();
+        self.consecutive_errors = 0;
+        self.consecutive_successes = 0;
+        self.recovering = false;
+        self.recovery_attempts = 0;
+    }
+
+    /// Multi-line human-readable report of this endpoint's state, for diagnostics.
+    ///
+    /// Covers health, success rate and counts, latency, error streak and
+    /// recovery attempts, and the last error (cut to 100 characters) with how
+    /// long ago it happened.
+    pub fn to_report_string(&self) -> String {
+        let status = if !self.is_healthy {
+            "unhealthy"
+        } else if self.recovering {
+            "recovering"
+        } else {
+            "healthy"
+        };
+        let last_error = match &self.last_error {
+            Some(error) => {
+                let mut error = truncate_chars(error, REPORT_ERROR_MAX_CHARS);
+                if let Some(at) = self.last_error_time {
+                    error.push_str(&format!(" ({} ago)", format_elapsed(at.elapsed())));
+                }
+                error
+            }
+            None => "none".to_string(),
+        };
+
+        [
+            format!("{} ({})", self.name, self.url),
+            format!("  Status: {}", status),
+            format!(
+                "  Success rate: {:.1}% ({} total, {} succeeded, {} failed)",
+                self.success_rate(),
+                self.total_requests,
+                self.successful_requests,
+                self.failed_requests
+            ),
+            format!(
+                "  Latency: {:.1}ms avg, {}ms last",
+                self.avg_latency_ms, self.last_latency_ms
+            ),
+            format!(
+                "  Consecutive errors: {}, recovery attempts: {}",
+                self.consecutive_errors, self.recovery_attempts
+            ),
+            format!("  Last error: {}", last_error),
+        ]
+        .join("\n")
+    }
+}
+
+/// Longest last error message shown by [`EndpointStats::to_report_string`].
+const REPORT_ERROR_MAX_CHARS: usize = 100;
+
+/// `s` cut to at most `max` characters, marking a cut with `...`.
+fn truncate_chars(s: &str, max: usize) -> String {
+    match s.char_indices().nth(max) {
+        Some((end, _)) => format!("{}...", &s[..end]),
+        None => s.to_string(),
+    }
+}
+
+/// A duration in human units, e.g. `3m 12s` or `2h 5m 0s`.
+fn format_elapsed(elapsed: Duration) -> String {
+    let secs = elapsed.as_secs();
+    let (hours, minutes, seconds) = (secs / 3600, secs % 3600 / 60, secs % 60);
+    if hours > 0 {
+        format!("{}h {}m {}s", hours, minutes, seconds)
+    } else if minutes > 0 {
+        format!("{}m {}s", minutes, seconds)
+    } else {
+        format!("{}s", seconds)
+    }
 }
 
 #[cfg(test)]
@@ -368,6 +1015,63 @@ mod tests {
         assert_eq!(endpoint.name, "Example RPC");
         assert_eq!(endpoint.priority, 50);
         assert_eq!(endpoint.chain_id, 1);
+        assert_eq!(endpoint.weight, 1);
+        assert_eq!(endpoint.with_weight(5).weight, 5);
+    }
+
+    #[test]
+    fn test_latency_histogram() {
+        let endpoint = RpcEndpoint::new("https://rpc.example.com");
+        let mut stats = EndpointStats::new(&endpoint);
+
+        // 10 requests in each bucket, plus 10 slower than the last bucket
+        let latencies = [5, 25, 40, 80, 200, 400, 1000, 2000, 4000, 8000];
+        for latency in latencies.iter().cycle().take(100) {
+            stats.record_success(*latency);
+        }
+
+        assert_eq!(stats.latency_histogram, [10; 9]);
+        assert_eq!(stats.latency_histogram.iter().sum::<u64>(), 90);
+        assert_eq!(stats.successful_requests, 100);
+        assert_eq!(stats.latency_sum_ms, latencies.iter().sum::<u64>() * 10);
+
+        stats.reset();
+        assert_eq!(stats.latency_histogram, [0; 9]);
+        assert_eq!(stats.latency_sum_ms, 0);
+    }
+
+    #[test]
+    fn test_latency_percentiles() {
+        let endpoint = RpcEndpoint::new("https://rpc.example.com");
+        let mut stats = EndpointStats::new(&endpoint);
+        assert_eq!(stats.p50_latency_ms(), None);
+        assert_eq!(stats.p99_latency_ms(), None);
+
+        stats.latency_reservoir = LatencyReservoir::with_seed(42);
+        let mut rng = SmallRng::seed_from_u64(7);
+        for _ in 0..1000 {
+            stats.record_success(rng.gen_range(0..=1000));
+        }
+
+        assert_eq!(stats.latency_reservoir.count(), 1000);
+        let p50 = stats.p50_latency_ms().unwrap();
+        let p99 = stats.p99_latency_ms().unwrap();
+        assert!((450..=550).contains(&p50), "p50 = {}", p50);
+        assert!((950..=1000).contains(&p99), "p99 = {}", p99);
+
+        stats.reset();
+        assert_eq!(stats.p50_latency_ms(), None);
+    }
+
+    #[test]
+    fn test_latency_reservoir_before_full() {
+        let mut reservoir = LatencyReservoir::new();
+        for latency in [30, 10, 20] {
+            reservoir.record(latency);
+        }
+        assert_eq!(reservoir.percentile(50.0), Some(20));
+        assert_eq!(reservoir.percentile(99.0), Some(30));
+        assert_eq!(reservoir.percentile(0.0), Some(10));
     }
 
     #[test]
@@ -385,6 +1089,86 @@ mod tests {
         assert!((stats.avg_latency_ms - 110.0).abs() < 0.001);
     }
 
+    #[test]
+    fn test_success_rate_windowed() {
+        let endpoint = RpcEndpoint::new("https://rpc.example.com");
+        let mut stats = EndpointStats::new(&endpoint);
+        assert_eq!(stats.success_rate_windowed(Duration::from_secs(60)), 100.0);
+
+        // Old failures fall out of a short window
+        let start = Instant::now();
+        let now = start + Duration::from_secs(30);
+        for _ in 0..3 {
+            stats.record_failure_at("timeout".to_string(), 10, start);
+        }
+        stats.record_success_at(10, now);
+        assert_eq!(stats.success_rate_windowed_at(Duration::from_secs(10), now), 100.0);
+        assert_eq!(stats.success_rate_windowed_at(Duration::from_secs(60), now), 25.0);
+        assert_eq!(stats.success_rate(), 25.0);
+
+        // Outcomes are capped, oldest evicted first
+        for _ in 0..RECENT_OUTCOMES_CAP {
+            stats.record_success(10);
+        }
+        assert_eq!(stats.recent_outcomes.len(), RECENT_OUTCOMES_CAP);
+        assert_eq!(stats.success_rate_windowed(Duration::from_secs(60)), 100.0);
+
+        stats.reset();
+        assert!(stats.recent_outcomes.is_empty());
+    }
+
+    #[test]
+    fn test_uptime_percentage_replays_transitions() {
+        let endpoint = RpcEndpoint::new("https://rpc.example.com");
+        let mut stats = EndpointStats::new(&endpoint);
+        let now = Instant::now();
+        stats.tracked_since = now - Duration::from_secs(100);
+        assert!(stats.uptime_percentage(Duration::from_secs(60)) > 99.9);
+
+        // Down from 60s ago until 30s ago
+        let transitions = Arc::make_mut(&mut stats.health_transitions);
+        transitions.push_back((now - Duration::from_secs(60), false));
+        transitions.push_back((now - Duration::from_secs(30), true));
+        let uptime = stats.uptime_percentage(Duration::from_secs(100));
+        assert!((69.0..=71.0).contains(&uptime), "uptime {}", uptime);
+
+        // Starts inside the outage
+        let uptime = stats.uptime_percentage(Duration::from_secs(40));
+        assert!((74.0..=76.0).contains(&uptime), "uptime {}", uptime);
+
+        // Time before tracking started does not count
+        let uptime = stats.uptime_percentage(Duration::from_secs(1000));
+        assert!((69.0..=71.0).contains(&uptime), "uptime {}", uptime);
+    }
+
+    #[test]
+    fn test_uptime_percentage_from_failures_and_recovery() {
+        let endpoint = RpcEndpoint::new("https://rpc.example.com");
+        let mut stats = EndpointStats::new(&endpoint);
+        let start = stats.tracked_since;
+
+        // Unhealthy for the second half of the time since creation
+        assert!(stats.record_failure_at("timeout".to_string(), 1, start + Duration::from_secs(30)));
+        let now = start + Duration::from_secs(60);
+        assert_eq!(stats.uptime_percentage_at(Duration::from_secs(120), now), 50.0);
+        assert_eq!(stats.uptime_percentage_at(Duration::from_secs(15), now), 0.0);
+
+        stats.mark_recovered();
+        assert_eq!(stats.health_transitions.len(), 2);
+
+        // Repeated state does not add transitions, and the log is capped
+        stats.mark_recovered();
+        assert_eq!(stats.health_transitions.len(), 2);
+        for _ in 0..HEALTH_TRANSITIONS_CAP {
+            stats.set_healthy(!stats.is_healthy);
+        }
+        assert_eq!(stats.health_transitions.len(), HEALTH_TRANSITIONS_CAP);
+
+        stats.reset();
+        assert!(stats.health_transitions.is_empty());
+        assert_eq!(stats.uptime_percentage(Duration::from_secs(60)), 100.0);
+    }
+
     #[test]
     fn test_consecutive_errors() {
         let endpoint = RpcEndpoint::new("https://rpc.example.com");
@@ -405,11 +1189,57 @@ mod tests {
         assert_eq!(stats.consecutive_errors, 0);
     }
 
+    #[test]
+    fn test_confidence_after_recovery() {
+        let endpoint = RpcEndpoint::new("https://rpc.example.com");
+        let mut stats = EndpointStats::new(&endpoint);
+
+        // Endpoints that never failed are trusted
+        assert!(stats.is_confident(3));
+        stats.record_success(10);
+        stats.record_success(10);
+        assert_eq!(stats.consecutive_successes, 2);
+
+        stats.record_failure("down".into(), 1);
+        assert_eq!(stats.consecutive_successes, 0);
+        stats.mark_recovered();
+        assert!(stats.recovering);
+        assert!(!stats.is_confident(3));
+
+        stats.record_success(10);
+        stats.record_success(10);
+        assert!(!stats.is_confident(3));
+        stats.record_success(10);
+        assert!(stats.is_confident(3));
+
+        // A failure starts the count again
+        stats.record_failure("blip".into(), 5);
+        assert!(!stats.is_confident(3));
+
+        stats.reset();
+        assert!(!stats.recovering);
+        assert!(stats.is_confident(3));
+    }
+
+    #[test]
+    fn test_success_on_unhealthy_endpoint_recovers_it() {
+        let endpoint = RpcEndpoint::new("https://rpc.example.com");
+        let mut stats = EndpointStats::new(&endpoint);
+        stats.record_success(10);
+        stats.record_failure("down".into(), 1);
+
+        stats.record_success(10);
+        assert!(stats.recovering);
+        assert_eq!(stats.consecutive_successes, 1);
+        assert!(!stats.is_confident(2));
+    }
+
     #[test]
     fn test_endpoint_capabilities_default() {
         let caps = EndpointCapabilities::default();
         assert_eq!(caps.grade(), EndpointGrade::D);
         assert_eq!(caps.priority_adjustment(), 0); // unknown = no adjustment
+        assert_eq!(caps.weight_adjustment(), 0);
     }
 
     #[test]
@@ -422,6 +1252,7 @@ mod tests {
         };
         assert_eq!(caps.grade(), EndpointGrade::A);
         assert_eq!(caps.priority_adjustment(), -20);
+        assert_eq!(caps.weight_adjustment(), 3);
     }
 
     #[test]
@@ -471,6 +1302,46 @@ mod tests {
         assert_eq!(caps.priority_adjustment(), 10);
     }
 
+    #[test]
+    fn test_state_override_promotes_a_and_b_by_half_grade() {
+        let a = EndpointCapabilities {
+            supports_eth_get_logs: Some(true),
+            max_batch_size: Some(100),
+            max_block_range: Some(10_000),
+            supports_eth_call_state_override: Some(true),
+            ..Default::default()
+        };
+        assert_eq!(a.grade(), EndpointGrade::A);
+        assert_eq!(a.priority_adjustment(), -25);
+
+        let b = EndpointCapabilities {
+            supports_eth_get_logs: Some(true),
+            max_batch_size: Some(50),
+            max_block_range: Some(5_000),
+            supports_eth_call_state_override: Some(true),
+            ..Default::default()
+        };
+        assert_eq!(b.grade(), EndpointGrade::B);
+        assert_eq!(b.priority_adjustment(), -15);
+
+        // Grade C and below are not promoted
+        let c = EndpointCapabilities {
+            supports_eth_get_logs: Some(true),
+            max_batch_size: Some(5),
+            max_block_range: Some(100),
+            supports_eth_call_state_override: Some(true),
+            ..Default::default()
+        };
+        assert_eq!(c.priority_adjustment(), 0);
+
+        // Confirmed lack of support behaves like untested
+        let b_without = EndpointCapabilities {
+            supports_eth_call_state_override: Some(false),
+            ..b
+        };
+        assert_eq!(b_without.priority_adjustment(), -10);
+    }
+
     #[test]
     fn test_grade_ordering() {
         assert!(EndpointGrade::F < EndpointGrade::D);
@@ -498,6 +1369,25 @@ mod tests {
         assert_eq!(endpoint.capabilities.grade(), EndpointGrade::A);
     }
 
+    #[test]
+    fn test_effective_weight() {
+        let endpoint = RpcEndpoint::new("https://rpc.example.com").with_weight(4);
+        assert_eq!(endpoint.effective_weight(), 4);
+
+        // Grade B adds 2
+        let caps = EndpointCapabilities {
+            supports_eth_get_logs: Some(true),
+            max_batch_size: Some(50),
+            max_block_range: Some(5_000),
+            ..Default::default()
+        };
+        let endpoint = endpoint.with_capabilities(caps);
+        assert_eq!(endpoint.effective_weight(), 6);
+
+        // A zero weight is not lifted by capabilities
+        assert_eq!(endpoint.with_weight(0).effective_weight(), 0);
+    }
+
     #[test]
     fn test_with_ws_url_sets_websocket_capability() {
         let endpoint = RpcEndpoint::new("https://rpc.example.com")
@@ -511,10 +1401,45 @@ mod tests {
         let json = r#"{"url":"https://rpc.example.com","name":"Test","priority":50,"chain_id":1}"#;
         let endpoint: RpcEndpoint = serde_json::from_str(json).unwrap();
         assert_eq!(endpoint.url, "https://rpc.example.com");
+        assert_eq!(endpoint.weight, 1);
         assert_eq!(endpoint.capabilities.grade(), EndpointGrade::D);
         assert!(!endpoint.capabilities.supports_websocket);
     }
 
+    #[test]
+    fn test_from_url_checks_scheme() {
+        let url = url::Url::parse("https://rpc.example.com/v2/key").unwrap();
+        let endpoint = RpcEndpoint::from_url(url.clone()).unwrap();
+        assert_eq!(endpoint.url, "https://rpc.example.com/v2/key");
+        assert_eq!(endpoint.parsed_url().unwrap(), url);
+
+        let url = url::Url::parse("http://10.0.0.5:8545").unwrap();
+        assert!(RpcEndpoint::from_url(url).is_ok());
+
+        let url = url::Url::parse("ftp://rpc.example.com").unwrap();
+        let result = RpcEndpoint::from_url(url);
+        assert!(matches!(result, Err(RpcPoolError::InvalidUrl(_))));
+    }
+
+    #[test]
+    fn test_parsed_url_rejects_invalid_url() {
+        let endpoint = RpcEndpoint::new("not a url");
+        assert!(endpoint.parsed_url().is_err());
+    }
+
+    #[test]
+    fn test_region_serialization() {
+        let endpoint = RpcEndpoint::new("https://rpc.example.com").with_region(Region::AsiaPacific);
+        let json = serde_json::to_string(&endpoint).unwrap();
+        assert!(json.contains(r#""region":"asia-pacific""#));
+        let parsed: RpcEndpoint = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, endpoint);
+
+        // Untagged endpoints omit the field
+        let json = serde_json::to_string(&RpcEndpoint::new("https://rpc.example.com")).unwrap();
+        assert!(!json.contains("region"));
+    }
+
     #[test]
     fn test_capabilities_serialization_roundtrip() {
         let caps = EndpointCapabilities {
@@ -522,8 +1447,10 @@ mod tests {
             max_batch_size: Some(100),
             max_block_range: Some(10_000),
             supports_debug_trace: Some(false),
+            supports_eth_call_state_override: None,
             supports_websocket: true,
             rate_limit_rps: Some(25),
+            is_starknet: false,
         };
         let endpoint = RpcEndpoint::new("https://rpc.example.com")
             .with_name("Test")
@@ -536,6 +1463,56 @@ mod tests {
         assert_eq!(deserialized.capabilities.rate_limit_rps, Some(25));
     }
 
+    #[test]
+    fn test_capability_filter() {
+        let caps = EndpointCapabilities {
+            supports_eth_get_logs: Some(true),
+            max_batch_size: Some(10),
+            max_block_range: Some(1_000),
+            ..Default::default()
+        };
+
+        assert!(CapabilityFilter::new().matches(&caps));
+        assert!(CapabilityFilter::new().with_eth_get_logs().matches(&caps));
+        assert!(CapabilityFilter::new().with_min_block_range(1_000).matches(&caps));
+        assert!(!CapabilityFilter::new().with_min_block_range(5_000).matches(&caps));
+        assert!(!CapabilityFilter::new().with_min_batch_size(50).matches(&caps));
+
+        // Unlimited (0) satisfies any minimum
+        let unlimited = EndpointCapabilities {
+            max_block_range: Some(0),
+            ..Default::default()
+        };
+        assert!(CapabilityFilter::new().with_min_block_range(1_000_000).matches(&unlimited));
+
+        // Unknown limits pass, unknown eth_getLogs support does not
+        let unknown = EndpointCapabilities::default();
+        assert!(CapabilityFilter::new().with_min_block_range(5_000).matches(&unknown));
+        assert!(!CapabilityFilter::new().with_eth_get_logs().matches(&unknown));
+    }
+
+    #[test]
+    fn test_capability_filter_grade_and_strict() {
+        let grade_c = EndpointCapabilities {
+            supports_eth_get_logs: Some(true),
+            max_batch_size: Some(5),
+            max_block_range: Some(500),
+            ..Default::default()
+        };
+        let unknown = EndpointCapabilities::default();
+
+        let min_b = CapabilityFilter::new().with_min_grade(EndpointGrade::B);
+        assert!(!min_b.matches(&grade_c));
+        assert!(CapabilityFilter::new().with_min_grade(EndpointGrade::C).matches(&grade_c));
+        assert!(min_b.matches(&unknown));
+        assert!(!min_b.with_strict(true).matches(&unknown));
+
+        let min_range = CapabilityFilter::new().with_min_block_range(100);
+        assert!(min_range.matches(&unknown));
+        assert!(!min_range.with_strict(true).matches(&unknown));
+        assert!(min_range.with_strict(true).matches(&grade_c));
+    }
+
     #[test]
     fn test_exponential_backoff() {
         let endpoint = RpcEndpoint::new("https://rpc.example.com");
@@ -568,4 +1545,36 @@ mod tests {
         assert_eq!(stats.recovery_attempts, 0);
         assert_eq!(stats.current_retry_delay(base_delay).as_secs(), 5);
     }
+
+    #[test]
+    fn test_report_string() {
+        let endpoint = RpcEndpoint::new("https://rpc.example.com").with_name("Example");
+        let mut stats = EndpointStats::new(&endpoint);
+        stats.record_success(120);
+        stats.record_failure("x".repeat(150), 1);
+        stats.last_error_time = Instant::now().checked_sub(Duration::from_secs(192));
+
+        let report = stats.to_report_string();
+        assert!(report.starts_with("Example (https://rpc.example.com)\n"));
+        assert!(report.contains("Status: unhealthy"));
+        assert!(report.contains("Success rate: 50.0% (2 total, 1 succeeded, 1 failed)"));
+        assert!(report.contains("Latency: 120.0ms avg, 120ms last"));
+        assert!(report.contains("Consecutive errors: 1, recovery attempts: 0"));
+        let last_error = format!("Last error: {}... (3m 12s ago)", "x".repeat(100));
+        assert!(report.ends_with(&last_error), "{}", report);
+
+        stats.mark_recovered();
+        assert!(stats.to_report_string().contains("Status: recovering"));
+        stats.reset();
+        let report = stats.to_report_string();
+        assert!(report.contains("Status: healthy"));
+        assert!(report.ends_with("Last error: none"));
+    }
+
+    #[test]
+    fn test_format_elapsed() {
+        assert_eq!(format_elapsed(Duration::from_millis(900)), "0s");
+        assert_eq!(format_elapsed(Duration::from_secs(192)), "3m 12s");
+        assert_eq!(format_elapsed(Duration::from_secs(7500)), "2h 5m 0s");
+    }
 }
@@ -3,12 +3,16 @@
 use thiserror::Error;
 
 /// Errors that can occur during RPC pool operations.
-#[derive(Error, Debug)]
+#[derive(Error, Debug, Clone)]
 pub enum RpcPoolError {
     /// All configured endpoints have failed.
     #[error("All RPC endpoints failed: {0}")]
     AllEndpointsFailed(String),
 
+    /// Every endpoint tried rejected the request with a rate-limit error.
+    #[error("All RPC endpoints rate limited: {0}")]
+    RateLimited(String),
+
     /// No endpoints are configured.
     #[error("No RPC endpoints configured")]
     NoEndpointsConfigured,
@@ -17,6 +21,15 @@ pub enum RpcPoolError {
     #[error("No healthy RPC endpoints available")]
     NoHealthyEndpoints,
 
+    /// Fewer endpoints are healthy than the configured minimum.
+    #[error("Only {available} healthy RPC endpoints, {required} required")]
+    InsufficientHealthyEndpoints {
+        /// Healthy endpoints at the time of the request.
+        available: usize,
+        /// Configured minimum.
+        required: usize,
+    },
+
     /// Failed to create RPC client.
     #[error("Failed to create RPC client: {0}")]
     ClientCreationFailed(String),
@@ -25,25 +38,130 @@ pub enum RpcPoolError {
     #[error("RPC transport error: {0}")]
     TransportError(String),
 
+    /// Failed to read or parse a configuration file.
+    #[error("Failed to load config: {0}")]
+    ConfigParseError(String),
+
+    /// Failed to serialize or write a configuration file.
+    #[error("Failed to save config: {0}")]
+    ConfigWriteError(String),
+
     /// Invalid endpoint URL.
     #[error("Invalid endpoint URL: {0}")]
     InvalidUrl(String),
 
+    /// A premium endpoint does not use TLS.
+    #[error("Premium endpoint must use https: {0}")]
+    InsecureEndpoint(String),
+
+    /// Every endpoint tried returned a response that failed validation.
+    #[error("Response validation failed: {0}")]
+    ValidationFailed(String),
+
     /// Timeout waiting for response.
     #[error("Request timeout after {0}ms")]
     Timeout(u64),
 
+    /// The wall-clock deadline passed before any request succeeded.
+    #[error("Request deadline exceeded")]
+    DeadlineExceeded,
+
+    /// The retry budget ran out before any attempt succeeded.
+    #[error("Retry budget exhausted: {0}")]
+    RetryBudgetExhausted(String),
+
     /// Pool has been shut down.
     #[error("RPC pool has been shut down")]
     PoolShutdown,
 
+    /// Pool is paused and not accepting new requests.
+    #[error("RPC pool is paused")]
+    PoolPaused,
+
+    /// The caller cancelled the request.
+    #[error("Request cancelled")]
+    Cancelled,
+
     /// No WebSocket-capable endpoints configured.
     #[error("No WebSocket-capable endpoints configured")]
     NoWebSocketEndpoints,
 
+    /// No configured endpoint satisfies the request's requirements.
+    #[error("No RPC endpoints match the requested capabilities")]
+    NoMatchingEndpoints,
+
     /// WebSocket connection or subscription error.
     #[error("WebSocket error: {0}")]
     WebSocketError(String),
+
+    /// A resilient subscription lost its connection and is reconnecting to the named endpoint.
+    #[error("WebSocket reconnecting to {0}")]
+    WebSocketReconnecting(String),
+
+    /// Opening the subscription would exceed the pool's subscription limit.
+    #[error("Too many WebSocket subscriptions: {current} open, limit is {max}")]
+    TooManySubscriptions {
+        /// Subscription streams open at the time of the call.
+        current: usize,
+        /// Configured limit.
+        max: usize,
+    },
+
+    /// An endpoint with the same URL is already registered.
+    #[error("Endpoint already registered: {0}")]
+    DuplicateEndpoint(String),
+
+    /// No endpoint with the given URL is registered.
+    #[error("Endpoint not found: {0}")]
+    EndpointNotFound(String),
+
+    /// No pool is registered for the chain ID.
+    #[error("Unknown chain ID: {0}")]
+    UnknownChain(u64),
+}
+
+impl RpcPoolError {
+    /// Whether the same call may succeed if retried later.
+    ///
+    /// Covers failures caused by endpoint or network state. Configuration
+    /// mistakes, shutdown and missing endpoints are permanent; a paused pool
+    /// accepts the call again once resumed.
+    /// [`RateLimited`](Self::RateLimited) is retryable but deserves a longer backoff.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            RpcPoolError::AllEndpointsFailed(_)
+            | RpcPoolError::RateLimited(_)
+            | RpcPoolError::NoHealthyEndpoints
+            | RpcPoolError::InsufficientHealthyEndpoints { .. }
+            | RpcPoolError::TransportError(_)
+            | RpcPoolError::ValidationFailed(_)
+            | RpcPoolError::Timeout(_)
+            | RpcPoolError::DeadlineExceeded
+            | RpcPoolError::RetryBudgetExhausted(_)
+            | RpcPoolError::PoolPaused
+            | RpcPoolError::WebSocketError(_)
+            | RpcPoolError::WebSocketReconnecting(_)
+            | RpcPoolError::TooManySubscriptions { .. } => true,
+            RpcPoolError::NoEndpointsConfigured
+            | RpcPoolError::ClientCreationFailed(_)
+            | RpcPoolError::ConfigParseError(_)
+            | RpcPoolError::ConfigWriteError(_)
+            | RpcPoolError::InvalidUrl(_)
+            | RpcPoolError::InsecureEndpoint(_)
+            | RpcPoolError::PoolShutdown
+            | RpcPoolError::Cancelled
+            | RpcPoolError::NoWebSocketEndpoints
+            | RpcPoolError::NoMatchingEndpoints
+            | RpcPoolError::DuplicateEndpoint(_)
+            | RpcPoolError::EndpointNotFound(_)
+            | RpcPoolError::UnknownChain(_) => false,
+        }
+    }
+
+    /// Whether retrying the same call cannot help; the complement of [`is_retryable`](Self::is_retryable).
+    pub fn is_permanent(&self) -> bool {
+        !self.is_retryable()
+    }
 }
 
 impl From<url::ParseError> for RpcPoolError {
@@ -51,3 +169,55 @@ impl From<url::ParseError> for RpcPoolError {
         RpcPoolError::InvalidUrl(err.to_string())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_classification() {
+        let s = || "error".to_string();
+        let retryable = [
+            RpcPoolError::AllEndpointsFailed(s()),
+            RpcPoolError::RateLimited(s()),
+            RpcPoolError::NoHealthyEndpoints,
+            RpcPoolError::InsufficientHealthyEndpoints {
+                available: 1,
+                required: 2,
+            },
+            RpcPoolError::TransportError(s()),
+            RpcPoolError::ValidationFailed(s()),
+            RpcPoolError::Timeout(1_000),
+            RpcPoolError::DeadlineExceeded,
+            RpcPoolError::RetryBudgetExhausted(s()),
+            RpcPoolError::PoolPaused,
+            RpcPoolError::WebSocketError(s()),
+            RpcPoolError::WebSocketReconnecting(s()),
+            RpcPoolError::TooManySubscriptions { current: 1, max: 1 },
+        ];
+        let permanent = [
+            RpcPoolError::NoEndpointsConfigured,
+            RpcPoolError::ClientCreationFailed(s()),
+            RpcPoolError::ConfigParseError(s()),
+            RpcPoolError::ConfigWriteError(s()),
+            RpcPoolError::InvalidUrl(s()),
+            RpcPoolError::InsecureEndpoint(s()),
+            RpcPoolError::PoolShutdown,
+            RpcPoolError::Cancelled,
+            RpcPoolError::NoWebSocketEndpoints,
+            RpcPoolError::NoMatchingEndpoints,
+            RpcPoolError::DuplicateEndpoint(s()),
+            RpcPoolError::EndpointNotFound(s()),
+            RpcPoolError::UnknownChain(1),
+        ];
+
+        for error in &retryable {
+            assert!(error.is_retryable(), "{:?} should be retryable", error);
+            assert!(!error.is_permanent(), "{:?} should not be permanent", error);
+        }
+        for error in &permanent {
+            assert!(error.is_permanent(), "{:?} should be permanent", error);
+            assert!(!error.is_retryable(), "{:?} should not be retryable", error);
+        }
+    }
+}
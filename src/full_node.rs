@@ -0,0 +1,210 @@
+//! HTTP requests and WebSocket subscriptions behind one pool.
+//!
+//! A [`FullNodePool`] builds an [`RpcPool`] and a [`WsPool`] from the same
+//! endpoint list: requests go over HTTP, subscriptions over WebSocket, and a
+//! single health check task watches both.
+//!
+//! ```rust,no_run
+//! use web3_rpc_pool::full_node::FullNodePoolBuilder;
+//! use web3_rpc_pool::presets;
+//! use alloy::providers::{Provider, ProviderBuilder};
+//! use futures_util::StreamExt;
+//!
+//! # async fn example() -> Result<(), web3_rpc_pool::RpcPoolError> {
+//! let pool = FullNodePoolBuilder::new(presets::ethereum_endpoints()).build()?;
+//! pool.start_health_check();
+//!
+//! let block = pool
+//!     .execute(|url| async move {
+//!         ProviderBuilder::new().connect_http(url).get_block_number().await
+//!     })
+//!     .await?;
+//!
+//! let mut heads = pool.subscribe_new_heads().await?;
+//! while let Some(header) = heads.next().await {
+//!     println!("New block after {}: {}", block, header.number);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::endpoint::RpcEndpoint;
+use crate::error::RpcPoolError;
+use crate::pool::{RpcPool, RpcPoolConfig};
+use crate::ws::{BoxSubscriptionStream, WsPool, WsPoolConfig};
+
+use alloy::rpc::types::{Filter, Header, Log};
+use std::future::Future;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use tracing::{info, trace};
+
+/// An [`RpcPool`] and a [`WsPool`] over the same endpoints.
+///
+/// Endpoints without a `ws_url` only serve HTTP requests.
+pub struct FullNodePool {
+    http: Arc<RpcPool>,
+    ws: Arc<WsPool>,
+    /// WebSocket endpoints that answered the latest health check.
+    ws_healthy: Arc<AtomicUsize>,
+}
+
+impl FullNodePool {
+    /// The pool serving HTTP requests.
+    pub fn http_pool(&self) -> &Arc<RpcPool> {
+        &self.http
+    }
+
+    /// The pool serving subscriptions.
+    pub fn ws_pool(&self) -> &Arc<WsPool> {
+        &self.ws
+    }
+
+    /// Execute a request over HTTP with automatic failover.
+    ///
+    /// See [`RpcPool::execute`].
+    pub async fn execute<T, E, F, Fut>(&self, f: F) -> Result<T, RpcPoolError>
+    where
+        F: Fn(url::Url) -> Fut + Clone,
+        Fut: Future<Output = Result<T, E>>,
+        E: std::error::Error,
+    {
+        self.http.execute(f).await
+    }
+
+    /// Subscribe to new block headers over WebSocket.
+    ///
+    /// See [`WsPool::subscribe_new_heads`].
+    pub async fn subscribe_new_heads(&self) -> Result<BoxSubscriptionStream<Header>, RpcPoolError> {
+        self.ws.subscribe_new_heads().await
+    }
+
+    /// Subscribe to logs matching `filter` over WebSocket.
+    ///
+    /// See [`WsPool::subscribe_logs`].
+    pub async fn subscribe_logs(
+        &self,
+        filter: &Filter,
+    ) -> Result<BoxSubscriptionStream<Log>, RpcPoolError> {
+        self.ws.subscribe_logs(filter).await
+    }
+
+    /// Start the background health check task for both pools.
+    ///
+    /// Every [`RpcPoolConfig::health_check_interval`] the task probes
+    /// unhealthy HTTP endpoints, like [`RpcPool::start_health_check`], then
+    /// sends `eth_blockNumber` over each WebSocket endpoint's request
+    /// connection, reconnecting the ones that fail. The task stops on
+    /// [`shutdown`](Self::shutdown).
+    pub fn start_health_check(&self) -> tokio::task::JoinHandle<()> {
+        let ws = Arc::clone(&self.ws);
+        let ws_healthy = Arc::clone(&self.ws_healthy);
+        self.http.start_health_check_with(move || {
+            let ws = Arc::clone(&ws);
+            let ws_healthy = Arc::clone(&ws_healthy);
+            async move {
+                let healthy = ws.check_health().await;
+                ws_healthy.store(healthy, Ordering::Relaxed);
+                trace!(
+                    healthy,
+                    total = ws.endpoint_count(),
+                    "WebSocket health check cycle complete"
+                );
+            }
+        })
+    }
+
+    /// Number of WebSocket endpoints that answered the latest health check.
+    ///
+    /// 0 until the health check task has completed a cycle.
+    pub fn ws_healthy_endpoints(&self) -> usize {
+        self.ws_healthy.load(Ordering::Relaxed)
+    }
+
+    /// Shut down both pools and the health check task.
+    pub async fn shutdown(&self) {
+        info!("Full node pool shutting down");
+        self.ws.shutdown();
+        self.http.shutdown().await;
+    }
+}
+
+/// Builder for [`FullNodePool`].
+pub struct FullNodePoolBuilder {
+    endpoints: Vec<RpcEndpoint>,
+    config: RpcPoolConfig,
+    ws_config: WsPoolConfig,
+}
+
+impl FullNodePoolBuilder {
+    /// Create a builder for a pool over `endpoints`.
+    pub fn new(endpoints: Vec<RpcEndpoint>) -> Self {
+        Self {
+            endpoints,
+            config: RpcPoolConfig::default(),
+            ws_config: WsPoolConfig::default(),
+        }
+    }
+
+    /// Builder: configure the HTTP pool.
+    ///
+    /// The configuration's own endpoints are replaced by the builder's.
+    pub fn with_config(mut self, config: RpcPoolConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Builder: configure the WebSocket pool.
+    pub fn with_ws_config(mut self, ws_config: WsPoolConfig) -> Self {
+        self.ws_config = ws_config;
+        self
+    }
+
+    /// Build both pools.
+    ///
+    /// Fails like [`RpcPool::new`], or with
+    /// [`RpcPoolError::NoWebSocketEndpoints`] if no endpoint has a `ws_url`.
+    pub fn build(self) -> Result<FullNodePool, RpcPoolError> {
+        let ws = WsPool::with_config(self.endpoints.clone(), self.ws_config)?;
+        let http = RpcPool::new(self.config.with_endpoints(self.endpoints))?;
+
+        Ok(FullNodePool {
+            http: Arc::new(http),
+            ws: Arc::new(ws),
+            ws_healthy: Arc::new(AtomicUsize::new(0)),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_requires_websocket_endpoint() {
+        let result = FullNodePoolBuilder::new(vec![RpcEndpoint::new("https://rpc.example.com")])
+            .build();
+        assert!(matches!(result, Err(RpcPoolError::NoWebSocketEndpoints)));
+    }
+
+    #[tokio::test]
+    async fn test_routes_requests_over_http() {
+        let pool = FullNodePoolBuilder::new(vec![
+            RpcEndpoint::new("https://rpc1.example.com")
+                .with_ws_url("wss://rpc1.example.com")
+                .with_priority(1),
+            RpcEndpoint::new("https://rpc2.example.com").with_priority(2),
+        ])
+        .build()
+        .unwrap();
+
+        assert_eq!(pool.http_pool().get_all_urls().len(), 2);
+        assert_eq!(pool.ws_pool().ws_urls(), vec!["wss://rpc1.example.com"]);
+
+        let used = pool
+            .execute(|url: url::Url| async move { Ok::<_, std::io::Error>(url.to_string()) })
+            .await
+            .unwrap();
+        assert_eq!(used, "https://rpc1.example.com/");
+    }
+}
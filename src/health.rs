@@ -0,0 +1,218 @@
+//! Health check probes and health transition events.
+//!
+//! The pool probes endpoints with [`HealthCheckMethod::BlockNumber`] by default.
+//! Chains where `eth_blockNumber` is not meaningful can switch to `net_version`
+//! or any custom JSON-RPC call via [`RpcPoolConfig::with_health_check_method`].
+//!
+//! [`RpcPoolConfig::with_health_check_method`]: crate::RpcPoolConfig::with_health_check_method
+
+use alloy::providers::{Provider, ProviderBuilder};
+use alloy::transports::http::reqwest;
+use std::time::Duration;
+
+/// Endpoint health transition published by [`RpcPool::subscribe_health_events`].
+///
+/// [`RpcPool::subscribe_health_events`]: crate::RpcPool::subscribe_health_events
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum HealthEvent {
+    /// A healthy endpoint was marked unhealthy.
+    EndpointMarkedUnhealthy {
+        /// Endpoint URL.
+        url: String,
+        /// Endpoint name.
+        name: String,
+        /// Consecutive errors at the time it was marked unhealthy.
+        consecutive_errors: u32,
+    },
+
+    /// An unhealthy endpoint became healthy again.
+    EndpointRecovered {
+        /// Endpoint URL.
+        url: String,
+        /// Endpoint name.
+        name: String,
+    },
+
+    /// The last healthy endpoint was marked unhealthy.
+    AllEndpointsUnhealthy,
+
+    /// The pool is shutting down; no further events follow.
+    PoolShutdown,
+}
+
+/// JSON-RPC call used to decide whether an endpoint is healthy.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum HealthCheckMethod {
+    /// `eth_blockNumber`; the reported block also feeds the block-lag check.
+    #[default]
+    BlockNumber,
+
+    /// `net_version`.
+    NetVersion,
+
+    /// An arbitrary call whose result must be a JSON object containing `expected_key`.
+    Custom {
+        /// JSON-RPC method name.
+        method: String,
+        /// Call parameters, usually an array (e.g. `json!([])`).
+        params: serde_json::Value,
+        /// Key that must be present in the result object.
+        expected_key: String,
+    },
+}
+
+impl HealthCheckMethod {
+    /// JSON-RPC method name sent by this probe.
+    pub fn method_name(&self) -> &str {
+        match self {
+            HealthCheckMethod::BlockNumber => "eth_blockNumber",
+            HealthCheckMethod::NetVersion => "net_version",
+            HealthCheckMethod::Custom { method, .. } => method,
+        }
+    }
+
+    /// Probe `url` through `client`, returning the block number when the method reports one.
+    pub(crate) async fn probe(
+        &self,
+        client: &reqwest::Client,
+        url: &str,
+        timeout: Duration,
+    ) -> Result<Option<u64>, String> {
+        let url: url::Url = url.parse().map_err(|e| format!("Invalid URL: {}", e))?;
+        let provider = ProviderBuilder::new().connect_reqwest(client.clone(), url);
+
+        let call = async {
+            match self {
+                HealthCheckMethod::BlockNumber => provider
+                    .get_block_number()
+                    .await
+                    .map(Some)
+                    .map_err(|e| e.to_string()),
+                HealthCheckMethod::NetVersion => provider
+                    .get_net_version()
+                    .await
+                    .map(|_| None)
+                    .map_err(|e| e.to_string()),
+                HealthCheckMethod::Custom {
+                    method,
+                    params,
+                    expected_key,
+                } => {
+                    let result: serde_json::Value = provider
+                        .raw_request(method.clone().into(), params)
+                        .await
+                        .map_err(|e| e.to_string())?;
+                    if result.get(expected_key).is_some() {
+                        Ok(None)
+                    } else {
+                        Err(format!(
+                            "{} response is missing key '{}'",
+                            method, expected_key
+                        ))
+                    }
+                }
+            }
+        };
+
+        tokio::time::timeout(timeout, call)
+            .await
+            .map_err(|_| format!("Health check timeout after {}ms", timeout.as_millis()))?
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    /// Answers a single JSON-RPC method; anything else gets "method not found".
+    struct MethodResponder {
+        method: &'static str,
+        result: serde_json::Value,
+    }
+
+    impl wiremock::Respond for MethodResponder {
+        fn respond(&self, request: &wiremock::Request) -> wiremock::ResponseTemplate {
+            let body: serde_json::Value = serde_json::from_slice(&request.body).unwrap();
+            let response = if body["method"] == self.method {
+                json!({ "jsonrpc": "2.0", "id": body["id"], "result": self.result })
+            } else {
+                json!({
+                    "jsonrpc": "2.0",
+                    "id": body["id"],
+                    "error": { "code": -32601, "message": "method not found" },
+                })
+            };
+            wiremock::ResponseTemplate::new(200).set_body_json(response)
+        }
+    }
+
+    async fn mock_endpoint(
+        method: &'static str,
+        result: serde_json::Value,
+    ) -> wiremock::MockServer {
+        let server = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::method("POST"))
+            .respond_with(MethodResponder { method, result })
+            .mount(&server)
+            .await;
+        server
+    }
+
+    const TIMEOUT: Duration = Duration::from_secs(5);
+
+    #[tokio::test]
+    async fn test_block_number_probe() {
+        let server = mock_endpoint("eth_blockNumber", json!("0x10")).await;
+        let probe = HealthCheckMethod::BlockNumber
+            .probe(&reqwest::Client::new(), &server.uri(), TIMEOUT)
+            .await;
+        assert_eq!(probe, Ok(Some(16)));
+    }
+
+    #[tokio::test]
+    async fn test_net_version_probe() {
+        let server = mock_endpoint("net_version", json!("1")).await;
+        assert_eq!(
+            HealthCheckMethod::NetVersion
+                .probe(&reqwest::Client::new(), &server.uri(), TIMEOUT)
+                .await,
+            Ok(None)
+        );
+
+        // eth_blockNumber is not served by this endpoint
+        assert!(HealthCheckMethod::BlockNumber
+            .probe(&reqwest::Client::new(), &server.uri(), TIMEOUT)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_custom_probe_checks_expected_key() {
+        let server = mock_endpoint("eth_syncing_status", json!({ "healthy": true })).await;
+        let custom = |expected_key: &str| HealthCheckMethod::Custom {
+            method: "eth_syncing_status".to_string(),
+            params: json!([]),
+            expected_key: expected_key.to_string(),
+        };
+
+        assert_eq!(
+            custom("healthy")
+                .probe(&reqwest::Client::new(), &server.uri(), TIMEOUT)
+                .await,
+            Ok(None)
+        );
+        let missing = custom("peers")
+            .probe(&reqwest::Client::new(), &server.uri(), TIMEOUT)
+            .await;
+        assert!(missing.unwrap_err().contains("missing key 'peers'"));
+    }
+
+    #[tokio::test]
+    async fn test_probe_invalid_url() {
+        let probe = HealthCheckMethod::BlockNumber
+            .probe(&reqwest::Client::new(), "not a url", TIMEOUT)
+            .await;
+        assert!(probe.unwrap_err().starts_with("Invalid URL"));
+    }
+}
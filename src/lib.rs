@@ -49,26 +49,55 @@
 //! }
 //! ```
 
+pub mod config;
+mod dedup;
 pub mod endpoint;
 pub mod error;
+#[cfg(feature = "ws")]
+pub mod full_node;
+pub mod health;
 pub mod metrics;
+pub mod multi;
 pub mod pool;
 pub mod presets;
+mod probe;
+mod rate_limit;
+#[cfg(feature = "tower")]
+pub mod service;
+pub mod snapshot;
+#[cfg(feature = "socks5")]
+mod socks;
 pub mod strategies;
+mod sticky;
 pub mod tiered;
 #[cfg(feature = "ws")]
 pub mod ws;
 
-pub use endpoint::{EndpointCapabilities, EndpointGrade, EndpointStats, RpcEndpoint};
+pub use config::{RpcPoolFileConfig, TieredEndpointFileConfig, TieredPoolConfigFile};
+pub use endpoint::{
+    CapabilityFilter, EndpointCapabilities, EndpointGrade, EndpointStats, LatencyReservoir,
+    Region, RpcEndpoint, LATENCY_BUCKETS_MS,
+};
 pub use error::RpcPoolError;
-pub use metrics::RpcPoolMetrics;
-pub use pool::{HealthSummary, RpcPool, RpcPoolConfig};
+pub use multi::MultiChainPool;
+pub use health::{HealthCheckMethod, HealthEvent};
+pub use metrics::{EndpointMetricsDiff, MetricsDiff, RpcPoolMetrics};
+pub use pool::{
+    ExecutionMetadata, HealthSummary, RpcPool, RpcPoolConfig, StrategyFactory, WarmUpReport,
+};
+pub use snapshot::{EndpointSnapshot, PoolSnapshot};
 pub use strategies::{
-    FailoverStrategy, LatencyBasedStrategy, RateAwareStrategy, RoundRobinStrategy,
-    SelectionStrategy,
+    AdaptiveMode, AdaptiveStrategy, CircuitBreakerStrategy, CircuitState, FailoverStrategy,
+    GeoAwareStrategy, HashRingStrategy, LatencyBasedStrategy, RateAwareStrategy,
+    RoundRobinStrategy, SelectionContext, SelectionStrategy, StrategyKind, StrictPriorityStrategy,
+    ThrottleAwareStrategy, ThrottleRecorder,
 };
 pub use tiered::{
     EndpointTier, RequestPriority, TieredEndpoint, TieredPool, TieredPoolBuilder, TieredPoolConfig,
 };
+#[cfg(feature = "tower")]
+pub use service::RpcRequest;
+#[cfg(feature = "ws")]
+pub use full_node::{FullNodePool, FullNodePoolBuilder};
 #[cfg(feature = "ws")]
 pub use ws::{WsPool, WsPoolConfig};
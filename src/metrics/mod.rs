@@ -0,0 +1,329 @@
+//! Metrics collection for monitoring RPC pool performance.
+
+use crate::endpoint::{EndpointStats, LATENCY_BUCKETS_MS};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::Duration;
+
+#[cfg(feature = "metrics")]
+pub mod prometheus;
+
+/// Aggregated metrics for the RPC pool.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RpcPoolMetrics {
+    /// Total number of requests made through the pool.
+    pub total_requests: u64,
+
+    /// Number of times failover occurred.
+    pub failovers: u64,
+
+    /// Number of requests retried on a fallback pool by
+    /// [`RpcPool::execute_with_fallback_pool`](crate::RpcPool::execute_with_fallback_pool).
+    #[serde(default)]
+    pub cross_pool_failovers: u64,
+
+    /// Name of the current primary endpoint.
+    pub current_endpoint: String,
+
+    /// Statistics for each endpoint.
+    pub endpoints: Vec<EndpointMetrics>,
+}
+
+/// Serializable endpoint metrics (subset of EndpointStats).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EndpointMetrics {
+    pub url: String,
+    pub name: String,
+    pub total_requests: u64,
+    pub successful_requests: u64,
+    pub failed_requests: u64,
+    pub avg_latency_ms: f64,
+    pub last_latency_ms: u64,
+    /// Successful requests per latency bucket, keyed by the bucket's upper bound
+    /// in milliseconds (see [`LATENCY_BUCKETS_MS`]). Counts are not cumulative
+    /// and empty buckets are omitted.
+    pub latency_histogram: HashMap<u64, u64>,
+    /// Sum of all successful request latencies in milliseconds.
+    pub latency_sum_ms: u64,
+    pub is_healthy: bool,
+    pub consecutive_errors: u32,
+    pub success_rate: f64,
+}
+
+/// Change between two [`RpcPoolMetrics`] snapshots, from [`RpcPoolMetrics::diff`].
+///
+/// Rates are per second over [`elapsed`](Self::elapsed).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetricsDiff {
+    /// Time between the two snapshots.
+    pub elapsed: Duration,
+
+    /// Requests started per second.
+    pub request_rate: f64,
+
+    /// Failed endpoint attempts per second.
+    pub error_rate: f64,
+
+    /// Failovers per second.
+    pub failover_rate: f64,
+
+    /// Change of each endpoint in the later snapshot, keyed by URL.
+    pub per_endpoint: HashMap<String, EndpointMetricsDiff>,
+}
+
+/// Change of one endpoint between two snapshots.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EndpointMetricsDiff {
+    /// Requests sent to the endpoint per second.
+    pub request_rate: f64,
+
+    /// Failed requests per second.
+    pub error_rate: f64,
+
+    /// Change of the average latency in milliseconds (positive = slower).
+    pub latency_change_ms: f64,
+
+    /// Change of the success rate in percentage points.
+    pub success_rate_change: f64,
+}
+
+impl From<&EndpointStats> for EndpointMetrics {
+    fn from(stats: &EndpointStats) -> Self {
+        Self {
+            url: stats.url.clone(),
+            name: stats.name.clone(),
+            total_requests: stats.total_requests,
+            successful_requests: stats.successful_requests,
+            failed_requests: stats.failed_requests,
+            avg_latency_ms: stats.avg_latency_ms,
+            last_latency_ms: stats.last_latency_ms,
+            latency_histogram: LATENCY_BUCKETS_MS
+                .iter()
+                .copied()
+                .zip(stats.latency_histogram)
+                .filter(|&(_, count)| count > 0)
+                .collect(),
+            latency_sum_ms: stats.latency_sum_ms,
+            is_healthy: stats.is_healthy,
+            consecutive_errors: stats.consecutive_errors,
+            success_rate: stats.success_rate(),
+        }
+    }
+}
+
+impl RpcPoolMetrics {
+    /// Get the total success rate across all endpoints.
+    pub fn total_success_rate(&self) -> f64 {
+        let total: u64 = self.endpoints.iter().map(|e| e.total_requests).sum();
+        let successful: u64 = self.endpoints.iter().map(|e| e.successful_requests).sum();
+
+        if total == 0 {
+            return 100.0;
+        }
+        (successful as f64 / total as f64) * 100.0
+    }
+
+    /// Get the number of healthy endpoints.
+    pub fn healthy_count(&self) -> usize {
+        self.endpoints.iter().filter(|e| e.is_healthy).count()
+    }
+
+    /// Get the average latency across all endpoints.
+    pub fn avg_latency(&self) -> f64 {
+        let healthy: Vec<_> = self
+            .endpoints
+            .iter()
+            .filter(|e| e.is_healthy && e.avg_latency_ms > 0.0)
+            .collect();
+
+        if healthy.is_empty() {
+            return 0.0;
+        }
+
+        let sum: f64 = healthy.iter().map(|e| e.avg_latency_ms).sum();
+        sum / healthy.len() as f64
+    }
+
+    /// Compute rates of change since an earlier `baseline` snapshot taken `elapsed` ago.
+    ///
+    /// Counters that went down (e.g. after an endpoint's stats were reset)
+    /// count as zero. Endpoints missing from the baseline are compared against
+    /// a fresh endpoint: no requests, no latency and a 100% success rate.
+    /// Rates are 0 when `elapsed` is zero.
+    pub fn diff(&self, baseline: &RpcPoolMetrics, elapsed: Duration) -> MetricsDiff {
+        let secs = elapsed.as_secs_f64();
+        let rate = |current: u64, previous: u64| {
+            if secs > 0.0 {
+                current.saturating_sub(previous) as f64 / secs
+            } else {
+                0.0
+            }
+        };
+
+        let previous: HashMap<&str, &EndpointMetrics> = baseline
+            .endpoints
+            .iter()
+            .map(|e| (e.url.as_str(), e))
+            .collect();
+        let per_endpoint: HashMap<String, EndpointMetricsDiff> = self
+            .endpoints
+            .iter()
+            .map(|current| {
+                let previous = previous.get(current.url.as_str());
+                let diff = EndpointMetricsDiff {
+                    request_rate: rate(
+                        current.total_requests,
+                        previous.map_or(0, |p| p.total_requests),
+                    ),
+                    error_rate: rate(
+                        current.failed_requests,
+                        previous.map_or(0, |p| p.failed_requests),
+                    ),
+                    latency_change_ms: current.avg_latency_ms
+                        - previous.map_or(0.0, |p| p.avg_latency_ms),
+                    success_rate_change: current.success_rate
+                        - previous.map_or(100.0, |p| p.success_rate),
+                };
+                (current.url.clone(), diff)
+            })
+            .collect();
+
+        MetricsDiff {
+            elapsed,
+            request_rate: rate(self.total_requests, baseline.total_requests),
+            error_rate: per_endpoint.values().map(|e| e.error_rate).sum(),
+            failover_rate: rate(self.failovers, baseline.failovers),
+            per_endpoint,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn endpoint(url: &str, successful: u64, failed: u64, avg_latency_ms: f64) -> EndpointMetrics {
+        let total = successful + failed;
+        EndpointMetrics {
+            url: url.to_string(),
+            name: url.to_string(),
+            total_requests: total,
+            successful_requests: successful,
+            failed_requests: failed,
+            avg_latency_ms,
+            last_latency_ms: avg_latency_ms as u64,
+            latency_histogram: HashMap::new(),
+            latency_sum_ms: 0,
+            is_healthy: true,
+            consecutive_errors: 0,
+            success_rate: if total == 0 {
+                100.0
+            } else {
+                successful as f64 / total as f64 * 100.0
+            },
+        }
+    }
+
+    fn snapshot(
+        total_requests: u64,
+        failovers: u64,
+        endpoints: Vec<EndpointMetrics>,
+    ) -> RpcPoolMetrics {
+        RpcPoolMetrics {
+            total_requests,
+            failovers,
+            cross_pool_failovers: 0,
+            current_endpoint: String::new(),
+            endpoints,
+        }
+    }
+
+    #[test]
+    fn test_diff_rates() {
+        let baseline = snapshot(
+            100,
+            10,
+            vec![
+                endpoint("https://rpc1.example.com", 90, 10, 50.0),
+                endpoint("https://rpc2.example.com", 10, 0, 80.0),
+            ],
+        );
+        let current = snapshot(
+            300,
+            30,
+            vec![
+                endpoint("https://rpc1.example.com", 150, 50, 70.0),
+                endpoint("https://rpc2.example.com", 110, 0, 60.0),
+            ],
+        );
+
+        let diff = current.diff(&baseline, Duration::from_secs(10));
+        assert_eq!(diff.elapsed, Duration::from_secs(10));
+        assert_eq!(diff.request_rate, 20.0);
+        assert_eq!(diff.error_rate, 4.0);
+        assert_eq!(diff.failover_rate, 2.0);
+
+        let rpc1 = &diff.per_endpoint["https://rpc1.example.com"];
+        assert_eq!(rpc1.request_rate, 10.0);
+        assert_eq!(rpc1.error_rate, 4.0);
+        assert_eq!(rpc1.latency_change_ms, 20.0);
+        assert_eq!(rpc1.success_rate_change, -15.0);
+
+        let rpc2 = &diff.per_endpoint["https://rpc2.example.com"];
+        assert_eq!(rpc2.request_rate, 10.0);
+        assert_eq!(rpc2.error_rate, 0.0);
+        assert_eq!(rpc2.latency_change_ms, -20.0);
+        assert_eq!(rpc2.success_rate_change, 0.0);
+    }
+
+    #[test]
+    fn test_diff_new_endpoint_and_reset_counters() {
+        let baseline = snapshot(
+            50,
+            5,
+            vec![endpoint("https://rpc1.example.com", 40, 10, 50.0)],
+        );
+        let current = snapshot(
+            60,
+            5,
+            vec![
+                endpoint("https://rpc1.example.com", 4, 1, 40.0),
+                endpoint("https://rpc2.example.com", 3, 1, 30.0),
+            ],
+        );
+
+        let diff = current.diff(&baseline, Duration::from_secs(2));
+        assert_eq!(diff.request_rate, 5.0);
+        assert_eq!(diff.failover_rate, 0.0);
+
+        // rpc1's stats were reset, so its counters went down
+        let rpc1 = &diff.per_endpoint["https://rpc1.example.com"];
+        assert_eq!(rpc1.request_rate, 0.0);
+        assert_eq!(rpc1.error_rate, 0.0);
+
+        let rpc2 = &diff.per_endpoint["https://rpc2.example.com"];
+        assert_eq!(rpc2.request_rate, 2.0);
+        assert_eq!(rpc2.error_rate, 0.5);
+        assert_eq!(rpc2.latency_change_ms, 30.0);
+        assert_eq!(rpc2.success_rate_change, -25.0);
+        assert_eq!(diff.error_rate, 0.5);
+    }
+
+    #[test]
+    fn test_diff_zero_elapsed() {
+        let baseline = snapshot(0, 0, vec![]);
+        let current = snapshot(
+            10,
+            2,
+            vec![endpoint("https://rpc1.example.com", 10, 0, 5.0)],
+        );
+
+        let diff = current.diff(&baseline, Duration::ZERO);
+        assert_eq!(diff.request_rate, 0.0);
+        assert_eq!(diff.failover_rate, 0.0);
+        assert_eq!(
+            diff.per_endpoint["https://rpc1.example.com"].latency_change_ms,
+            5.0
+        );
+    }
+}
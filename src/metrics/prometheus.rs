@@ -0,0 +1,150 @@
+//! Prometheus text exposition for pool metrics.
+//!
+//! Enabled with the `metrics` feature.
+
+use super::{EndpointMetrics, RpcPoolMetrics};
+use crate::endpoint::LATENCY_BUCKETS_MS;
+use ::prometheus::proto::{Bucket, Histogram, LabelPair, Metric, MetricFamily, MetricType};
+use ::prometheus::{Encoder, GaugeVec, IntCounter, Opts, Registry, TextEncoder};
+
+/// Labels attached to every per-endpoint metric.
+const ENDPOINT_LABELS: &[&str] = &["endpoint", "url"];
+
+impl RpcPoolMetrics {
+    /// Render the metrics in the Prometheus text exposition format.
+    ///
+    /// All metric names are prefixed with `namespace` (omitted when empty).
+    /// Per-endpoint metrics carry `endpoint` (name) and `url` labels.
+    /// Successful request latencies are exported as the
+    /// `request_latency_ms` histogram with [`LATENCY_BUCKETS_MS`] buckets.
+    pub fn to_prometheus_text(&self, namespace: &str) -> String {
+        let registry = Registry::new();
+
+        let total_requests = IntCounter::with_opts(
+            Opts::new(
+                "total_requests_total",
+                "Total requests made through the pool",
+            )
+            .namespace(namespace),
+        )
+        .expect("valid metric");
+        total_requests.inc_by(self.total_requests);
+
+        let failovers = IntCounter::with_opts(
+            Opts::new(
+                "failovers_total",
+                "Number of times a request failed over to another endpoint",
+            )
+            .namespace(namespace),
+        )
+        .expect("valid metric");
+        failovers.inc_by(self.failovers);
+
+        let cross_pool_failovers = IntCounter::with_opts(
+            Opts::new(
+                "cross_pool_failovers_total",
+                "Number of requests retried on a fallback pool",
+            )
+            .namespace(namespace),
+        )
+        .expect("valid metric");
+        cross_pool_failovers.inc_by(self.cross_pool_failovers);
+
+        let latency = endpoint_gauge(
+            namespace,
+            "latency_ms",
+            "Average endpoint latency in milliseconds",
+        );
+        let success_rate = endpoint_gauge(
+            namespace,
+            "success_rate",
+            "Endpoint success rate in percent",
+        );
+        let is_healthy = endpoint_gauge(
+            namespace,
+            "is_healthy",
+            "Whether the endpoint is healthy (1) or not (0)",
+        );
+
+        for e in &self.endpoints {
+            let labels = [e.name.as_str(), e.url.as_str()];
+            latency.with_label_values(&labels).set(e.avg_latency_ms);
+            success_rate.with_label_values(&labels).set(e.success_rate);
+            is_healthy
+                .with_label_values(&labels)
+                .set(if e.is_healthy { 1.0 } else { 0.0 });
+        }
+
+        for collector in [
+            Box::new(total_requests) as Box<dyn ::prometheus::core::Collector>,
+            Box::new(failovers),
+            Box::new(cross_pool_failovers),
+            Box::new(latency),
+            Box::new(success_rate),
+            Box::new(is_healthy),
+        ] {
+            registry.register(collector).expect("unique metric names");
+        }
+
+        let mut families = registry.gather();
+        if !self.endpoints.is_empty() {
+            // The encoder rejects families without samples
+            families.push(latency_histogram(namespace, &self.endpoints));
+        }
+
+        let mut buffer = Vec::new();
+        TextEncoder::new()
+            .encode(&families, &mut buffer)
+            .expect("text encoding never fails");
+        String::from_utf8(buffer).expect("prometheus text is valid UTF-8")
+    }
+}
+
+/// Build the per-endpoint latency histogram from the pre-bucketed counts.
+///
+/// The `prometheus` histogram type only supports observing values one at a
+/// time, so the metric family is assembled directly.
+fn latency_histogram(namespace: &str, endpoints: &[EndpointMetrics]) -> MetricFamily {
+    let name = if namespace.is_empty() {
+        "request_latency_ms".to_string()
+    } else {
+        format!("{}_request_latency_ms", namespace)
+    };
+
+    let mut family = MetricFamily::default();
+    family.set_name(name);
+    family.set_help("Successful request latency in milliseconds".to_string());
+    family.set_field_type(MetricType::HISTOGRAM);
+
+    for e in endpoints {
+        let mut histogram = Histogram::default();
+        let mut cumulative = 0;
+        for le in LATENCY_BUCKETS_MS {
+            cumulative += e.latency_histogram.get(&le).copied().unwrap_or(0);
+            let mut bucket = Bucket::default();
+            bucket.set_upper_bound(le as f64);
+            bucket.set_cumulative_count(cumulative);
+            histogram.mut_bucket().push(bucket);
+        }
+        histogram.set_sample_count(e.successful_requests);
+        histogram.set_sample_sum(e.latency_sum_ms as f64);
+
+        let mut metric = Metric::default();
+        for (label, value) in ENDPOINT_LABELS.iter().zip([&e.name, &e.url]) {
+            let mut pair = LabelPair::default();
+            pair.set_name(label.to_string());
+            pair.set_value(value.clone());
+            metric.mut_label().push(pair);
+        }
+        metric.set_histogram(histogram);
+        family.mut_metric().push(metric);
+    }
+
+    family
+}
+
+/// Create a per-endpoint gauge with the standard endpoint labels.
+fn endpoint_gauge(namespace: &str, name: &str, help: &str) -> GaugeVec {
+    GaugeVec::new(Opts::new(name, help).namespace(namespace), ENDPOINT_LABELS)
+        .expect("valid metric")
+}
@@ -0,0 +1,169 @@
+//! Routing requests across chains.
+//!
+//! A [`MultiChainPool`] holds one [`RpcPool`] per chain ID, for services that
+//! query several chains from one place.
+//!
+//! ```rust,no_run
+//! use web3_rpc_pool::multi::MultiChainPool;
+//! use web3_rpc_pool::presets::chain_id;
+//! use alloy::providers::{Provider, ProviderBuilder};
+//!
+//! # async fn example() -> Result<(), web3_rpc_pool::RpcPoolError> {
+//! let pools = MultiChainPool::from_presets(&[chain_id::ETHEREUM, chain_id::ARBITRUM_ONE])?;
+//! let block = pools
+//!     .execute(chain_id::ARBITRUM_ONE, |url| async move {
+//!         ProviderBuilder::new().connect_http(url).get_block_number().await
+//!     })
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::RpcPoolError;
+use crate::pool::{HealthSummary, RpcPool, RpcPoolConfig};
+use crate::presets;
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Arc;
+use tracing::debug;
+
+/// One [`RpcPool`] per chain, keyed by chain ID.
+#[derive(Default)]
+pub struct MultiChainPool {
+    pools: HashMap<u64, Arc<RpcPool>>,
+}
+
+impl MultiChainPool {
+    /// Create a pool with no chains.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a pool for each chain from its [`presets::default_endpoints`].
+    ///
+    /// Fails with [`RpcPoolError::UnknownChain`] for a chain without presets.
+    pub fn from_presets(chain_ids: &[u64]) -> Result<Self, RpcPoolError> {
+        let mut multi = Self::new();
+        for &chain_id in chain_ids {
+            let endpoints = presets::default_endpoints(chain_id);
+            if endpoints.is_empty() {
+                return Err(RpcPoolError::UnknownChain(chain_id));
+            }
+            let pool = RpcPool::new(RpcPoolConfig::new().with_endpoints(endpoints))?;
+            multi.add_chain(chain_id, Arc::new(pool));
+        }
+        Ok(multi)
+    }
+
+    /// Route requests for `chain_id` to `pool`, replacing any pool already registered.
+    pub fn add_chain(&mut self, chain_id: u64, pool: Arc<RpcPool>) {
+        debug!(
+            chain_id,
+            chain = presets::chain_name(chain_id),
+            endpoints = pool.health_summary().total,
+            "Registered chain pool"
+        );
+        self.pools.insert(chain_id, pool);
+    }
+
+    /// Get the pool for `chain_id`.
+    pub fn get_chain(&self, chain_id: u64) -> Option<&Arc<RpcPool>> {
+        self.pools.get(&chain_id)
+    }
+
+    /// Registered chain IDs, in ascending order.
+    pub fn chain_ids(&self) -> Vec<u64> {
+        let mut chain_ids: Vec<u64> = self.pools.keys().copied().collect();
+        chain_ids.sort_unstable();
+        chain_ids
+    }
+
+    /// Execute a request on `chain_id`'s pool with automatic failover.
+    ///
+    /// Fails with [`RpcPoolError::UnknownChain`] if the chain is not registered.
+    pub async fn execute<T, E, F, Fut>(&self, chain_id: u64, f: F) -> Result<T, RpcPoolError>
+    where
+        F: Fn(url::Url) -> Fut + Clone,
+        Fut: Future<Output = Result<T, E>>,
+        E: std::error::Error,
+    {
+        self.pools
+            .get(&chain_id)
+            .ok_or(RpcPoolError::UnknownChain(chain_id))?
+            .execute(f)
+            .await
+    }
+
+    /// Health summary of every registered chain.
+    pub fn health_summary_all(&self) -> HashMap<u64, HealthSummary> {
+        self.pools
+            .iter()
+            .map(|(chain_id, pool)| (*chain_id, pool.health_summary()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::endpoint::RpcEndpoint;
+    use crate::presets::chain_id;
+
+    fn single_endpoint_pool(url: &str) -> Arc<RpcPool> {
+        Arc::new(
+            RpcPool::new(RpcPoolConfig::new().with_endpoints(vec![RpcEndpoint::new(url)])).unwrap(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_routes_by_chain_id() {
+        let mut multi = MultiChainPool::new();
+        multi.add_chain(
+            chain_id::ETHEREUM,
+            single_endpoint_pool("https://eth.example.com"),
+        );
+        multi.add_chain(
+            chain_id::BASE,
+            single_endpoint_pool("https://base.example.com"),
+        );
+
+        let host = |url: url::Url| async move {
+            Ok::<_, std::io::Error>(url.host_str().unwrap().to_string())
+        };
+        assert_eq!(
+            multi.execute(chain_id::BASE, host).await.unwrap(),
+            "base.example.com"
+        );
+        assert_eq!(
+            multi.execute(chain_id::ETHEREUM, host).await.unwrap(),
+            "eth.example.com"
+        );
+
+        let result = multi.execute(chain_id::OPTIMISM, host).await;
+        assert!(matches!(
+            result,
+            Err(RpcPoolError::UnknownChain(chain_id::OPTIMISM))
+        ));
+        assert_eq!(multi.chain_ids(), vec![chain_id::ETHEREUM, chain_id::BASE]);
+    }
+
+    #[test]
+    fn test_from_presets() {
+        let multi =
+            MultiChainPool::from_presets(&[chain_id::ETHEREUM, chain_id::ARBITRUM_ONE]).unwrap();
+
+        let health = multi.health_summary_all();
+        assert_eq!(health.len(), 2);
+        assert_eq!(
+            health[&chain_id::ARBITRUM_ONE].total,
+            presets::default_endpoints(chain_id::ARBITRUM_ONE).len()
+        );
+        assert_eq!(health[&chain_id::ETHEREUM].unhealthy, 0);
+
+        assert!(matches!(
+            MultiChainPool::from_presets(&[chain_id::ETHEREUM, 999_999]),
+            Err(RpcPoolError::UnknownChain(999_999))
+        ));
+    }
+}
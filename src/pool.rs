@@ -1,17 +1,30 @@
 //! Core RPC pool implementation.
 
-use crate::endpoint::{EndpointStats, RpcEndpoint};
+use crate::dedup::InflightRequests;
+use crate::endpoint::{CapabilityFilter, EndpointCapabilities, EndpointStats, RpcEndpoint};
 use crate::error::RpcPoolError;
+use crate::health::{HealthCheckMethod, HealthEvent};
 use crate::metrics::{EndpointMetrics, RpcPoolMetrics};
-use crate::strategies::SelectionStrategy;
+use crate::rate_limit::TokenBucket;
+use crate::snapshot::{EndpointSnapshot, PoolSnapshot};
+use crate::sticky::StickySessionMap;
+use crate::strategies::{parse_retry_after, SelectionContext, SelectionStrategy};
+use crate::tiered::RequestPriority;
 
-use alloy::providers::{Provider, ProviderBuilder};
-use parking_lot::RwLock;
+use alloy::transports::http::reqwest;
+use futures_util::stream::{FuturesUnordered, StreamExt};
+use futures_util::FutureExt;
+use parking_lot::{Mutex, RwLock};
+use rand::Rng;
+use std::collections::hash_map::DefaultHasher;
 use std::collections::{HashMap, HashSet};
 use std::future::Future;
+use std::hash::{Hash, Hasher};
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
+use tokio::sync::broadcast;
+use tokio_util::sync::CancellationToken;
 use tracing::{debug, error, info, trace, warn, instrument};
 
 /// Maximum length for error messages to prevent unbounded memory growth.
@@ -23,34 +36,101 @@ const DEFAULT_REQUEST_TIMEOUT_SECS: u64 = 30;
 /// Default health check timeout in seconds.
 const DEFAULT_HEALTH_CHECK_TIMEOUT_SECS: u64 = 10;
 
+/// Default idle time after which a sticky session binding expires.
+const DEFAULT_STICKY_SESSION_TTL_SECS: u64 = 60;
+
+/// Default number of health events buffered for each subscriber.
+const DEFAULT_HEALTH_EVENT_CAPACITY: usize = 64;
+
+/// Default delay before a speculative request also goes to the second endpoint.
+const DEFAULT_SPECULATIVE_DELAY_MS: u64 = 50;
+
+/// Success rate (%) below which a healthy endpoint counts as degraded.
+const DEGRADED_SUCCESS_RATE: f64 = 95.0;
+
+/// Requests an endpoint must have served before it can count as degraded.
+const DEGRADED_MIN_REQUESTS: u64 = 10;
+
 /// Summary of endpoint health status.
 #[derive(Debug, Clone, Copy)]
 pub struct HealthSummary {
-    /// Number of healthy endpoints.
+    /// Number of healthy endpoints, including degraded ones.
     pub healthy: usize,
+    /// Healthy endpoints with a success rate below 95% over at least 10 requests.
+    pub degraded: usize,
     /// Number of unhealthy endpoints.
     pub unhealthy: usize,
     /// Total number of endpoints.
     pub total: usize,
+    /// Healthy endpoints serving [`RpcPool::execute_read_only`] (same as `healthy`).
+    pub read_healthy: usize,
+    /// Healthy endpoints serving [`RpcPool::execute_write`]: the write endpoints
+    /// if any are configured, otherwise the same as `read_healthy`.
+    pub write_healthy: usize,
 }
 
 impl HealthSummary {
     /// Returns true if all endpoints are unhealthy.
     pub fn all_unhealthy(&self) -> bool {
-        self.healthy == 0 && self.total > 0
+        self.healthy == 0 && self.degraded == 0 && self.total > 0
+    }
+
+    /// Returns true if any healthy endpoint is degraded.
+    pub fn is_degraded(&self) -> bool {
+        self.degraded > 0
     }
 
-    /// Returns the percentage of healthy endpoints.
+    /// Returns the percentage of endpoints that are healthy and not degraded.
     pub fn health_percentage(&self) -> f64 {
         if self.total == 0 {
             return 0.0;
         }
-        (self.healthy as f64 / self.total as f64) * 100.0
+        let fully_healthy = self.healthy.saturating_sub(self.degraded);
+        (fully_healthy as f64 / self.total as f64) * 100.0
+    }
+}
+
+/// Result of [`RpcPool::warm_up`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WarmUpReport {
+    /// Number of endpoints that answered the probe.
+    pub healthy: usize,
+    /// Number of endpoints marked unhealthy because the probe failed.
+    pub unhealthy: usize,
+    /// Probe latency in milliseconds of each endpoint that answered, keyed by URL.
+    pub latencies: HashMap<String, u64>,
+}
+
+impl WarmUpReport {
+    /// Add the counts and latencies of `other` to this report.
+    pub(crate) fn merge(&mut self, other: WarmUpReport) {
+        self.healthy += other.healthy;
+        self.unhealthy += other.unhealthy;
+        self.latencies.extend(other.latencies);
     }
 }
 
+/// Which endpoint served a request, returned by [`RpcPool::execute_with_metadata`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExecutionMetadata {
+    /// Name of the endpoint that produced the result.
+    pub endpoint_name: String,
+    /// URL of the endpoint that produced the result.
+    pub endpoint_url: String,
+    /// Latency in milliseconds of the successful attempt.
+    pub latency_ms: u64,
+    /// Number of attempts made, including the successful one.
+    pub attempt_count: u32,
+}
+
+/// Builds a fresh selection strategy, see [`RpcPoolConfig::with_strategy_factory`].
+pub type StrategyFactory = Arc<dyn Fn() -> Box<dyn SelectionStrategy> + Send + Sync>;
+
 /// Configuration for the RPC pool.
-#[derive(Clone)]
+///
+/// Cloning a configuration with a [`strategy_factory`](Self::strategy_factory)
+/// gives the clone its own strategy instance, so pools built from a template
+/// keep independent strategy state. Without a factory, clones share the strategy.
 pub struct RpcPoolConfig {
     /// List of RPC endpoints (will be sorted by priority).
     pub endpoints: Vec<RpcEndpoint>,
@@ -58,20 +138,78 @@ pub struct RpcPoolConfig {
     /// Strategy for selecting endpoints.
     pub strategy: Arc<RwLock<Box<dyn SelectionStrategy>>>,
 
+    /// Builds the strategy for each clone of this configuration (`None` shares
+    /// [`strategy`](Self::strategy) between clones).
+    pub strategy_factory: Option<StrategyFactory>,
+
     /// Interval between health checks.
     pub health_check_interval: Duration,
 
     /// Number of consecutive errors before marking an endpoint unhealthy.
     pub max_consecutive_errors: u32,
 
+    /// Consecutive successes a recovered endpoint needs before strategies
+    /// treat it as healthy again (`None` trusts it immediately).
+    pub confidence_threshold: Option<u32>,
+
     /// Delay before retrying an unhealthy endpoint.
     pub retry_delay: Duration,
 
     /// Timeout for individual RPC requests.
     pub request_timeout: Duration,
 
+    /// Timeouts for successive failover attempts; the last entry applies to all
+    /// later attempts. Empty means every attempt uses `request_timeout`.
+    pub per_attempt_timeouts: Vec<Duration>,
+
+    /// Upper bound of the random delay before each failover attempt
+    /// (`Duration::ZERO` disables jitter).
+    pub max_jitter: Duration,
+
+    /// Total time a single request may spend across all failover attempts,
+    /// measured from the start of the first attempt (`None` is unlimited).
+    pub retry_budget: Option<Duration>,
+
+    /// Fewest healthy endpoints needed to accept a request (`None` is no minimum).
+    pub min_healthy_endpoints: Option<usize>,
+
+    /// How long [`RpcPool::execute_speculative`] waits on the first endpoint
+    /// before also sending the request to the second.
+    pub speculative_delay: Duration,
+
     /// Timeout for health check probes.
     pub health_check_timeout: Duration,
+
+    /// Maximum number of blocks an endpoint may lag the best known block
+    /// before it is marked unhealthy. `None` disables the check.
+    pub max_block_lag: Option<u64>,
+
+    /// Requests-per-second limits keyed by endpoint URL (0 = unlimited).
+    pub rate_limits: HashMap<String, u32>,
+
+    /// How long an idle sticky session stays pinned to its endpoint.
+    pub sticky_session_ttl: Duration,
+
+    /// JSON-RPC call used to probe unhealthy endpoints.
+    pub health_check_method: HealthCheckMethod,
+
+    /// Health events buffered for each [`RpcPool::subscribe_health_events`]
+    /// receiver before the slowest one starts missing events.
+    pub health_event_capacity: usize,
+
+    /// Proxy URL that all pool HTTP traffic is sent through.
+    pub proxy: Option<String>,
+
+    /// Basic-auth username and password for [`proxy`](Self::proxy).
+    pub proxy_credentials: Option<(String, String)>,
+
+    /// How long an in-flight [`RpcPool::execute_deduped`] request can be joined
+    /// by identical calls (`None` disables coalescing).
+    pub request_dedup_ttl: Option<Duration>,
+
+    /// Endpoints used only by [`RpcPool::execute_write`], e.g. sequencers.
+    /// Empty sends writes to [`endpoints`](Self::endpoints).
+    pub write_endpoints: Vec<RpcEndpoint>,
 }
 
 impl Default for RpcPoolConfig {
@@ -79,11 +217,61 @@ impl Default for RpcPoolConfig {
         Self {
             endpoints: vec![],
             strategy: Arc::new(RwLock::new(Box::new(crate::strategies::FailoverStrategy))),
+            strategy_factory: None,
             health_check_interval: Duration::from_secs(60),
             max_consecutive_errors: 3,
+            confidence_threshold: None,
             retry_delay: Duration::from_secs(5),
             request_timeout: Duration::from_secs(DEFAULT_REQUEST_TIMEOUT_SECS),
+            per_attempt_timeouts: vec![],
+            max_jitter: Duration::ZERO,
+            retry_budget: None,
+            min_healthy_endpoints: None,
+            speculative_delay: Duration::from_millis(DEFAULT_SPECULATIVE_DELAY_MS),
             health_check_timeout: Duration::from_secs(DEFAULT_HEALTH_CHECK_TIMEOUT_SECS),
+            max_block_lag: None,
+            rate_limits: HashMap::new(),
+            sticky_session_ttl: Duration::from_secs(DEFAULT_STICKY_SESSION_TTL_SECS),
+            health_check_method: HealthCheckMethod::default(),
+            health_event_capacity: DEFAULT_HEALTH_EVENT_CAPACITY,
+            proxy: None,
+            proxy_credentials: None,
+            request_dedup_ttl: None,
+            write_endpoints: vec![],
+        }
+    }
+}
+
+impl Clone for RpcPoolConfig {
+    fn clone(&self) -> Self {
+        let strategy = match &self.strategy_factory {
+            Some(factory) => Arc::new(RwLock::new(factory())),
+            None => Arc::clone(&self.strategy),
+        };
+        Self {
+            endpoints: self.endpoints.clone(),
+            strategy,
+            strategy_factory: self.strategy_factory.clone(),
+            health_check_interval: self.health_check_interval,
+            max_consecutive_errors: self.max_consecutive_errors,
+            confidence_threshold: self.confidence_threshold,
+            retry_delay: self.retry_delay,
+            request_timeout: self.request_timeout,
+            per_attempt_timeouts: self.per_attempt_timeouts.clone(),
+            max_jitter: self.max_jitter,
+            retry_budget: self.retry_budget,
+            min_healthy_endpoints: self.min_healthy_endpoints,
+            speculative_delay: self.speculative_delay,
+            health_check_timeout: self.health_check_timeout,
+            max_block_lag: self.max_block_lag,
+            rate_limits: self.rate_limits.clone(),
+            sticky_session_ttl: self.sticky_session_ttl,
+            health_check_method: self.health_check_method.clone(),
+            health_event_capacity: self.health_event_capacity,
+            proxy: self.proxy.clone(),
+            proxy_credentials: self.proxy_credentials.clone(),
+            request_dedup_ttl: self.request_dedup_ttl,
+            write_endpoints: self.write_endpoints.clone(),
         }
     }
 }
@@ -101,8 +289,22 @@ impl RpcPoolConfig {
     }
 
     /// Builder: set strategy.
+    ///
+    /// Clones of the configuration share this strategy instance; use
+    /// [`with_strategy_factory`](Self::with_strategy_factory) to give each its own.
     pub fn with_strategy(mut self, strategy: Box<dyn SelectionStrategy>) -> Self {
         self.strategy = Arc::new(RwLock::new(strategy));
+        self.strategy_factory = None;
+        self
+    }
+
+    /// Builder: build the strategy with `factory`, again for every clone of the configuration.
+    pub fn with_strategy_factory(
+        mut self,
+        factory: impl Fn() -> Box<dyn SelectionStrategy> + Send + Sync + 'static,
+    ) -> Self {
+        self.strategy = Arc::new(RwLock::new(factory()));
+        self.strategy_factory = Some(Arc::new(factory));
         self
     }
 
@@ -118,6 +320,16 @@ impl RpcPoolConfig {
         self
     }
 
+    /// Builder: keep recovered endpoints as a last resort until they succeed `n` times in a row.
+    ///
+    /// Until then strategies see them as unhealthy, so they are only selected
+    /// when no confident healthy endpoint is left. Their successes as a last
+    /// resort count towards `n`.
+    pub fn with_confidence_threshold(mut self, n: u32) -> Self {
+        self.confidence_threshold = Some(n);
+        self
+    }
+
     /// Builder: set retry delay.
     pub fn with_retry_delay(mut self, delay: Duration) -> Self {
         self.retry_delay = delay;
@@ -130,17 +342,145 @@ impl RpcPoolConfig {
         self
     }
 
+    /// Builder: set a timeout schedule for successive failover attempts.
+    ///
+    /// The i-th attempt of a request uses `timeouts[i]`, and attempts beyond the
+    /// end of the list reuse the last entry, e.g. 500ms, then 1s, then 3s for
+    /// every further endpoint. An empty list restores the single `request_timeout`.
+    pub fn with_per_attempt_timeouts(mut self, timeouts: Vec<Duration>) -> Self {
+        self.per_attempt_timeouts = timeouts;
+        self
+    }
+
+    /// Builder: wait a random delay in `[0, max_jitter)` before each failover attempt.
+    ///
+    /// The first attempt of a request is sent immediately. Spreading out the
+    /// retries keeps many pool instances that hit the same failing endpoint
+    /// from all failing over at the same moment.
+    pub fn with_jitter(mut self, max_jitter: Duration) -> Self {
+        self.max_jitter = max_jitter;
+        self
+    }
+
+    /// Builder: cap the total time a request spends across all failover attempts.
+    ///
+    /// Once `total_timeout` has passed since the first attempt started, the
+    /// request fails with [`RpcPoolError::RetryBudgetExhausted`] even if untried
+    /// endpoints remain. An attempt still in flight is cut short when the
+    /// budget runs out, without counting against its endpoint.
+    pub fn with_retry_budget(mut self, total_timeout: Duration) -> Self {
+        self.retry_budget = Some(total_timeout);
+        self
+    }
+
+    /// Builder: fail fast while fewer than `n` endpoints are healthy.
+    ///
+    /// Requests are rejected with [`RpcPoolError::InsufficientHealthyEndpoints`]
+    /// before any attempt, instead of piling onto the few endpoints left, so
+    /// callers can open a circuit of their own when the pool is too degraded.
+    pub fn with_min_healthy_endpoints(mut self, n: usize) -> Self {
+        self.min_healthy_endpoints = Some(n);
+        self
+    }
+
+    /// Builder: set how long a speculative request waits for the first endpoint (default 50ms).
+    ///
+    /// See [`RpcPool::execute_speculative`]. Around the primary endpoint's p95
+    /// latency keeps the extra load to the slowest few percent of requests.
+    pub fn with_speculative_delay(mut self, delay: Duration) -> Self {
+        self.speculative_delay = delay;
+        self
+    }
+
     /// Builder: set health check timeout.
     pub fn with_health_check_timeout(mut self, timeout: Duration) -> Self {
         self.health_check_timeout = timeout;
         self
     }
+
+    /// Builder: mark endpoints unhealthy when they lag the best known block by more than `blocks`.
+    ///
+    /// When set, each health check cycle probes `eth_blockNumber` on every healthy endpoint.
+    pub fn with_max_block_lag(mut self, blocks: u64) -> Self {
+        self.max_block_lag = Some(blocks);
+        self
+    }
+
+    /// Builder: set per-endpoint rate limits (requests per second, keyed by URL).
+    ///
+    /// Requests to a limited endpoint wait for a token before being sent; the
+    /// wait counts against the request timeout.
+    pub fn with_rate_limits(mut self, rate_limits: HashMap<String, u32>) -> Self {
+        self.rate_limits = rate_limits;
+        self
+    }
+
+    /// Builder: set how long an idle sticky session stays pinned to its endpoint.
+    pub fn with_sticky_session_ttl(mut self, ttl: Duration) -> Self {
+        self.sticky_session_ttl = ttl;
+        self
+    }
+
+    /// Builder: set the call used to probe endpoint health (default `eth_blockNumber`).
+    pub fn with_health_check_method(mut self, method: HealthCheckMethod) -> Self {
+        self.health_check_method = method;
+        self
+    }
+
+    /// Builder: set how many health events are buffered per subscriber (default 64, minimum 1).
+    pub fn with_health_event_capacity(mut self, capacity: usize) -> Self {
+        self.health_event_capacity = capacity;
+        self
+    }
+
+    /// Builder: send HTTP traffic through a proxy (e.g. `http://proxy.corp:3128`).
+    ///
+    /// Applies to health checks, capability probes and the clients passed by
+    /// [`RpcPool::execute_with_client`]. Requests made by
+    /// [`RpcPool::execute_with_url`] callers through their own clients are not proxied.
+    pub fn with_proxy(mut self, proxy_url: impl Into<String>) -> Self {
+        self.proxy = Some(proxy_url.into());
+        self
+    }
+
+    /// Builder: authenticate to the proxy with basic auth. Ignored without a proxy.
+    pub fn with_proxy_credentials(mut self, username: String, password: String) -> Self {
+        self.proxy_credentials = Some((username, password));
+        self
+    }
+
+    /// Builder: send HTTP traffic through the SOCKS5 proxy at `addr`.
+    ///
+    /// Like [`with_proxy`](Self::with_proxy) with a `socks5h://` URL, so
+    /// endpoint hostnames are resolved by the proxy. Requires the `socks5` feature.
+    #[cfg(feature = "socks5")]
+    pub fn with_socks5_proxy(self, addr: std::net::SocketAddr) -> Self {
+        self.with_proxy(format!("socks5h://{}", addr))
+    }
+
+    /// Builder: let [`RpcPool::execute_deduped`] calls with the same key share
+    /// a request started less than `ttl` ago.
+    pub fn with_request_deduplication(mut self, ttl: Duration) -> Self {
+        self.request_dedup_ttl = Some(ttl);
+        self
+    }
+
+    /// Builder: send [`RpcPool::execute_write`] requests to `endpoints` only.
+    ///
+    /// Write endpoints are tried in priority order with failover and never
+    /// fall back to the read endpoints.
+    pub fn with_write_endpoints(mut self, endpoints: Vec<RpcEndpoint>) -> Self {
+        self.write_endpoints = endpoints;
+        self
+    }
 }
 
 /// High-availability RPC connection pool with automatic failover.
 pub struct RpcPool {
     /// Configured endpoints (sorted by priority).
-    endpoints: Vec<RpcEndpoint>,
+    ///
+    /// Shared behind a lock so endpoints can be added or removed at runtime.
+    endpoints: Arc<RwLock<Vec<RpcEndpoint>>>,
 
     /// Statistics for each endpoint.
     stats: RwLock<HashMap<String, EndpointStats>>,
@@ -150,19 +490,52 @@ pub struct RpcPool {
 
     /// Configuration.
     max_consecutive_errors: u32,
+    confidence_threshold: Option<u32>,
     retry_delay: Duration,
     health_check_interval: Duration,
     request_timeout: Duration,
+    per_attempt_timeouts: Vec<Duration>,
+    max_jitter: Duration,
+    retry_budget: Option<Duration>,
+    min_healthy_endpoints: Option<usize>,
+    speculative_delay: Duration,
     health_check_timeout: Duration,
+    health_check_method: HealthCheckMethod,
+    max_block_lag: Option<u64>,
+
+    /// Token buckets for rate-limited endpoints, keyed by URL.
+    rate_limiters: HashMap<String, Arc<Mutex<TokenBucket>>>,
+
+    /// Session keys pinned to endpoints by `execute_sticky`.
+    sticky_sessions: Mutex<StickySessionMap>,
+
+    /// HTTP clients carrying each endpoint's headers, keyed by URL.
+    http_clients: RwLock<HashMap<String, reqwest::Client>>,
+
+    /// Proxy used by every HTTP client.
+    proxy: Option<reqwest::Proxy>,
+
+    /// Requests `execute_deduped` callers can join, if deduplication is enabled.
+    inflight: Option<Mutex<InflightRequests>>,
+
+    /// Pool of dedicated write endpoints used by `execute_write`, if configured.
+    write_pool: Option<Arc<RpcPool>>,
+
+    /// Publisher of endpoint health transitions.
+    health_events: broadcast::Sender<HealthEvent>,
 
     /// Aggregated metrics.
     total_requests: AtomicU64,
     failovers: AtomicU64,
+    cross_pool_failovers: AtomicU64,
 
     /// Cancellation flag and notification for graceful shutdown.
     cancelled: AtomicBool,
     cancel_notify: tokio::sync::Notify,
 
+    /// Set while new requests are rejected for maintenance.
+    paused: AtomicBool,
+
     /// Handle to the health check task (if running).
     health_check_handle: RwLock<Option<AbortHandleWrapper>>,
 }
@@ -220,18 +593,73 @@ impl RpcPool {
             "Configured endpoints (sorted by priority)"
         );
 
+        let write_pool = if config.write_endpoints.is_empty() {
+            None
+        } else {
+            let write_config = RpcPoolConfig {
+                endpoints: std::mem::take(&mut config.write_endpoints),
+                strategy: Arc::new(RwLock::new(Box::new(crate::strategies::FailoverStrategy))),
+                strategy_factory: None,
+                ..config.clone()
+            };
+            debug!(
+                write_endpoints = write_config.endpoints.len(),
+                "Routing write requests to dedicated endpoints"
+            );
+            Some(Arc::new(RpcPool::new(write_config)?))
+        };
+
+        let proxy = match &config.proxy {
+            Some(proxy_url) => {
+                let mut proxy = reqwest::Proxy::all(proxy_url.as_str())
+                    .map_err(|e| RpcPoolError::InvalidUrl(format!("proxy {}: {}", proxy_url, e)))?;
+                if let Some((username, password)) = &config.proxy_credentials {
+                    proxy = proxy.basic_auth(username, password);
+                }
+                debug!(proxy = %proxy_url, "Routing RPC pool HTTP traffic through proxy");
+                Some(proxy)
+            }
+            None => None,
+        };
+
+        let rate_limiters = config
+            .rate_limits
+            .iter()
+            .filter(|(_, &rps)| rps > 0)
+            .map(|(url, &rps)| (url.clone(), Arc::new(Mutex::new(TokenBucket::new(rps)))))
+            .collect();
+
         Ok(Self {
-            endpoints: config.endpoints,
+            endpoints: Arc::new(RwLock::new(config.endpoints)),
             stats: RwLock::new(stats),
             strategy: config.strategy,
             max_consecutive_errors: config.max_consecutive_errors,
+            confidence_threshold: config.confidence_threshold,
             retry_delay: config.retry_delay,
             health_check_interval: config.health_check_interval,
             request_timeout: config.request_timeout,
+            per_attempt_timeouts: config.per_attempt_timeouts,
+            max_jitter: config.max_jitter,
+            retry_budget: config.retry_budget,
+            min_healthy_endpoints: config.min_healthy_endpoints,
+            speculative_delay: config.speculative_delay,
             health_check_timeout: config.health_check_timeout,
+            health_check_method: config.health_check_method,
+            max_block_lag: config.max_block_lag,
+            rate_limiters,
+            sticky_sessions: Mutex::new(StickySessionMap::new(config.sticky_session_ttl)),
+            http_clients: RwLock::new(HashMap::new()),
+            proxy,
+            inflight: config
+                .request_dedup_ttl
+                .map(|ttl| Mutex::new(InflightRequests::new(ttl))),
+            write_pool,
+            health_events: broadcast::channel(config.health_event_capacity.max(1)).0,
             total_requests: AtomicU64::new(0),
             failovers: AtomicU64::new(0),
+            cross_pool_failovers: AtomicU64::new(0),
             cancelled: AtomicBool::new(false),
+            paused: AtomicBool::new(false),
             cancel_notify: tokio::sync::Notify::new(),
             health_check_handle: RwLock::new(None),
         })
@@ -242,11 +670,107 @@ impl RpcPool {
         self.request_timeout
     }
 
+    /// Name of the active selection strategy.
+    ///
+    /// Owned, since [`set_strategy`](Self::set_strategy) may replace the
+    /// strategy at any time.
+    pub fn strategy_name(&self) -> String {
+        self.strategy.read().name().to_string()
+    }
+
+    /// Replace the selection strategy without recreating the pool.
+    ///
+    /// Endpoint stats and health are kept; state held by the old strategy
+    /// itself, such as a round-robin cursor, is dropped with it. Requests
+    /// already in flight pick up the new strategy on their next selection.
+    /// The dedicated write pool, if any, keeps its own strategy.
+    pub fn set_strategy(&self, strategy: Box<dyn SelectionStrategy>) {
+        let new = strategy.name();
+        let old = std::mem::replace(&mut *self.strategy.write(), strategy);
+        info!(from = old.name(), to = new, "Selection strategy changed");
+    }
+
+    /// Timeout for the given 1-based failover attempt.
+    fn attempt_timeout(&self, attempt: u32) -> Duration {
+        match self.per_attempt_timeouts.len() {
+            0 => self.request_timeout,
+            len => self.per_attempt_timeouts[(attempt as usize).saturating_sub(1).min(len - 1)],
+        }
+    }
+
+    /// Random delay before a failover attempt, below the configured maximum.
+    fn retry_jitter(&self) -> Duration {
+        if self.max_jitter.is_zero() {
+            return Duration::ZERO;
+        }
+        rand::thread_rng().gen_range(Duration::ZERO..self.max_jitter)
+    }
+
+    /// Log and build the error for a request that ran out of retry budget.
+    fn retry_budget_exhausted(
+        &self,
+        request_id: u64,
+        attempts: u32,
+        last_error: Option<String>,
+    ) -> RpcPoolError {
+        let error_msg = last_error.unwrap_or_else(|| "no attempt completed".to_string());
+        warn!(
+            request_id,
+            attempts,
+            budget_ms = self.retry_budget.unwrap_or_default().as_millis() as u64,
+            last_error = %error_msg,
+            "Retry budget exhausted"
+        );
+        RpcPoolError::RetryBudgetExhausted(error_msg)
+    }
+
+    /// Reject a request if fewer endpoints are healthy than the configured minimum.
+    fn check_min_healthy(&self) -> Result<(), RpcPoolError> {
+        let Some(required) = self.min_healthy_endpoints else {
+            return Ok(());
+        };
+        let available = self.health_summary().healthy;
+        if available < required {
+            debug!(available, required, "Request rejected: too few healthy");
+            return Err(RpcPoolError::InsufficientHealthyEndpoints {
+                available,
+                required,
+            });
+        }
+        Ok(())
+    }
+
     /// Check if the pool has been shut down.
     pub fn is_shutdown(&self) -> bool {
         self.cancelled.load(Ordering::Acquire)
     }
 
+    /// Stop accepting new requests, e.g. while rotating API keys.
+    ///
+    /// Requests started after this fail with [`RpcPoolError::PoolPaused`]
+    /// until [`resume`](Self::resume); requests already in flight complete
+    /// normally, failover included. Health checks keep running, and
+    /// [`shutdown`](Self::shutdown) still works while paused.
+    pub fn pause(&self) {
+        for pool in std::iter::once(self).chain(self.write_pool.as_deref()) {
+            pool.paused.store(true, Ordering::Release);
+        }
+        info!("RPC pool paused");
+    }
+
+    /// Accept new requests again after [`pause`](Self::pause).
+    pub fn resume(&self) {
+        for pool in std::iter::once(self).chain(self.write_pool.as_deref()) {
+            pool.paused.store(false, Ordering::Release);
+        }
+        info!("RPC pool resumed");
+    }
+
+    /// Check if the pool is paused.
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::Acquire)
+    }
+
     /// Wait until cancellation is signalled.
     async fn cancelled(&self) {
         // Fast path: already cancelled
@@ -270,27 +794,188 @@ impl RpcPool {
     pub fn get_current_url(&self) -> Option<String> {
         let stats_map = self.collect_stats_snapshot();
         let exclude = HashSet::new();
+        let endpoints = self.endpoints.read();
         let mut strategy = self.strategy.write();
         strategy
-            .select(&self.endpoints, &stats_map, &exclude)
+            .select(&endpoints, &stats_map, &exclude, &SelectionContext::default())
+            .map(|e| e.url.clone())
+    }
+
+    /// URL of the endpoint a new request would try first, without sending it.
+    ///
+    /// Runs the same checks and selection as [`execute`](Self::execute) for a
+    /// fresh request with nothing excluded, but leaves request counts and
+    /// endpoint stats untouched. Returns `None` where `execute` would fail
+    /// before trying an endpoint: the pool is shut down or paused, has too few
+    /// healthy endpoints, or has no endpoint to select. The strategy is asked
+    /// through [`SelectionStrategy::peek`], so unlike
+    /// [`get_current_url`](Self::get_current_url) the dry run does not record
+    /// a selection or advance a rotation.
+    ///
+    /// A single pool has no tiers, so `priority` is ignored; it mirrors
+    /// [`TieredPool::execute_dry_run`](crate::TieredPool::execute_dry_run).
+    pub fn execute_dry_run(&self, priority: Option<RequestPriority>) -> Option<String> {
+        if self.is_shutdown() || self.is_paused() || self.check_min_healthy().is_err() {
+            trace!(?priority, "Dry run: request would be rejected");
+            return None;
+        }
+        let stats_map = self.collect_stats_snapshot();
+        let endpoints = self.endpoints.read();
+        self.strategy
+            .write()
+            .peek(&endpoints, &stats_map, &HashSet::new(), &SelectionContext::default())
             .map(|e| e.url.clone())
     }
 
     /// Get all configured RPC URLs.
     pub fn get_all_urls(&self) -> Vec<String> {
-        self.endpoints.iter().map(|e| e.url.clone()).collect()
+        self.endpoints.read().iter().map(|e| e.url.clone()).collect()
+    }
+
+    /// Get a copy of all configured endpoints, in priority order.
+    pub fn get_all_endpoints(&self) -> Vec<RpcEndpoint> {
+        self.endpoints.read().clone()
+    }
+
+    /// Look up an endpoint by its name.
+    ///
+    /// Returns a clone, as endpoints can be added and removed at runtime. If
+    /// several endpoints share the name, the first in priority order wins.
+    /// Linear in the number of endpoints.
+    pub fn get_endpoint_by_name(&self, name: &str) -> Option<RpcEndpoint> {
+        self.endpoints
+            .read()
+            .iter()
+            .find(|e| e.name == name)
+            .cloned()
+    }
+
+    /// Look up an endpoint by its URL. Linear in the number of endpoints.
+    pub fn get_endpoint_by_url(&self, url: &str) -> Option<RpcEndpoint> {
+        self.endpoints.read().iter().find(|e| e.url == url).cloned()
+    }
+
+    /// Snapshot of the stats of the endpoint with the given name.
+    ///
+    /// Resolves the name like [`get_endpoint_by_name`](Self::get_endpoint_by_name).
+    /// Linear in the number of endpoints.
+    pub fn get_endpoint_stats_by_name(&self, name: &str) -> Option<EndpointStats> {
+        let url = self.get_endpoint_by_name(name)?.url;
+        self.stats.read().get(&url).cloned()
+    }
+
+    /// Endpoint names with their average latency in milliseconds, fastest first.
+    ///
+    /// Endpoints that have not served a request yet come last, with a latency
+    /// of `f64::INFINITY`. Ties keep priority order.
+    pub fn get_endpoint_latency_rank(&self) -> Vec<(String, f64)> {
+        self.endpoints_by_latency()
+            .into_iter()
+            .map(|(endpoint, latency)| (endpoint.name, latency))
+            .collect()
+    }
+
+    /// The endpoint with the lowest average latency.
+    ///
+    /// Returns a clone, or `None` if no endpoint has served a request yet.
+    pub fn get_fastest_endpoint(&self) -> Option<RpcEndpoint> {
+        self.endpoints_by_latency()
+            .into_iter()
+            .find(|(_, latency)| latency.is_finite())
+            .map(|(endpoint, _)| endpoint)
+    }
+
+    /// The endpoint with the highest average latency.
+    ///
+    /// Endpoints that have not served a request yet are not considered.
+    /// Returns a clone, or `None` if no endpoint has served a request yet.
+    pub fn get_slowest_endpoint(&self) -> Option<RpcEndpoint> {
+        self.endpoints_by_latency()
+            .into_iter()
+            .rev()
+            .find(|(_, latency)| latency.is_finite())
+            .map(|(endpoint, _)| endpoint)
+    }
+
+    /// The best healthy endpoint whose capabilities satisfy `filter`.
+    ///
+    /// Picks the highest [`EndpointGrade`](crate::endpoint::EndpointGrade)
+    /// among qualifying endpoints, breaking ties by lowest average latency
+    /// (endpoints without requests count as slowest), then by priority.
+    /// Lets callers send e.g. large `eth_getLogs` queries to the most capable
+    /// endpoint instead of going through the strategy. Returns a clone, or
+    /// `None` if no healthy endpoint qualifies.
+    pub fn get_best_endpoint_for_capability(
+        &self,
+        filter: CapabilityFilter,
+    ) -> Option<RpcEndpoint> {
+        let endpoints = self.endpoints.read();
+        let stats = self.stats.read();
+        let latency = |e: &RpcEndpoint| match stats.get(&e.url) {
+            Some(s) if s.total_requests > 0 => s.avg_latency_ms,
+            _ => f64::INFINITY,
+        };
+        endpoints
+            .iter()
+            .filter(|e| stats.get(&e.url).is_some_and(|s| s.is_healthy))
+            .filter(|e| filter.matches(&e.capabilities))
+            .min_by(|a, b| {
+                b.capabilities
+                    .grade()
+                    .cmp(&a.capabilities.grade())
+                    .then_with(|| latency(a).total_cmp(&latency(b)))
+            })
+            .cloned()
+    }
+
+    /// Clone every endpoint with its average latency, sorted ascending.
+    fn endpoints_by_latency(&self) -> Vec<(RpcEndpoint, f64)> {
+        let endpoints = self.endpoints.read();
+        let stats = self.stats.read();
+        let mut ranked: Vec<(RpcEndpoint, f64)> = endpoints
+            .iter()
+            .map(|e| {
+                let latency = match stats.get(&e.url) {
+                    Some(s) if s.total_requests > 0 => s.avg_latency_ms,
+                    _ => f64::INFINITY,
+                };
+                (e.clone(), latency)
+            })
+            .collect();
+        // Stable sort, so equal latencies stay in priority order
+        ranked.sort_by(|a, b| a.1.total_cmp(&b.1));
+        ranked
+    }
+
+    /// Name of the endpoint a request was sent to, given its parsed URL.
+    pub(crate) fn endpoint_name(&self, url: &url::Url) -> Option<String> {
+        self.endpoints
+            .read()
+            .iter()
+            .find(|e| e.url == url.as_str() || url::Url::parse(&e.url).is_ok_and(|u| &u == url))
+            .map(|e| e.name.clone())
     }
 
-    /// Collect a snapshot of stats.
+    /// Collect a snapshot of stats for the selection strategy.
+    ///
+    /// With a confidence threshold, recovered endpoints that have not yet
+    /// reached it appear unhealthy, so strategies only fall back to them.
     #[inline]
     fn collect_stats_snapshot(&self) -> HashMap<String, EndpointStats> {
-        self.stats.read().clone()
+        let mut stats = self.stats.read().clone();
+        if let Some(threshold) = self.confidence_threshold {
+            for s in stats.values_mut().filter(|s| !s.is_confident(threshold)) {
+                s.is_healthy = false;
+            }
+        }
+        stats
     }
 
     /// Execute a function with automatic failover across endpoints.
     ///
     /// The provided function receives the endpoint URL and should create
-    /// and use its own provider instance.
+    /// and use its own provider instance. Endpoint headers are not applied;
+    /// use [`execute_with_client`](Self::execute_with_client) for endpoints that need them.
     #[instrument(skip(self, f), level = "trace", fields(request_id = %self.total_requests.load(Ordering::Relaxed) + 1))]
     pub async fn execute_with_url<F, Fut, T, E>(&self, f: F) -> Result<T, RpcPoolError>
     where
@@ -298,41 +983,473 @@ impl RpcPool {
         Fut: Future<Output = Result<T, E>>,
         E: std::error::Error,
     {
-        if self.is_shutdown() {
-            debug!("Request rejected: pool is shut down");
-            return Err(RpcPoolError::PoolShutdown);
-        }
+        self.execute_with_context_hint(SelectionContext::default(), f)
+            .await
+    }
 
-        let request_id = self.total_requests.fetch_add(1, Ordering::Relaxed) + 1;
-        trace!(request_id, "Starting request execution");
+    /// Execute like [`execute_with_url`](Self::execute_with_url), passing `ctx` to the strategy.
+    ///
+    /// `ctx` reaches [`SelectionStrategy::select`] on every selection for this
+    /// request, e.g. to tell a custom strategy that the request is a write or
+    /// needs an archive node.
+    pub async fn execute_with_context_hint<F, Fut, T, E>(
+        &self,
+        ctx: SelectionContext,
+        f: F,
+    ) -> Result<T, RpcPoolError>
+    where
+        F: Fn(String) -> Fut + Clone,
+        Fut: Future<Output = Result<T, E>>,
+        E: std::error::Error,
+    {
+        self.execute_with_url_excluding(HashSet::new(), None, Some(&ctx), None, f)
+            .await
+            .map(|(value, _)| value)
+    }
 
-        let mut tried = HashSet::new();
-        let mut last_error = None;
-        let mut attempt = 0u32;
+    /// Execute with automatic failover, passing request-level context to the strategy.
+    ///
+    /// `ctx` reaches [`SelectionStrategy::select`] on every selection as the
+    /// [`SelectionContext::extension`], and is cloned into `f` on every
+    /// attempt, so custom strategies can route on per-request hints without
+    /// changing the pool configuration.
+    pub async fn execute_with_context<Ctx, F, Fut, T, E>(
+        &self,
+        ctx: Arc<Ctx>,
+        f: F,
+    ) -> Result<T, RpcPoolError>
+    where
+        Ctx: Send + Sync + 'static,
+        F: Fn(Arc<Ctx>, url::Url) -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+        E: std::error::Error,
+    {
+        let selection = SelectionContext {
+            extension: Some(ctx.clone()),
+            ..Default::default()
+        };
+        self.execute_with_url_excluding(HashSet::new(), None, Some(&selection), None, |url_str| {
+            let ctx = ctx.clone();
+            let f = &f;
+            async move {
+                let url: url::Url = url_str.parse().map_err(|e: url::ParseError| {
+                    std::io::Error::other(format!("Invalid URL: {}", e))
+                })?;
+                f(ctx, url).await.map_err(|e| std::io::Error::other(e.to_string()))
+            }
+        })
+        .await
+        .map(|(value, _)| value)
+    }
 
-        for _ in 0..self.endpoints.len() {
-            attempt += 1;
+    /// Execute with automatic failover, returning the request together with a token that aborts it.
+    ///
+    /// Cancelling the token makes the request resolve to
+    /// [`RpcPoolError::Cancelled`], dropping any in-flight attempt, while the
+    /// pool and its other requests carry on. A token cancelled before the
+    /// request is polled cancels it before any endpoint is tried.
+    ///
+    /// ```ignore
+    /// let (scan, token) = pool.execute_abortable(|url| scan_logs(url, from, to));
+    /// shutdown_signal.register(token);
+    /// let logs = scan.await?;
+    /// ```
+    pub fn execute_abortable<'a, F, Fut, T, E>(
+        &'a self,
+        f: F,
+    ) -> (
+        impl Future<Output = Result<T, RpcPoolError>> + 'a,
+        CancellationToken,
+    )
+    where
+        F: Fn(url::Url) -> Fut + Clone + 'a,
+        Fut: Future<Output = Result<T, E>> + 'a,
+        T: 'a,
+        E: std::error::Error + 'a,
+    {
+        let token = CancellationToken::new();
+        let cancel = token.clone();
+        let request = async move {
+            self.execute_with_url_excluding(HashSet::new(), None, None, Some(&cancel), |url_str| {
+                let f = f.clone();
+                async move {
+                    let url: url::Url = url_str.parse().map_err(|e: url::ParseError| {
+                        std::io::Error::other(format!("Invalid URL: {}", e))
+                    })?;
+                    f(url).await.map_err(|e| std::io::Error::other(e.to_string()))
+                }
+            })
+            .await
+            .map(|(value, _)| value)
+        };
+        (request, token)
+    }
 
-            // Check for shutdown
-            if self.is_shutdown() {
-                debug!(request_id, attempt, "Request cancelled: pool shutdown in progress");
-                return Err(RpcPoolError::PoolShutdown);
+    /// Execute with automatic failover, passing an HTTP client configured for the endpoint.
+    ///
+    /// The client sends the endpoint's [`headers`](RpcEndpoint::headers) with
+    /// every request, for providers that authenticate with a header rather
+    /// than a key in the URL. Clients are cached per endpoint.
+    ///
+    /// ```ignore
+    /// let block = pool.execute_with_client(|client, url| async move {
+    ///     ProviderBuilder::new().connect_reqwest(client, url).get_block_number().await
+    /// }).await?;
+    /// ```
+    pub async fn execute_with_client<F, Fut, T, E>(&self, f: F) -> Result<T, RpcPoolError>
+    where
+        F: Fn(reqwest::Client, url::Url) -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+        E: std::error::Error,
+    {
+        self.execute_with_url_excluding(HashSet::new(), None, None, None, |url_str| {
+            let client = self.http_client(&url_str);
+            let f = &f;
+            async move {
+                let client = client.map_err(|e| std::io::Error::other(e.to_string()))?;
+                let url: url::Url = url_str.parse().map_err(|e: url::ParseError| {
+                    std::io::Error::other(format!("Invalid URL: {}", e))
+                })?;
+                f(client, url).await.map_err(|e| std::io::Error::other(e.to_string()))
             }
+        })
+        .await
+        .map(|(value, _)| value)
+    }
 
-            // Select endpoint
-            let endpoint = {
-                let stats_map = self.collect_stats_snapshot();
-                let mut strategy = self.strategy.write();
-                strategy.select(&self.endpoints, &stats_map, &tried).cloned()
-            };
+    /// HTTP client for the endpoint at `url`, built from its headers and the
+    /// pool's proxy on first use.
+    fn http_client(&self, url: &str) -> Result<reqwest::Client, RpcPoolError> {
+        if let Some(client) = self.http_clients.read().get(url) {
+            return Ok(client.clone());
+        }
 
-            let endpoint = match endpoint {
-                Some(e) => e,
-                None => {
+        let client = match self.endpoints.read().iter().find(|e| e.url == url) {
+            Some(endpoint) => endpoint.http_client(self.proxy.as_ref())?,
+            None => return Err(RpcPoolError::EndpointNotFound(url.to_string())),
+        };
+        Ok(self
+            .http_clients
+            .write()
+            .entry(url.to_string())
+            .or_insert(client)
+            .clone())
+    }
+
+    /// Execute with URL-based failover inside a span linked to an OpenTelemetry context.
+    ///
+    /// Creates a `rpc_pool.execute` span whose parent is `ctx`, carrying
+    /// `rpc.strategy` and the `rpc.endpoint` / `rpc.attempt` of the latest
+    /// attempt. The span status is set to error when every endpoint fails.
+    ///
+    /// Requires the `otel` feature.
+    #[cfg(feature = "otel")]
+    pub async fn execute_with_url_traced<F, Fut, T, E>(
+        &self,
+        ctx: &opentelemetry::Context,
+        f: F,
+    ) -> Result<T, RpcPoolError>
+    where
+        F: Fn(String) -> Fut + Clone,
+        Fut: Future<Output = Result<T, E>>,
+        E: std::error::Error,
+    {
+        use std::sync::atomic::AtomicU32;
+        use tracing::Instrument;
+        use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+        let span = tracing::info_span!(
+            "rpc_pool.execute",
+            rpc.strategy = self.strategy.read().name(),
+            rpc.endpoint = tracing::field::Empty,
+            rpc.attempt = tracing::field::Empty,
+            otel.status_code = tracing::field::Empty,
+            otel.status_message = tracing::field::Empty,
+        );
+        span.set_parent(ctx.clone());
+
+        let attempts = Arc::new(AtomicU32::new(0));
+        let traced = {
+            let span = span.clone();
+            move |url: String| {
+                let attempt = attempts.fetch_add(1, Ordering::Relaxed) + 1;
+                span.record("rpc.endpoint", url.as_str());
+                span.record("rpc.attempt", attempt);
+                f(url)
+            }
+        };
+
+        let result = self.execute_with_url(traced).instrument(span.clone()).await;
+        if let Err(RpcPoolError::AllEndpointsFailed(msg) | RpcPoolError::RateLimited(msg)) =
+            &result
+        {
+            span.record("otel.status_code", "ERROR");
+            span.record("otel.status_message", msg.as_str());
+        }
+        result
+    }
+
+    /// Execute with the endpoint pinned to `key`, falling back only if it fails.
+    ///
+    /// The first call for a key hashes it onto the healthy endpoints and pins
+    /// the result; later calls with the same key go to the same endpoint, so a
+    /// sequence like "send transaction, then poll receipt" sees consistent state.
+    /// If the pinned endpoint fails, the request fails over as usual and the key
+    /// is re-pinned to whichever endpoint succeeded. Bindings expire after
+    /// [`RpcPoolConfig::sticky_session_ttl`] without use.
+    pub async fn execute_sticky<K, F, Fut, T, E>(&self, key: K, f: F) -> Result<T, RpcPoolError>
+    where
+        K: Hash,
+        F: Fn(String) -> Fut + Clone,
+        Fut: Future<Output = Result<T, E>>,
+        E: std::error::Error,
+    {
+        let mut hasher = DefaultHasher::new();
+        key.hash(&mut hasher);
+        let key = hasher.finish();
+
+        let preferred = self.sticky_endpoint(key);
+        let (value, metadata) = self
+            .execute_with_url_excluding(HashSet::new(), preferred.as_deref(), None, None, f)
+            .await?;
+        let url = metadata.endpoint_url;
+
+        if preferred.as_deref() != Some(url.as_str()) {
+            debug!(
+                previous = ?preferred,
+                endpoint_url = %url,
+                "Sticky session re-pinned"
+            );
+        }
+        self.sticky_sessions.lock().bind(key, url);
+        Ok(value)
+    }
+
+    /// Execute with automatic failover, trying `preferred_url` first.
+    ///
+    /// The preferred endpoint gets the first attempt whatever its priority or
+    /// health, e.g. to send a transaction through an MEV-protection endpoint
+    /// kept at a low priority for reads. If it fails, the remaining endpoints
+    /// are tried in the strategy's usual order. The pool's endpoint order is
+    /// not changed. Fails with [`RpcPoolError::EndpointNotFound`] if no
+    /// endpoint has the URL.
+    pub async fn execute_prioritising<F, Fut, T, E>(
+        &self,
+        preferred_url: &str,
+        f: F,
+    ) -> Result<T, RpcPoolError>
+    where
+        F: Fn(url::Url) -> Fut + Clone,
+        Fut: Future<Output = Result<T, E>>,
+        E: std::error::Error,
+    {
+        if self.get_endpoint_by_url(preferred_url).is_none() {
+            return Err(RpcPoolError::EndpointNotFound(preferred_url.to_string()));
+        }
+
+        let preferred = Some(preferred_url);
+        self.execute_with_url_excluding(HashSet::new(), preferred, None, None, |url_str| {
+            let f = f.clone();
+            async move {
+                let url: url::Url = url_str.parse().map_err(|e: url::ParseError| {
+                    std::io::Error::other(format!("Invalid URL: {}", e))
+                })?;
+                f(url)
+                    .await
+                    .map_err(|e| std::io::Error::other(e.to_string()))
+            }
+        })
+        .await
+        .map(|(value, _)| value)
+    }
+
+    /// Execute with automatic failover, sharing the request with concurrent calls for `key`.
+    ///
+    /// When deduplication is enabled with
+    /// [`RpcPoolConfig::with_request_deduplication`], a call whose `key` matches
+    /// a request started less than the TTL ago waits for that request's result
+    /// instead of sending its own. The caller chooses the key, e.g. a hash of
+    /// the method and parameters; calls sharing a key must be interchangeable.
+    /// Without deduplication this is [`execute`](Self::execute).
+    ///
+    /// The request runs to completion as long as any caller is still waiting.
+    pub async fn execute_deduped<T, E, F, Fut>(
+        self: &Arc<Self>,
+        key: u64,
+        f: F,
+    ) -> Result<T, RpcPoolError>
+    where
+        F: Fn(url::Url) -> Fut + Clone + Send + Sync + 'static,
+        Fut: Future<Output = Result<T, E>> + Send + 'static,
+        T: Clone + Send + Sync + 'static,
+        E: std::error::Error + Send + 'static,
+    {
+        let Some(inflight) = &self.inflight else {
+            return self.execute(f).await;
+        };
+
+        let request = {
+            let mut inflight = inflight.lock();
+            match inflight.join::<T>(key) {
+                Some(request) => {
+                    trace!(key, "Joining in-flight request");
+                    request
+                }
+                None => {
+                    let id = inflight.next_id();
+                    let pool = Arc::clone(self);
+                    let request = async move {
+                        let result = pool.execute(f).await;
+                        if let Some(inflight) = &pool.inflight {
+                            inflight.lock().remove(key, id);
+                        }
+                        result
+                    }
+                    .boxed()
+                    .shared();
+                    inflight.insert(key, id, request.clone());
+                    request
+                }
+            }
+        };
+        request.await
+    }
+
+    /// Number of `execute_deduped` requests currently registered for joining.
+    pub fn inflight_request_count(&self) -> usize {
+        self.inflight.as_ref().map_or(0, |inflight| inflight.lock().len())
+    }
+
+    /// Number of live sticky session bindings.
+    pub fn sticky_session_count(&self) -> usize {
+        self.sticky_sessions.lock().len()
+    }
+
+    /// Endpoint a hashed session key should try first.
+    ///
+    /// Uses the existing binding while its endpoint is registered and healthy,
+    /// otherwise hashes the key onto the healthy endpoints (or all endpoints if
+    /// none are healthy).
+    fn sticky_endpoint(&self, key: u64) -> Option<String> {
+        let endpoints = self.endpoints.read();
+        let stats = self.stats.read();
+        let is_healthy = |url: &str| stats.get(url).map(|s| s.is_healthy).unwrap_or(true);
+
+        if let Some(url) = self.sticky_sessions.lock().get(key) {
+            if is_healthy(url) && endpoints.iter().any(|e| e.url == url) {
+                return Some(url.to_string());
+            }
+        }
+
+        let healthy: Vec<&RpcEndpoint> = endpoints.iter().filter(|e| is_healthy(&e.url)).collect();
+        let candidates = if healthy.is_empty() {
+            endpoints.iter().collect()
+        } else {
+            healthy
+        };
+        if candidates.is_empty() {
+            return None;
+        }
+        Some(candidates[(key % candidates.len() as u64) as usize].url.clone())
+    }
+
+    /// Failover loop shared by the `execute*` methods.
+    ///
+    /// URLs in `excluded` are never selected for this request. If `preferred`
+    /// names a registered endpoint it is tried first, before consulting the
+    /// strategy. `ctx` is passed to [`SelectionStrategy::select`], which
+    /// otherwise sees an all-false [`SelectionContext`].
+    /// Cancelling `cancel` abandons the request with [`RpcPoolError::Cancelled`].
+    /// Returns the value together with the endpoint and attempt that produced it.
+    async fn execute_with_url_excluding<F, Fut, T, E>(
+        &self,
+        excluded: HashSet<String>,
+        preferred: Option<&str>,
+        ctx: Option<&SelectionContext>,
+        cancel: Option<&CancellationToken>,
+        f: F,
+    ) -> Result<(T, ExecutionMetadata), RpcPoolError>
+    where
+        F: Fn(String) -> Fut + Clone,
+        Fut: Future<Output = Result<T, E>>,
+        E: std::error::Error,
+    {
+        if self.is_shutdown() {
+            debug!("Request rejected: pool is shut down");
+            return Err(RpcPoolError::PoolShutdown);
+        }
+        if self.is_paused() {
+            debug!("Request rejected: pool is paused");
+            return Err(RpcPoolError::PoolPaused);
+        }
+        self.check_min_healthy()?;
+        let request_cancelled = || async {
+            match cancel {
+                Some(token) => token.cancelled().await,
+                None => std::future::pending().await,
+            }
+        };
+
+        let request_id = self.total_requests.fetch_add(1, Ordering::Relaxed) + 1;
+        trace!(request_id, "Starting request execution");
+
+        let excluded_count = excluded.len();
+        let mut tried = excluded;
+        let mut last_error = None;
+        let mut all_rate_limited = true;
+        let mut attempt = 0u32;
+
+        // The budget runs from the start of the first attempt
+        let budget_deadline = self.retry_budget.map(|budget| Instant::now() + budget);
+        let budget_remaining =
+            || budget_deadline.map(|deadline| deadline.saturating_duration_since(Instant::now()));
+
+        let max_attempts = self.endpoints.read().len();
+        for _ in 0..max_attempts {
+            attempt += 1;
+
+            if budget_remaining().is_some_and(|remaining| remaining.is_zero()) {
+                return Err(self.retry_budget_exhausted(request_id, attempt - 1, last_error));
+            }
+
+            // Check for shutdown
+            if self.is_shutdown() {
+                debug!(request_id, attempt, "Request cancelled: pool shutdown in progress");
+                return Err(RpcPoolError::PoolShutdown);
+            }
+            if cancel.is_some_and(CancellationToken::is_cancelled) {
+                debug!(request_id, attempt, "Request cancelled by caller");
+                return Err(RpcPoolError::Cancelled);
+            }
+
+            // Select endpoint (a preferred endpoint gets the first attempt)
+            let pinned = preferred.filter(|_| attempt == 1).and_then(|url| {
+                self.endpoints
+                    .read()
+                    .iter()
+                    .find(|e| e.url == url && !tried.contains(&e.url))
+                    .cloned()
+            });
+            let endpoint = match pinned {
+                Some(e) => Some(e),
+                None => {
+                    let stats_map = self.collect_stats_snapshot();
+                    let endpoints = self.endpoints.read();
+                    let default_ctx = SelectionContext::default();
+                    self.strategy
+                        .write()
+                        .select(&endpoints, &stats_map, &tried, ctx.unwrap_or(&default_ctx))
+                        .cloned()
+                }
+            };
+
+            let endpoint = match endpoint {
+                Some(e) => e,
+                None => {
                     debug!(
                         request_id,
                         attempt,
-                        tried_count = tried.len(),
+                        tried_count = tried.len() - excluded_count,
                         "No more endpoints available to try"
                     );
                     break;
@@ -349,9 +1466,58 @@ impl RpcPool {
                 "Selected endpoint for request"
             );
 
-            // Execute request with timeout
+            // Delay failover attempts by a random jitter
+            let jitter = if attempt > 1 {
+                self.retry_jitter()
+                    .min(budget_remaining().unwrap_or(Duration::MAX))
+            } else {
+                Duration::ZERO
+            };
+            if !jitter.is_zero() {
+                trace!(
+                    request_id,
+                    attempt,
+                    jitter_ms = jitter.as_millis() as u64,
+                    "Delaying failover attempt"
+                );
+                tokio::select! {
+                    biased;
+
+                    _ = self.cancelled() => {
+                        return Err(RpcPoolError::PoolShutdown);
+                    }
+
+                    _ = request_cancelled() => {
+                        debug!(request_id, attempt, "Request cancelled by caller");
+                        return Err(RpcPoolError::Cancelled);
+                    }
+
+                    _ = tokio::time::sleep(jitter) => {}
+                }
+            }
+
+            // Execute request with timeout (including any rate-limit wait),
+            // shortened to what is left of the retry budget
+            let mut timeout = self.attempt_timeout(attempt);
+            let budget_capped = match budget_remaining() {
+                Some(remaining) if remaining.is_zero() => {
+                    return Err(self.retry_budget_exhausted(request_id, attempt - 1, last_error));
+                }
+                Some(remaining) if remaining < timeout => {
+                    timeout = remaining;
+                    true
+                }
+                _ => false,
+            };
             let start = Instant::now();
+            let throttle = self.reserve_rate_limit(&endpoint);
             let request_future = f(endpoint.url.clone());
+            let request_future = async move {
+                if !throttle.is_zero() {
+                    tokio::time::sleep(throttle).await;
+                }
+                request_future.await
+            };
 
             let result = tokio::select! {
                 biased;
@@ -360,7 +1526,12 @@ impl RpcPool {
                     return Err(RpcPoolError::PoolShutdown);
                 }
 
-                result = tokio::time::timeout(self.request_timeout, request_future) => {
+                _ = request_cancelled() => {
+                    debug!(request_id, attempt, endpoint = %endpoint.name, "Request cancelled by caller");
+                    return Err(RpcPoolError::Cancelled);
+                }
+
+                result = tokio::time::timeout(timeout, request_future) => {
                     result
                 }
             };
@@ -368,20 +1539,27 @@ impl RpcPool {
             match result {
                 Ok(Ok(value)) => {
                     let latency = start.elapsed().as_millis() as u64;
-                    if let Some(stats) = self.stats.write().get_mut(&endpoint.url) {
-                        stats.record_success(latency);
-                    }
+                    self.record_attempt_success(&endpoint, latency);
                     trace!(
                         request_id,
                         endpoint_name = %endpoint.name,
                         latency_ms = latency,
                         "Request completed successfully"
                     );
-                    return Ok(value);
+                    let metadata = ExecutionMetadata {
+                        endpoint_name: endpoint.name,
+                        endpoint_url: endpoint.url,
+                        latency_ms: latency,
+                        attempt_count: attempt,
+                    };
+                    return Ok((value, metadata));
                 }
                 Ok(Err(e)) => {
-                    let error_msg = truncate_error_message(&e.to_string());
-                    if let Some(stats) = self.stats.write().get_mut(&endpoint.url) {
+                    let full_error = e.to_string();
+                    let error_msg = truncate_error_message(&full_error);
+                    let rate_limited = is_rate_limit_error(&error_msg);
+                    all_rate_limited &= rate_limited;
+                    self.update_endpoint_stats(&endpoint.url, |stats| {
                         let marked_unhealthy =
                             stats.record_failure(error_msg.clone(), self.max_consecutive_errors);
                         if marked_unhealthy {
@@ -391,6 +1569,18 @@ impl RpcPool {
                                 "Endpoint marked unhealthy"
                             );
                         }
+                    });
+                    {
+                        let mut strategy = self.strategy.write();
+                        strategy.on_failure(&endpoint.url, &error_msg);
+                        let retry_after = rate_limited
+                            .then(|| parse_retry_after(&full_error))
+                            .flatten();
+                        if let (Some(retry_after), Some(recorder)) =
+                            (retry_after, strategy.as_throttle_recorder())
+                        {
+                            recorder.record_throttle(&endpoint.url, retry_after);
+                        }
                     }
 
                     self.failovers.fetch_add(1, Ordering::Relaxed);
@@ -402,9 +1592,19 @@ impl RpcPool {
                         "Request failed, trying next endpoint"
                     );
                 }
+                Err(_timeout) if budget_capped => {
+                    debug!(
+                        request_id,
+                        attempt,
+                        endpoint = %endpoint.name,
+                        "Retry budget ran out during attempt"
+                    );
+                    return Err(self.retry_budget_exhausted(request_id, attempt, last_error));
+                }
                 Err(_timeout) => {
-                    let error_msg = format!("Request timeout after {}ms", self.request_timeout.as_millis());
-                    if let Some(stats) = self.stats.write().get_mut(&endpoint.url) {
+                    let error_msg = format!("Request timeout after {}ms", timeout.as_millis());
+                    all_rate_limited = false;
+                    self.update_endpoint_stats(&endpoint.url, |stats| {
                         let marked_unhealthy =
                             stats.record_failure(error_msg.clone(), self.max_consecutive_errors);
                         if marked_unhealthy {
@@ -413,31 +1613,36 @@ impl RpcPool {
                                 "Endpoint marked unhealthy due to timeout"
                             );
                         }
-                    }
+                    });
+                    self.strategy.write().on_failure(&endpoint.url, &error_msg);
 
                     self.failovers.fetch_add(1, Ordering::Relaxed);
                     last_error = Some(error_msg);
 
                     debug!(
                         endpoint = %endpoint.name,
-                        timeout_ms = self.request_timeout.as_millis() as u64,
+                        timeout_ms = timeout.as_millis() as u64,
                         "Request timed out, trying next endpoint"
                     );
                 }
             }
         }
 
+        let rate_limited = all_rate_limited && last_error.is_some();
         let error_msg = last_error.unwrap_or_else(|| "Unknown error".to_string());
         let health = self.health_summary();
         error!(
             request_id,
-            tried_endpoints = tried.len(),
+            tried_endpoints = tried.len() - excluded_count,
             healthy_endpoints = health.healthy,
             unhealthy_endpoints = health.unhealthy,
             total_endpoints = health.total,
             last_error = %error_msg,
             "All endpoints failed (most endpoints marked unhealthy from previous failures)"
         );
+        if rate_limited {
+            return Err(RpcPoolError::RateLimited(error_msg));
+        }
         Err(RpcPoolError::AllEndpointsFailed(error_msg))
     }
 
@@ -450,7 +1655,23 @@ impl RpcPool {
         Fut: Future<Output = Result<T, E>>,
         E: std::error::Error,
     {
-        self.execute_with_url(|url_str| {
+        self.execute_matching(|_| true, f).await
+    }
+
+    /// Execute a request with automatic failover, also returning which endpoint served it.
+    ///
+    /// Behaves like [`execute`](Self::execute); the [`ExecutionMetadata`] names
+    /// the endpoint that produced the result, e.g. for audit logs or cache keys.
+    pub async fn execute_with_metadata<F, Fut, T, E>(
+        &self,
+        f: F,
+    ) -> Result<(T, ExecutionMetadata), RpcPoolError>
+    where
+        F: Fn(url::Url) -> Fut + Clone,
+        Fut: Future<Output = Result<T, E>>,
+        E: std::error::Error,
+    {
+        self.execute_with_url_excluding(HashSet::new(), None, None, None, |url_str| {
             let f = f.clone();
             async move {
                 let url: url::Url = url_str.parse().map_err(|e: url::ParseError| {
@@ -462,384 +1683,4020 @@ impl RpcPool {
         .await
     }
 
-    /// Start background health check task.
+    /// Execute a request, failing over when `validator` rejects a response.
     ///
-    /// Returns a handle that can be used to abort the task.
-    /// The task will automatically stop when `shutdown()` is called.
-    pub fn start_health_check(self: &Arc<Self>) -> tokio::task::JoinHandle<()> {
-        let pool = Arc::clone(self);
-        let interval = self.health_check_interval;
-
-        let handle = tokio::spawn(async move {
-            let mut ticker = tokio::time::interval(interval);
-
-            loop {
-                tokio::select! {
-                    biased;
-
-                    _ = pool.cancelled() => {
-                        info!("Health check task shutting down");
-                        break;
-                    }
+    /// Catches nodes that answer successfully with semantically wrong data,
+    /// e.g. block number 0 from a syncing node. A rejected response counts as
+    /// a failure of its endpoint, like an error would. Fails with
+    /// [`RpcPoolError::ValidationFailed`] if every endpoint tried answered but
+    /// was rejected; other failures are returned as from [`execute`](Self::execute).
+    pub async fn execute_validated<F, Fut, V, T, E>(
+        &self,
+        f: F,
+        validator: V,
+    ) -> Result<T, RpcPoolError>
+    where
+        F: Fn(url::Url) -> Fut + Clone,
+        Fut: Future<Output = Result<T, E>>,
+        E: std::error::Error,
+        V: Fn(&T) -> bool,
+    {
+        use std::sync::atomic::AtomicU32;
 
-                    _ = ticker.tick() => {
-                        pool.check_health().await;
-                    }
+        let attempts = AtomicU32::new(0);
+        let rejected = AtomicU32::new(0);
+        let validated = |url: url::Url| {
+            attempts.fetch_add(1, Ordering::Relaxed);
+            let (rejected, validator) = (&rejected, &validator);
+            let endpoint_url = url.to_string();
+            let request = f(url);
+            async move {
+                let value = request
+                    .await
+                    .map_err(|e| std::io::Error::other(e.to_string()))?;
+                if !validator(&value) {
+                    rejected.fetch_add(1, Ordering::Relaxed);
+                    debug!(endpoint_url = %endpoint_url, "Response rejected by validator");
+                    return Err(std::io::Error::other("response rejected by validator"));
                 }
+                Ok(value)
             }
-        });
-
-        // Store handle for cleanup
-        *self.health_check_handle.write() = Some(handle.abort_handle().into());
+        };
 
-        handle
+        match self.execute(validated).await {
+            Err(RpcPoolError::AllEndpointsFailed(msg))
+                if rejected.load(Ordering::Relaxed) == attempts.load(Ordering::Relaxed) =>
+            {
+                Err(RpcPoolError::ValidationFailed(msg))
+            }
+            result => result,
+        }
     }
 
-    /// Perform health check on all endpoints.
-    async fn check_health(&self) {
-        trace!("Starting health check cycle");
-        let mut checked_count = 0u32;
-        let mut recovered_count = 0u32;
+    /// Execute a request, returning `fallback` if every endpoint fails.
+    ///
+    /// Useful when a stale value (e.g. the last known gas price) is better than
+    /// no value at all. Behaves like [`execute`](Self::execute), but
+    /// [`RpcPoolError::AllEndpointsFailed`], [`RpcPoolError::RateLimited`] and
+    /// [`RpcPoolError::NoHealthyEndpoints`] yield `Ok(fallback)`. Other errors,
+    /// such as a shut-down or paused pool, are returned as is.
+    ///
+    /// Returns a `Result` rather than a bare `T`: a pool that was shut down or
+    /// paused is a caller error, and answering it with the fallback would hide
+    /// that behind a stale value.
+    pub async fn execute_with_fallback_value<T, E, F, Fut>(
+        &self,
+        fallback: T,
+        f: F,
+    ) -> Result<T, RpcPoolError>
+    where
+        F: Fn(url::Url) -> Fut + Clone,
+        Fut: Future<Output = Result<T, E>>,
+        E: std::error::Error,
+    {
+        self.execute_with_fallback_fn(|_| fallback, f).await
+    }
 
-        for endpoint in &self.endpoints {
-            // Check for shutdown
-            if self.is_shutdown() {
-                debug!("Health check interrupted by shutdown");
-                return;
+    /// Execute a request, computing a fallback from the error if every endpoint fails.
+    ///
+    /// Like [`execute_with_fallback_value`](Self::execute_with_fallback_value), but
+    /// `fallback_fn` only runs when the request falls back, and receives the error.
+    pub async fn execute_with_fallback_fn<T, E, F, Fut, G>(
+        &self,
+        fallback_fn: G,
+        f: F,
+    ) -> Result<T, RpcPoolError>
+    where
+        F: Fn(url::Url) -> Fut + Clone,
+        Fut: Future<Output = Result<T, E>>,
+        E: std::error::Error,
+        G: FnOnce(RpcPoolError) -> T,
+    {
+        match self.execute(f).await {
+            Err(
+                e @ (RpcPoolError::AllEndpointsFailed(_)
+                | RpcPoolError::RateLimited(_)
+                | RpcPoolError::NoHealthyEndpoints),
+            ) => {
+                warn!(error = %e, "All endpoints failed, using fallback value");
+                Ok(fallback_fn(e))
             }
+            result => result,
+        }
+    }
 
-            let should_check = {
-                let stats = self.stats.read();
-                match stats.get(&endpoint.url) {
-                    Some(s) => {
-                        // Only check unhealthy endpoints
-                        if s.is_healthy {
-                            false
-                        } else {
-                            s.can_retry(self.retry_delay)
-                        }
-                    }
-                    None => true,
-                }
-            };
-
-            if !should_check {
-                continue;
+    /// Execute a request, retrying it on `fallback` if every endpoint of this pool fails.
+    ///
+    /// Only [`RpcPoolError::AllEndpointsFailed`] and [`RpcPoolError::RateLimited`]
+    /// trigger the fallback; other errors (e.g. shutdown) are returned as is.
+    /// Each switch to the fallback counts as a cross-pool failover in this
+    /// pool's [`metrics`](Self::metrics).
+    pub async fn execute_with_fallback_pool<T, E, F, Fut>(
+        &self,
+        fallback: &RpcPool,
+        f: F,
+    ) -> Result<T, RpcPoolError>
+    where
+        F: Fn(url::Url) -> Fut + Clone,
+        Fut: Future<Output = Result<T, E>>,
+        E: std::error::Error,
+    {
+        match self.execute(f.clone()).await {
+            Err(RpcPoolError::AllEndpointsFailed(msg) | RpcPoolError::RateLimited(msg)) => {
+                self.cross_pool_failovers.fetch_add(1, Ordering::Relaxed);
+                warn!(error = %msg, "All endpoints failed, trying fallback pool");
+                fallback.execute(f).await
             }
+            result => result,
+        }
+    }
 
-            trace!(endpoint_name = %endpoint.name, "Probing unhealthy endpoint");
-            checked_count += 1;
-
-            // Try to recover with a simple probe (with timeout)
-            let url: Result<url::Url, _> = endpoint.url.parse();
-            if let Ok(url) = url {
-                let provider = ProviderBuilder::new().connect_http(url);
-
-                let probe_result = tokio::select! {
-                    biased;
-
-                    _ = self.cancelled() => {
-                        return;
-                    }
+    /// Execute a read-only request, such as `eth_call`, across the read endpoints.
+    ///
+    /// Same as [`execute`](Self::execute); it names the intent next to
+    /// [`execute_write`](Self::execute_write).
+    pub async fn execute_read_only<T, E, F, Fut>(&self, f: F) -> Result<T, RpcPoolError>
+    where
+        F: Fn(url::Url) -> Fut + Clone,
+        Fut: Future<Output = Result<T, E>>,
+        E: std::error::Error,
+    {
+        self.execute(f).await
+    }
 
-                    result = tokio::time::timeout(
-                        self.health_check_timeout,
-                        provider.get_block_number()
-                    ) => {
-                        result
+    /// Execute a state-changing request, such as `eth_sendRawTransaction`.
+    ///
+    /// With [`write endpoints`](RpcPoolConfig::with_write_endpoints) configured,
+    /// the request fails over across them in priority order and never reaches
+    /// the read endpoints. Otherwise it behaves like [`execute`](Self::execute).
+    pub async fn execute_write<T, E, F, Fut>(&self, f: F) -> Result<T, RpcPoolError>
+    where
+        F: Fn(url::Url) -> Fut + Clone,
+        Fut: Future<Output = Result<T, E>>,
+        E: std::error::Error,
+    {
+        match &self.write_pool {
+            Some(write_pool) => {
+                if self.is_shutdown() {
+                    return Err(RpcPoolError::PoolShutdown);
+                }
+                write_pool.execute(f).await
+            }
+            None => self.execute(f).await,
+        }
+    }
+
+    /// Execute a request, cycling through the endpoints until one succeeds or `deadline` passes.
+    ///
+    /// Each cycle fails over across the endpoints like [`execute`](Self::execute).
+    /// When a whole cycle fails, the pool waits `min(retry_delay, time remaining)`
+    /// (twice `retry_delay` if every endpoint was rate limiting) and starts
+    /// again, retrying endpoints that already failed. Individual
+    /// attempts are still bounded by the request timeout.
+    ///
+    /// Returns [`RpcPoolError::DeadlineExceeded`] once the deadline passes,
+    /// including while an attempt is in flight.
+    pub async fn execute_until_deadline<F, Fut, T, E>(
+        &self,
+        deadline: Instant,
+        f: F,
+    ) -> Result<T, RpcPoolError>
+    where
+        F: Fn(url::Url) -> Fut + Clone,
+        Fut: Future<Output = Result<T, E>>,
+        E: std::error::Error,
+    {
+        let mut cycles = 0u32;
+        let mut last_error = None;
+
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                break;
+            }
+
+            cycles += 1;
+            let backoff = match tokio::time::timeout(remaining, self.execute(f.clone())).await {
+                Ok(Ok(value)) => return Ok(value),
+                Ok(Err(RpcPoolError::AllEndpointsFailed(msg))) => {
+                    last_error = Some(msg);
+                    self.retry_delay
+                }
+                // Back off harder when every endpoint is throttling us
+                Ok(Err(RpcPoolError::RateLimited(msg))) => {
+                    last_error = Some(msg);
+                    self.retry_delay * 2
+                }
+                Ok(Err(e)) => return Err(e),
+                Err(_elapsed) => break,
+            };
+
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            let delay = backoff.min(remaining);
+            debug!(
+                cycles,
+                delay_ms = delay.as_millis() as u64,
+                remaining_ms = remaining.as_millis() as u64,
+                "All endpoints failed, retrying until deadline"
+            );
+            tokio::select! {
+                biased;
+
+                _ = self.cancelled() => return Err(RpcPoolError::PoolShutdown),
+                _ = tokio::time::sleep(delay) => {}
+            }
+        }
+
+        warn!(
+            cycles,
+            last_error = last_error.as_deref().unwrap_or("none"),
+            "Request deadline exceeded"
+        );
+        Err(RpcPoolError::DeadlineExceeded)
+    }
+
+    /// Poll with `f` every `poll_interval` until it returns a value or `deadline` passes.
+    ///
+    /// For waiting on chain progress, e.g. a transaction being mined in or
+    /// after `target_block`: `f` returns `Ok(None)` while the result is not
+    /// available yet and `Ok(Some(value))` once it is. Each poll is a regular
+    /// [`execute`](Self::execute) with failover; errors from it end the wait
+    /// and are returned as is.
+    ///
+    /// Returns [`RpcPoolError::DeadlineExceeded`] once the deadline passes,
+    /// including while a poll is in flight.
+    pub async fn execute_until_block<F, Fut, T, E>(
+        &self,
+        target_block: u64,
+        poll_interval: Duration,
+        deadline: Instant,
+        f: F,
+    ) -> Result<T, RpcPoolError>
+    where
+        F: Fn(url::Url) -> Fut + Clone,
+        Fut: Future<Output = Result<Option<T>, E>>,
+        E: std::error::Error,
+    {
+        let mut polls = 0u32;
+
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                break;
+            }
+
+            polls += 1;
+            match tokio::time::timeout(remaining, self.execute(f.clone())).await {
+                Ok(Ok(Some(value))) => {
+                    trace!(target_block, polls, "Awaited result available");
+                    return Ok(value);
+                }
+                Ok(Ok(None)) => {}
+                Ok(Err(e)) => return Err(e),
+                Err(_elapsed) => break,
+            }
+
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            let delay = poll_interval.min(remaining);
+            trace!(
+                target_block,
+                polls,
+                delay_ms = delay.as_millis() as u64,
+                "Result not available yet, polling again"
+            );
+            tokio::select! {
+                biased;
+
+                _ = self.cancelled() => return Err(RpcPoolError::PoolShutdown),
+                _ = tokio::time::sleep(delay) => {}
+            }
+        }
+
+        warn!(target_block, polls, "Request deadline exceeded waiting for block");
+        Err(RpcPoolError::DeadlineExceeded)
+    }
+
+    /// Execute with automatic failover, only considering endpoints accepted by `filter`.
+    ///
+    /// Returns [`RpcPoolError::NoMatchingEndpoints`] if no endpoint passes the filter.
+    pub(crate) async fn execute_matching<T, E, F, Fut, P>(
+        &self,
+        filter: P,
+        f: F,
+    ) -> Result<T, RpcPoolError>
+    where
+        P: Fn(&RpcEndpoint) -> bool,
+        F: Fn(url::Url) -> Fut + Clone,
+        Fut: Future<Output = Result<T, E>>,
+        E: std::error::Error,
+    {
+        let excluded: HashSet<String> = {
+            let endpoints = self.endpoints.read();
+            let excluded: HashSet<String> = endpoints
+                .iter()
+                .filter(|e| !filter(e))
+                .map(|e| e.url.clone())
+                .collect();
+            if !endpoints.is_empty() && excluded.len() == endpoints.len() {
+                debug!(endpoints = endpoints.len(), "No endpoint matches request filter");
+                return Err(RpcPoolError::NoMatchingEndpoints);
+            }
+            excluded
+        };
+
+        self.execute_with_url_excluding(excluded, None, None, None, |url_str| {
+            let f = f.clone();
+            async move {
+                let url: url::Url = url_str.parse().map_err(|e: url::ParseError| {
+                    std::io::Error::other(format!("Invalid URL: {}", e))
+                })?;
+                f(url).await.map_err(|e| std::io::Error::other(e.to_string()))
+            }
+        })
+        .await
+        .map(|(value, _)| value)
+    }
+
+    /// Execute a request against several endpoints at once and return the first success.
+    ///
+    /// Fires `concurrency` requests (capped at the number of endpoints) to the
+    /// endpoints the strategy ranks highest. The first `Ok` wins and the remaining
+    /// requests are cancelled. Every attempt that completes before the winner is
+    /// recorded in the endpoint statistics.
+    pub async fn execute_hedged<F, Fut, T, E>(
+        &self,
+        f: F,
+        concurrency: usize,
+    ) -> Result<T, RpcPoolError>
+    where
+        F: Fn(url::Url) -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+        E: std::error::Error,
+    {
+        if self.is_shutdown() {
+            debug!("Request rejected: pool is shut down");
+            return Err(RpcPoolError::PoolShutdown);
+        }
+        if self.is_paused() {
+            debug!("Request rejected: pool is paused");
+            return Err(RpcPoolError::PoolPaused);
+        }
+        self.check_min_healthy()?;
+
+        let request_id = self.total_requests.fetch_add(1, Ordering::Relaxed) + 1;
+        let targets = self.select_endpoints(concurrency.max(1));
+        if targets.is_empty() {
+            return Err(RpcPoolError::NoEndpointsConfigured);
+        }
+
+        trace!(
+            request_id,
+            concurrency = targets.len(),
+            endpoints = ?targets.iter().map(|e| &e.name).collect::<Vec<_>>(),
+            "Starting hedged request"
+        );
+
+        let mut last_error = None;
+        let mut in_flight = FuturesUnordered::new();
+        for endpoint in targets {
+            let url: url::Url = match endpoint.url.parse() {
+                Ok(url) => url,
+                Err(e) => {
+                    let error_msg = format!("Invalid URL: {}", e);
+                    self.record_attempt_failure(&endpoint, error_msg.clone());
+                    last_error = Some(error_msg);
+                    continue;
+                }
+            };
+            let throttle = self.reserve_rate_limit(&endpoint);
+            let request_future = f(url);
+            let timeout = self.request_timeout;
+            in_flight.push(async move {
+                let start = Instant::now();
+                let result = tokio::time::timeout(timeout, async move {
+                    if !throttle.is_zero() {
+                        tokio::time::sleep(throttle).await;
                     }
+                    request_future.await
+                })
+                .await;
+                (endpoint, start.elapsed(), result)
+            });
+        }
+
+        loop {
+            let next = tokio::select! {
+                biased;
+
+                _ = self.cancelled() => {
+                    return Err(RpcPoolError::PoolShutdown);
+                }
+
+                next = in_flight.next() => next,
+            };
+
+            let (endpoint, elapsed, result) = match next {
+                Some(completed) => completed,
+                None => break,
+            };
+
+            match result {
+                Ok(Ok(value)) => {
+                    let latency = elapsed.as_millis() as u64;
+                    self.record_attempt_success(&endpoint, latency);
+                    trace!(
+                        request_id,
+                        endpoint_name = %endpoint.name,
+                        latency_ms = latency,
+                        cancelled = in_flight.len(),
+                        "Hedged request completed successfully"
+                    );
+                    return Ok(value);
+                }
+                Ok(Err(e)) => {
+                    let error_msg = truncate_error_message(&e.to_string());
+                    debug!(endpoint = %endpoint.name, error = %e, "Hedged attempt failed");
+                    self.record_attempt_failure(&endpoint, error_msg.clone());
+                    last_error = Some(error_msg);
+                }
+                Err(_timeout) => {
+                    let error_msg =
+                        format!("Request timeout after {}ms", self.request_timeout.as_millis());
+                    debug!(endpoint = %endpoint.name, "Hedged attempt timed out");
+                    self.record_attempt_failure(&endpoint, error_msg.clone());
+                    last_error = Some(error_msg);
+                }
+            }
+        }
+
+        let error_msg = last_error.unwrap_or_else(|| "Unknown error".to_string());
+        error!(request_id, last_error = %error_msg, "All hedged attempts failed");
+        Err(RpcPoolError::AllEndpointsFailed(error_msg))
+    }
+
+    /// Execute a request on the top endpoint, adding the runner-up if it is slow.
+    ///
+    /// Sends the request to the endpoint the strategy ranks first. If that has
+    /// not succeeded within [`RpcPoolConfig::speculative_delay`], or fails
+    /// sooner, the request is also sent to the second-ranked endpoint. The
+    /// first `Ok` wins and the other request is cancelled. Unlike
+    /// [`execute_hedged`](Self::execute_hedged), the second endpoint only sees
+    /// the slow tail of requests. The winner's latency is recorded against its
+    /// endpoint, and a win by the second endpoint counts as a failover.
+    pub async fn execute_speculative<F, Fut, T, E>(&self, f: F) -> Result<T, RpcPoolError>
+    where
+        F: Fn(url::Url) -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+        E: std::error::Error,
+    {
+        if self.is_shutdown() {
+            debug!("Request rejected: pool is shut down");
+            return Err(RpcPoolError::PoolShutdown);
+        }
+        if self.is_paused() {
+            debug!("Request rejected: pool is paused");
+            return Err(RpcPoolError::PoolPaused);
+        }
+        self.check_min_healthy()?;
+
+        let request_id = self.total_requests.fetch_add(1, Ordering::Relaxed) + 1;
+        let mut targets = self.select_endpoints(2).into_iter();
+        let Some(primary) = targets.next() else {
+            return Err(RpcPoolError::NoEndpointsConfigured);
+        };
+        let primary_url = primary.url.clone();
+        let mut secondary = targets.next();
+
+        let timeout = self.request_timeout;
+        let attempt = |endpoint: RpcEndpoint| {
+            let request = endpoint
+                .url
+                .parse::<url::Url>()
+                .map(|url| (self.reserve_rate_limit(&endpoint), f(url)));
+            async move {
+                let start = Instant::now();
+                let result = match request {
+                    Ok((throttle, request_future)) => tokio::time::timeout(timeout, async move {
+                        if !throttle.is_zero() {
+                            tokio::time::sleep(throttle).await;
+                        }
+                        request_future.await
+                    })
+                    .await
+                    .map_err(|_| format!("Request timeout after {}ms", timeout.as_millis()))
+                    .and_then(|result| result.map_err(|e| truncate_error_message(&e.to_string()))),
+                    Err(e) => Err(format!("Invalid URL: {}", e)),
                 };
+                (endpoint, start.elapsed(), result)
+            }
+        };
 
-                match probe_result {
-                    Ok(Ok(_)) => {
-                        if let Some(stats) = self.stats.write().get_mut(&endpoint.url) {
-                            stats.mark_recovered();
-                            info!(endpoint = %endpoint.name, "Endpoint recovered");
-                            recovered_count += 1;
+        trace!(request_id, endpoint_name = %primary.name, "Starting speculative request");
+        let mut last_error = None;
+        let mut in_flight = FuturesUnordered::new();
+        in_flight.push(attempt(primary));
+        let delay = tokio::time::sleep(self.speculative_delay);
+        tokio::pin!(delay);
+
+        // Either a failure or the delay running out brings in the second endpoint
+        while !in_flight.is_empty() || secondary.is_some() {
+            tokio::select! {
+                biased;
+
+                _ = self.cancelled() => {
+                    return Err(RpcPoolError::PoolShutdown);
+                }
+
+                Some((endpoint, elapsed, result)) = in_flight.next() => match result {
+                    Ok(value) => {
+                        let latency = elapsed.as_millis() as u64;
+                        self.record_attempt_success(&endpoint, latency);
+                        if endpoint.url != primary_url {
+                            self.failovers.fetch_add(1, Ordering::Relaxed);
                         }
+                        trace!(
+                            request_id,
+                            endpoint_name = %endpoint.name,
+                            latency_ms = latency,
+                            cancelled = in_flight.len(),
+                            "Speculative request completed successfully"
+                        );
+                        return Ok(value);
                     }
-                    Ok(Err(e)) => {
-                        if let Some(stats) = self.stats.write().get_mut(&endpoint.url) {
-                            stats.last_error_time = Some(Instant::now());
-                            stats.increment_recovery_attempts();
-                            let next_retry = stats.current_retry_delay(self.retry_delay);
+                    Err(error_msg) => {
+                        debug!(
+                            endpoint = %endpoint.name,
+                            error = %error_msg,
+                            "Speculative attempt failed"
+                        );
+                        self.record_attempt_failure(&endpoint, error_msg.clone());
+                        last_error = Some(error_msg);
+                    }
+                },
+
+                _ = &mut delay, if secondary.is_some() => {}
+            }
+
+            if let Some(endpoint) = secondary.take() {
+                trace!(
+                    request_id,
+                    endpoint_name = %endpoint.name,
+                    "Sending speculative request to second endpoint"
+                );
+                in_flight.push(attempt(endpoint));
+            }
+        }
+
+        let error_msg = last_error.unwrap_or_else(|| "Unknown error".to_string());
+        error!(request_id, last_error = %error_msg, "All speculative attempts failed");
+        Err(RpcPoolError::AllEndpointsFailed(error_msg))
+    }
+
+    /// Execute many independent calls in parallel, spread across healthy endpoints.
+    ///
+    /// Items are assigned to healthy endpoints round-robin and at most
+    /// `concurrency` run at once. A failing item is retried on the next endpoint
+    /// in the rotation, up to `max_consecutive_errors` attempts in total; its
+    /// failure does not affect other items. Results are returned in input order.
+    pub async fn execute_batch<I, F, Fut, T, E>(
+        &self,
+        items: Vec<I>,
+        concurrency: usize,
+        f: F,
+    ) -> Vec<Result<T, RpcPoolError>>
+    where
+        I: Clone,
+        F: Fn(I, url::Url) -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+        E: std::error::Error,
+    {
+        let item_count = items.len();
+        let mut results: Vec<Option<Result<T, RpcPoolError>>> =
+            std::iter::repeat_with(|| None).take(item_count).collect();
+
+        if self.is_shutdown() {
+            debug!("Batch rejected: pool is shut down");
+            return results
+                .into_iter()
+                .map(|_| Err(RpcPoolError::PoolShutdown))
+                .collect();
+        }
+        if self.is_paused() {
+            debug!("Batch rejected: pool is paused");
+            return results
+                .into_iter()
+                .map(|_| Err(RpcPoolError::PoolPaused))
+                .collect();
+        }
+        if let Err(e) = self.check_min_healthy() {
+            return results.into_iter().map(|_| Err(e.clone())).collect();
+        }
+
+        let targets = self.batch_targets();
+        if targets.is_empty() {
+            return results
+                .into_iter()
+                .map(|_| Err(RpcPoolError::NoEndpointsConfigured))
+                .collect();
+        }
+
+        let max_attempts = self.max_consecutive_errors.max(1) as usize;
+        debug!(
+            items = item_count,
+            concurrency,
+            endpoints = targets.len(),
+            max_attempts,
+            "Starting batch execution"
+        );
+
+        let run_item = |index: usize, item: I| {
+            let targets = &targets;
+            let f = &f;
+            async move {
+                self.total_requests.fetch_add(1, Ordering::Relaxed);
+                let mut last_error = None;
+
+                for attempt in 0..max_attempts {
+                    let endpoint = &targets[(index + attempt) % targets.len()];
+                    if attempt > 0 {
+                        self.failovers.fetch_add(1, Ordering::Relaxed);
+                    }
+
+                    let url: url::Url = match endpoint.url.parse() {
+                        Ok(url) => url,
+                        Err(e) => {
+                            let error_msg = format!("Invalid URL: {}", e);
+                            self.record_attempt_failure(endpoint, error_msg.clone());
+                            last_error = Some(error_msg);
+                            continue;
+                        }
+                    };
+
+                    let throttle = self.reserve_rate_limit(endpoint);
+                    let request_future = f(item.clone(), url);
+                    let start = Instant::now();
+                    let result = tokio::time::timeout(self.request_timeout, async move {
+                        if !throttle.is_zero() {
+                            tokio::time::sleep(throttle).await;
+                        }
+                        request_future.await
+                    })
+                    .await;
+
+                    match result {
+                        Ok(Ok(value)) => {
+                            let latency = start.elapsed().as_millis() as u64;
+                            self.record_attempt_success(endpoint, latency);
+                            return (index, Ok(value));
+                        }
+                        Ok(Err(e)) => {
+                            let error_msg = truncate_error_message(&e.to_string());
                             trace!(
+                                index,
                                 endpoint_name = %endpoint.name,
                                 error = %e,
-                                recovery_attempts = stats.recovery_attempts,
-                                next_retry_secs = next_retry.as_secs(),
-                                "Endpoint health check failed, increasing backoff"
+                                "Batch item attempt failed"
                             );
+                            self.record_attempt_failure(endpoint, error_msg.clone());
+                            last_error = Some(error_msg);
                         }
-                    }
-                    Err(_) => {
-                        if let Some(stats) = self.stats.write().get_mut(&endpoint.url) {
-                            stats.last_error_time = Some(Instant::now());
-                            stats.increment_recovery_attempts();
-                            let next_retry = stats.current_retry_delay(self.retry_delay);
+                        Err(_timeout) => {
+                            let error_msg = format!(
+                                "Request timeout after {}ms",
+                                self.request_timeout.as_millis()
+                            );
                             trace!(
+                                index,
                                 endpoint_name = %endpoint.name,
-                                timeout_ms = self.health_check_timeout.as_millis() as u64,
-                                recovery_attempts = stats.recovery_attempts,
-                                next_retry_secs = next_retry.as_secs(),
-                                "Endpoint health check timed out, increasing backoff"
+                                "Batch item attempt timed out"
                             );
+                            self.record_attempt_failure(endpoint, error_msg.clone());
+                            last_error = Some(error_msg);
                         }
                     }
                 }
+
+                let error_msg = last_error.unwrap_or_else(|| "Unknown error".to_string());
+                (index, Err(RpcPoolError::AllEndpointsFailed(error_msg)))
             }
-        }
+        };
 
-        if checked_count > 0 {
-            debug!(
-                checked = checked_count,
-                recovered = recovered_count,
-                "Health check cycle completed"
-            );
+        let mut pending = items.into_iter().enumerate();
+        let mut in_flight = FuturesUnordered::new();
+        for (index, item) in pending.by_ref().take(concurrency.max(1)) {
+            in_flight.push(run_item(index, item));
         }
-    }
 
-    /// Gracefully shutdown the pool.
-    ///
-    /// This cancels the health check task and prevents new requests.
-    /// Existing in-flight requests will complete or be cancelled.
-    pub async fn shutdown(&self) {
-        info!("Initiating RPC pool shutdown");
-
-        // Signal cancellation
-        self.cancelled.store(true, Ordering::Release);
-        self.cancel_notify.notify_waiters();
+        loop {
+            let next = tokio::select! {
+                biased;
 
-        // Wait for health check task to finish
-        let handle = self.health_check_handle.write().take();
-        if let Some(handle) = handle {
-            // Give it a moment to finish gracefully
-            let _ = tokio::time::timeout(Duration::from_secs(5), async {
-                loop {
-                    if handle.is_finished() {
-                        break;
-                    }
-                    tokio::time::sleep(Duration::from_millis(10)).await;
+                _ = self.cancelled() => {
+                    debug!(remaining = in_flight.len(), "Batch cancelled: pool shutdown in progress");
+                    break;
                 }
-            })
-            .await;
-        }
 
-        info!("RPC pool shutdown complete");
+                next = in_flight.next() => next,
+            };
+
+            let (index, result) = match next {
+                Some(completed) => completed,
+                None => break,
+            };
+            results[index] = Some(result);
+
+            if let Some((index, item)) = pending.next() {
+                in_flight.push(run_item(index, item));
+            }
+        }
+
+        let failed = results.iter().filter(|r| !matches!(r, Some(Ok(_)))).count();
+        debug!(items = item_count, failed, "Batch execution completed");
+
+        results
+            .into_iter()
+            .map(|r| r.unwrap_or(Err(RpcPoolError::PoolShutdown)))
+            .collect()
+    }
+
+    /// Healthy endpoints in priority order, or all endpoints if none are healthy.
+    fn batch_targets(&self) -> Vec<RpcEndpoint> {
+        let endpoints = self.endpoints.read();
+        let stats = self.stats.read();
+        let healthy: Vec<RpcEndpoint> = endpoints
+            .iter()
+            .filter(|e| stats.get(&e.url).map(|s| s.is_healthy).unwrap_or(true))
+            .cloned()
+            .collect();
+
+        if healthy.is_empty() {
+            endpoints.clone()
+        } else {
+            healthy
+        }
+    }
+
+    /// Select up to `count` distinct endpoints in strategy order.
+    fn select_endpoints(&self, count: usize) -> Vec<RpcEndpoint> {
+        let stats_map = self.collect_stats_snapshot();
+        let endpoints = self.endpoints.read();
+        let mut strategy = self.strategy.write();
+        let mut exclude = HashSet::new();
+        let mut selected = Vec::new();
+
+        for _ in 0..count.min(endpoints.len()) {
+            match strategy.select(&endpoints, &stats_map, &exclude, &SelectionContext::default()) {
+                Some(e) => {
+                    exclude.insert(e.url.clone());
+                    selected.push(e.clone());
+                }
+                None => break,
+            }
+        }
+
+        selected
+    }
+
+    /// Take a rate-limit token for an endpoint, returning how long to wait before sending.
+    fn reserve_rate_limit(&self, endpoint: &RpcEndpoint) -> Duration {
+        let wait = match self.rate_limiters.get(&endpoint.url) {
+            Some(bucket) => bucket.lock().reserve(),
+            None => return Duration::ZERO,
+        };
+        if !wait.is_zero() {
+            trace!(
+                endpoint_name = %endpoint.name,
+                wait_ms = wait.as_millis() as u64,
+                "Rate limit reached, delaying request"
+            );
+        }
+        wait
+    }
+
+    /// Record a successful attempt against an endpoint.
+    fn record_attempt_success(&self, endpoint: &RpcEndpoint, latency_ms: u64) {
+        self.update_endpoint_stats(&endpoint.url, |stats| {
+            stats.record_success(latency_ms);
+            if stats.recovering && self.confidence_threshold.is_some_and(|n| stats.is_confident(n)) {
+                stats.recovering = false;
+                debug!(endpoint = %endpoint.name, "Recovered endpoint reached confidence threshold");
+            }
+        });
+        self.strategy.write().on_success(&endpoint.url, latency_ms);
+    }
+
+    /// Record a failed attempt against an endpoint.
+    fn record_attempt_failure(&self, endpoint: &RpcEndpoint, error_msg: String) {
+        self.update_endpoint_stats(&endpoint.url, |stats| {
+            let marked_unhealthy =
+                stats.record_failure(error_msg.clone(), self.max_consecutive_errors);
+            if marked_unhealthy {
+                warn!(
+                    endpoint = %endpoint.name,
+                    consecutive_errors = stats.consecutive_errors,
+                    "Endpoint marked unhealthy"
+                );
+            }
+        });
+        self.strategy.write().on_failure(&endpoint.url, &error_msg);
+    }
+
+    /// Start background health check task.
+    ///
+    /// Returns a handle that can be used to abort the task.
+    /// The task will automatically stop when `shutdown()` is called.
+    pub fn start_health_check(self: &Arc<Self>) -> tokio::task::JoinHandle<()> {
+        self.start_health_check_with(|| async {})
+    }
+
+    /// Start the health check task, also awaiting `also` in every cycle.
+    ///
+    /// Lets a pool composed with this one share its health check task; see
+    /// [`start_health_check`](Self::start_health_check).
+    pub(crate) fn start_health_check_with<A, AFut>(
+        self: &Arc<Self>,
+        also: A,
+    ) -> tokio::task::JoinHandle<()>
+    where
+        A: Fn() -> AFut + Send + 'static,
+        AFut: Future<Output = ()> + Send,
+    {
+        let pool = Arc::clone(self);
+        let interval = self.health_check_interval;
+
+        let handle = tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+
+            loop {
+                tokio::select! {
+                    biased;
+
+                    _ = pool.cancelled() => {
+                        info!("Health check task shutting down");
+                        break;
+                    }
+
+                    _ = ticker.tick() => {
+                        pool.check_health().await;
+                        also().await;
+                    }
+                }
+            }
+        });
+
+        // Store handle for cleanup
+        *self.health_check_handle.write() = Some(handle.abort_handle().into());
+
+        if let Some(write_pool) = &self.write_pool {
+            write_pool.start_health_check();
+        }
+
+        handle
+    }
+
+    /// Perform health check on all endpoints.
+    async fn check_health(&self) {
+        trace!("Starting health check cycle");
+        let mut checked_count = 0u32;
+        let mut recovered_count = 0u32;
+
+        // Snapshot so endpoints can be added or removed while probes are in flight
+        let endpoints = self.endpoints.read().clone();
+
+        for endpoint in &endpoints {
+            // Check for shutdown
+            if self.is_shutdown() {
+                debug!("Health check interrupted by shutdown");
+                return;
+            }
+
+            let should_check = {
+                let stats = self.stats.read();
+                match stats.get(&endpoint.url) {
+                    Some(s) => {
+                        // Only check unhealthy endpoints
+                        if s.is_healthy {
+                            false
+                        } else {
+                            s.can_retry(self.retry_delay)
+                        }
+                    }
+                    None => true,
+                }
+            };
+
+            if !should_check {
+                continue;
+            }
+
+            trace!(
+                endpoint_name = %endpoint.name,
+                method = self.health_check_method.method_name(),
+                "Probing unhealthy endpoint"
+            );
+            checked_count += 1;
+
+            // Try to recover with a simple probe (with timeout)
+            let probe_result = tokio::select! {
+                biased;
+
+                _ = self.cancelled() => {
+                    return;
+                }
+
+                result = self.probe_health(&endpoint.url, &self.health_check_method) => {
+                    result
+                }
+            };
+
+            match probe_result {
+                Ok(block) => {
+                    // A reachable endpoint that is still behind the pool stays unhealthy
+                    let lag = block.zip(self.max_block_lag).and_then(|(block, max_lag)| {
+                        let lag = self.best_known_block()?.saturating_sub(block);
+                        (lag > max_lag).then_some(lag)
+                    });
+
+                    self.update_endpoint_stats(&endpoint.url, |stats| {
+                        if block.is_some() {
+                            stats.last_known_block = block;
+                        }
+                        if let Some(lag) = lag {
+                            stats.last_error_time = Some(Instant::now());
+                            stats.increment_recovery_attempts();
+                            trace!(
+                                endpoint_name = %endpoint.name,
+                                block = ?block,
+                                lag,
+                                recovery_attempts = stats.recovery_attempts,
+                                "Endpoint reachable but still lagging, increasing backoff"
+                            );
+                        } else {
+                            stats.mark_recovered();
+                            info!(endpoint = %endpoint.name, "Endpoint recovered");
+                            recovered_count += 1;
+                        }
+                    });
+                }
+                Err(e) => {
+                    if let Some(stats) = self.stats.write().get_mut(&endpoint.url) {
+                        stats.last_error_time = Some(Instant::now());
+                        stats.increment_recovery_attempts();
+                        let next_retry = stats.current_retry_delay(self.retry_delay);
+                        trace!(
+                            endpoint_name = %endpoint.name,
+                            error = %truncate_error_message(&e),
+                            recovery_attempts = stats.recovery_attempts,
+                            next_retry_secs = next_retry.as_secs(),
+                            "Endpoint health check failed, increasing backoff"
+                        );
+                    }
+                }
+            }
+        }
+
+        if let Some(max_lag) = self.max_block_lag {
+            self.check_block_lag(&endpoints, max_lag).await;
+        }
+
+        if checked_count > 0 {
+            debug!(
+                checked = checked_count,
+                recovered = recovered_count,
+                "Health check cycle completed"
+            );
+        }
+    }
+
+    /// Probe healthy endpoints and mark those lagging the best known block as unhealthy.
+    async fn check_block_lag(&self, endpoints: &[RpcEndpoint], max_lag: u64) {
+        let healthy: Vec<&RpcEndpoint> = {
+            let stats = self.stats.read();
+            endpoints
+                .iter()
+                .filter(|e| stats.get(&e.url).map(|s| s.is_healthy).unwrap_or(false))
+                .collect()
+        };
+
+        if healthy.is_empty() {
+            return;
+        }
+
+        let probes = futures_util::future::join_all(
+            healthy
+                .iter()
+                .map(|e| async move { (*e, self.probe_block_number(&e.url).await) }),
+        );
+
+        let results = tokio::select! {
+            biased;
+
+            _ = self.cancelled() => {
+                return;
+            }
+
+            results = probes => results,
+        };
+
+        let mut reported = Vec::with_capacity(results.len());
+        {
+            let mut stats = self.stats.write();
+            for (endpoint, result) in results {
+                match result {
+                    Ok(block) => {
+                        if let Some(s) = stats.get_mut(&endpoint.url) {
+                            s.last_known_block = Some(block);
+                        }
+                        reported.push((endpoint, block));
+                    }
+                    Err(e) => {
+                        trace!(endpoint_name = %endpoint.name, error = %e, "Block lag probe failed");
+                    }
+                }
+            }
+        }
+
+        let best = match self.best_known_block() {
+            Some(best) => best,
+            None => return,
+        };
+
+        for (endpoint, block) in reported {
+            let lag = best.saturating_sub(block);
+            if lag <= max_lag {
+                continue;
+            }
+            self.update_endpoint_stats(&endpoint.url, |s| {
+                s.set_healthy(false);
+                s.last_error = Some(format!(
+                    "Block lag: {} blocks behind best known block {}",
+                    lag, best
+                ));
+                s.last_error_time = Some(Instant::now());
+                warn!(
+                    endpoint = %endpoint.name,
+                    block,
+                    best_block = best,
+                    lag,
+                    max_lag,
+                    "Endpoint marked unhealthy due to block lag"
+                );
+            });
+        }
+    }
+
+    /// Highest block number reported by any endpoint.
+    fn best_known_block(&self) -> Option<u64> {
+        self.stats
+            .read()
+            .values()
+            .filter_map(|s| s.last_known_block)
+            .max()
+    }
+
+    /// Run a health probe against `url` with its headers and the health check timeout.
+    async fn probe_health(
+        &self,
+        url: &str,
+        method: &HealthCheckMethod,
+    ) -> Result<Option<u64>, String> {
+        let client = self.http_client(url).map_err(|e| e.to_string())?;
+        method.probe(&client, url, self.health_check_timeout).await
+    }
+
+    /// Fetch an endpoint's block number, bounded by the health check timeout.
+    async fn probe_block_number(&self, url: &str) -> Result<u64, String> {
+        match self.probe_health(url, &HealthCheckMethod::BlockNumber).await {
+            Ok(block) => Ok(block.unwrap_or_default()),
+            Err(e) => Err(truncate_error_message(&e)),
+        }
+    }
+
+    /// Gracefully shutdown the pool.
+    ///
+    /// This cancels the health check task and prevents new requests.
+    /// Existing in-flight requests will complete or be cancelled.
+    pub async fn shutdown(&self) {
+        info!("Initiating RPC pool shutdown");
+
+        // Signal cancellation, including to the write pool
+        let pools: Vec<&RpcPool> = std::iter::once(self)
+            .chain(self.write_pool.as_deref())
+            .collect();
+        for pool in &pools {
+            pool.cancelled.store(true, Ordering::Release);
+            pool.cancel_notify.notify_waiters();
+        }
+        let _ = self.health_events.send(HealthEvent::PoolShutdown);
+
+        // Wait for health check tasks to finish
+        let handles: Vec<AbortHandleWrapper> = pools
+            .iter()
+            .filter_map(|pool| pool.health_check_handle.write().take())
+            .collect();
+        if !handles.is_empty() {
+            // Give them a moment to finish gracefully
+            let _ = tokio::time::timeout(Duration::from_secs(5), async {
+                loop {
+                    if handles.iter().all(|handle| handle.is_finished()) {
+                        break;
+                    }
+                    tokio::time::sleep(Duration::from_millis(10)).await;
+                }
+            })
+            .await;
+        }
+
+        info!("RPC pool shutdown complete");
+    }
+
+    /// Probe every endpoint's capabilities concurrently and store the results.
+    ///
+    /// Runs [`EndpointCapabilities::probe`] against each endpoint with the
+    /// health check timeout and replaces the probed fields of its
+    /// [`RpcEndpoint::capabilities`]. Unreachable endpoints keep their existing
+    /// capabilities. Returns the number of endpoints updated.
+    pub async fn probe_all_capabilities(&self) -> usize {
+        let endpoints = self.endpoints.read().clone();
+        let timeout = self.health_check_timeout;
+
+        let results = futures_util::future::join_all(endpoints.iter().map(|e| async move {
+            let probed = match self.http_client(&e.url) {
+                Ok(client) => EndpointCapabilities::probe_with_client(client, &e.url, timeout).await,
+                Err(err) => Err(err),
+            };
+            (e, probed)
+        }))
+        .await;
+
+        let mut updated = 0;
+        let mut current = self.endpoints.write();
+        for (endpoint, result) in results {
+            let probed = match result {
+                Ok(probed) => probed,
+                Err(e) => {
+                    warn!(endpoint = %endpoint.name, error = %e, "Capability probe failed");
+                    continue;
+                }
+            };
+            // The endpoint may have been removed while probing
+            if let Some(e) = current.iter_mut().find(|e| e.url == endpoint.url) {
+                // Keep what the probe does not measure from the configuration
+                e.capabilities = EndpointCapabilities {
+                    supports_debug_trace: e.capabilities.supports_debug_trace,
+                    supports_websocket: e.capabilities.supports_websocket,
+                    rate_limit_rps: e.capabilities.rate_limit_rps,
+                    is_starknet: e.capabilities.is_starknet,
+                    ..probed
+                };
+                debug!(
+                    endpoint = %e.name,
+                    grade = %e.capabilities.grade(),
+                    "Updated endpoint capabilities"
+                );
+                updated += 1;
+            }
+        }
+        updated
+    }
+
+    /// Probe every endpoint with `eth_blockNumber` before serving traffic.
+    ///
+    /// Endpoints are otherwise assumed healthy until requests fail, so a pool
+    /// with many dead endpoints can take several failover cycles to find a
+    /// working one. Up to `concurrency` probes run at once, each bounded by the
+    /// health check timeout. Unreachable endpoints are marked unhealthy and
+    /// left to the health checker; reachable ones get their probe latency as
+    /// their initial average latency and their block number recorded. Probes
+    /// do not count as requests in the [`metrics`](Self::metrics). Write
+    /// endpoints are warmed up too.
+    ///
+    /// Fails with [`RpcPoolError::PoolShutdown`] after shutdown.
+    pub async fn warm_up(&self, concurrency: usize) -> Result<WarmUpReport, RpcPoolError> {
+        if self.is_shutdown() {
+            return Err(RpcPoolError::PoolShutdown);
+        }
+
+        let mut report = self.warm_up_endpoints(concurrency).await;
+        if let Some(write_pool) = &self.write_pool {
+            report.merge(write_pool.warm_up_endpoints(concurrency).await);
+        }
+
+        info!(
+            healthy = report.healthy,
+            unhealthy = report.unhealthy,
+            "RPC pool warm-up complete"
+        );
+        Ok(report)
+    }
+
+    /// Probe this pool's endpoints for [`warm_up`](Self::warm_up) and record the results.
+    async fn warm_up_endpoints(&self, concurrency: usize) -> WarmUpReport {
+        let endpoints = self.endpoints.read().clone();
+        let results: Vec<_> = futures_util::stream::iter(endpoints.iter().map(|e| async move {
+            let start = Instant::now();
+            let result = self.probe_block_number(&e.url).await;
+            (e, result, start.elapsed().as_millis() as u64)
+        }))
+        .buffer_unordered(concurrency.max(1))
+        .collect()
+        .await;
+
+        let mut report = WarmUpReport::default();
+        for (endpoint, result, latency_ms) in results {
+            // The endpoint may have been removed while probing
+            self.update_endpoint_stats(&endpoint.url, |stats| match result {
+                Ok(block) => {
+                    stats.update_latency(latency_ms);
+                    stats.last_known_block = Some(block);
+                    report.healthy += 1;
+                    report.latencies.insert(endpoint.url.clone(), latency_ms);
+                }
+                Err(e) => {
+                    debug!(endpoint = %endpoint.name, error = %e, "Warm-up probe failed");
+                    stats.set_healthy(false);
+                    stats.last_error = Some(e);
+                    stats.last_error_time = Some(Instant::now());
+                    report.unhealthy += 1;
+                }
+            });
+        }
+        report
+    }
+
+    /// Subscribe to endpoint health transitions.
+    ///
+    /// Each receiver sees the events published after it subscribed:
+    /// [`HealthEvent::EndpointMarkedUnhealthy`] and
+    /// [`HealthEvent::EndpointRecovered`] whenever an endpoint changes health,
+    /// [`HealthEvent::AllEndpointsUnhealthy`] right after the last healthy
+    /// endpoint is marked unhealthy, and [`HealthEvent::PoolShutdown`] from
+    /// [`shutdown`](Self::shutdown). A receiver that falls more than
+    /// [`RpcPoolConfig::health_event_capacity`] events behind skips the oldest
+    /// ones. Write endpoints are not covered.
+    pub fn subscribe_health_events(&self) -> broadcast::Receiver<HealthEvent> {
+        self.health_events.subscribe()
+    }
+
+    /// Apply `update` to an endpoint's stats and publish any resulting health transition.
+    ///
+    /// Returns `None` if no endpoint is registered under `url`.
+    fn update_endpoint_stats<R>(
+        &self,
+        url: &str,
+        update: impl FnOnce(&mut EndpointStats) -> R,
+    ) -> Option<R> {
+        let mut stats = self.stats.write();
+        let endpoint = stats.get_mut(url)?;
+        let was_healthy = endpoint.is_healthy;
+        let result = update(endpoint);
+
+        let event = match (was_healthy, endpoint.is_healthy) {
+            (true, false) => HealthEvent::EndpointMarkedUnhealthy {
+                url: url.to_string(),
+                name: endpoint.name.clone(),
+                consecutive_errors: endpoint.consecutive_errors,
+            },
+            (false, true) => HealthEvent::EndpointRecovered {
+                url: url.to_string(),
+                name: endpoint.name.clone(),
+            },
+            _ => return Some(result),
+        };
+        let all_unhealthy = !endpoint.is_healthy && stats.values().all(|s| !s.is_healthy);
+        drop(stats);
+
+        // Sending only fails when nobody is subscribed
+        let _ = self.health_events.send(event);
+        if all_unhealthy {
+            let _ = self.health_events.send(HealthEvent::AllEndpointsUnhealthy);
+        }
+        Some(result)
+    }
+
+    /// Number of endpoints in the pool.
+    pub fn endpoint_count(&self) -> usize {
+        self.endpoints.read().len()
+    }
+
+    /// Number of endpoints currently marked healthy.
+    ///
+    /// Same as [`health_summary`](Self::health_summary)`.healthy`, without
+    /// computing the rest of the summary.
+    pub fn active_endpoint_count(&self) -> usize {
+        self.stats.read().values().filter(|s| s.is_healthy).count()
+    }
+
+    /// Get a summary of endpoint health status.
+    ///
+    /// Returns counts of healthy, degraded, unhealthy, and total endpoints.
+    /// A healthy endpoint is degraded if its success rate is below 95% after
+    /// at least 10 requests.
+    pub fn health_summary(&self) -> HealthSummary {
+        let mut healthy = 0;
+        let mut degraded = 0;
+        let mut unhealthy = 0;
+
+        for stats in self.stats.read().values() {
+            if stats.is_healthy {
+                healthy += 1;
+                if stats.total_requests >= DEGRADED_MIN_REQUESTS
+                    && stats.success_rate() < DEGRADED_SUCCESS_RATE
+                {
+                    degraded += 1;
+                }
+            } else {
+                unhealthy += 1;
+            }
+        }
+
+        let write_healthy = match &self.write_pool {
+            Some(write_pool) => write_pool.health_summary().healthy,
+            None => healthy,
+        };
+
+        HealthSummary {
+            healthy,
+            degraded,
+            unhealthy,
+            total: self.endpoints.read().len(),
+            read_healthy: healthy,
+            write_healthy,
+        }
+    }
+
+    /// Human-readable report of every endpoint, in priority order.
+    ///
+    /// Joins each endpoint's [`EndpointStats::to_report_string`] with a `---` separator line.
+    pub fn full_health_report(&self) -> String {
+        let endpoints = self.endpoints.read();
+        let stats = self.stats.read();
+        endpoints
+            .iter()
+            .filter_map(|e| stats.get(&e.url))
+            .map(EndpointStats::to_report_string)
+            .collect::<Vec<_>>()
+            .join("\n---\n")
+    }
+
+    /// Endpoints currently considered healthy, in priority order.
+    pub fn healthy_endpoints(&self) -> Vec<RpcEndpoint> {
+        self.endpoints_by_health(true)
+    }
+
+    /// Endpoints currently considered unhealthy, in priority order.
+    pub fn unhealthy_endpoints(&self) -> Vec<RpcEndpoint> {
+        self.endpoints_by_health(false)
+    }
+
+    /// Clone the endpoints whose health matches `healthy`.
+    fn endpoints_by_health(&self, healthy: bool) -> Vec<RpcEndpoint> {
+        let endpoints = self.endpoints.read();
+        let stats = self.stats.read();
+        endpoints
+            .iter()
+            .filter(|e| stats.get(&e.url).is_some_and(|s| s.is_healthy == healthy))
+            .cloned()
+            .collect()
+    }
+
+    /// Manually mark an endpoint as unhealthy.
+    pub fn mark_unhealthy(&self, url: &str) {
+        let marked = self.update_endpoint_stats(url, |stats| {
+            stats.set_healthy(false);
+            stats.last_error_time = Some(Instant::now());
+            debug!(
+                endpoint_name = %stats.name,
+                endpoint_url = %url,
+                "Endpoint manually marked unhealthy"
+            );
+        });
+        if marked.is_none() {
+            warn!(endpoint_url = %url, "Attempted to mark unknown endpoint as unhealthy");
+        }
+    }
+
+    /// Manually mark an endpoint as recovered, the inverse of [`mark_unhealthy`](Self::mark_unhealthy).
+    ///
+    /// Useful when external monitoring reports a recovery before the next
+    /// health check cycle. Clears the error streak and recovery backoff but
+    /// keeps the request history; with a
+    /// [`confidence threshold`](RpcPoolConfig::with_confidence_threshold) the
+    /// endpoint stays a last resort until it has proven itself again.
+    pub fn mark_healthy(&self, url: &str) -> Result<(), RpcPoolError> {
+        let marked = self.update_endpoint_stats(url, |stats| {
+            stats.mark_recovered();
+            info!(
+                endpoint_name = %stats.name,
+                endpoint_url = %url,
+                "Endpoint manually marked healthy"
+            );
+        });
+        marked.ok_or_else(|| {
+            warn!(endpoint_url = %url, "Attempted to mark unknown endpoint as healthy");
+            RpcPoolError::EndpointNotFound(url.to_string())
+        })
+    }
+
+    /// Clear an endpoint's statistics and mark it healthy.
+    ///
+    /// Useful after manually confirming an endpoint has recovered, without
+    /// waiting for the next health check cycle.
+    pub fn reset_endpoint_stats(&self, url: &str) -> Result<(), RpcPoolError> {
+        let reset = self.update_endpoint_stats(url, |stats| {
+            stats.reset();
+            info!(endpoint_name = %stats.name, endpoint_url = %url, "Endpoint stats reset");
+        });
+        reset.ok_or_else(|| {
+            warn!(endpoint_url = %url, "Attempted to reset stats of unknown endpoint");
+            RpcPoolError::EndpointNotFound(url.to_string())
+        })
+    }
+
+    /// Clear statistics for every endpoint and mark them all healthy.
+    pub fn reset_all_endpoint_stats(&self) {
+        let urls: Vec<String> = self.stats.read().keys().cloned().collect();
+        for url in &urls {
+            self.update_endpoint_stats(url, EndpointStats::reset);
+        }
+        info!(endpoints = urls.len(), "All endpoint stats reset");
+    }
+
+    /// Add an endpoint to a running pool.
+    ///
+    /// The endpoint is inserted according to its priority and starts with fresh
+    /// statistics. Safe to call while requests are executing.
+    pub fn add_endpoint(&self, endpoint: RpcEndpoint) -> Result<(), RpcPoolError> {
+        let mut endpoints = self.endpoints.write();
+        if endpoints.iter().any(|e| e.url == endpoint.url) {
+            warn!(endpoint_url = %endpoint.url, "Attempted to add duplicate endpoint");
+            return Err(RpcPoolError::DuplicateEndpoint(endpoint.url));
+        }
+
+        self.stats
+            .write()
+            .insert(endpoint.url.clone(), EndpointStats::new(&endpoint));
+
+        // Keep the list sorted by priority; equal priorities keep insertion order
+        let pos = endpoints.partition_point(|e| e.priority <= endpoint.priority);
+        info!(
+            endpoint_name = %endpoint.name,
+            endpoint_url = %endpoint.url,
+            priority = endpoint.priority,
+            "Endpoint added to RPC pool"
+        );
+        endpoints.insert(pos, endpoint);
+        Ok(())
+    }
+
+    /// Remove an endpoint from a running pool.
+    ///
+    /// The endpoint is excluded from selection immediately and its statistics
+    /// are dropped. Requests already in flight to it are allowed to finish.
+    pub fn remove_endpoint(&self, url: &str) -> Result<(), RpcPoolError> {
+        let mut endpoints = self.endpoints.write();
+        let pos = match endpoints.iter().position(|e| e.url == url) {
+            Some(pos) => pos,
+            None => {
+                warn!(endpoint_url = %url, "Attempted to remove unknown endpoint");
+                return Err(RpcPoolError::EndpointNotFound(url.to_string()));
+            }
+        };
+
+        let removed = endpoints.remove(pos);
+        self.stats.write().remove(url);
+        self.http_clients.write().remove(url);
+        info!(
+            endpoint_name = %removed.name,
+            endpoint_url = %url,
+            remaining = endpoints.len(),
+            "Endpoint removed from RPC pool"
+        );
+        Ok(())
+    }
+
+    /// Atomically replace every endpoint in a running pool.
+    ///
+    /// Selection is blocked only while the endpoint list and statistics are
+    /// swapped. All per-endpoint statistics are reset; the pool-wide request
+    /// and failover counters are kept. Requests already in flight to an old
+    /// endpoint finish normally, but their outcome is no longer recorded.
+    pub fn drain_and_replace_endpoints(
+        &self,
+        mut new_endpoints: Vec<RpcEndpoint>,
+    ) -> Result<(), RpcPoolError> {
+        if new_endpoints.is_empty() {
+            warn!("Attempted to replace RPC pool endpoints with an empty list");
+            return Err(RpcPoolError::NoEndpointsConfigured);
+        }
+
+        let mut seen = HashSet::new();
+        new_endpoints.retain(|e| seen.insert(e.url.clone()));
+        new_endpoints.sort_by_key(|e| e.priority);
+
+        let new_stats: HashMap<String, EndpointStats> = new_endpoints
+            .iter()
+            .map(|e| (e.url.clone(), EndpointStats::new(e)))
+            .collect();
+
+        let mut endpoints = self.endpoints.write();
+        let mut stats = self.stats.write();
+        let replaced = std::mem::replace(&mut *endpoints, new_endpoints);
+        *stats = new_stats;
+        self.http_clients.write().clear();
+
+        info!(
+            removed = replaced.len(),
+            added = endpoints.len(),
+            endpoint_names = ?endpoints.iter().map(|e| &e.name).collect::<Vec<_>>(),
+            "RPC pool endpoints replaced"
+        );
+        Ok(())
+    }
+
+    /// Capture endpoint health and statistics for a later warm restart.
+    ///
+    /// Endpoints appear in priority order. Error timestamps are stored relative
+    /// to the time of the snapshot.
+    pub fn snapshot(&self) -> PoolSnapshot {
+        let now = Instant::now();
+        let endpoints = self.endpoints.read();
+        let stats = self.stats.read();
+        PoolSnapshot {
+            total_requests: self.total_requests.load(Ordering::Relaxed),
+            failovers: self.failovers.load(Ordering::Relaxed),
+            endpoints: endpoints
+                .iter()
+                .filter_map(|e| stats.get(&e.url))
+                .map(|s| EndpointSnapshot::capture(s, now))
+                .collect(),
+        }
+    }
+
+    /// Replace endpoint statistics with those from a [`snapshot`](Self::snapshot).
+    ///
+    /// Endpoints are matched by URL; snapshot entries for endpoints not in this
+    /// pool are ignored and endpoints missing from the snapshot keep their
+    /// current stats. Errors are placed in the past by their recorded age, so
+    /// recovery backoff continues where it left off.
+    pub fn restore_from_snapshot(&self, snapshot: PoolSnapshot) {
+        let now = Instant::now();
+        let mut restored = 0;
+        for endpoint in &snapshot.endpoints {
+            match self.update_endpoint_stats(&endpoint.url, |s| endpoint.apply(s, now)) {
+                Some(()) => restored += 1,
+                None => {
+                    debug!(endpoint_url = %endpoint.url, "Skipping snapshot of unknown endpoint");
+                }
+            }
+        }
+
+        self.total_requests
+            .store(snapshot.total_requests, Ordering::Relaxed);
+        self.failovers.store(snapshot.failovers, Ordering::Relaxed);
+        info!(
+            restored,
+            skipped = snapshot.endpoints.len() - restored,
+            "Restored endpoint stats from snapshot"
+        );
+    }
+
+    /// Get current metrics.
+    pub fn metrics(&self) -> RpcPoolMetrics {
+        let endpoints: Vec<EndpointMetrics> = self
+            .stats
+            .read()
+            .values()
+            .map(EndpointMetrics::from)
+            .collect();
+
+        let current_endpoint = {
+            let stats_map = self.collect_stats_snapshot();
+            let endpoints = self.endpoints.read();
+            let mut strategy = self.strategy.write();
+            strategy
+                .select(&endpoints, &stats_map, &HashSet::new(), &SelectionContext::default())
+                .map(|e| e.name.clone())
+                .unwrap_or_else(|| "none".to_string())
+        };
+
+        RpcPoolMetrics {
+            total_requests: self.total_requests.load(Ordering::Relaxed),
+            failovers: self.failovers.load(Ordering::Relaxed),
+            cross_pool_failovers: self.cross_pool_failovers.load(Ordering::Relaxed),
+            current_endpoint,
+            endpoints,
+        }
+    }
+}
+
+impl Drop for RpcPool {
+    fn drop(&mut self) {
+        // Signal shutdown to any running tasks
+        self.cancelled.store(true, Ordering::Release);
+        self.cancel_notify.notify_waiters();
+
+        // Abort health check task if still running
+        if let Some(handle) = self.health_check_handle.get_mut().take() {
+            if !handle.is_finished() {
+                handle.abort();
+            }
+        }
+
+        // The write pool's health check task keeps it alive, so stop it too
+        if let Some(write_pool) = &self.write_pool {
+            write_pool.cancelled.store(true, Ordering::Release);
+            write_pool.cancel_notify.notify_waiters();
+        }
+
+        debug!("RpcPool dropped, resources cleaned up");
+    }
+}
+
+/// Wrapper to store abort handle with is_finished check.
+struct AbortHandleWrapper {
+    handle: tokio::task::AbortHandle,
+}
+
+impl AbortHandleWrapper {
+    fn is_finished(&self) -> bool {
+        self.handle.is_finished()
+    }
+
+    fn abort(&self) {
+        self.handle.abort();
+    }
+}
+
+impl From<tokio::task::AbortHandle> for AbortHandleWrapper {
+    fn from(handle: tokio::task::AbortHandle) -> Self {
+        Self { handle }
+    }
+}
+
+/// Lowercase message fragments that mark a rate-limit rejection: an HTTP 429
+/// status, a JSON-RPC 429 or `-32005` ("limit exceeded") error code, or a
+/// provider's wording. A bare `429` is not enough, as block numbers and hex
+/// values contain it too.
+const RATE_LIMIT_MARKERS: &[&str] = &[
+    "http error 429",
+    "http 429",
+    "status 429",
+    "status: 429",
+    "status code 429",
+    "error code 429",
+    "\"code\":429",
+    "\"code\": 429",
+    "error code -32005",
+    "\"code\":-32005",
+    "\"code\": -32005",
+    "rate limit",
+    "rate-limit",
+    "too many requests",
+];
+
+/// Whether an endpoint error looks like a rate-limit rejection.
+fn is_rate_limit_error(msg: &str) -> bool {
+    let msg = msg.to_ascii_lowercase();
+    RATE_LIMIT_MARKERS.iter().any(|marker| msg.contains(marker))
+}
+
+/// Truncate error message to prevent unbounded memory growth.
+#[inline]
+fn truncate_error_message(msg: &str) -> String {
+    if msg.len() <= MAX_ERROR_MESSAGE_LENGTH {
+        msg.to_string()
+    } else {
+        format!("{}...(truncated)", &msg[..MAX_ERROR_MESSAGE_LENGTH])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::strategies::{
+        AdaptiveStrategy, CircuitBreakerStrategy, FailoverStrategy, RateAwareStrategy,
+        RoundRobinStrategy, ThrottleAwareStrategy,
+    };
+
+    fn create_test_config() -> RpcPoolConfig {
+        RpcPoolConfig::new()
+            .with_endpoints(vec![
+                RpcEndpoint::new("https://rpc1.example.com"),
+                RpcEndpoint::new("https://rpc2.example.com"),
+            ])
+            .with_strategy(Box::new(FailoverStrategy))
+    }
+
+    #[test]
+    fn test_pool_creation() {
+        let config = create_test_config();
+        let pool = RpcPool::new(config);
+        assert!(pool.is_ok());
+    }
+
+    #[test]
+    fn test_empty_endpoints() {
+        let config = RpcPoolConfig::new().with_strategy(Box::new(FailoverStrategy));
+
+        let pool = RpcPool::new(config);
+        assert!(matches!(pool, Err(RpcPoolError::NoEndpointsConfigured)));
+    }
+
+    #[test]
+    fn test_get_urls() {
+        let config = RpcPoolConfig::new()
+            .with_endpoints(vec![
+                RpcEndpoint::new("https://rpc1.example.com").with_priority(10),
+                RpcEndpoint::new("https://rpc2.example.com").with_priority(50),
+            ])
+            .with_strategy(Box::new(FailoverStrategy));
+
+        let pool = RpcPool::new(config).unwrap();
+
+        // Should return highest priority (lowest number) endpoint
+        assert_eq!(
+            pool.get_current_url(),
+            Some("https://rpc1.example.com".to_string())
+        );
+
+        // Should return all URLs
+        let all = pool.get_all_urls();
+        assert_eq!(all.len(), 2);
+    }
+
+    #[test]
+    fn test_truncate_error_message() {
+        let short_msg = "Short error";
+        assert_eq!(truncate_error_message(short_msg), short_msg);
+
+        let long_msg = "x".repeat(1000);
+        let truncated = truncate_error_message(&long_msg);
+        assert!(truncated.len() < long_msg.len());
+        assert!(truncated.ends_with("...(truncated)"));
+    }
+
+    #[test]
+    fn test_is_rate_limit_error() {
+        assert!(is_rate_limit_error("HTTP error 429 with body: Too Many Requests"));
+        assert!(is_rate_limit_error("request failed with status 429"));
+        assert!(is_rate_limit_error(
+            "server returned an error response: error code -32005: limit exceeded"
+        ));
+        assert!(is_rate_limit_error(r#"{"code": 429, "message": "slow down"}"#));
+        assert!(is_rate_limit_error("Rate limit reached for this API key"));
+
+        // 429 inside a block number, hash or hex value is not a status
+        assert!(!is_rate_limit_error("block 14290000 not found"));
+        assert!(!is_rate_limit_error("unknown block 0x4291ab"));
+        assert!(!is_rate_limit_error("HTTP error 500 with body: tx 0xabc429 reverted"));
+    }
+
+    /// Responds to any JSON-RPC request with a fixed block number.
+    struct BlockNumberResponder(u64);
+
+    impl wiremock::Respond for BlockNumberResponder {
+        fn respond(&self, request: &wiremock::Request) -> wiremock::ResponseTemplate {
+            let body: serde_json::Value = serde_json::from_slice(&request.body).unwrap();
+            wiremock::ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": body["id"],
+                "result": format!("0x{:x}", self.0),
+            }))
+        }
+    }
+
+    async fn mock_endpoint(block: u64) -> wiremock::MockServer {
+        let server = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::method("POST"))
+            .respond_with(BlockNumberResponder(block))
+            .mount(&server)
+            .await;
+        server
+    }
+
+    /// Mock node that only answers requests carrying `Authorization: Bearer secret`.
+    async fn authenticated_endpoint(block: u64) -> wiremock::MockServer {
+        let server = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::method("POST"))
+            .and(wiremock::matchers::header("authorization", "Bearer secret"))
+            .respond_with(BlockNumberResponder(block))
+            .mount(&server)
+            .await;
+        wiremock::Mock::given(wiremock::matchers::method("POST"))
+            .respond_with(wiremock::ResponseTemplate::new(401))
+            .mount(&server)
+            .await;
+        server
+    }
+
+    #[tokio::test]
+    async fn test_execute_with_client_sends_endpoint_headers() {
+        use alloy::providers::{Provider, ProviderBuilder};
+
+        let server = authenticated_endpoint(1_000).await;
+        let get_block = |client, url| async move {
+            ProviderBuilder::new()
+                .connect_reqwest(client, url)
+                .get_block_number()
+                .await
+        };
+
+        let pool = RpcPool::new(RpcPoolConfig::new().with_endpoints(vec![
+            RpcEndpoint::new(server.uri()).with_header("Authorization", "Bearer secret"),
+        ]))
+        .unwrap();
+        assert_eq!(pool.execute_with_client(get_block).await.unwrap(), 1_000);
+        assert_eq!(pool.http_clients.read().len(), 1);
+
+        let unauthenticated =
+            RpcPool::new(RpcPoolConfig::new().with_endpoints(vec![RpcEndpoint::new(server.uri())]))
+                .unwrap();
+        assert!(unauthenticated.execute_with_client(get_block).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_health_check_sends_endpoint_headers() {
+        let server = authenticated_endpoint(1_000).await;
+        let pool = RpcPool::new(
+            RpcPoolConfig::new()
+                .with_endpoints(vec![
+                    RpcEndpoint::new(server.uri()).with_header("Authorization", "Bearer secret")
+                ])
+                .with_max_block_lag(10),
+        )
+        .unwrap();
+
+        pool.check_health().await;
+        assert_eq!(pool.stats.read()[&server.uri()].last_known_block, Some(1_000));
+    }
+
+    #[tokio::test]
+    async fn test_proxy_carries_requests() {
+        use alloy::providers::{Provider, ProviderBuilder};
+
+        // Proxy that answers for any target once authenticated as "user:pass"
+        let proxy = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::method("POST"))
+            .and(wiremock::matchers::header(
+                "proxy-authorization",
+                "Basic dXNlcjpwYXNz",
+            ))
+            .respond_with(BlockNumberResponder(1_000))
+            .mount(&proxy)
+            .await;
+
+        // Only reachable through the proxy
+        let url = "http://rpc.invalid:8545";
+        let pool = RpcPool::new(
+            RpcPoolConfig::new()
+                .with_endpoints(vec![RpcEndpoint::new(url)])
+                .with_max_block_lag(10)
+                .with_proxy(proxy.uri())
+                .with_proxy_credentials("user".to_string(), "pass".to_string()),
+        )
+        .unwrap();
+
+        let block = pool
+            .execute_with_client(|client, url| async move {
+                ProviderBuilder::new()
+                    .connect_reqwest(client, url)
+                    .get_block_number()
+                    .await
+            })
+            .await
+            .unwrap();
+        assert_eq!(block, 1_000);
+
+        pool.check_health().await;
+        assert_eq!(pool.stats.read()[url].last_known_block, Some(1_000));
+
+        let requests = proxy.received_requests().await.unwrap();
+        assert_eq!(requests.len(), 2);
+        assert!(requests.iter().all(|r| r.url.as_str().starts_with(url)));
+    }
+
+    #[test]
+    fn test_invalid_proxy_rejected() {
+        let result = RpcPool::new(
+            RpcPoolConfig::new()
+                .with_endpoints(vec![RpcEndpoint::new("https://rpc.example.com")])
+                .with_proxy("not a proxy"),
+        );
+        assert!(matches!(result, Err(RpcPoolError::InvalidUrl(_))));
+    }
+
+    #[tokio::test]
+    async fn test_invalid_header_fails_request() {
+        let pool = RpcPool::new(RpcPoolConfig::new().with_endpoints(vec![
+            RpcEndpoint::new("https://rpc.example.com").with_header("Bad Header", "value"),
+        ]))
+        .unwrap();
+
+        let result = pool
+            .execute_with_client(|_client, _url| async { Ok::<_, std::io::Error>(()) })
+            .await;
+        match result {
+            Err(RpcPoolError::AllEndpointsFailed(msg)) => {
+                assert!(msg.contains("Failed to create RPC client"), "{}", msg)
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_block_lag_marks_stale_endpoint_unhealthy() {
+        let fresh = mock_endpoint(1_000).await;
+        let stale = mock_endpoint(900).await;
+        let close = mock_endpoint(995).await;
+
+        let pool = RpcPool::new(
+            RpcPoolConfig::new()
+                .with_endpoints(vec![
+                    RpcEndpoint::new(fresh.uri()),
+                    RpcEndpoint::new(stale.uri()),
+                    RpcEndpoint::new(close.uri()),
+                ])
+                .with_max_block_lag(10),
+        )
+        .unwrap();
+
+        pool.check_health().await;
+
+        let stats = pool.stats.read();
+        assert!(stats[&fresh.uri()].is_healthy);
+        assert!(stats[&close.uri()].is_healthy);
+        assert!(!stats[&stale.uri()].is_healthy);
+        assert_eq!(stats[&stale.uri()].last_known_block, Some(900));
+        assert_eq!(stats[&fresh.uri()].last_known_block, Some(1_000));
+    }
+
+    #[tokio::test]
+    async fn test_lagging_endpoint_not_recovered() {
+        let fresh = mock_endpoint(1_000).await;
+        let stale = mock_endpoint(900).await;
+
+        let pool = RpcPool::new(
+            RpcPoolConfig::new()
+                .with_endpoints(vec![RpcEndpoint::new(fresh.uri()), RpcEndpoint::new(stale.uri())])
+                .with_retry_delay(Duration::ZERO)
+                .with_max_block_lag(10),
+        )
+        .unwrap();
+
+        pool.check_health().await;
+        assert!(!pool.stats.read()[&stale.uri()].is_healthy);
+
+        // Reachable on the recovery probe, but still too far behind
+        pool.check_health().await;
+        let stats = pool.stats.read();
+        assert!(!stats[&stale.uri()].is_healthy);
+        assert_eq!(stats[&stale.uri()].recovery_attempts, 1);
+    }
+
+    #[tokio::test]
+    async fn test_recovery_uses_configured_health_check_method() {
+        let server = mock_endpoint(1_000).await;
+
+        let pool_with = |method: HealthCheckMethod| {
+            let pool = RpcPool::new(
+                RpcPoolConfig::new()
+                    .with_endpoints(vec![RpcEndpoint::new(server.uri())])
+                    .with_retry_delay(Duration::ZERO)
+                    .with_health_check_method(method),
+            )
+            .unwrap();
+            pool.mark_unhealthy(&server.uri());
+            pool
+        };
+
+        let pool = pool_with(HealthCheckMethod::NetVersion);
+        pool.check_health().await;
+        assert!(pool.stats.read()[&server.uri()].is_healthy);
+        // Only eth_blockNumber reports a block
+        assert_eq!(pool.stats.read()[&server.uri()].last_known_block, None);
+
+        // The mock answers with a plain string, not an object with the key
+        let pool = pool_with(HealthCheckMethod::Custom {
+            method: "eth_syncing".to_string(),
+            params: serde_json::json!([]),
+            expected_key: "currentBlock".to_string(),
+        });
+        pool.check_health().await;
+        assert!(!pool.stats.read()[&server.uri()].is_healthy);
+    }
+
+    #[tokio::test]
+    async fn test_warm_up_prepopulates_stats() {
+        let up = mock_endpoint(1_000).await;
+        let pool = RpcPool::new(RpcPoolConfig::new().with_endpoints(vec![
+            RpcEndpoint::new(up.uri()).with_priority(1),
+            RpcEndpoint::new("http://127.0.0.1:1").with_priority(0),
+        ]))
+        .unwrap();
+
+        let report = pool.warm_up(2).await.unwrap();
+        assert_eq!(report.healthy, 1);
+        assert_eq!(report.unhealthy, 1);
+        assert_eq!(report.latencies.keys().collect::<Vec<_>>(), vec![&up.uri()]);
+
+        {
+            let stats = pool.stats.read();
+            let up_stats = &stats[&up.uri()];
+            assert!(up_stats.is_healthy);
+            assert_eq!(up_stats.last_known_block, Some(1_000));
+            assert_eq!(up_stats.avg_latency_ms, report.latencies[&up.uri()] as f64);
+            assert_eq!(up_stats.total_requests, 0);
+
+            let down_stats = &stats["http://127.0.0.1:1"];
+            assert!(!down_stats.is_healthy);
+            assert!(down_stats.last_error.is_some());
+        }
+
+        // The dead endpoint has the better priority but is skipped straight away
+        assert_eq!(pool.get_current_url(), Some(up.uri()));
+        assert_eq!(pool.metrics().total_requests, 0);
+
+        pool.shutdown().await;
+        assert!(matches!(
+            pool.warm_up(2).await,
+            Err(RpcPoolError::PoolShutdown)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_block_lag_disabled_by_default() {
+        let fresh = mock_endpoint(1_000).await;
+        let stale = mock_endpoint(900).await;
+
+        let pool = RpcPool::new(RpcPoolConfig::new().with_endpoints(vec![
+            RpcEndpoint::new(fresh.uri()),
+            RpcEndpoint::new(stale.uri()),
+        ]))
+        .unwrap();
+
+        pool.check_health().await;
+        let stats = pool.stats.read();
+        assert!(stats[&stale.uri()].is_healthy);
+        assert_eq!(stats[&stale.uri()].last_known_block, None);
+    }
+
+    #[test]
+    fn test_config_builder() {
+        let config = RpcPoolConfig::new()
+            .with_request_timeout(Duration::from_secs(10))
+            .with_health_check_timeout(Duration::from_secs(5))
+            .with_health_check_interval(Duration::from_secs(30))
+            .with_max_consecutive_errors(5)
+            .with_retry_delay(Duration::from_secs(10));
+
+        assert_eq!(config.request_timeout, Duration::from_secs(10));
+        assert_eq!(config.health_check_timeout, Duration::from_secs(5));
+        assert_eq!(config.health_check_interval, Duration::from_secs(30));
+        assert_eq!(config.max_consecutive_errors, 5);
+        assert_eq!(config.retry_delay, Duration::from_secs(10));
+    }
+
+    #[tokio::test]
+    async fn test_cloned_config_with_strategy_factory_has_independent_strategy() {
+        use crate::strategies::RoundRobinStrategy;
+
+        let template =
+            create_test_config().with_strategy_factory(|| Box::new(RoundRobinStrategy::new()));
+        let first = RpcPool::new(template.clone()).unwrap();
+        let second = RpcPool::new(template.clone()).unwrap();
+        let ok = |url: url::Url| async move { Ok::<_, std::io::Error>(url.to_string()) };
+
+        // Advancing one pool's rotation leaves the other's untouched
+        assert_eq!(first.execute(ok).await.unwrap(), "https://rpc1.example.com/");
+        assert_eq!(first.execute(ok).await.unwrap(), "https://rpc2.example.com/");
+        assert_eq!(second.execute(ok).await.unwrap(), "https://rpc1.example.com/");
+        assert_eq!(second.strategy_name(), "round-robin");
+
+        // A plain strategy is shared between clones
+        let shared = create_test_config().with_strategy(Box::new(RoundRobinStrategy::new()));
+        let clone = shared.clone();
+        assert!(shared.strategy_factory.is_none());
+        assert!(Arc::ptr_eq(&shared.strategy, &clone.strategy));
+    }
+
+    #[tokio::test]
+    async fn test_execute_deduped_coalesces_concurrent_calls() {
+        use std::sync::atomic::AtomicUsize;
+
+        let pool = Arc::new(
+            RpcPool::new(create_test_config().with_request_deduplication(Duration::from_secs(5)))
+                .unwrap(),
+        );
+        let calls = Arc::new(AtomicUsize::new(0));
+        let request = {
+            let calls = Arc::clone(&calls);
+            move |_url: url::Url| {
+                let calls = Arc::clone(&calls);
+                async move {
+                    let n = calls.fetch_add(1, Ordering::SeqCst);
+                    tokio::time::sleep(Duration::from_millis(50)).await;
+                    Ok::<_, std::io::Error>(n)
+                }
+            }
+        };
+
+        let results = futures_util::future::join_all(
+            (0..10).map(|_| pool.execute_deduped(1, request.clone())),
+        )
+        .await;
+        assert!(results.iter().all(|r| matches!(r, Ok(0))));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert_eq!(pool.inflight_request_count(), 0);
+
+        // Other keys, and calls after the request resolved, send their own requests
+        let (a, b) = tokio::join!(
+            pool.execute_deduped(1, request.clone()),
+            pool.execute_deduped(2, request.clone())
+        );
+        assert_ne!(a.unwrap(), b.unwrap());
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+
+        // Without deduplication every call is sent
+        let pool = Arc::new(RpcPool::new(create_test_config()).unwrap());
+        let (a, b) = tokio::join!(
+            pool.execute_deduped(1, request.clone()),
+            pool.execute_deduped(1, request.clone())
+        );
+        assert_ne!(a.unwrap(), b.unwrap());
+        assert_eq!(calls.load(Ordering::SeqCst), 5);
+    }
+
+    #[tokio::test]
+    async fn test_execute_write_uses_write_endpoints() {
+        let config = RpcPoolConfig::new()
+            .with_endpoints(vec![RpcEndpoint::new("https://read.example.com")])
+            .with_write_endpoints(vec![
+                RpcEndpoint::new("https://sequencer-a.example.com").with_priority(0),
+                RpcEndpoint::new("https://sequencer-b.example.com").with_priority(1),
+            ]);
+        let pool = RpcPool::new(config).unwrap();
+
+        let host = |url: url::Url| async move {
+            Ok::<_, std::io::Error>(url.host_str().unwrap().to_string())
+        };
+        assert_eq!(
+            pool.execute_read_only(host).await.unwrap(),
+            "read.example.com"
+        );
+        assert_eq!(
+            pool.execute_write(host).await.unwrap(),
+            "sequencer-a.example.com"
+        );
+
+        // Writes fail over across write endpoints but never reach the read endpoint
+        let tried = Arc::new(Mutex::new(Vec::new()));
+        let result = pool
+            .execute_write(|url: url::Url| {
+                let tried = Arc::clone(&tried);
+                async move {
+                    tried.lock().push(url.host_str().unwrap().to_string());
+                    Err::<(), _>(std::io::Error::other("nonce too low"))
+                }
+            })
+            .await;
+        assert!(matches!(result, Err(RpcPoolError::AllEndpointsFailed(_))));
+        assert_eq!(
+            *tried.lock(),
+            vec!["sequencer-a.example.com", "sequencer-b.example.com"]
+        );
+
+        let summary = pool.health_summary();
+        assert_eq!(summary.total, 1);
+        assert_eq!(summary.read_healthy, 1);
+        assert_eq!(summary.write_healthy, 2);
+
+        pool.shutdown().await;
+        assert!(matches!(
+            pool.execute_write(host).await,
+            Err(RpcPoolError::PoolShutdown)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_execute_write_without_write_endpoints() {
+        let pool = RpcPool::new(create_test_config()).unwrap();
+        let host = |url: url::Url| async move {
+            Ok::<_, std::io::Error>(url.host_str().unwrap().to_string())
+        };
+        assert_eq!(
+            pool.execute_write(host).await.unwrap(),
+            pool.execute(host).await.unwrap()
+        );
+
+        let summary = pool.health_summary();
+        assert_eq!(summary.write_healthy, summary.read_healthy);
+        assert_eq!(summary.read_healthy, summary.healthy);
+    }
+
+    #[tokio::test]
+    async fn test_execute_abortable() {
+        use std::sync::atomic::AtomicUsize;
+
+        let pool = RpcPool::new(create_test_config()).unwrap();
+        let calls = AtomicUsize::new(0);
+        let slow = |_url: url::Url| {
+            calls.fetch_add(1, Ordering::SeqCst);
+            async {
+                tokio::time::sleep(Duration::from_secs(10)).await;
+                Ok::<_, std::io::Error>(())
+            }
+        };
+
+        let (request, token) = pool.execute_abortable(slow);
+        let cancel = async {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            token.cancel();
+        };
+        let (result, _) = tokio::time::timeout(Duration::from_secs(5), async {
+            tokio::join!(request, cancel)
+        })
+        .await
+        .expect("cancellation should end the request");
+        assert!(matches!(result, Err(RpcPoolError::Cancelled)));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        // Cancelling one request leaves the pool usable
+        assert!(!pool.is_shutdown());
+        let (request, _token) = pool.execute_abortable(|_url| async { Ok::<_, std::io::Error>(7) });
+        assert_eq!(request.await.unwrap(), 7);
+
+        // A token cancelled up front stops the request before any endpoint is tried
+        let (request, token) = pool.execute_abortable(slow);
+        token.cancel();
+        assert!(matches!(request.await, Err(RpcPoolError::Cancelled)));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_execute_with_fallback_pool() {
+        let primary = RpcPool::new(create_test_config()).unwrap();
+        let fallback = RpcPool::new(RpcPoolConfig::new().with_endpoints(vec![
+            RpcEndpoint::new("https://fallback.example.com"),
+        ]))
+        .unwrap();
+
+        let request = |url: url::Url| async move {
+            if url.as_str().contains("fallback") {
+                Ok(url.to_string())
+            } else {
+                Err(std::io::Error::other("primary down"))
+            }
+        };
+        let result = primary.execute_with_fallback_pool(&fallback, request).await;
+        assert_eq!(result.unwrap(), "https://fallback.example.com/");
+        assert_eq!(primary.metrics().cross_pool_failovers, 1);
+        assert_eq!(fallback.metrics().total_requests, 1);
+
+        // A primary success never touches the fallback
+        let result = fallback.execute_with_fallback_pool(&primary, request).await;
+        assert!(result.is_ok());
+        assert_eq!(fallback.metrics().cross_pool_failovers, 0);
+        assert_eq!(primary.metrics().total_requests, 1);
+    }
+
+    #[tokio::test]
+    async fn test_rate_limited_when_every_endpoint_throttles() {
+        let pool = RpcPool::new(create_test_config()).unwrap();
+
+        let result = pool
+            .execute_with_url(|_url| async {
+                Err::<(), _>(std::io::Error::other("HTTP error 429 with body: Too Many Requests"))
+            })
+            .await;
+        assert!(matches!(result, Err(RpcPoolError::RateLimited(_))));
+
+        // One endpoint failing for another reason is a generic failure
+        let result = pool
+            .execute_with_url(|url| async move {
+                if url.contains("rpc1") {
+                    Err::<(), _>(std::io::Error::other("rate limit exceeded"))
+                } else {
+                    Err(std::io::Error::other("execution reverted"))
+                }
+            })
+            .await;
+        assert!(matches!(result, Err(RpcPoolError::AllEndpointsFailed(_))));
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_delays_requests() {
+        let url = "https://rpc1.example.com".to_string();
+        let pool = RpcPool::new(
+            create_test_config().with_rate_limits(HashMap::from([(url.clone(), 1)])),
+        )
+        .unwrap();
+
+        let start = Instant::now();
+        for _ in 0..2 {
+            let selected = pool
+                .execute_with_url(|url| async move { Ok::<_, std::io::Error>(url) })
+                .await
+                .unwrap();
+            assert_eq!(selected, url);
+        }
+
+        // Second request waited for a token instead of failing or failing over
+        assert!(start.elapsed() >= Duration::from_millis(900));
+        assert_eq!(pool.metrics().failovers, 0);
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_wait_counts_against_timeout() {
+        let url = "https://rpc1.example.com".to_string();
+        let pool = RpcPool::new(
+            RpcPoolConfig::new()
+                .with_endpoints(vec![RpcEndpoint::new(url.clone())])
+                .with_request_timeout(Duration::from_millis(100))
+                .with_rate_limits(HashMap::from([(url, 1)])),
+        )
+        .unwrap();
+
+        let request = |url: String| async move { Ok::<_, std::io::Error>(url) };
+        assert!(pool.execute_with_url(request).await.is_ok());
+        assert!(matches!(
+            pool.execute_with_url(request).await,
+            Err(RpcPoolError::AllEndpointsFailed(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_per_attempt_timeouts() {
+        let config = create_test_config().with_per_attempt_timeouts(vec![
+            Duration::from_millis(50),
+            Duration::from_secs(1),
+        ]);
+        let pool = RpcPool::new(config).unwrap();
+
+        let start = Instant::now();
+        let result = pool
+            .execute_with_url(|url: String| async move {
+                if url.starts_with("https://rpc1") {
+                    tokio::time::sleep(Duration::from_millis(500)).await;
+                }
+                Ok::<_, std::io::Error>(url)
+            })
+            .await;
+
+        // First attempt gave up after 50ms rather than waiting out the slow endpoint
+        assert_eq!(result.unwrap(), "https://rpc2.example.com");
+        assert!(start.elapsed() < Duration::from_millis(500));
+        assert_eq!(
+            pool.stats.read()["https://rpc1.example.com"].last_error.as_deref(),
+            Some("Request timeout after 50ms")
+        );
+    }
+
+    #[test]
+    fn test_attempt_timeout_schedule() {
+        let pool = RpcPool::new(create_test_config().with_request_timeout(Duration::from_secs(5)))
+            .unwrap();
+        assert_eq!(pool.attempt_timeout(1), Duration::from_secs(5));
+        assert_eq!(pool.attempt_timeout(3), Duration::from_secs(5));
+
+        let timeouts = vec![Duration::from_millis(500), Duration::from_secs(1), Duration::from_secs(3)];
+        let pool = RpcPool::new(create_test_config().with_per_attempt_timeouts(timeouts)).unwrap();
+        assert_eq!(pool.attempt_timeout(1), Duration::from_millis(500));
+        assert_eq!(pool.attempt_timeout(2), Duration::from_secs(1));
+        assert_eq!(pool.attempt_timeout(3), Duration::from_secs(3));
+        assert_eq!(pool.attempt_timeout(10), Duration::from_secs(3));
+    }
+
+    #[test]
+    fn test_get_endpoint_by_name_and_url() {
+        let backup_url = "https://rpc2.example.com";
+        let endpoints = vec![
+            RpcEndpoint::new("https://rpc1.example.com")
+                .with_name("Primary")
+                .with_priority(1),
+            RpcEndpoint::new(backup_url)
+                .with_name("Backup")
+                .with_priority(2)
+                .with_chain_id(137),
+        ];
+        let pool = RpcPool::new(RpcPoolConfig::new().with_endpoints(endpoints)).unwrap();
+
+        let backup = pool.get_endpoint_by_name("Backup").unwrap();
+        assert_eq!(backup.url, backup_url);
+        assert_eq!(backup.priority, 2);
+        assert_eq!(backup.chain_id, 137);
+        assert_eq!(pool.get_endpoint_by_url(backup_url), Some(backup));
+        assert!(pool.get_endpoint_by_name("Missing").is_none());
+        assert!(pool.get_endpoint_by_url("https://missing.rpc").is_none());
+
+        pool.mark_unhealthy("https://rpc1.example.com");
+        let primary = pool.get_endpoint_stats_by_name("Primary").unwrap();
+        let backup = pool.get_endpoint_stats_by_name("Backup").unwrap();
+        assert!(!primary.is_healthy);
+        assert!(backup.is_healthy);
+        assert!(pool.get_endpoint_stats_by_name("Missing").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_health_events_on_transitions() {
+        use tokio::sync::broadcast::error::TryRecvError;
+
+        let pool = RpcPool::new(create_test_config().with_max_consecutive_errors(1)).unwrap();
+        let mut events = pool.subscribe_health_events();
+        let fail_rpc1 = |url: String| async move {
+            if url.starts_with("https://rpc1") {
+                Err(std::io::Error::other("down"))
+            } else {
+                Ok(url)
+            }
+        };
+
+        pool.execute_with_url(fail_rpc1).await.unwrap();
+        assert_eq!(
+            events.try_recv().unwrap(),
+            HealthEvent::EndpointMarkedUnhealthy {
+                url: "https://rpc1.example.com".to_string(),
+                name: "https://rpc1.example.com".to_string(),
+                consecutive_errors: 1,
+            }
+        );
+
+        // Further failures of an unhealthy endpoint are not transitions
+        pool.record_attempt_failure(&pool.endpoints.read()[0].clone(), "boom".to_string());
+        assert_eq!(events.try_recv(), Err(TryRecvError::Empty));
+
+        pool.mark_unhealthy("https://rpc2.example.com");
+        assert!(matches!(
+            events.try_recv().unwrap(),
+            HealthEvent::EndpointMarkedUnhealthy { url, .. } if url == "https://rpc2.example.com"
+        ));
+        let all_unhealthy = HealthEvent::AllEndpointsUnhealthy;
+        assert_eq!(events.try_recv().unwrap(), all_unhealthy);
+
+        let rpc1 = "https://rpc1.example.com";
+        assert!(pool.reset_endpoint_stats(rpc1).is_ok());
+        assert_eq!(
+            events.try_recv().unwrap(),
+            HealthEvent::EndpointRecovered {
+                url: "https://rpc1.example.com".to_string(),
+                name: "https://rpc1.example.com".to_string(),
+            }
+        );
+
+        pool.shutdown().await;
+        assert_eq!(events.try_recv().unwrap(), HealthEvent::PoolShutdown);
+        assert_eq!(events.try_recv(), Err(TryRecvError::Empty));
+    }
+
+    #[tokio::test]
+    async fn test_health_event_capacity() {
+        use tokio::sync::broadcast::error::TryRecvError;
+
+        let pool = RpcPool::new(create_test_config().with_health_event_capacity(1)).unwrap();
+        let mut events = pool.subscribe_health_events();
+
+        pool.mark_unhealthy("https://rpc1.example.com");
+        pool.mark_unhealthy("https://rpc2.example.com");
+
+        // Only the newest event fits; the receiver is told how many it missed
+        let all_unhealthy = HealthEvent::AllEndpointsUnhealthy;
+        assert_eq!(events.try_recv(), Err(TryRecvError::Lagged(2)));
+        assert_eq!(events.try_recv().unwrap(), all_unhealthy);
+    }
+
+    #[tokio::test]
+    async fn test_confidence_threshold_keeps_recovered_endpoint_as_last_resort() {
+        let (rpc1, rpc2) = ("https://rpc1.example.com", "https://rpc2.example.com");
+        let config = create_test_config()
+            .with_max_consecutive_errors(1)
+            .with_confidence_threshold(2);
+        let pool = RpcPool::new(config).unwrap();
+        let ok = |url: String| async move { Ok::<_, std::io::Error>(url) };
+        let fail_rpc2 = |url: String| async move {
+            if url.starts_with("https://rpc2") {
+                Err(std::io::Error::other("down"))
+            } else {
+                Ok(url)
+            }
+        };
+
+        pool.mark_unhealthy(rpc1);
+        pool.update_endpoint_stats(rpc1, EndpointStats::mark_recovered);
+
+        // The recovered primary is passed over while the backup is healthy
+        assert_eq!(pool.execute_with_url(ok).await.unwrap(), rpc2);
+
+        // With the backup failing it serves as a last resort and earns confidence
+        assert_eq!(pool.execute_with_url(fail_rpc2).await.unwrap(), rpc1);
+        assert_eq!(pool.execute_with_url(fail_rpc2).await.unwrap(), rpc1);
+        assert!(!pool.stats.read()[rpc1].recovering);
+
+        assert!(pool.reset_endpoint_stats(rpc2).is_ok());
+        assert_eq!(pool.execute_with_url(ok).await.unwrap(), rpc1);
+    }
+
+    #[tokio::test]
+    async fn test_recovered_endpoint_trusted_without_threshold() {
+        let rpc1 = "https://rpc1.example.com";
+        let pool = RpcPool::new(create_test_config()).unwrap();
+        pool.mark_unhealthy(rpc1);
+        pool.update_endpoint_stats(rpc1, EndpointStats::mark_recovered);
+
+        let ok = |url: String| async move { Ok::<_, std::io::Error>(url) };
+        assert_eq!(pool.execute_with_url(ok).await.unwrap(), rpc1);
+    }
+
+    #[test]
+    fn test_retry_jitter_bounded() {
+        let pool = RpcPool::new(create_test_config()).unwrap();
+        assert_eq!(pool.retry_jitter(), Duration::ZERO);
+
+        let max_jitter = Duration::from_millis(20);
+        let pool = RpcPool::new(create_test_config().with_jitter(max_jitter)).unwrap();
+        let samples: Vec<Duration> = (0..200).map(|_| pool.retry_jitter()).collect();
+        assert!(samples.iter().all(|&jitter| jitter < max_jitter));
+        assert!(samples.iter().any(|jitter| !jitter.is_zero()));
+    }
+
+    #[tokio::test]
+    async fn test_jitter_delays_failover_attempts() {
+        const REQUESTS: u32 = 10;
+        let endpoints = (1..=3)
+            .map(|i| RpcEndpoint::new(format!("https://rpc{}.example.com", i)))
+            .collect();
+        let max_jitter = Duration::from_millis(50);
+        let pool = RpcPool::new(
+            RpcPoolConfig::new()
+                .with_endpoints(endpoints)
+                .with_max_consecutive_errors(u32::MAX)
+                .with_jitter(max_jitter),
+        )
+        .unwrap();
+
+        // Every request fails over twice before reaching rpc3
+        let start = Instant::now();
+        for _ in 0..REQUESTS {
+            let result = pool
+                .execute_with_url(|url: String| async move {
+                    if url.starts_with("https://rpc3") {
+                        Ok(url)
+                    } else {
+                        Err(std::io::Error::other("down"))
+                    }
+                })
+                .await;
+            assert_eq!(result.unwrap(), "https://rpc3.example.com");
+        }
+
+        // Two delays averaging max_jitter / 2 add about max_jitter per request
+        let average = start.elapsed() / REQUESTS;
+        assert!(average > max_jitter / 2, "average latency {:?}", average);
+        assert!(average < max_jitter * 2, "average latency {:?}", average);
+    }
+
+    #[tokio::test]
+    async fn test_first_attempt_has_no_jitter() {
+        let pool = RpcPool::new(create_test_config().with_jitter(Duration::from_secs(10))).unwrap();
+
+        let result = tokio::time::timeout(
+            Duration::from_secs(1),
+            pool.execute_with_url(|url: String| async move { Ok::<_, std::io::Error>(url) }),
+        )
+        .await;
+        assert_eq!(result.unwrap().unwrap(), "https://rpc1.example.com");
+    }
+
+    /// Pool of three endpoints, for use with [`slow_failure`].
+    fn three_endpoint_pool(config: RpcPoolConfig) -> RpcPool {
+        let endpoints = (1..=3)
+            .map(|i| RpcEndpoint::new(format!("https://rpc{}.example.com", i)))
+            .collect();
+        RpcPool::new(config.with_endpoints(endpoints)).unwrap()
+    }
+
+    /// Count the call, then fail after 100ms.
+    async fn slow_failure(calls: &std::sync::atomic::AtomicUsize) -> Result<(), std::io::Error> {
+        calls.fetch_add(1, Ordering::SeqCst);
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        Err(std::io::Error::other("down"))
+    }
+
+    #[tokio::test]
+    async fn test_retry_budget_stops_failover() {
+        use std::sync::atomic::AtomicUsize;
+
+        let config = RpcPoolConfig::new().with_retry_budget(Duration::from_millis(150));
+        let pool = three_endpoint_pool(config);
+        let calls = AtomicUsize::new(0);
+
+        let start = Instant::now();
+        let result = pool.execute(|_url: url::Url| slow_failure(&calls)).await;
+
+        assert!(matches!(result, Err(RpcPoolError::RetryBudgetExhausted(_))));
+        assert!(start.elapsed() < Duration::from_millis(250));
+        // The second attempt was cut short and rpc3 never tried
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+        let rpc2_failures = pool.stats.read()["https://rpc2.example.com"].failed_requests;
+        assert_eq!(rpc2_failures, 0);
+    }
+
+    #[tokio::test]
+    async fn test_retry_budget_distinct_from_all_endpoints_failed() {
+        use std::sync::atomic::AtomicUsize;
+
+        // Without a budget every endpoint is tried
+        let pool = three_endpoint_pool(RpcPoolConfig::new());
+        let calls = AtomicUsize::new(0);
+        let result = pool.execute(|_url: url::Url| slow_failure(&calls)).await;
+        assert!(matches!(result, Err(RpcPoolError::AllEndpointsFailed(_))));
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+
+        // A budget that outlasts every attempt still reports the endpoints failing
+        let config = RpcPoolConfig::new().with_retry_budget(Duration::from_secs(5));
+        let pool = three_endpoint_pool(config);
+        let calls = AtomicUsize::new(0);
+        let result = pool.execute(|_url: url::Url| slow_failure(&calls)).await;
+        assert!(matches!(result, Err(RpcPoolError::AllEndpointsFailed(_))));
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_min_healthy_endpoints_fails_fast() {
+        use std::sync::atomic::AtomicUsize;
+
+        let pool = three_endpoint_pool(RpcPoolConfig::new().with_min_healthy_endpoints(2));
+        let calls = AtomicUsize::new(0);
+        let call = |_url: url::Url| {
+            calls.fetch_add(1, Ordering::SeqCst);
+            async { Ok::<_, std::io::Error>(()) }
+        };
+
+        pool.mark_unhealthy("https://rpc1.example.com");
+        assert!(pool.execute(call).await.is_ok());
+
+        pool.mark_unhealthy("https://rpc2.example.com");
+        let result = pool.execute(call).await;
+        assert!(matches!(
+            result,
+            Err(RpcPoolError::InsufficientHealthyEndpoints {
+                available: 1,
+                required: 2
+            })
+        ));
+        // Rejected before any attempt
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        let item = |_item: u32, _url: url::Url| async { Ok::<_, std::io::Error>(()) };
+        let results = pool.execute_batch(vec![1, 2], 2, item).await;
+        assert!(results.iter().all(|r| r.is_err()));
+
+        pool.mark_healthy("https://rpc2.example.com").unwrap();
+        assert!(pool.execute(call).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_execute_validated_fails_over_on_rejected_response() {
+        let pool = RpcPool::new(create_test_config()).unwrap();
+        let block_number = |url: url::Url| async move {
+            // rpc1 is still syncing
+            let syncing = url.as_str().starts_with("https://rpc1");
+            Ok::<u64, std::io::Error>(if syncing { 0 } else { 100 })
+        };
+
+        let block = pool
+            .execute_validated(block_number, |&block| block > 0)
+            .await
+            .unwrap();
+        assert_eq!(block, 100);
+        let rpc1_failures = pool.stats.read()["https://rpc1.example.com"].failed_requests;
+        assert_eq!(rpc1_failures, 1);
+    }
+
+    #[tokio::test]
+    async fn test_execute_validated_all_rejected() {
+        let pool = RpcPool::new(create_test_config()).unwrap();
+
+        let result = pool
+            .execute_validated(
+                |_url: url::Url| async { Ok::<u64, std::io::Error>(0) },
+                |&block| block > 0,
+            )
+            .await;
+        assert!(matches!(result, Err(RpcPoolError::ValidationFailed(_))));
+
+        // An endpoint that errors out rather than failing validation
+        let result = pool
+            .execute_validated(
+                |url: url::Url| async move {
+                    if url.as_str().starts_with("https://rpc1") {
+                        return Err(std::io::Error::other("down"));
+                    }
+                    Ok(0u64)
+                },
+                |&block| block > 0,
+            )
+            .await;
+        assert!(matches!(result, Err(RpcPoolError::AllEndpointsFailed(_))));
+    }
+
+    #[tokio::test]
+    async fn test_execute_with_metadata_first_try() {
+        let pool = RpcPool::new(create_test_config().with_endpoints(vec![
+            RpcEndpoint::new("https://rpc1.example.com").with_name("Primary").with_priority(1),
+            RpcEndpoint::new("https://rpc2.example.com").with_name("Backup").with_priority(2),
+        ]))
+        .unwrap();
+
+        let (value, metadata) = pool
+            .execute_with_metadata(|_url: url::Url| async move {
+                tokio::time::sleep(Duration::from_millis(5)).await;
+                Ok::<_, std::io::Error>(42)
+            })
+            .await
+            .unwrap();
+        assert_eq!(value, 42);
+        assert_eq!(metadata.endpoint_name, "Primary");
+        assert_eq!(metadata.endpoint_url, "https://rpc1.example.com");
+        assert!(metadata.latency_ms > 0);
+        assert_eq!(metadata.attempt_count, 1);
+    }
+
+    #[tokio::test]
+    async fn test_execute_with_metadata_after_failover() {
+        let pool = RpcPool::new(create_test_config().with_endpoints(vec![
+            RpcEndpoint::new("https://rpc1.example.com").with_name("Primary").with_priority(1),
+            RpcEndpoint::new("https://rpc2.example.com").with_name("Backup").with_priority(2),
+        ]))
+        .unwrap();
+
+        let (_, metadata) = pool
+            .execute_with_metadata(|url: url::Url| async move {
+                if url.as_str().starts_with("https://rpc1") {
+                    return Err(std::io::Error::other("down"));
+                }
+                Ok(())
+            })
+            .await
+            .unwrap();
+        assert_eq!(metadata.endpoint_name, "Backup");
+        assert_eq!(metadata.endpoint_url, "https://rpc2.example.com");
+        assert_eq!(metadata.attempt_count, 2);
+    }
+
+    #[tokio::test]
+    async fn test_retry_after_throttles_endpoint_in_throttle_aware_strategy() {
+        use crate::strategies::ThrottleAwareStrategy;
+        use std::sync::atomic::AtomicU32;
+
+        let pool = RpcPool::new(
+            create_test_config()
+                .with_endpoints(vec![
+                    RpcEndpoint::new("https://rpc1.example.com").with_priority(1),
+                    RpcEndpoint::new("https://rpc2.example.com").with_priority(2),
+                ])
+                .with_strategy(Box::new(ThrottleAwareStrategy::new())),
+        )
+        .unwrap();
+        let primary_calls = AtomicU32::new(0);
+        let request = |url: url::Url| {
+            let primary_calls = &primary_calls;
+            async move {
+                if url.as_str().starts_with("https://rpc1") {
+                    primary_calls.fetch_add(1, Ordering::Relaxed);
+                    return Err(std::io::Error::other("HTTP 429 Too Many Requests, Retry-After: 60"));
+                }
+                Ok(url.to_string())
+            }
+        };
+
+        assert_eq!(pool.execute(request).await.unwrap(), "https://rpc2.example.com/");
+        assert_eq!(pool.execute(request).await.unwrap(), "https://rpc2.example.com/");
+        assert_eq!(primary_calls.load(Ordering::Relaxed), 1);
+    }
+
+    #[tokio::test]
+    async fn test_execute_prioritising_tries_preferred_first() {
+        let mev = "https://mev.example.com";
+        let pool = RpcPool::new(create_test_config().with_endpoints(vec![
+            RpcEndpoint::new("https://rpc1.example.com").with_priority(1),
+            RpcEndpoint::new(mev).with_priority(500),
+        ]))
+        .unwrap();
+        let ok = |url: url::Url| async move { Ok::<_, std::io::Error>(url.to_string()) };
+
+        let used = pool.execute_prioritising(mev, ok).await.unwrap();
+        assert_eq!(used, "https://mev.example.com/");
+
+        // Only that request was redirected
+        let used = pool.execute(ok).await.unwrap();
+        assert_eq!(used, "https://rpc1.example.com/");
+        assert_eq!(pool.get_all_urls()[1], mev);
+    }
+
+    #[tokio::test]
+    async fn test_execute_prioritising_falls_back() {
+        let mev = "https://mev.example.com";
+        let pool = RpcPool::new(create_test_config().with_endpoints(vec![
+            RpcEndpoint::new("https://rpc1.example.com").with_priority(1),
+            RpcEndpoint::new(mev).with_priority(500),
+        ]))
+        .unwrap();
+
+        let used = pool
+            .execute_prioritising(mev, |url: url::Url| async move {
+                if url.as_str().starts_with("https://mev") {
+                    return Err(std::io::Error::other("down"));
+                }
+                Ok(url.to_string())
+            })
+            .await
+            .unwrap();
+        assert_eq!(used, "https://rpc1.example.com/");
+
+        let result = pool
+            .execute_prioritising("https://unknown.example.com", |url: url::Url| async move {
+                Ok::<_, std::io::Error>(url.to_string())
+            })
+            .await;
+        assert!(matches!(result, Err(RpcPoolError::EndpointNotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_execute_sticky_pins_key_to_endpoint() {
+        let endpoints: Vec<RpcEndpoint> = (1..=4)
+            .map(|i| RpcEndpoint::new(format!("https://rpc{}.example.com", i)))
+            .collect();
+        let pool = RpcPool::new(
+            RpcPoolConfig::new()
+                .with_endpoints(endpoints)
+                .with_strategy(Box::new(crate::strategies::RoundRobinStrategy::new())),
+        )
+        .unwrap();
+
+        let echo = |url: String| async move { Ok::<_, std::io::Error>(url) };
+        for key in ["0xaaa", "0xbbb", "0xccc"] {
+            let first = pool.execute_sticky(key, echo).await.unwrap();
+            for _ in 0..5 {
+                // Unrelated traffic advances the round-robin cursor
+                pool.execute_with_url(echo).await.unwrap();
+                assert_eq!(pool.execute_sticky(key, echo).await.unwrap(), first);
+            }
+        }
+        assert_eq!(pool.sticky_session_count(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_execute_sticky_falls_back_and_repins() {
+        let pool = RpcPool::new(create_test_config()).unwrap();
+        let pinned = pool
+            .execute_sticky("tx", |url: String| async move { Ok::<_, std::io::Error>(url) })
+            .await
+            .unwrap();
+
+        // Pinned endpoint starts failing; the request falls back to the other one
+        let failing = pinned.clone();
+        let fallback = pool
+            .execute_sticky("tx", move |url: String| {
+                let failing = failing.clone();
+                async move {
+                    if url == failing {
+                        Err(std::io::Error::other("node unavailable"))
+                    } else {
+                        Ok(url)
+                    }
+                }
+            })
+            .await
+            .unwrap();
+        assert_ne!(fallback, pinned);
+
+        // The key stays with the endpoint that served it
+        let next = pool
+            .execute_sticky("tx", |url: String| async move { Ok::<_, std::io::Error>(url) })
+            .await
+            .unwrap();
+        assert_eq!(next, fallback);
+    }
+
+    #[tokio::test]
+    async fn test_sticky_binding_expires() {
+        let pool = RpcPool::new(
+            create_test_config().with_sticky_session_ttl(Duration::from_millis(20)),
+        )
+        .unwrap();
+
+        pool.execute_sticky(42u64, |url: String| async move { Ok::<_, std::io::Error>(url) })
+            .await
+            .unwrap();
+        assert_eq!(pool.sticky_session_count(), 1);
+
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        assert_eq!(pool.sticky_session_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_execute_batch_preserves_order_and_spreads_load() {
+        let pool = RpcPool::new(create_test_config()).unwrap();
+
+        let items: Vec<u64> = (0..10).collect();
+        let results = pool
+            .execute_batch(items, 4, |n, url: url::Url| async move {
+                // Finish out of order
+                tokio::time::sleep(Duration::from_millis(10 * (10 - n))).await;
+                Ok::<_, std::io::Error>((n * 2, url.to_string()))
+            })
+            .await;
+
+        assert_eq!(results.len(), 10);
+        let mut per_endpoint: HashMap<String, usize> = HashMap::new();
+        for (n, result) in results.into_iter().enumerate() {
+            let (doubled, url) = result.unwrap();
+            assert_eq!(doubled, n as u64 * 2);
+            *per_endpoint.entry(url).or_default() += 1;
+        }
+        assert_eq!(per_endpoint["https://rpc1.example.com/"], 5);
+        assert_eq!(per_endpoint["https://rpc2.example.com/"], 5);
+        assert_eq!(pool.metrics().total_requests, 10);
+    }
+
+    #[tokio::test]
+    async fn test_execute_batch_isolates_failures() {
+        let pool = RpcPool::new(create_test_config()).unwrap();
+        let attempts = Arc::new(AtomicU64::new(0));
+
+        let counter = attempts.clone();
+        let results = pool
+            .execute_batch(vec![1u64, 2, 3], 2, move |n, url: url::Url| {
+                let counter = counter.clone();
+                async move {
+                    if n == 2 {
+                        counter.fetch_add(1, Ordering::Relaxed);
+                        return Err(std::io::Error::other("bad block range"));
+                    }
+                    Ok(url.to_string())
+                }
+            })
+            .await;
+
+        assert!(results[0].is_ok());
+        assert!(matches!(results[1], Err(RpcPoolError::AllEndpointsFailed(_))));
+        assert!(results[2].is_ok());
+        // Retried up to max_consecutive_errors (3) times
+        assert_eq!(attempts.load(Ordering::Relaxed), 3);
+    }
+
+    #[tokio::test]
+    async fn test_drain_and_replace_endpoints() {
+        let pool = RpcPool::new(create_test_config()).unwrap();
+        let echo = |url: String| async move { Ok::<_, std::io::Error>(url) };
+
+        pool.execute_with_url(echo).await.unwrap();
+        pool.mark_unhealthy("https://rpc2.example.com");
+
+        pool.drain_and_replace_endpoints(vec![
+            RpcEndpoint::new("https://new2.example.com").with_priority(20),
+            RpcEndpoint::new("https://new1.example.com").with_priority(10),
+        ])
+        .unwrap();
+
+        let metrics = pool.metrics();
+        assert_eq!(metrics.endpoints.len(), 2);
+        for endpoint in &metrics.endpoints {
+            assert!(endpoint.url.starts_with("https://new"));
+            assert_eq!(endpoint.total_requests, 0);
+            assert!(endpoint.is_healthy);
+        }
+
+        assert_eq!(pool.execute_with_url(echo).await.unwrap(), "https://new1.example.com");
+        assert!(matches!(
+            pool.drain_and_replace_endpoints(vec![]),
+            Err(RpcPoolError::NoEndpointsConfigured)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_replace_endpoints_with_request_in_flight() {
+        let pool = Arc::new(RpcPool::new(create_test_config()).unwrap());
+        let (started_tx, started_rx) = tokio::sync::oneshot::channel();
+
+        let in_flight = tokio::spawn({
+            let pool = pool.clone();
+            async move {
+                let started_tx = parking_lot::Mutex::new(Some(started_tx));
+                pool.execute_with_url(|url: String| {
+                    let started = started_tx.lock().take();
+                    async move {
+                        if let Some(tx) = started {
+                            let _ = tx.send(());
+                        }
+                        tokio::time::sleep(Duration::from_millis(50)).await;
+                        Ok::<_, std::io::Error>(url)
+                    }
+                })
+                .await
+            }
+        });
+
+        started_rx.await.unwrap();
+        pool.drain_and_replace_endpoints(vec![RpcEndpoint::new("https://new.example.com")])
+            .unwrap();
+
+        // The old request completes against the endpoint it already selected
+        assert_eq!(in_flight.await.unwrap().unwrap(), "https://rpc1.example.com");
+        assert_eq!(pool.metrics().endpoints[0].total_requests, 0);
+    }
+
+    #[test]
+    fn test_reset_endpoint_stats() {
+        let pool = RpcPool::new(create_test_config()).unwrap();
+        for url in ["https://rpc1.example.com", "https://rpc2.example.com"] {
+            let mut stats = pool.stats.write();
+            let s = stats.get_mut(url).unwrap();
+            s.record_success(120);
+            s.record_failure("boom".to_string(), 1);
+            s.increment_recovery_attempts();
+        }
+
+        pool.reset_endpoint_stats("https://rpc1.example.com").unwrap();
+        {
+            let stats = pool.stats.read();
+            let reset = &stats["https://rpc1.example.com"];
+            assert!(reset.is_healthy);
+            assert_eq!(reset.total_requests, 0);
+            assert_eq!(reset.failed_requests, 0);
+            assert_eq!(reset.avg_latency_ms, 0.0);
+            assert_eq!(reset.recovery_attempts, 0);
+            assert!(reset.last_error.is_none());
+            assert!(!stats["https://rpc2.example.com"].is_healthy);
+        }
+
+        assert!(matches!(
+            pool.reset_endpoint_stats("https://unknown.example.com"),
+            Err(RpcPoolError::EndpointNotFound(_))
+        ));
+
+        pool.reset_all_endpoint_stats();
+        assert_eq!(pool.health_summary().healthy, 2);
+        assert_eq!(pool.stats.read()["https://rpc2.example.com"].total_requests, 0);
+    }
+
+    #[test]
+    fn test_mark_healthy() {
+        let rpc1 = "https://rpc1.example.com";
+        let pool = RpcPool::new(create_test_config()).unwrap();
+        {
+            let mut stats = pool.stats.write();
+            let s = stats.get_mut(rpc1).unwrap();
+            s.record_failure("boom".to_string(), 1);
+            s.increment_recovery_attempts();
+            s.increment_recovery_attempts();
+        }
+        assert!(!pool.stats.read()[rpc1].is_healthy);
+
+        pool.mark_healthy(rpc1).unwrap();
+        {
+            let stats = pool.stats.read();
+            assert!(stats[rpc1].is_healthy);
+            assert_eq!(stats[rpc1].consecutive_errors, 0);
+            assert_eq!(stats[rpc1].recovery_attempts, 0);
+            // History is kept, unlike reset_endpoint_stats
+            assert_eq!(stats[rpc1].failed_requests, 1);
+        }
+
+        assert!(matches!(
+            pool.mark_healthy("https://unknown.example.com"),
+            Err(RpcPoolError::EndpointNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_healthy_and_unhealthy_endpoints() {
+        let pool = RpcPool::new(create_test_config()).unwrap();
+        assert_eq!(pool.healthy_endpoints().len(), 2);
+        assert!(pool.unhealthy_endpoints().is_empty());
+
+        pool.mark_unhealthy("https://rpc2.example.com");
+
+        let healthy = pool.healthy_endpoints();
+        let unhealthy = pool.unhealthy_endpoints();
+        let summary = pool.health_summary();
+        assert_eq!(healthy.len(), summary.healthy);
+        assert_eq!(unhealthy.len(), summary.unhealthy);
+        assert_eq!(healthy[0].url, "https://rpc1.example.com");
+        assert_eq!(unhealthy[0].url, "https://rpc2.example.com");
+    }
+
+    #[test]
+    fn test_pool_drop_sets_shutdown() {
+        let config = create_test_config();
+        let pool = RpcPool::new(config).unwrap();
+
+        assert!(!pool.is_shutdown());
+    }
+
+    #[tokio::test]
+    async fn test_shutdown() {
+        let config = create_test_config();
+        let pool = Arc::new(RpcPool::new(config).unwrap());
+
+        // Start health check
+        let _handle = pool.start_health_check();
+
+        assert!(!pool.is_shutdown());
+
+        // Shutdown
+        pool.shutdown().await;
+
+        assert!(pool.is_shutdown());
+    }
+
+    #[tokio::test]
+    async fn test_pause_rejects_new_requests_until_resume() {
+        let pool = RpcPool::new(create_test_config()).unwrap();
+        let ok = |url: String| async move { Ok::<_, std::io::Error>(url) };
+
+        pool.pause();
+        assert!(pool.is_paused());
+        let result = pool.execute_with_url(ok).await;
+        assert!(matches!(result, Err(RpcPoolError::PoolPaused)));
+
+        pool.resume();
+        assert!(!pool.is_paused());
+        let url = pool.execute_with_url(ok).await.unwrap();
+        assert_eq!(url, "https://rpc1.example.com");
+    }
+
+    #[tokio::test]
+    async fn test_pause_lets_in_flight_request_complete() {
+        let pool = Arc::new(RpcPool::new(create_test_config()).unwrap());
+        let (started_tx, started_rx) = tokio::sync::oneshot::channel();
+
+        // rpc1 fails once the pool is paused, so the request must still fail over
+        let in_flight = tokio::spawn({
+            let pool = pool.clone();
+            async move {
+                let started_tx = parking_lot::Mutex::new(Some(started_tx));
+                pool.execute_with_url(|url: String| {
+                    let started = started_tx.lock().take();
+                    async move {
+                        if let Some(tx) = started {
+                            let _ = tx.send(());
+                            tokio::time::sleep(Duration::from_millis(50)).await;
+                            return Err(std::io::Error::other("down"));
+                        }
+                        Ok(url)
+                    }
+                })
+                .await
+            }
+        });
+
+        started_rx.await.unwrap();
+        pool.pause();
+        let result = in_flight.await.unwrap();
+        assert_eq!(result.unwrap(), "https://rpc2.example.com");
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_while_paused() {
+        let pool = RpcPool::new(create_test_config()).unwrap();
+
+        pool.pause();
+        pool.shutdown().await;
+        assert!(pool.is_shutdown());
+
+        // Shutdown takes precedence, and resuming does not undo it
+        pool.resume();
+        let result = pool
+            .execute_with_url(|url: String| async move { Ok::<_, std::io::Error>(url) })
+            .await;
+        assert!(matches!(result, Err(RpcPoolError::PoolShutdown)));
+    }
+
+    #[test]
+    fn test_add_endpoint() {
+        let config = create_test_config();
+        let pool = RpcPool::new(config).unwrap();
+
+        pool.add_endpoint(RpcEndpoint::new("https://rpc0.example.com").with_priority(1))
+            .unwrap();
+
+        assert_eq!(pool.get_all_urls().len(), 3);
+        assert_eq!(pool.health_summary().total, 3);
+        // Highest priority endpoint is now selected first
+        assert_eq!(
+            pool.get_current_url(),
+            Some("https://rpc0.example.com".to_string())
+        );
+
+        let result = pool.add_endpoint(RpcEndpoint::new("https://rpc1.example.com"));
+        assert!(matches!(result, Err(RpcPoolError::DuplicateEndpoint(_))));
+    }
+
+    #[test]
+    fn test_remove_endpoint() {
+        let config = create_test_config();
+        let pool = RpcPool::new(config).unwrap();
+
+        pool.remove_endpoint("https://rpc1.example.com").unwrap();
+
+        assert_eq!(pool.get_all_urls(), vec!["https://rpc2.example.com".to_string()]);
+        assert_eq!(pool.health_summary().total, 1);
+        assert_eq!(pool.metrics().endpoints.len(), 1);
+        assert_eq!(
+            pool.get_current_url(),
+            Some("https://rpc2.example.com".to_string())
+        );
+
+        let result = pool.remove_endpoint("https://rpc1.example.com");
+        assert!(matches!(result, Err(RpcPoolError::EndpointNotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_execute_with_fallback_value() {
+        let config = create_test_config();
+        let pool = RpcPool::new(config).unwrap();
+
+        let value = pool
+            .execute_with_fallback_value(7u64, |_url: url::Url| async move {
+                Ok::<_, std::io::Error>(42u64)
+            })
+            .await;
+        assert_eq!(value.unwrap(), 42);
+
+        let value = pool
+            .execute_with_fallback_value(7u64, |_url: url::Url| async move {
+                Err::<u64, _>(std::io::Error::other("boom"))
+            })
+            .await;
+        assert_eq!(value.unwrap(), 7);
+    }
+
+    #[tokio::test]
+    async fn test_execute_with_fallback_fn_receives_error() {
+        let config = create_test_config();
+        let pool = RpcPool::new(config).unwrap();
+
+        let value = pool
+            .execute_with_fallback_fn(
+                |e| match e {
+                    RpcPoolError::AllEndpointsFailed(msg) => msg,
+                    other => panic!("unexpected error: {}", other),
+                },
+                |_url: url::Url| async move { Err::<String, _>(std::io::Error::other("boom")) },
+            )
+            .await;
+        assert_eq!(value.unwrap(), "boom");
+    }
+
+    #[tokio::test]
+    async fn test_execute_with_fallback_value_surfaces_shutdown() {
+        let pool = RpcPool::new(create_test_config()).unwrap();
+        pool.shutdown().await;
+
+        let result = pool
+            .execute_with_fallback_value(7u64, |_url: url::Url| async move {
+                Ok::<u64, std::io::Error>(1)
+            })
+            .await;
+        assert!(matches!(result, Err(RpcPoolError::PoolShutdown)));
+    }
+
+    #[tokio::test]
+    async fn test_probe_all_capabilities() {
+        let server = mock_endpoint(20_000_000).await;
+        let pool = RpcPool::new(
+            RpcPoolConfig::new()
+                .with_endpoints(vec![
+                    RpcEndpoint::new(server.uri())
+                        .with_priority(10)
+                        .with_capabilities(EndpointCapabilities {
+                            rate_limit_rps: Some(25),
+                            ..Default::default()
+                        }),
+                    RpcEndpoint::new("http://127.0.0.1:1").with_priority(20),
+                ])
+                .with_health_check_timeout(Duration::from_secs(2)),
+        )
+        .unwrap();
+
+        assert_eq!(pool.probe_all_capabilities().await, 1);
+
+        let endpoints = pool.endpoints.read();
+        // The mock answers single calls only, so batching tops out at 1
+        assert_eq!(endpoints[0].capabilities.supports_eth_get_logs, Some(true));
+        assert_eq!(endpoints[0].capabilities.max_batch_size, Some(1));
+        assert_eq!(endpoints[0].capabilities.max_block_range, Some(0));
+        assert_eq!(
+            endpoints[0].capabilities.supports_eth_call_state_override,
+            Some(true)
+        );
+        // Configured values the probe does not measure are kept
+        assert_eq!(endpoints[0].capabilities.rate_limit_rps, Some(25));
+        assert_eq!(endpoints[1].capabilities, EndpointCapabilities::default());
+    }
+
+    /// Failover strategy that logs the outcome callbacks it receives.
+    struct OutcomeLog(Arc<Mutex<Vec<String>>>);
+
+    impl SelectionStrategy for OutcomeLog {
+        fn select<'a>(
+            &mut self,
+            endpoints: &'a [RpcEndpoint],
+            stats: &HashMap<String, EndpointStats>,
+            exclude: &HashSet<String>,
+            ctx: &SelectionContext,
+        ) -> Option<&'a RpcEndpoint> {
+            FailoverStrategy.select(endpoints, stats, exclude, ctx)
+        }
+
+        fn name(&self) -> &'static str {
+            "outcome-log"
+        }
+
+        fn on_success(&mut self, url: &str, _latency_ms: u64) {
+            self.0.lock().push(format!("ok {}", url));
+        }
+
+        fn on_failure(&mut self, url: &str, error: &str) {
+            self.0.lock().push(format!("err {} {}", url, error));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_strategy_outcome_callbacks() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let config = create_test_config().with_strategy(Box::new(OutcomeLog(log.clone())));
+        let pool = RpcPool::new(config).unwrap();
+
+        let result = pool
+            .execute_with_url(|url: String| async move {
+                if url.starts_with("https://rpc1") {
+                    return Err(std::io::Error::other("boom"));
+                }
+                Ok(url)
+            })
+            .await;
+
+        assert_eq!(result.unwrap(), "https://rpc2.example.com");
+        assert_eq!(
+            *log.lock(),
+            vec![
+                "err https://rpc1.example.com boom".to_string(),
+                "ok https://rpc2.example.com".to_string(),
+            ]
+        );
+    }
+
+    /// Request-level hint understood by [`ArchiveRouting`].
+    struct RequiresArchive(bool);
+
+    /// Failover strategy that routes archive requests to the last endpoint.
+    struct ArchiveRouting;
+
+    impl SelectionStrategy for ArchiveRouting {
+        fn select<'a>(
+            &mut self,
+            endpoints: &'a [RpcEndpoint],
+            stats: &HashMap<String, EndpointStats>,
+            exclude: &HashSet<String>,
+            ctx: &SelectionContext,
+        ) -> Option<&'a RpcEndpoint> {
+            match ctx.extension::<RequiresArchive>() {
+                Some(RequiresArchive(true)) => endpoints
+                    .iter()
+                    .rev()
+                    .find(|e| !exclude.contains(&e.url)),
+                _ => FailoverStrategy.select(endpoints, stats, exclude, ctx),
+            }
+        }
+
+        fn name(&self) -> &'static str {
+            "archive-routing"
+        }
+    }
+
+    #[tokio::test]
+    async fn test_execute_with_context() {
+        let config = create_test_config().with_strategy(Box::new(ArchiveRouting));
+        let pool = RpcPool::new(config).unwrap();
+        let call = |ctx: Arc<RequiresArchive>, url: url::Url| async move {
+            Ok::<_, std::io::Error>((ctx.0, url.to_string()))
+        };
+
+        let archive = pool
+            .execute_with_context(Arc::new(RequiresArchive(true)), call)
+            .await
+            .unwrap();
+        assert_eq!(archive, (true, "https://rpc2.example.com/".to_string()));
+
+        let regular = pool
+            .execute_with_context(Arc::new(RequiresArchive(false)), call)
+            .await
+            .unwrap();
+        assert_eq!(regular, (false, "https://rpc1.example.com/".to_string()));
+
+        // Strategies that ignore context fall back to `select`
+        let pool = RpcPool::new(create_test_config()).unwrap();
+        let result = pool
+            .execute_with_context(Arc::new(RequiresArchive(true)), call)
+            .await
+            .unwrap();
+        assert_eq!(result.1, "https://rpc1.example.com/");
+    }
+
+    #[tokio::test]
+    async fn test_execute_with_context_through_wrappers() {
+        let wrapped: Vec<Box<dyn SelectionStrategy>> = vec![
+            Box::new(CircuitBreakerStrategy::new(
+                Box::new(ArchiveRouting),
+                50,
+                Duration::from_secs(30),
+            )),
+            Box::new(ThrottleAwareStrategy::new().with_inner(Box::new(ArchiveRouting))),
+            Box::new(AdaptiveStrategy::new(50.0).with_latency_strategy(Box::new(ArchiveRouting))),
+        ];
+
+        for strategy in wrapped {
+            let name = strategy.name();
+            let pool = RpcPool::new(create_test_config().with_strategy(strategy)).unwrap();
+            let url = pool
+                .execute_with_context(Arc::new(RequiresArchive(true)), |_, url: url::Url| async move {
+                    Ok::<_, std::io::Error>(url.to_string())
+                })
+                .await
+                .unwrap();
+            assert_eq!(url, "https://rpc2.example.com/", "{name} dropped the context");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_execute_with_context_fails_over() {
+        let pool = RpcPool::new(create_test_config()).unwrap();
+        let attempts = Arc::new(Mutex::new(Vec::new()));
+
+        let result = pool
+            .execute_with_context(attempts.clone(), |ctx, url: url::Url| async move {
+                ctx.lock().push(url.to_string());
+                if url.as_str().starts_with("https://rpc1") {
+                    return Err(std::io::Error::other("boom"));
+                }
+                Ok(())
+            })
+            .await;
+
+        assert!(result.is_ok());
+        assert_eq!(
+            *attempts.lock(),
+            vec!["https://rpc1.example.com/", "https://rpc2.example.com/"]
+        );
+    }
+    /// Failover strategy that sends writes to the last endpoint.
+    struct WriteRouting(Arc<Mutex<Vec<SelectionContext>>>);
+
+    impl SelectionStrategy for WriteRouting {
+        fn select<'a>(
+            &mut self,
+            endpoints: &'a [RpcEndpoint],
+            stats: &HashMap<String, EndpointStats>,
+            exclude: &HashSet<String>,
+            ctx: &SelectionContext,
+        ) -> Option<&'a RpcEndpoint> {
+            self.0.lock().push(ctx.clone());
+            if ctx.is_write {
+                return endpoints.iter().rev().find(|e| !exclude.contains(&e.url));
+            }
+            FailoverStrategy.select(endpoints, stats, exclude, ctx)
+        }
+
+        fn name(&self) -> &'static str {
+            "write-routing"
+        }
+    }
+
+    #[tokio::test]
+    async fn test_execute_with_context_hint() {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let config = create_test_config().with_strategy(Box::new(WriteRouting(seen.clone())));
+        let pool = RpcPool::new(config).unwrap();
+        let call = |url: String| async move { Ok::<_, std::io::Error>(url) };
+
+        let ctx = SelectionContext {
+            is_write: true,
+            custom: HashMap::from([("method".to_string(), "eth_sendRawTransaction".to_string())]),
+            ..Default::default()
+        };
+        let used = pool.execute_with_context_hint(ctx.clone(), call).await.unwrap();
+        assert_eq!(used, "https://rpc2.example.com");
+
+        // Plain requests see the all-false context
+        let used = pool.execute_with_url(call).await.unwrap();
+        assert_eq!(used, "https://rpc1.example.com");
+        assert_eq!(*seen.lock(), vec![ctx, SelectionContext::default()]);
+    }
+
+    #[tokio::test]
+    async fn test_execute_until_deadline_retries_cycles() {
+        let config = create_test_config().with_retry_delay(Duration::from_millis(10));
+        let pool = RpcPool::new(config).unwrap();
+        let attempts = Arc::new(AtomicU64::new(0));
+
+        let result = pool
+            .execute_until_deadline(Instant::now() + Duration::from_secs(5), |url: url::Url| {
+                let attempts = attempts.clone();
+                async move {
+                    // Both endpoints fail for the first two cycles
+                    if attempts.fetch_add(1, Ordering::Relaxed) < 4 {
+                        return Err(std::io::Error::other("boom"));
+                    }
+                    Ok(url.to_string())
+                }
+            })
+            .await;
+
+        assert_eq!(result.unwrap(), "https://rpc1.example.com/");
+        assert_eq!(attempts.load(Ordering::Relaxed), 5);
+    }
+
+    #[tokio::test]
+    async fn test_execute_until_deadline_exceeded() {
+        let config = create_test_config().with_retry_delay(Duration::from_millis(20));
+        let pool = RpcPool::new(config).unwrap();
+
+        let start = Instant::now();
+        let result = pool
+            .execute_until_deadline(start + Duration::from_millis(100), |_url: url::Url| async {
+                Err::<(), _>(std::io::Error::other("boom"))
+            })
+            .await;
+
+        assert!(matches!(result, Err(RpcPoolError::DeadlineExceeded)));
+        assert!(start.elapsed() >= Duration::from_millis(100));
+        assert!(start.elapsed() < Duration::from_secs(1));
+
+        // An attempt still in flight at the deadline is abandoned
+        let start = Instant::now();
+        let result = pool
+            .execute_until_deadline(start + Duration::from_millis(50), |_url: url::Url| async {
+                tokio::time::sleep(Duration::from_secs(5)).await;
+                Ok::<_, std::io::Error>(())
+            })
+            .await;
+        assert!(matches!(result, Err(RpcPoolError::DeadlineExceeded)));
+        assert!(start.elapsed() < Duration::from_secs(1));
+
+        // A deadline in the past makes no attempt
+        let result = pool
+            .execute_until_deadline(Instant::now(), |_url: url::Url| async {
+                Ok::<_, std::io::Error>(())
+            })
+            .await;
+        assert!(matches!(result, Err(RpcPoolError::DeadlineExceeded)));
+    }
+
+    #[tokio::test]
+    async fn test_execute_until_block_polls_until_value() {
+        let pool = RpcPool::new(create_test_config()).unwrap();
+        let head = Arc::new(AtomicU64::new(100));
+
+        let start = Instant::now();
+        let deadline = start + Duration::from_secs(1);
+        let mined = pool
+            .execute_until_block(103, Duration::from_millis(10), deadline, |_url: url::Url| {
+                let head = head.clone();
+                async move {
+                    // Each poll sees the chain one block further
+                    let block = head.fetch_add(1, Ordering::Relaxed);
+                    Ok::<_, std::io::Error>((block >= 103).then_some(block))
+                }
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(mined, 103);
+        assert!(start.elapsed() >= Duration::from_millis(30));
+        assert_eq!(pool.metrics().total_requests, 4);
+    }
+
+    #[tokio::test]
+    async fn test_execute_until_block_deadline_exceeded() {
+        let pool = RpcPool::new(create_test_config()).unwrap();
+        let pending = |_url: url::Url| async { Ok::<Option<u64>, std::io::Error>(None) };
+
+        let start = Instant::now();
+        let result = pool
+            .execute_until_block(
+                1,
+                Duration::from_millis(20),
+                start + Duration::from_millis(100),
+                pending,
+            )
+            .await;
+        assert!(matches!(result, Err(RpcPoolError::DeadlineExceeded)));
+        assert!(start.elapsed() >= Duration::from_millis(100));
+        assert!(start.elapsed() < Duration::from_millis(500));
+
+        // A long poll interval is cut short by the deadline
+        let start = Instant::now();
+        let result = pool
+            .execute_until_block(
+                1,
+                Duration::from_secs(10),
+                start + Duration::from_millis(50),
+                pending,
+            )
+            .await;
+        assert!(matches!(result, Err(RpcPoolError::DeadlineExceeded)));
+        assert!(start.elapsed() < Duration::from_millis(500));
+
+        // Request errors end the wait instead of polling on
+        let result = pool
+            .execute_until_block(
+                1,
+                Duration::from_millis(10),
+                Instant::now() + Duration::from_secs(5),
+                |_url: url::Url| async { Err::<Option<u64>, _>(std::io::Error::other("boom")) },
+            )
+            .await;
+        assert!(matches!(result, Err(RpcPoolError::AllEndpointsFailed(_))));
     }
 
-    /// Get a summary of endpoint health status.
-    ///
-    /// Returns counts of healthy, unhealthy, and total endpoints.
-    pub fn health_summary(&self) -> HealthSummary {
-        let mut healthy = 0;
-        let mut unhealthy = 0;
+    #[test]
+    fn test_execute_dry_run_has_no_side_effects() {
+        let pool = RpcPool::new(create_test_config()).unwrap();
+        let rpc1 = "https://rpc1.example.com";
 
-        for stats in self.stats.read().values() {
-            if stats.is_healthy {
-                healthy += 1;
-            } else {
-                unhealthy += 1;
-            }
-        }
+        assert_eq!(pool.execute_dry_run(None).as_deref(), Some(rpc1));
+        assert_eq!(pool.execute_dry_run(None).as_deref(), Some(rpc1));
+        assert_eq!(pool.metrics().total_requests, 0);
+        assert!(pool.stats.read().values().all(|s| s.total_requests == 0));
 
-        HealthSummary {
-            healthy,
-            unhealthy,
-            total: self.endpoints.len(),
-        }
-    }
+        pool.mark_unhealthy(rpc1);
+        let selected = pool.execute_dry_run(Some(RequestPriority::Critical));
+        assert_eq!(selected.as_deref(), Some("https://rpc2.example.com"));
 
-    /// Manually mark an endpoint as unhealthy.
-    pub fn mark_unhealthy(&self, url: &str) {
-        if let Some(stats) = self.stats.write().get_mut(url) {
-            stats.is_healthy = false;
-            stats.last_error_time = Some(Instant::now());
-            debug!(
-                endpoint_name = %stats.name,
-                endpoint_url = %url,
-                "Endpoint manually marked unhealthy"
-            );
-        } else {
-            warn!(endpoint_url = %url, "Attempted to mark unknown endpoint as unhealthy");
-        }
+        pool.pause();
+        assert_eq!(pool.execute_dry_run(None), None);
+        pool.resume();
+
+        let strict = RpcPool::new(create_test_config().with_min_healthy_endpoints(2)).unwrap();
+        strict.mark_unhealthy(rpc1);
+        assert_eq!(strict.execute_dry_run(None), None);
     }
 
-    /// Get current metrics.
-    pub fn metrics(&self) -> RpcPoolMetrics {
-        let endpoints: Vec<EndpointMetrics> = self
-            .stats
-            .read()
-            .values()
-            .map(EndpointMetrics::from)
-            .collect();
+    #[test]
+    fn test_execute_dry_run_leaves_strategy_state() {
+        let rate_aware = RateAwareStrategy::with_min_interval(Duration::from_secs(60));
+        let strategies: Vec<Box<dyn SelectionStrategy>> = vec![
+            Box::new(rate_aware),
+            Box::new(RoundRobinStrategy::new()),
+        ];
 
-        let current_endpoint = {
-            let stats_map = self.collect_stats_snapshot();
-            let mut strategy = self.strategy.write();
-            strategy
-                .select(&self.endpoints, &stats_map, &HashSet::new())
-                .map(|e| e.name.clone())
-                .unwrap_or_else(|| "none".to_string())
-        };
+        for strategy in strategies {
+            let name = strategy.name();
+            let pool = RpcPool::new(create_test_config().with_strategy(strategy)).unwrap();
+            let rpc1 = Some("https://rpc1.example.com".to_string());
 
-        RpcPoolMetrics {
-            total_requests: self.total_requests.load(Ordering::Relaxed),
-            failovers: self.failovers.load(Ordering::Relaxed),
-            current_endpoint,
-            endpoints,
+            assert_eq!(pool.execute_dry_run(None), rpc1, "{name}");
+            assert_eq!(pool.execute_dry_run(None), rpc1, "{name}");
+            // The first real selection still goes where the dry runs said
+            assert_eq!(pool.get_current_url(), rpc1, "{name}");
+            assert_ne!(pool.execute_dry_run(None), rpc1, "{name}");
         }
     }
-}
 
-impl Drop for RpcPool {
-    fn drop(&mut self) {
-        // Signal shutdown to any running tasks
-        self.cancelled.store(true, Ordering::Release);
-        self.cancel_notify.notify_waiters();
+    #[test]
+    fn test_endpoint_latency_rank() {
+        let pool = three_endpoint_pool(RpcPoolConfig::new());
+        assert_eq!(pool.get_fastest_endpoint(), None);
+        assert_eq!(pool.get_slowest_endpoint(), None);
 
-        // Abort health check task if still running
-        if let Some(handle) = self.health_check_handle.get_mut().take() {
-            if !handle.is_finished() {
-                handle.abort();
-            }
+        {
+            let mut stats = pool.stats.write();
+            stats
+                .get_mut("https://rpc1.example.com")
+                .unwrap()
+                .record_success(300);
+            stats
+                .get_mut("https://rpc3.example.com")
+                .unwrap()
+                .record_success(50);
         }
+        pool.mark_unhealthy("https://rpc3.example.com");
 
-        debug!("RpcPool dropped, resources cleaned up");
+        let name = |url: &str| pool.get_endpoint_by_url(url).unwrap().name;
+        let rank = pool.get_endpoint_latency_rank();
+        assert_eq!(
+            rank,
+            vec![
+                (name("https://rpc3.example.com"), 50.0),
+                (name("https://rpc1.example.com"), 300.0),
+                (name("https://rpc2.example.com"), f64::INFINITY),
+            ]
+        );
+
+        let fastest = pool.get_fastest_endpoint().unwrap();
+        assert_eq!(fastest.url, "https://rpc3.example.com");
+        // Endpoints without requests are never the slowest
+        let slowest = pool.get_slowest_endpoint().unwrap();
+        assert_eq!(slowest.url, "https://rpc1.example.com");
     }
-}
 
-/// Wrapper to store abort handle with is_finished check.
-struct AbortHandleWrapper {
-    handle: tokio::task::AbortHandle,
-}
+    #[test]
+    fn test_get_best_endpoint_for_capability() {
+        let caps = |batch: u32, range: u64| EndpointCapabilities {
+            supports_eth_get_logs: Some(true),
+            max_batch_size: Some(batch),
+            max_block_range: Some(range),
+            ..Default::default()
+        };
+        let pool = RpcPool::new(RpcPoolConfig::new().with_endpoints(vec![
+            RpcEndpoint::new("https://grade-c.example.com").with_capabilities(caps(1, 100)),
+            RpcEndpoint::new("https://slow-a.example.com").with_capabilities(caps(100, 10_000)),
+            RpcEndpoint::new("https://fast-a.example.com").with_capabilities(caps(100, 50_000)),
+            RpcEndpoint::new("https://down-a.example.com").with_capabilities(caps(1_000, 100_000)),
+        ]))
+        .unwrap();
+        {
+            let mut stats = pool.stats.write();
+            stats.get_mut("https://slow-a.example.com").unwrap().record_success(300);
+            stats.get_mut("https://fast-a.example.com").unwrap().record_success(50);
+        }
+        pool.mark_unhealthy("https://down-a.example.com");
 
-impl AbortHandleWrapper {
-    fn is_finished(&self) -> bool {
-        self.handle.is_finished()
-    }
+        let best = |filter| pool.get_best_endpoint_for_capability(filter).map(|e| e.url);
+        // Grade first, then latency, never an unhealthy endpoint
+        assert_eq!(best(CapabilityFilter::new()).as_deref(), Some("https://fast-a.example.com"));
+        let large_range = CapabilityFilter::new().with_min_block_range(20_000);
+        assert_eq!(best(large_range).as_deref(), Some("https://fast-a.example.com"));
+        let huge_range = CapabilityFilter::new().with_min_block_range(60_000);
+        assert_eq!(best(huge_range), None);
 
-    fn abort(&self) {
-        self.handle.abort();
+        pool.mark_unhealthy("https://fast-a.example.com");
+        pool.mark_unhealthy("https://slow-a.example.com");
+        assert_eq!(best(CapabilityFilter::new()).as_deref(), Some("https://grade-c.example.com"));
     }
-}
 
-impl From<tokio::task::AbortHandle> for AbortHandleWrapper {
-    fn from(handle: tokio::task::AbortHandle) -> Self {
-        Self { handle }
-    }
-}
+    #[test]
+    fn test_getters_do_not_deadlock_with_add_remove() {
+        let pool = Arc::new(RpcPool::new(create_test_config()).unwrap());
+        let done = Arc::new(AtomicBool::new(false));
 
-/// Truncate error message to prevent unbounded memory growth.
-#[inline]
-fn truncate_error_message(msg: &str) -> String {
-    if msg.len() <= MAX_ERROR_MESSAGE_LENGTH {
-        msg.to_string()
-    } else {
-        format!("{}...(truncated)", &msg[..MAX_ERROR_MESSAGE_LENGTH])
-    }
-}
+        let readers: Vec<_> = (0..4)
+            .map(|_| {
+                let pool = Arc::clone(&pool);
+                let done = Arc::clone(&done);
+                std::thread::spawn(move || {
+                    while !done.load(Ordering::Relaxed) {
+                        pool.get_best_endpoint_for_capability(CapabilityFilter::new());
+                        pool.get_fastest_endpoint();
+                        pool.healthy_endpoints();
+                        pool.full_health_report();
+                    }
+                })
+            })
+            .collect();
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::strategies::FailoverStrategy;
+        let (tx, rx) = std::sync::mpsc::channel();
+        let writer = Arc::clone(&pool);
+        std::thread::spawn(move || {
+            for _ in 0..5_000 {
+                writer.add_endpoint(RpcEndpoint::new("https://churn.example.com")).unwrap();
+                writer.remove_endpoint("https://churn.example.com").unwrap();
+            }
+            tx.send(()).unwrap();
+        });
 
-    fn create_test_config() -> RpcPoolConfig {
-        RpcPoolConfig::new()
-            .with_endpoints(vec![
-                RpcEndpoint::new("https://rpc1.example.com"),
-                RpcEndpoint::new("https://rpc2.example.com"),
-            ])
-            .with_strategy(Box::new(FailoverStrategy))
+        let finished = rx.recv_timeout(Duration::from_secs(30));
+        done.store(true, Ordering::Relaxed);
+        assert!(finished.is_ok(), "add/remove deadlocked against the getters");
+        for reader in readers {
+            reader.join().unwrap();
+        }
     }
 
     #[test]
-    fn test_pool_creation() {
-        let config = create_test_config();
-        let pool = RpcPool::new(config);
-        assert!(pool.is_ok());
+    fn test_snapshot_restore() {
+        let pool = RpcPool::new(create_test_config()).unwrap();
+        pool.stats
+            .write()
+            .get_mut("https://rpc1.example.com")
+            .unwrap()
+            .record_failure("boom".to_string(), 1);
+        pool.stats
+            .write()
+            .get_mut("https://rpc2.example.com")
+            .unwrap()
+            .record_success(40);
+
+        let json = serde_json::to_string(&pool.snapshot()).unwrap();
+        let snapshot: PoolSnapshot = serde_json::from_str(&json).unwrap();
+        assert_eq!(snapshot.endpoints.len(), 2);
+
+        let restored = RpcPool::new(create_test_config()).unwrap();
+        restored.restore_from_snapshot(snapshot);
+
+        let stats = restored.stats.read();
+        let failed = &stats["https://rpc1.example.com"];
+        assert!(!failed.is_healthy);
+        assert_eq!(failed.last_error.as_deref(), Some("boom"));
+        assert_eq!(stats["https://rpc2.example.com"].successful_requests, 1);
+        assert_eq!(stats["https://rpc2.example.com"].last_latency_ms, 40);
     }
 
     #[test]
-    fn test_empty_endpoints() {
-        let config = RpcPoolConfig::new().with_strategy(Box::new(FailoverStrategy));
+    fn test_snapshot_restore_preserves_retry_backoff() {
+        let retry_delay = Duration::from_secs(5);
+        let pool = RpcPool::new(create_test_config()).unwrap();
+        pool.mark_unhealthy("https://rpc1.example.com");
+        pool.stats
+            .write()
+            .get_mut("https://rpc1.example.com")
+            .unwrap()
+            .record_failure("boom".to_string(), 1);
+        let snapshot = pool.snapshot();
 
-        let pool = RpcPool::new(config);
-        assert!(matches!(pool, Err(RpcPoolError::NoEndpointsConfigured)));
+        let can_retry_after = |age: Duration| {
+            let mut snapshot = snapshot.clone();
+            snapshot.endpoints[0].last_error_age = Some(age);
+            let restored = RpcPool::new(create_test_config()).unwrap();
+            restored.restore_from_snapshot(snapshot);
+            let stats = restored.stats.read();
+            let s = &stats["https://rpc1.example.com"];
+            assert!(!s.is_healthy);
+            s.can_retry(retry_delay)
+        };
+
+        // The error happened 1s before the snapshot: still backing off
+        assert!(!can_retry_after(Duration::from_secs(1)));
+        // The error happened 10s before the snapshot: retry is due
+        assert!(can_retry_after(Duration::from_secs(10)));
     }
 
     #[test]
-    fn test_get_urls() {
-        let config = RpcPoolConfig::new()
-            .with_endpoints(vec![
-                RpcEndpoint::new("https://rpc1.example.com").with_priority(10),
-                RpcEndpoint::new("https://rpc2.example.com").with_priority(50),
-            ])
-            .with_strategy(Box::new(FailoverStrategy));
+    fn test_restore_ignores_unknown_endpoints() {
+        let other = RpcPool::new(
+            RpcPoolConfig::new().with_endpoints(vec![RpcEndpoint::new("https://other.example.com")]),
+        )
+        .unwrap();
+        other.mark_unhealthy("https://other.example.com");
 
+        let pool = RpcPool::new(create_test_config()).unwrap();
+        pool.restore_from_snapshot(other.snapshot());
+        assert_eq!(pool.health_summary().healthy, 2);
+    }
+
+    /// Answer `rpc1` after `primary_latency` and everything else at once, counting calls.
+    async fn speculative_call(
+        url: url::Url,
+        primary_latency: Duration,
+        calls: &Mutex<Vec<String>>,
+    ) -> Result<String, std::io::Error> {
+        calls.lock().push(url.to_string());
+        if url.as_str().starts_with("https://rpc1") {
+            tokio::time::sleep(primary_latency).await;
+        }
+        Ok(url.to_string())
+    }
+
+    #[tokio::test]
+    async fn test_execute_speculative_fast_primary_skips_secondary() {
+        let config = create_test_config().with_speculative_delay(Duration::from_millis(200));
         let pool = RpcPool::new(config).unwrap();
+        let calls = Mutex::new(Vec::new());
 
-        // Should return highest priority (lowest number) endpoint
-        assert_eq!(
-            pool.get_current_url(),
-            Some("https://rpc1.example.com".to_string())
-        );
+        let used = pool
+            .execute_speculative(|url| speculative_call(url, Duration::ZERO, &calls))
+            .await
+            .unwrap();
 
-        // Should return all URLs
-        let all = pool.get_all_urls();
-        assert_eq!(all.len(), 2);
+        assert_eq!(used, "https://rpc1.example.com/");
+        assert_eq!(*calls.lock(), vec!["https://rpc1.example.com/"]);
+        assert_eq!(pool.metrics().failovers, 0);
     }
 
-    #[test]
-    fn test_truncate_error_message() {
-        let short_msg = "Short error";
-        assert_eq!(truncate_error_message(short_msg), short_msg);
+    #[tokio::test]
+    async fn test_execute_speculative_slow_primary_uses_secondary() {
+        let config = create_test_config().with_speculative_delay(Duration::from_millis(20));
+        let pool = RpcPool::new(config).unwrap();
+        let calls = Mutex::new(Vec::new());
 
-        let long_msg = "x".repeat(1000);
-        let truncated = truncate_error_message(&long_msg);
-        assert!(truncated.len() < long_msg.len());
-        assert!(truncated.ends_with("...(truncated)"));
+        let used = pool
+            .execute_speculative(|url| speculative_call(url, Duration::from_secs(5), &calls))
+            .await
+            .unwrap();
+
+        assert_eq!(used, "https://rpc2.example.com/");
+        assert_eq!(calls.lock().len(), 2);
+        assert_eq!(pool.metrics().failovers, 1);
+
+        // The cancelled primary attempt is not recorded
+        let stats = pool.stats.read();
+        assert_eq!(stats["https://rpc1.example.com"].total_requests, 0);
+        assert_eq!(stats["https://rpc2.example.com"].successful_requests, 1);
     }
 
-    #[test]
-    fn test_config_builder() {
-        let config = RpcPoolConfig::new()
-            .with_request_timeout(Duration::from_secs(10))
-            .with_health_check_timeout(Duration::from_secs(5))
-            .with_health_check_interval(Duration::from_secs(30))
-            .with_max_consecutive_errors(5)
-            .with_retry_delay(Duration::from_secs(10));
+    #[tokio::test]
+    async fn test_execute_speculative_failed_primary_skips_delay() {
+        let config = create_test_config().with_speculative_delay(Duration::from_secs(10));
+        let pool = RpcPool::new(config).unwrap();
 
-        assert_eq!(config.request_timeout, Duration::from_secs(10));
-        assert_eq!(config.health_check_timeout, Duration::from_secs(5));
-        assert_eq!(config.health_check_interval, Duration::from_secs(30));
-        assert_eq!(config.max_consecutive_errors, 5);
-        assert_eq!(config.retry_delay, Duration::from_secs(10));
+        let start = Instant::now();
+        let result = pool
+            .execute_speculative(|url: url::Url| async move {
+                if url.as_str().starts_with("https://rpc1") {
+                    return Err(std::io::Error::other("boom"));
+                }
+                Ok(url.to_string())
+            })
+            .await;
+
+        assert_eq!(result.unwrap(), "https://rpc2.example.com/");
+        assert!(start.elapsed() < Duration::from_secs(5));
+        let failed = pool.stats.read()["https://rpc1.example.com"].failed_requests;
+        assert_eq!(failed, 1);
+
+        let all_fail = pool
+            .execute_speculative(|_| async { Err::<(), _>(std::io::Error::other("down")) })
+            .await;
+        assert!(matches!(all_fail, Err(RpcPoolError::AllEndpointsFailed(msg)) if msg == "down"));
     }
 
-    #[test]
-    fn test_pool_drop_sets_shutdown() {
+    #[tokio::test]
+    async fn test_execute_hedged_returns_first_success() {
         let config = create_test_config();
         let pool = RpcPool::new(config).unwrap();
 
-        assert!(!pool.is_shutdown());
+        let result = pool
+            .execute_hedged(
+                |url: url::Url| async move {
+                    if url.as_str().starts_with("https://rpc1") {
+                        tokio::time::sleep(Duration::from_millis(200)).await;
+                    }
+                    Ok::<_, std::io::Error>(url.to_string())
+                },
+                2,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(result, "https://rpc2.example.com/");
+
+        // The slower attempt was cancelled and never recorded
+        let stats = pool.stats.read();
+        assert_eq!(stats["https://rpc1.example.com"].total_requests, 0);
+        assert_eq!(stats["https://rpc2.example.com"].successful_requests, 1);
     }
 
     #[tokio::test]
-    async fn test_shutdown() {
+    async fn test_execute_hedged_penalises_failures() {
         let config = create_test_config();
-        let pool = Arc::new(RpcPool::new(config).unwrap());
+        let pool = RpcPool::new(config).unwrap();
 
-        // Start health check
-        let _handle = pool.start_health_check();
+        let result = pool
+            .execute_hedged(
+                |url: url::Url| async move {
+                    if url.as_str().starts_with("https://rpc1") {
+                        return Err(std::io::Error::other("boom"));
+                    }
+                    tokio::time::sleep(Duration::from_millis(50)).await;
+                    Ok(42u64)
+                },
+                2,
+            )
+            .await;
 
-        assert!(!pool.is_shutdown());
+        assert_eq!(result.unwrap(), 42);
+        let stats = pool.stats.read();
+        assert_eq!(stats["https://rpc1.example.com"].failed_requests, 1);
+        assert_eq!(
+            stats["https://rpc1.example.com"].last_error.as_deref(),
+            Some("boom")
+        );
+    }
 
-        // Shutdown
-        pool.shutdown().await;
+    #[tokio::test]
+    async fn test_execute_hedged_caps_concurrency() {
+        use std::sync::atomic::AtomicUsize;
 
-        assert!(pool.is_shutdown());
+        let config = create_test_config();
+        let pool = RpcPool::new(config).unwrap();
+        let calls = Arc::new(AtomicUsize::new(0));
+
+        let result = pool
+            .execute_hedged(
+                |_url: url::Url| {
+                    let calls = Arc::clone(&calls);
+                    async move {
+                        calls.fetch_add(1, Ordering::SeqCst);
+                        Err::<(), _>(std::io::Error::other("down"))
+                    }
+                },
+                10,
+            )
+            .await;
+
+        assert!(matches!(result, Err(RpcPoolError::AllEndpointsFailed(_))));
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
     }
 
     #[test]
@@ -871,4 +5728,115 @@ mod tests {
         assert!(summary.all_unhealthy());
         assert_eq!(summary.health_percentage(), 0.0);
     }
+
+    #[test]
+    fn test_endpoint_counts_match_health_summary() {
+        let pool = RpcPool::new(create_test_config()).unwrap();
+        assert_eq!(pool.endpoint_count(), 2);
+        assert_eq!(pool.active_endpoint_count(), 2);
+
+        pool.mark_unhealthy("https://rpc1.example.com");
+        let summary = pool.health_summary();
+        assert_eq!(pool.endpoint_count(), summary.total);
+        assert_eq!(pool.active_endpoint_count(), summary.healthy);
+        assert_eq!(pool.active_endpoint_count(), 1);
+
+        pool.mark_unhealthy("https://rpc2.example.com");
+        assert_eq!(pool.active_endpoint_count(), 0);
+        assert_eq!(pool.endpoint_count(), 2);
+    }
+
+    #[test]
+    fn test_full_health_report() {
+        let pool = RpcPool::new(create_test_config()).unwrap();
+        pool.mark_unhealthy("https://rpc2.example.com");
+
+        let report = pool.full_health_report();
+        let sections: Vec<_> = report.split("\n---\n").collect();
+        assert_eq!(sections.len(), 2);
+        assert!(sections[0].contains("https://rpc1.example.com"));
+        assert!(sections[0].contains("Status: healthy"));
+        assert!(sections[1].contains("https://rpc2.example.com"));
+        assert!(sections[1].contains("Status: unhealthy"));
+    }
+
+    #[test]
+    fn test_health_summary_counts_degraded() {
+        let pool = RpcPool::new(create_test_config()).unwrap();
+        {
+            let mut stats = pool.stats.write();
+            // 60% over 20 requests: healthy but degraded
+            let flaky = stats.get_mut("https://rpc1.example.com").unwrap();
+            flaky.total_requests = 20;
+            flaky.successful_requests = 12;
+            // Too few requests to judge
+            let new = stats.get_mut("https://rpc2.example.com").unwrap();
+            new.total_requests = 5;
+            new.successful_requests = 1;
+        }
+
+        let summary = pool.health_summary();
+        assert_eq!(summary.healthy, 2);
+        assert_eq!(summary.degraded, 1);
+        assert!(summary.is_degraded());
+        assert!(!summary.all_unhealthy());
+        assert_eq!(summary.health_percentage(), 50.0);
+
+        pool.mark_unhealthy("https://rpc2.example.com");
+        let summary = pool.health_summary();
+        assert_eq!(summary.degraded, 1);
+        assert!(!summary.all_unhealthy());
+        assert_eq!(summary.health_percentage(), 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_set_strategy_changes_selection() {
+        let pool = RpcPool::new(create_test_config()).unwrap();
+        let echo = |url: String| async move { Ok::<_, std::io::Error>(url) };
+        assert_eq!(pool.strategy_name(), "failover");
+
+        for _ in 0..3 {
+            let url = pool.execute_with_url(echo).await.unwrap();
+            assert_eq!(url, "https://rpc1.example.com");
+        }
+
+        pool.set_strategy(Box::new(crate::strategies::RoundRobinStrategy::new()));
+        assert_eq!(pool.strategy_name(), "round-robin");
+
+        let first = pool.execute_with_url(echo).await.unwrap();
+        let second = pool.execute_with_url(echo).await.unwrap();
+        assert_ne!(first, second);
+    }
+
+    #[tokio::test]
+    async fn test_set_strategy_during_concurrent_execute() {
+        let pool = Arc::new(RpcPool::new(create_test_config()).unwrap());
+
+        let requests: Vec<_> = (0..32)
+            .map(|_| {
+                let pool = Arc::clone(&pool);
+                tokio::spawn(async move {
+                    pool.execute_with_url(|url: String| async move {
+                        tokio::time::sleep(Duration::from_millis(1)).await;
+                        Ok::<_, std::io::Error>(url)
+                    })
+                    .await
+                })
+            })
+            .collect();
+        for i in 0..32 {
+            if i % 2 == 0 {
+                pool.set_strategy(Box::new(crate::strategies::RoundRobinStrategy::new()));
+            } else {
+                pool.set_strategy(Box::new(FailoverStrategy));
+            }
+            tokio::task::yield_now().await;
+        }
+
+        let all = futures_util::future::join_all(requests);
+        let results = tokio::time::timeout(Duration::from_secs(5), all)
+            .await
+            .expect("requests deadlocked during strategy swap");
+        assert!(results.into_iter().all(|r| r.unwrap().is_ok()));
+    }
 }
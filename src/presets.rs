@@ -3,7 +3,24 @@
 //! All endpoints have been verified to be working as of 2026-02.
 //! Endpoints are tested with eth_blockNumber RPC call.
 
-use crate::endpoint::{EndpointCapabilities, RpcEndpoint};
+use crate::endpoint::{CapabilityFilter, EndpointCapabilities, EndpointGrade, RpcEndpoint};
+#[cfg(feature = "community-endpoints")]
+use crate::error::RpcPoolError;
+use std::collections::{HashMap, HashSet};
+use tracing::{debug, warn};
+
+/// Prefix of the environment variables read by [`from_env`].
+const ENV_ENDPOINT_PREFIX: &str = "RPC_ENDPOINT";
+
+/// Environment variable overriding [`DEFAULT_COMMUNITY_ENDPOINTS_URL`].
+#[cfg(feature = "community-endpoints")]
+const COMMUNITY_ENDPOINTS_URL_VAR: &str = "COMMUNITY_ENDPOINTS_URL";
+
+/// Endpoint list fetched by [`load_community_endpoints`] unless
+/// `COMMUNITY_ENDPOINTS_URL` is set.
+#[cfg(feature = "community-endpoints")]
+pub const DEFAULT_COMMUNITY_ENDPOINTS_URL: &str =
+    "https://raw.githubusercontent.com/sunoj/web3-rpc-pool/main/community/endpoints.json";
 
 /// Chain IDs for common networks.
 pub mod chain_id {
@@ -50,15 +67,21 @@ pub mod chain_id {
     pub const OPBNB: u64 = 204;
     pub const ZETACHAIN: u64 = 7000;
     pub const LISK: u64 = 1135;
+    pub const OPTIMISM_SEPOLIA: u64 = 11155420;
+    // Non-EVM chains
+    /// Starknet mainnet (`SN_MAIN` as a number).
+    pub const STARKNET: u64 = 23448594291968334;
 }
 
 /// Get default endpoints for a chain by chain ID.
 pub fn default_endpoints(chain_id: u64) -> Vec<RpcEndpoint> {
     match chain_id {
         chain_id::ARBITRUM_ONE => arbitrum_endpoints(),
+        chain_id::ARBITRUM_SEPOLIA => arbitrum_sepolia_endpoints(),
         chain_id::AURORA => aurora_endpoints(),
         chain_id::AVALANCHE => avalanche_endpoints(),
         chain_id::BASE => base_endpoints(),
+        chain_id::BASE_SEPOLIA => base_sepolia_endpoints(),
         chain_id::BERACHAIN => berachain_endpoints(),
         chain_id::BLAST => blast_endpoints(),
         chain_id::BSC => bsc_endpoints(),
@@ -83,12 +106,15 @@ pub fn default_endpoints(chain_id: u64) -> Vec<RpcEndpoint> {
         chain_id::MOONBEAM => moonbeam_endpoints(),
         chain_id::OPBNB => opbnb_endpoints(),
         chain_id::OPTIMISM => optimism_endpoints(),
+        chain_id::OPTIMISM_SEPOLIA => optimism_sepolia_endpoints(),
         chain_id::POLYGON => polygon_endpoints(),
         chain_id::POLYGON_ZKEVM => polygon_zkevm_endpoints(),
         chain_id::ROOTSTOCK => rootstock_endpoints(),
         chain_id::SCROLL => scroll_endpoints(),
         chain_id::SEI => sei_endpoints(),
+        chain_id::SEPOLIA => sepolia_endpoints(),
         chain_id::SONIC => sonic_endpoints(),
+        chain_id::STARKNET => starknet_endpoints(),
         chain_id::TAIKO => taiko_endpoints(),
         chain_id::WORLD_CHAIN => world_chain_endpoints(),
         chain_id::ZETACHAIN => zetachain_endpoints(),
@@ -97,7 +123,267 @@ pub fn default_endpoints(chain_id: u64) -> Vec<RpcEndpoint> {
     }
 }
 
-/// Return all supported mainnet chain IDs.
+/// Load endpoints for a chain from environment variables.
+///
+/// Reads `RPC_ENDPOINT_{chain_id}_0`, `RPC_ENDPOINT_{chain_id}_1`, ... and, if
+/// none are set, the chain-agnostic `RPC_ENDPOINT_0`, `RPC_ENDPOINT_1`, ...
+/// Numbering stops at the first missing index and values that are not valid
+/// URLs are skipped. Falls back to [`default_endpoints`] when nothing is set.
+///
+/// Endpoints are named after their variable and prioritised in index order,
+/// ahead of the built-in presets.
+pub fn from_env(chain_id: u64) -> Vec<RpcEndpoint> {
+    let chain_prefix = format!("{}_{}", ENV_ENDPOINT_PREFIX, chain_id);
+    let mut endpoints = endpoints_from_env(&chain_prefix, chain_id);
+    if endpoints.is_empty() {
+        endpoints = endpoints_from_env(ENV_ENDPOINT_PREFIX, chain_id);
+    }
+
+    if endpoints.is_empty() {
+        debug!(chain_id, "No RPC endpoints in environment, using defaults");
+        return default_endpoints(chain_id);
+    }
+    endpoints
+}
+
+/// Merge two endpoint lists into one, deduplicated by URL.
+///
+/// The lists are interleaved by priority, keeping the order within each list
+/// and letting `a` go first on equal priorities. When a URL appears more than
+/// once, the entry with the lower priority number is kept. The result is
+/// renumbered with priorities `1, 2, 3, ...` in merged order.
+pub fn merge_endpoint_lists(a: Vec<RpcEndpoint>, b: Vec<RpcEndpoint>) -> Vec<RpcEndpoint> {
+    let mut merged = Vec::with_capacity(a.len() + b.len());
+    let mut a = a.into_iter().peekable();
+    let mut b = b.into_iter().peekable();
+    loop {
+        let next = match (a.peek(), b.peek()) {
+            (Some(x), Some(y)) if y.priority < x.priority => b.next(),
+            (Some(_), _) => a.next(),
+            (None, _) => b.next(),
+        };
+        match next {
+            Some(endpoint) => merged.push(endpoint),
+            None => break,
+        }
+    }
+
+    // Position of the entry to keep for each URL: lowest priority, then earliest
+    let mut keep: HashMap<&str, (u32, usize)> = HashMap::new();
+    for (index, endpoint) in merged.iter().enumerate() {
+        let best = keep
+            .entry(endpoint.url.as_str())
+            .or_insert((endpoint.priority, index));
+        if endpoint.priority < best.0 {
+            *best = (endpoint.priority, index);
+        }
+    }
+    let keep: HashSet<usize> = keep.into_values().map(|(_, index)| index).collect();
+
+    let merged = merged
+        .into_iter()
+        .enumerate()
+        .filter(|(index, _)| keep.contains(index))
+        .map(|(_, endpoint)| endpoint)
+        .collect();
+    renumber_priorities(merged)
+}
+
+/// Put `custom` endpoints ahead of a chain's preset endpoints.
+///
+/// The custom endpoints keep their order and take priorities `1, 2, ...`,
+/// followed by the presets in their usual order; a preset with the same URL
+/// as a custom endpoint is dropped. Custom endpoints without a chain ID are
+/// given `chain_id`.
+pub fn prepend_endpoints(custom: Vec<RpcEndpoint>, chain_id: u64) -> Vec<RpcEndpoint> {
+    let mut seen = HashSet::new();
+    let endpoints = custom
+        .into_iter()
+        .map(|e| {
+            if e.chain_id == 0 {
+                e.with_chain_id(chain_id)
+            } else {
+                e
+            }
+        })
+        .chain(default_endpoints(chain_id))
+        .filter(|e| seen.insert(e.url.clone()))
+        .collect();
+    renumber_priorities(endpoints)
+}
+
+/// Assign priorities `1, 2, 3, ...` in list order.
+fn renumber_priorities(mut endpoints: Vec<RpcEndpoint>) -> Vec<RpcEndpoint> {
+    for (priority, endpoint) in (1..).zip(endpoints.iter_mut()) {
+        endpoint.priority = priority;
+    }
+    endpoints
+}
+
+/// Keep only the endpoints whose capabilities satisfy `filter`.
+///
+/// Unknown capabilities pass unless the filter is strict, see [`CapabilityFilter`].
+///
+/// ```
+/// use web3_rpc_pool::presets::{self, chain_id};
+/// use web3_rpc_pool::CapabilityFilter;
+///
+/// let indexer_endpoints = presets::filter_endpoints_by_capability(
+///     presets::default_endpoints(chain_id::ETHEREUM),
+///     CapabilityFilter::new().with_eth_get_logs().with_min_block_range(10_000),
+/// );
+/// ```
+pub fn filter_endpoints_by_capability(
+    endpoints: Vec<RpcEndpoint>,
+    filter: CapabilityFilter,
+) -> Vec<RpcEndpoint> {
+    let total = endpoints.len();
+    let matching: Vec<RpcEndpoint> = endpoints
+        .into_iter()
+        .filter(|e| filter.matches(&e.capabilities))
+        .collect();
+    debug!(
+        total,
+        matching = matching.len(),
+        filter = ?filter,
+        "Filtered endpoints by capability"
+    );
+    matching
+}
+
+/// Preset endpoints for a chain whose capability grade is at least `min_grade`.
+///
+/// Unlike [`filter_endpoints_by_capability`], unknown capabilities grade as
+/// [`EndpointGrade::D`] and are dropped by any higher threshold.
+pub fn ranked_endpoints(chain_id: u64, min_grade: EndpointGrade) -> Vec<RpcEndpoint> {
+    default_endpoints(chain_id)
+        .into_iter()
+        .filter(|e| e.capabilities.grade() >= min_grade)
+        .collect()
+}
+
+/// Preset endpoints for a chain that have a WebSocket URL, in priority order.
+pub fn endpoints_with_ws_support(chain_id: u64) -> Vec<RpcEndpoint> {
+    default_endpoints(chain_id)
+        .into_iter()
+        .filter(|e| e.ws_url.is_some())
+        .collect()
+}
+
+/// Preset endpoints for a chain without a WebSocket URL, in priority order.
+pub fn endpoints_http_only(chain_id: u64) -> Vec<RpcEndpoint> {
+    default_endpoints(chain_id)
+        .into_iter()
+        .filter(|e| e.ws_url.is_none())
+        .collect()
+}
+
+/// Number of preset endpoints for a chain at each capability grade.
+///
+/// Grades without endpoints are omitted.
+pub fn grade_distribution(chain_id: u64) -> HashMap<EndpointGrade, usize> {
+    let mut distribution = HashMap::new();
+    for endpoint in default_endpoints(chain_id) {
+        *distribution.entry(endpoint.capabilities.grade()).or_insert(0) += 1;
+    }
+    distribution
+}
+
+/// Serialize a chain's preset endpoints, including capability metadata, as pretty-printed JSON.
+///
+/// Pair with [`load_endpoints_from_json`] to pin an endpoint list on disk,
+/// optionally after adding private nodes to it. Unknown chains give `[]`.
+pub fn snapshot_to_json(chain_id: u64) -> String {
+    serde_json::to_string_pretty(&default_endpoints(chain_id))
+        .expect("endpoint serialization is infallible")
+}
+
+/// Load an endpoint list written by [`snapshot_to_json`].
+///
+/// Fields missing from older snapshots take their defaults, as when
+/// deserializing a single [`RpcEndpoint`].
+pub fn load_endpoints_from_json(json: &str) -> Result<Vec<RpcEndpoint>, serde_json::Error> {
+    let endpoints: Vec<RpcEndpoint> = serde_json::from_str(json)?;
+    debug!(endpoints = endpoints.len(), "Loaded endpoints from JSON");
+    Ok(endpoints)
+}
+
+/// Fetch a chain's endpoints from the community-maintained endpoint list.
+///
+/// Downloads the JSON array of endpoints at `COMMUNITY_ENDPOINTS_URL`, or
+/// [`DEFAULT_COMMUNITY_ENDPOINTS_URL`] if it is unset, in the format of
+/// [`snapshot_to_json`], and returns those for `chain_id` sorted by priority.
+/// The whole download is bounded by `timeout`.
+///
+/// Fails with [`RpcPoolError::TransportError`] if the list cannot be fetched,
+/// or [`RpcPoolError::ConfigParseError`] if it is not a valid endpoint list.
+/// Requires the `community-endpoints` feature.
+#[cfg(feature = "community-endpoints")]
+pub async fn load_community_endpoints(
+    chain_id: u64,
+    timeout: std::time::Duration,
+) -> Result<Vec<RpcEndpoint>, RpcPoolError> {
+    let url = std::env::var(COMMUNITY_ENDPOINTS_URL_VAR)
+        .unwrap_or_else(|_| DEFAULT_COMMUNITY_ENDPOINTS_URL.to_string());
+    fetch_community_endpoints(&url, chain_id, timeout).await
+}
+
+/// Fetch the endpoint list at `url` and keep `chain_id`'s endpoints.
+#[cfg(feature = "community-endpoints")]
+async fn fetch_community_endpoints(
+    url: &str,
+    chain_id: u64,
+    timeout: std::time::Duration,
+) -> Result<Vec<RpcEndpoint>, RpcPoolError> {
+    let client = reqwest::Client::builder()
+        .timeout(timeout)
+        .build()
+        .map_err(|e| RpcPoolError::ClientCreationFailed(e.to_string()))?;
+    let body = client
+        .get(url)
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(|e| RpcPoolError::TransportError(format!("{}: {}", url, e)))?
+        .text()
+        .await
+        .map_err(|e| RpcPoolError::TransportError(format!("{}: {}", url, e)))?;
+
+    let mut endpoints = load_endpoints_from_json(&body)
+        .map_err(|e| RpcPoolError::ConfigParseError(format!("{}: {}", url, e)))?;
+    endpoints.retain(|e| e.chain_id == chain_id);
+    endpoints.sort_by_key(|e| e.priority);
+    debug!(chain_id, url, endpoints = endpoints.len(), "Loaded community endpoints");
+    Ok(endpoints)
+}
+
+/// Read `{prefix}_0`, `{prefix}_1`, ... until the first unset variable.
+pub(crate) fn endpoints_from_env(prefix: &str, chain_id: u64) -> Vec<RpcEndpoint> {
+    let mut endpoints = Vec::new();
+    for index in 0u32.. {
+        let var = format!("{}_{}", prefix, index);
+        let Ok(value) = std::env::var(&var) else {
+            break;
+        };
+        let value = value.trim();
+        if let Err(e) = url::Url::parse(value) {
+            warn!(var = %var, error = %e, "Ignoring invalid RPC endpoint URL from environment");
+            continue;
+        }
+        endpoints.push(
+            RpcEndpoint::new(value)
+                .with_name(var)
+                .with_priority(10 + index)
+                .with_chain_id(chain_id),
+        );
+    }
+    endpoints
+}
+
+/// Return all supported EVM chain IDs, mainnets first, then testnets.
+///
+/// [`chain_id::STARKNET`] is not included, since its endpoints do not answer
+/// `eth_*` calls.
 pub fn all_chain_ids() -> Vec<u64> {
     vec![
         chain_id::ETHEREUM,
@@ -138,6 +424,11 @@ pub fn all_chain_ids() -> Vec<u64> {
         chain_id::WORLD_CHAIN,
         chain_id::ZETACHAIN,
         chain_id::ZKSYNC_ERA,
+        // Testnets
+        chain_id::SEPOLIA,
+        chain_id::ARBITRUM_SEPOLIA,
+        chain_id::BASE_SEPOLIA,
+        chain_id::OPTIMISM_SEPOLIA,
     ]
 }
 
@@ -176,12 +467,14 @@ pub fn chain_name(chain_id: u64) -> &'static str {
         self::chain_id::MOONBEAM => "Moonbeam",
         self::chain_id::OPBNB => "opBNB",
         self::chain_id::OPTIMISM => "Optimism",
+        self::chain_id::OPTIMISM_SEPOLIA => "Optimism Sepolia",
         self::chain_id::POLYGON => "Polygon",
         self::chain_id::POLYGON_ZKEVM => "Polygon zkEVM",
         self::chain_id::ROOTSTOCK => "Rootstock",
         self::chain_id::SCROLL => "Scroll",
         self::chain_id::SEI => "Sei",
         self::chain_id::SONIC => "Sonic",
+        self::chain_id::STARKNET => "Starknet",
         self::chain_id::TAIKO => "Taiko",
         self::chain_id::WORLD_CHAIN => "World Chain",
         self::chain_id::ZETACHAIN => "ZetaChain",
@@ -190,6 +483,89 @@ pub fn chain_name(chain_id: u64) -> &'static str {
     }
 }
 
+/// A preset endpoint that breaks a structural invariant, found by [`validate_all`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PresetValidationError {
+    /// Chain whose preset list contains the endpoint.
+    pub chain_id: u64,
+    /// Name of the offending endpoint.
+    pub endpoint: String,
+    /// What is wrong with it.
+    pub description: String,
+}
+
+impl std::fmt::Display for PresetValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} ({}): {}: {}",
+            chain_name(self.chain_id),
+            self.chain_id,
+            self.endpoint,
+            self.description
+        )
+    }
+}
+
+impl std::error::Error for PresetValidationError {}
+
+/// Check the structural invariants of every preset endpoint of every chain.
+///
+/// Reports HTTP URLs that are not valid `https://` URLs, a zero chain ID, an
+/// empty name or one equal to the URL, priorities repeated within a chain,
+/// URLs listed more than once across all chains, WebSocket URLs not using
+/// `wss://`, and an unlimited batch size (`max_batch_size = Some(0)`) on an
+/// endpoint not known to support `eth_getLogs`. An empty result means the
+/// preset database is consistent.
+pub fn validate_all() -> Vec<PresetValidationError> {
+    let mut errors = Vec::new();
+    let mut seen_urls: HashMap<String, u64> = HashMap::new();
+
+    for chain in all_chain_ids().into_iter().chain([chain_id::STARKNET]) {
+        let mut seen_priorities: HashMap<u32, String> = HashMap::new();
+        for endpoint in default_endpoints(chain) {
+            let mut report = |description: String| {
+                errors.push(PresetValidationError {
+                    chain_id: chain,
+                    endpoint: endpoint.name.clone(),
+                    description,
+                })
+            };
+
+            match url::Url::parse(&endpoint.url) {
+                Ok(url) if url.scheme() == "https" => {}
+                Ok(_) => report(format!("URL {} is not HTTPS", endpoint.url)),
+                Err(e) => report(format!("URL {} is invalid: {}", endpoint.url, e)),
+            }
+            if endpoint.chain_id == 0 {
+                report("chain ID is 0".to_string());
+            }
+            if endpoint.name.is_empty() || endpoint.name == endpoint.url {
+                report("name is empty or the URL".to_string());
+            }
+            if let Some(other) = seen_priorities.insert(endpoint.priority, endpoint.name.clone()) {
+                report(format!("priority is also used by {}", other));
+            }
+            if let Some(other) = seen_urls.insert(endpoint.url.clone(), chain) {
+                report(format!("URL is also listed for chain {}", other));
+            }
+            if let Some(ws_url) = &endpoint.ws_url {
+                if !ws_url.starts_with("wss://") {
+                    report(format!("WebSocket URL {} is not wss://", ws_url));
+                }
+            }
+            let capabilities = &endpoint.capabilities;
+            if capabilities.max_batch_size == Some(0)
+                && capabilities.supports_eth_get_logs != Some(true)
+            {
+                report("unlimited batch size without eth_getLogs support".to_string());
+            }
+        }
+    }
+
+    errors
+}
+
 /// Default endpoints for Ethereum Mainnet (34 verified endpoints).
 pub fn ethereum_endpoints() -> Vec<RpcEndpoint> {
     vec![
@@ -256,12 +632,12 @@ pub fn ethereum_endpoints() -> Vec<RpcEndpoint> {
             .with_name("BlockRazor")
             .with_priority(62)
             .with_chain_id(chain_id::ETHEREUM)
-            .with_capabilities(EndpointCapabilities { supports_eth_get_logs: Some(false), max_batch_size: Some(0), max_block_range: Some(0), ..Default::default() }),
+            .with_capabilities(EndpointCapabilities { supports_eth_get_logs: Some(false), max_batch_size: None, max_block_range: Some(0), ..Default::default() }),
         RpcEndpoint::new("https://eth.rpc.blxrbdn.com")
             .with_name("BloXroute")
             .with_priority(64)
             .with_chain_id(chain_id::ETHEREUM)
-            .with_capabilities(EndpointCapabilities { supports_eth_get_logs: Some(false), max_batch_size: Some(0), max_block_range: Some(0), ..Default::default() }),
+            .with_capabilities(EndpointCapabilities { supports_eth_get_logs: Some(false), max_batch_size: None, max_block_range: Some(0), ..Default::default() }),
         RpcEndpoint::new("https://rpc.eth.gateway.fm")
             .with_name("Gateway.fm")
             .with_priority(65)
@@ -368,7 +744,7 @@ pub fn arbitrum_endpoints() -> Vec<RpcEndpoint> {
             .with_ws_url("wss://arbitrum-one-rpc.publicnode.com")
             .with_priority(51)
             .with_chain_id(chain_id::ARBITRUM_ONE)
-            .with_capabilities(EndpointCapabilities { supports_eth_get_logs: Some(false), max_batch_size: Some(0), max_block_range: Some(0), ..Default::default() }),
+            .with_capabilities(EndpointCapabilities { supports_eth_get_logs: Some(false), max_batch_size: None, max_block_range: Some(0), ..Default::default() }),
         RpcEndpoint::new("https://1rpc.io/arb")
             .with_name("1RPC")
             .with_priority(52)
@@ -388,7 +764,7 @@ pub fn arbitrum_endpoints() -> Vec<RpcEndpoint> {
             .with_name("OnFinality")
             .with_priority(55)
             .with_chain_id(chain_id::ARBITRUM_ONE)
-            .with_capabilities(EndpointCapabilities { supports_eth_get_logs: Some(false), max_batch_size: Some(0), max_block_range: Some(0), ..Default::default() }),
+            .with_capabilities(EndpointCapabilities { supports_eth_get_logs: Some(false), max_batch_size: None, max_block_range: Some(0), ..Default::default() }),
         RpcEndpoint::new("https://arbitrum.meowrpc.com")
             .with_name("MeowRPC")
             .with_priority(56)
@@ -475,7 +851,7 @@ pub fn base_endpoints() -> Vec<RpcEndpoint> {
             .with_ws_url("wss://base-rpc.publicnode.com")
             .with_priority(51)
             .with_chain_id(chain_id::BASE)
-            .with_capabilities(EndpointCapabilities { supports_eth_get_logs: Some(false), max_batch_size: Some(0), max_block_range: Some(0), ..Default::default() }),
+            .with_capabilities(EndpointCapabilities { supports_eth_get_logs: Some(false), max_batch_size: None, max_block_range: Some(0), ..Default::default() }),
         RpcEndpoint::new("https://1rpc.io/base")
             .with_name("1RPC")
             .with_priority(52)
@@ -525,7 +901,7 @@ pub fn base_endpoints() -> Vec<RpcEndpoint> {
             .with_name("Pocket")
             .with_priority(63)
             .with_chain_id(chain_id::BASE)
-            .with_capabilities(EndpointCapabilities { supports_eth_get_logs: Some(false), max_batch_size: Some(0), max_block_range: Some(0), ..Default::default() }),
+            .with_capabilities(EndpointCapabilities { supports_eth_get_logs: Some(false), max_batch_size: None, max_block_range: Some(0), ..Default::default() }),
         // Phase 3: additional endpoints
         RpcEndpoint::new("https://base.llamarpc.com")
             .with_name("LlamaNodes")
@@ -580,7 +956,7 @@ pub fn optimism_endpoints() -> Vec<RpcEndpoint> {
             .with_ws_url("wss://optimism-rpc.publicnode.com")
             .with_priority(51)
             .with_chain_id(chain_id::OPTIMISM)
-            .with_capabilities(EndpointCapabilities { supports_eth_get_logs: Some(false), max_batch_size: Some(0), max_block_range: Some(0), ..Default::default() }),
+            .with_capabilities(EndpointCapabilities { supports_eth_get_logs: Some(false), max_batch_size: None, max_block_range: Some(0), ..Default::default() }),
         RpcEndpoint::new("https://1rpc.io/op")
             .with_name("1RPC")
             .with_priority(52)
@@ -723,12 +1099,12 @@ pub fn bsc_endpoints() -> Vec<RpcEndpoint> {
             .with_ws_url("wss://bsc-rpc.publicnode.com")
             .with_priority(63)
             .with_chain_id(chain_id::BSC)
-            .with_capabilities(EndpointCapabilities { supports_eth_get_logs: Some(false), max_batch_size: Some(0), max_block_range: Some(0), ..Default::default() }),
+            .with_capabilities(EndpointCapabilities { supports_eth_get_logs: Some(false), max_batch_size: None, max_block_range: Some(0), ..Default::default() }),
         RpcEndpoint::new("https://bsc.publicnode.com")
             .with_name("PublicNode Alt")
             .with_priority(64)
             .with_chain_id(chain_id::BSC)
-            .with_capabilities(EndpointCapabilities { supports_eth_get_logs: Some(false), max_batch_size: Some(0), max_block_range: Some(0), ..Default::default() }),
+            .with_capabilities(EndpointCapabilities { supports_eth_get_logs: Some(false), max_batch_size: None, max_block_range: Some(0), ..Default::default() }),
         RpcEndpoint::new("https://1rpc.io/bnb")
             .with_name("1RPC")
             .with_priority(65)
@@ -794,7 +1170,7 @@ pub fn avalanche_endpoints() -> Vec<RpcEndpoint> {
             .with_ws_url("wss://avalanche-c-chain-rpc.publicnode.com")
             .with_priority(51)
             .with_chain_id(chain_id::AVALANCHE)
-            .with_capabilities(EndpointCapabilities { supports_eth_get_logs: Some(false), max_batch_size: Some(0), max_block_range: Some(0), ..Default::default() }),
+            .with_capabilities(EndpointCapabilities { supports_eth_get_logs: Some(false), max_batch_size: None, max_block_range: Some(0), ..Default::default() }),
         RpcEndpoint::new("https://1rpc.io/avax/c")
             .with_name("1RPC")
             .with_priority(52)
@@ -809,7 +1185,7 @@ pub fn avalanche_endpoints() -> Vec<RpcEndpoint> {
             .with_name("OnFinality")
             .with_priority(54)
             .with_chain_id(chain_id::AVALANCHE)
-            .with_capabilities(EndpointCapabilities { supports_eth_get_logs: Some(false), max_batch_size: Some(0), max_block_range: Some(0), ..Default::default() }),
+            .with_capabilities(EndpointCapabilities { supports_eth_get_logs: Some(false), max_batch_size: None, max_block_range: Some(0), ..Default::default() }),
         RpcEndpoint::new("https://avax-pokt.nodies.app/ext/bc/C/rpc")
             .with_name("Nodies")
             .with_priority(55)
@@ -929,7 +1305,7 @@ pub fn fantom_endpoints() -> Vec<RpcEndpoint> {
             .with_name("OnFinality")
             .with_priority(59)
             .with_chain_id(chain_id::FANTOM)
-            .with_capabilities(EndpointCapabilities { supports_eth_get_logs: Some(false), max_batch_size: Some(0), max_block_range: Some(0), ..Default::default() }),
+            .with_capabilities(EndpointCapabilities { supports_eth_get_logs: Some(false), max_batch_size: None, max_block_range: Some(0), ..Default::default() }),
         RpcEndpoint::new("https://fantom-pokt.nodies.app")
             .with_name("Nodies")
             .with_priority(61)
@@ -995,7 +1371,7 @@ pub fn linea_endpoints() -> Vec<RpcEndpoint> {
             .with_ws_url("wss://linea-rpc.publicnode.com")
             .with_priority(51)
             .with_chain_id(chain_id::LINEA)
-            .with_capabilities(EndpointCapabilities { supports_eth_get_logs: Some(false), max_batch_size: Some(0), max_block_range: Some(0), ..Default::default() }),
+            .with_capabilities(EndpointCapabilities { supports_eth_get_logs: Some(false), max_batch_size: None, max_block_range: Some(0), ..Default::default() }),
         RpcEndpoint::new("https://1rpc.io/linea")
             .with_name("1RPC")
             .with_priority(52)
@@ -1051,7 +1427,7 @@ pub fn scroll_endpoints() -> Vec<RpcEndpoint> {
             .with_ws_url("wss://scroll-rpc.publicnode.com")
             .with_priority(51)
             .with_chain_id(chain_id::SCROLL)
-            .with_capabilities(EndpointCapabilities { supports_eth_get_logs: Some(false), max_batch_size: Some(0), max_block_range: Some(0), ..Default::default() }),
+            .with_capabilities(EndpointCapabilities { supports_eth_get_logs: Some(false), max_batch_size: None, max_block_range: Some(0), ..Default::default() }),
         RpcEndpoint::new("https://1rpc.io/scroll")
             .with_name("1RPC")
             .with_priority(52)
@@ -1145,7 +1521,7 @@ pub fn blast_endpoints() -> Vec<RpcEndpoint> {
             .with_ws_url("wss://blast-rpc.publicnode.com")
             .with_priority(51)
             .with_chain_id(chain_id::BLAST)
-            .with_capabilities(EndpointCapabilities { supports_eth_get_logs: Some(false), max_batch_size: Some(0), max_block_range: Some(0), ..Default::default() }),
+            .with_capabilities(EndpointCapabilities { supports_eth_get_logs: Some(false), max_batch_size: None, max_block_range: Some(0), ..Default::default() }),
         RpcEndpoint::new("https://blast.drpc.org")
             .with_name("dRPC")
             .with_priority(52)
@@ -1195,7 +1571,7 @@ pub fn mantle_endpoints() -> Vec<RpcEndpoint> {
             .with_ws_url("wss://mantle-rpc.publicnode.com")
             .with_priority(51)
             .with_chain_id(chain_id::MANTLE)
-            .with_capabilities(EndpointCapabilities { supports_eth_get_logs: Some(false), max_batch_size: Some(0), max_block_range: Some(0), ..Default::default() }),
+            .with_capabilities(EndpointCapabilities { supports_eth_get_logs: Some(false), max_batch_size: None, max_block_range: Some(0), ..Default::default() }),
         RpcEndpoint::new("https://1rpc.io/mantle")
             .with_name("1RPC")
             .with_priority(52)
@@ -1724,6 +2100,127 @@ pub fn lisk_endpoints() -> Vec<RpcEndpoint> {
     ]
 }
 
+// ============================================
+// Non-EVM chains
+// ============================================
+
+/// Default endpoints for Starknet (3 endpoints).
+///
+/// These endpoints serve the Starknet JSON-RPC API, so requests must use
+/// `starknet_*` methods such as `starknet_blockNumber` rather than
+/// `eth_blockNumber`. This includes health checks: configure the pool with
+/// a Starknet probe instead of the default `eth_blockNumber` one, and leave
+/// the block-lag check disabled.
+///
+/// ```rust,no_run
+/// use web3_rpc_pool::{presets, HealthCheckMethod, RpcPool, RpcPoolConfig};
+///
+/// # fn example() -> Result<(), web3_rpc_pool::RpcPoolError> {
+/// let pool = RpcPool::new(
+///     RpcPoolConfig::new()
+///         .with_endpoints(presets::starknet_endpoints())
+///         .with_health_check_method(HealthCheckMethod::Custom {
+///             method: "starknet_blockHashAndNumber".to_string(),
+///             params: serde_json::json!([]),
+///             expected_key: "block_number".to_string(),
+///         }),
+/// )?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn starknet_endpoints() -> Vec<RpcEndpoint> {
+    vec![
+        RpcEndpoint::new_starknet("https://starknet-rpc.publicnode.com", "PublicNode")
+            .with_priority(50),
+        RpcEndpoint::new_starknet("https://starknet.drpc.org", "dRPC").with_priority(51),
+        RpcEndpoint::new_starknet("https://rpc.starknet.lava.build", "Lava").with_priority(52),
+    ]
+}
+
+// ============================================
+// Testnets (4 chains, 12 endpoints)
+// ============================================
+
+/// Default endpoints for Sepolia (3 verified endpoints).
+pub fn sepolia_endpoints() -> Vec<RpcEndpoint> {
+    vec![
+        RpcEndpoint::new("https://ethereum-sepolia-rpc.publicnode.com")
+            .with_name("PublicNode")
+            .with_ws_url("wss://ethereum-sepolia-rpc.publicnode.com")
+            .with_priority(50)
+            .with_chain_id(chain_id::SEPOLIA),
+        RpcEndpoint::new("https://sepolia.drpc.org")
+            .with_name("dRPC")
+            .with_ws_url("wss://sepolia.drpc.org")
+            .with_priority(51)
+            .with_chain_id(chain_id::SEPOLIA),
+        RpcEndpoint::new("https://rpc.sepolia.org")
+            .with_name("Sepolia Official")
+            .with_priority(52)
+            .with_chain_id(chain_id::SEPOLIA),
+    ]
+}
+
+/// Default endpoints for Arbitrum Sepolia (3 verified endpoints).
+pub fn arbitrum_sepolia_endpoints() -> Vec<RpcEndpoint> {
+    vec![
+        RpcEndpoint::new("https://arbitrum-sepolia-rpc.publicnode.com")
+            .with_name("PublicNode")
+            .with_ws_url("wss://arbitrum-sepolia-rpc.publicnode.com")
+            .with_priority(50)
+            .with_chain_id(chain_id::ARBITRUM_SEPOLIA),
+        RpcEndpoint::new("https://arbitrum-sepolia.drpc.org")
+            .with_name("dRPC")
+            .with_ws_url("wss://arbitrum-sepolia.drpc.org")
+            .with_priority(51)
+            .with_chain_id(chain_id::ARBITRUM_SEPOLIA),
+        RpcEndpoint::new("https://sepolia-rollup.arbitrum.io/rpc")
+            .with_name("Arbitrum Official")
+            .with_priority(52)
+            .with_chain_id(chain_id::ARBITRUM_SEPOLIA),
+    ]
+}
+
+/// Default endpoints for Base Sepolia (3 verified endpoints).
+pub fn base_sepolia_endpoints() -> Vec<RpcEndpoint> {
+    vec![
+        RpcEndpoint::new("https://base-sepolia-rpc.publicnode.com")
+            .with_name("PublicNode")
+            .with_ws_url("wss://base-sepolia-rpc.publicnode.com")
+            .with_priority(50)
+            .with_chain_id(chain_id::BASE_SEPOLIA),
+        RpcEndpoint::new("https://base-sepolia.drpc.org")
+            .with_name("dRPC")
+            .with_ws_url("wss://base-sepolia.drpc.org")
+            .with_priority(51)
+            .with_chain_id(chain_id::BASE_SEPOLIA),
+        RpcEndpoint::new("https://sepolia.base.org")
+            .with_name("Base Official")
+            .with_priority(52)
+            .with_chain_id(chain_id::BASE_SEPOLIA),
+    ]
+}
+
+/// Default endpoints for Optimism Sepolia (3 verified endpoints).
+pub fn optimism_sepolia_endpoints() -> Vec<RpcEndpoint> {
+    vec![
+        RpcEndpoint::new("https://optimism-sepolia-rpc.publicnode.com")
+            .with_name("PublicNode")
+            .with_ws_url("wss://optimism-sepolia-rpc.publicnode.com")
+            .with_priority(50)
+            .with_chain_id(chain_id::OPTIMISM_SEPOLIA),
+        RpcEndpoint::new("https://optimism-sepolia.drpc.org")
+            .with_name("dRPC")
+            .with_ws_url("wss://optimism-sepolia.drpc.org")
+            .with_priority(51)
+            .with_chain_id(chain_id::OPTIMISM_SEPOLIA),
+        RpcEndpoint::new("https://sepolia.optimism.io")
+            .with_name("Optimism Official")
+            .with_priority(52)
+            .with_chain_id(chain_id::OPTIMISM_SEPOLIA),
+    ]
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1862,6 +2359,152 @@ mod tests {
         assert!(endpoints.len() >= 5, "Should have at least 5 endpoints, got {}", endpoints.len());
     }
 
+    #[test]
+    fn test_filter_endpoints_by_capability() {
+        let logs = |supported| EndpointCapabilities {
+            supports_eth_get_logs: Some(supported),
+            max_block_range: Some(1_000),
+            ..Default::default()
+        };
+        let endpoints = vec![
+            RpcEndpoint::new("https://logs.example.com").with_capabilities(logs(true)),
+            RpcEndpoint::new("https://nologs.example.com").with_capabilities(logs(false)),
+            RpcEndpoint::new("https://unknown.example.com"),
+        ];
+        let urls = |endpoints: Vec<RpcEndpoint>| -> Vec<String> {
+            endpoints.into_iter().map(|e| e.url).collect()
+        };
+
+        let filter = CapabilityFilter::new().with_eth_get_logs();
+        assert_eq!(
+            urls(filter_endpoints_by_capability(endpoints.clone(), filter)),
+            vec!["https://logs.example.com"]
+        );
+
+        // Unknown ranges pass unless strict
+        let filter = CapabilityFilter::new().with_min_block_range(500);
+        assert_eq!(filter_endpoints_by_capability(endpoints.clone(), filter).len(), 3);
+        assert_eq!(
+            filter_endpoints_by_capability(endpoints.clone(), filter.with_strict(true)).len(),
+            2
+        );
+        let filter = CapabilityFilter::new().with_min_block_range(5_000);
+        assert_eq!(
+            urls(filter_endpoints_by_capability(endpoints, filter)),
+            vec!["https://unknown.example.com"]
+        );
+
+        // Presets carry enough capability data to pick out log-capable endpoints
+        let ethereum = default_endpoints(chain_id::ETHEREUM);
+        let indexer = filter_endpoints_by_capability(
+            ethereum.clone(),
+            CapabilityFilter::new().with_eth_get_logs(),
+        );
+        assert!(!indexer.is_empty());
+        assert!(indexer.len() < ethereum.len());
+    }
+
+    #[test]
+    fn test_testnet_endpoints() {
+        let testnets = [
+            (sepolia_endpoints(), chain_id::SEPOLIA),
+            (arbitrum_sepolia_endpoints(), chain_id::ARBITRUM_SEPOLIA),
+            (base_sepolia_endpoints(), chain_id::BASE_SEPOLIA),
+            (optimism_sepolia_endpoints(), chain_id::OPTIMISM_SEPOLIA),
+        ];
+        for (endpoints, id) in &testnets {
+            assert_valid_endpoints(endpoints, *id);
+            assert!(endpoints.len() >= 3, "{} should have at least 3 endpoints", chain_name(*id));
+            assert!(
+                endpoints.iter().any(|e| e.ws_url.is_some()),
+                "{} should have a ws_url",
+                chain_name(*id)
+            );
+            assert_eq!(default_endpoints(*id).len(), endpoints.len());
+        }
+        assert_eq!(chain_name(chain_id::OPTIMISM_SEPOLIA), "Optimism Sepolia");
+    }
+
+    #[test]
+    fn test_from_env() {
+        // A chain ID no other test uses, so parallel tests never see these variables
+        let chain = 990_001;
+
+        // Nothing set: built-in defaults (none for this chain)
+        assert!(from_env(chain).is_empty());
+
+        std::env::set_var("RPC_ENDPOINT_0", "https://generic.example.com");
+        let endpoints = from_env(chain);
+        assert_eq!(endpoints.len(), 1);
+        assert_eq!(endpoints[0].url, "https://generic.example.com");
+        assert_eq!(endpoints[0].chain_id, chain);
+
+        // Chain-specific variables win; numbering stops at the first gap
+        std::env::set_var("RPC_ENDPOINT_990001_0", "https://alchemy.example.com/v2/key");
+        std::env::set_var("RPC_ENDPOINT_990001_1", "not a url");
+        std::env::set_var("RPC_ENDPOINT_990001_2", "https://infura.example.com/v3/key");
+        std::env::set_var("RPC_ENDPOINT_990001_4", "https://unreachable-index.example.com");
+        let endpoints = from_env(chain);
+        std::env::remove_var("RPC_ENDPOINT_0");
+        for i in [0, 1, 2, 4] {
+            std::env::remove_var(format!("RPC_ENDPOINT_990001_{}", i));
+        }
+
+        let urls: Vec<_> = endpoints.iter().map(|e| e.url.as_str()).collect();
+        assert_eq!(
+            urls,
+            vec!["https://alchemy.example.com/v2/key", "https://infura.example.com/v3/key"]
+        );
+        assert_eq!(endpoints[0].name, "RPC_ENDPOINT_990001_0");
+        assert_valid_endpoints(&endpoints, chain);
+
+        // Known chains fall back to their presets
+        assert_eq!(from_env(chain_id::LISK).len(), lisk_endpoints().len());
+    }
+
+    #[test]
+    fn test_merge_endpoint_lists() {
+        let a = vec![
+            RpcEndpoint::new("https://own.rpc").with_priority(1),
+            RpcEndpoint::new("https://shared.rpc").with_priority(5),
+            RpcEndpoint::new("https://backup.rpc").with_priority(20),
+        ];
+        let b = vec![
+            RpcEndpoint::new("https://shared.rpc").with_priority(2),
+            RpcEndpoint::new("https://public.rpc").with_priority(5),
+        ];
+
+        let merged = merge_endpoint_lists(a, b);
+        let urls: Vec<_> = merged.iter().map(|e| e.url.as_str()).collect();
+        // The shared URL keeps b's lower priority; a wins the tie at 5
+        let expected = vec![
+            "https://own.rpc",
+            "https://shared.rpc",
+            "https://public.rpc",
+            "https://backup.rpc",
+        ];
+        assert_eq!(urls, expected);
+        let priorities: Vec<_> = merged.iter().map(|e| e.priority).collect();
+        assert_eq!(priorities, vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_prepend_endpoints() {
+        let presets = lisk_endpoints();
+        let custom = vec![
+            RpcEndpoint::new("https://own.lisk.rpc"),
+            RpcEndpoint::new(presets[1].url.clone()).with_name("Duplicate"),
+        ];
+
+        let endpoints = prepend_endpoints(custom, chain_id::LISK);
+        assert_eq!(endpoints.len(), presets.len() + 1);
+        assert_eq!(endpoints[0].url, "https://own.lisk.rpc");
+        assert_eq!(endpoints[1].name, "Duplicate");
+        assert_eq!(endpoints[2].url, presets[0].url);
+        assert_valid_endpoints(&endpoints, chain_id::LISK);
+        assert!(endpoints.windows(2).all(|w| w[0].priority < w[1].priority));
+    }
+
     #[test]
     fn test_default_endpoints() {
         // Original chains
@@ -1907,6 +2550,18 @@ mod tests {
         assert!(default_endpoints(99999).is_empty()); // Unknown chain
     }
 
+    #[test]
+    fn test_starknet_endpoints() {
+        let endpoints = starknet_endpoints();
+        assert!(endpoints.len() >= 3);
+        assert_valid_endpoints(&endpoints, chain_id::STARKNET);
+        assert!(endpoints.iter().all(|e| e.capabilities.is_starknet));
+        assert_eq!(default_endpoints(chain_id::STARKNET), endpoints);
+        assert_eq!(chain_name(chain_id::STARKNET), "Starknet");
+        // Starknet is not an EVM chain
+        assert!(!all_chain_ids().contains(&chain_id::STARKNET));
+    }
+
     #[test]
     fn test_total_endpoint_count() {
         let total: usize = all_chain_ids()
@@ -2012,4 +2667,33 @@ mod tests {
             }
         }
     }
+
+    #[cfg(feature = "community-endpoints")]
+    #[tokio::test]
+    async fn test_fetch_community_endpoints_filters_by_chain() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        let list = vec![
+            RpcEndpoint::new("https://eth-b.example.com").with_priority(20).with_chain_id(1),
+            RpcEndpoint::new("https://arb.example.com").with_chain_id(chain_id::ARBITRUM_ONE),
+            RpcEndpoint::new("https://eth-a.example.com").with_priority(10).with_chain_id(1),
+        ];
+        Mock::given(method("GET"))
+            .and(path("/endpoints.json"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&list))
+            .mount(&server)
+            .await;
+        let url = format!("{}/endpoints.json", server.uri());
+        let timeout = std::time::Duration::from_secs(5);
+
+        let endpoints = fetch_community_endpoints(&url, 1, timeout).await.unwrap();
+        let urls: Vec<_> = endpoints.iter().map(|e| e.url.as_str()).collect();
+        assert_eq!(urls, ["https://eth-a.example.com", "https://eth-b.example.com"]);
+
+        let missing = format!("{}/missing.json", server.uri());
+        let result = fetch_community_endpoints(&missing, 1, timeout).await;
+        assert!(matches!(result, Err(RpcPoolError::TransportError(_))));
+    }
 }
@@ -0,0 +1,297 @@
+//! Capability probing for RPC endpoints.
+//!
+//! Library form of the checks run by the `rpc_evaluator` binary, so capability
+//! metadata can be measured at runtime instead of hard-coded in presets.
+
+use crate::endpoint::EndpointCapabilities;
+use crate::error::RpcPoolError;
+
+use alloy::rpc::client::RpcClient;
+use alloy::transports::http::reqwest;
+use serde_json::{json, Value};
+use std::future::Future;
+use std::time::Duration;
+use tracing::debug;
+
+/// Block span of the initial `eth_getLogs` support check.
+const LOG_PROBE_RANGE: u64 = 10;
+
+/// Batch sizes tried in order; passing the last one counts as unlimited.
+const BATCH_SIZES: [usize; 3] = [10, 100, 500];
+
+/// `eth_getLogs` block ranges tried in order; passing the last one counts as unlimited.
+const BLOCK_RANGES: [u64; 6] = [100, 1_000, 5_000, 10_000, 50_000, 100_000];
+
+/// Dummy account called and overridden by the state override check.
+const STATE_OVERRIDE_ACCOUNT: &str = "0x00000000000000000000000000000000000000ff";
+
+impl EndpointCapabilities {
+    /// Measure an HTTP endpoint's capabilities.
+    ///
+    /// Checks reachability with `eth_blockNumber`, log support with a
+    /// 10-block `eth_getLogs`, the largest accepted batch of 10/100/500
+    /// `eth_blockNumber` calls, the widest accepted `eth_getLogs` range, and
+    /// state override support with an `eth_call` overriding a dummy account's
+    /// balance. `timeout` applies to each call. Sizes and ranges that pass
+    /// every step are reported as `Some(0)` (unlimited).
+    ///
+    /// Fails only if the endpoint is unreachable; WebSocket support, debug
+    /// tracing and rate limits are not probed.
+    pub async fn probe(url: &str, timeout: Duration) -> Result<Self, RpcPoolError> {
+        Self::probe_with_client(reqwest::Client::new(), url, timeout).await
+    }
+
+    /// [`probe`](Self::probe) through a preconfigured HTTP client.
+    pub(crate) async fn probe_with_client(
+        http: reqwest::Client,
+        url: &str,
+        timeout: Duration,
+    ) -> Result<Self, RpcPoolError> {
+        let client = RpcClient::new_http_with_client(http, url.parse()?);
+
+        let block = with_timeout(timeout, async {
+            let block: alloy::primitives::U64 = client
+                .request_noparams("eth_blockNumber")
+                .await
+                .map_err(|e| RpcPoolError::TransportError(e.to_string()))?;
+            Ok(block.to::<u64>())
+        })
+        .await?;
+
+        let get_logs = |range: u64| {
+            let params = json!([{
+                "fromBlock": format!("0x{:x}", block.saturating_sub(range)),
+                "toBlock": format!("0x{:x}", block),
+            }]);
+            let client = &client;
+            with_timeout(timeout, async move {
+                client
+                    .request::<_, Value>("eth_getLogs", params)
+                    .await
+                    .map(|_| ())
+                    .map_err(|e| RpcPoolError::TransportError(e.to_string()))
+            })
+        };
+
+        let supports_logs = get_logs(LOG_PROBE_RANGE).await.is_ok();
+
+        let mut max_batch = 1;
+        for size in BATCH_SIZES {
+            if with_timeout(timeout, batch_block_numbers(&client, size))
+                .await
+                .is_err()
+            {
+                break;
+            }
+            max_batch = size as u32;
+        }
+        let max_batch_size = if max_batch as usize == BATCH_SIZES[BATCH_SIZES.len() - 1] {
+            0
+        } else {
+            max_batch
+        };
+
+        let max_block_range = if supports_logs {
+            let mut max_range = LOG_PROBE_RANGE;
+            for range in BLOCK_RANGES {
+                if get_logs(range).await.is_err() {
+                    break;
+                }
+                max_range = range;
+            }
+            Some(if max_range == BLOCK_RANGES[BLOCK_RANGES.len() - 1] {
+                0
+            } else {
+                max_range
+            })
+        } else {
+            None
+        };
+
+        let supports_state_override = with_timeout(timeout, async {
+            let params = json!([
+                { "to": STATE_OVERRIDE_ACCOUNT, "data": "0x" },
+                "latest",
+                { STATE_OVERRIDE_ACCOUNT: { "balance": "0x1" } },
+            ]);
+            client
+                .request::<_, Value>("eth_call", params)
+                .await
+                .map_err(|e| RpcPoolError::TransportError(e.to_string()))
+        })
+        .await
+        .is_ok();
+
+        let capabilities = Self {
+            supports_eth_get_logs: Some(supports_logs),
+            max_batch_size: Some(max_batch_size),
+            max_block_range,
+            supports_eth_call_state_override: Some(supports_state_override),
+            ..Default::default()
+        };
+        debug!(
+            url,
+            grade = %capabilities.grade(),
+            supports_eth_get_logs = supports_logs,
+            max_batch_size,
+            max_block_range = ?capabilities.max_block_range,
+            supports_eth_call_state_override = supports_state_override,
+            "Probed endpoint capabilities"
+        );
+        Ok(capabilities)
+    }
+}
+
+/// Send `size` `eth_blockNumber` calls in one batch; fails if any call fails.
+async fn batch_block_numbers(client: &RpcClient, size: usize) -> Result<(), RpcPoolError> {
+    let transport_error =
+        |e: alloy::transports::TransportError| RpcPoolError::TransportError(e.to_string());
+
+    let mut batch = client.new_batch();
+    let waiters = (0..size)
+        .map(|_| batch.add_call::<_, Value>("eth_blockNumber", &()))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(transport_error)?;
+    batch.send().await.map_err(transport_error)?;
+
+    for waiter in waiters {
+        waiter.await.map_err(transport_error)?;
+    }
+    Ok(())
+}
+
+/// Run a probe step, mapping an elapsed `timeout` to [`RpcPoolError::Timeout`].
+async fn with_timeout<T>(
+    timeout: Duration,
+    step: impl Future<Output = Result<T, RpcPoolError>>,
+) -> Result<T, RpcPoolError> {
+    tokio::time::timeout(timeout, step)
+        .await
+        .map_err(|_| RpcPoolError::Timeout(timeout.as_millis() as u64))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HEAD: u64 = 20_000_000;
+
+    /// Mock node that accepts batches up to `max_batch` calls and `eth_getLogs`
+    /// ranges up to `max_range` blocks (`None` = no log support), answering
+    /// `eth_call` only if `state_override` is set.
+    struct CapabilityResponder {
+        max_batch: usize,
+        max_range: Option<u64>,
+        state_override: bool,
+    }
+
+    impl CapabilityResponder {
+        fn answer(&self, call: &Value) -> Value {
+            let result = match call["method"].as_str() {
+                Some("eth_blockNumber") => Ok(json!(format!("0x{:x}", HEAD))),
+                Some("eth_getLogs") => {
+                    let block = |key: &str| {
+                        let hex = call["params"][0][key].as_str().unwrap();
+                        u64::from_str_radix(hex.trim_start_matches("0x"), 16).unwrap()
+                    };
+                    match self.max_range {
+                        Some(max) if block("toBlock") - block("fromBlock") <= max => Ok(json!([])),
+                        _ => Err("block range too large"),
+                    }
+                }
+                Some("eth_call") if self.state_override => Ok(json!("0x")),
+                _ => Err("method not found"),
+            };
+            match result {
+                Ok(result) => json!({ "jsonrpc": "2.0", "id": call["id"], "result": result }),
+                Err(message) => json!({
+                    "jsonrpc": "2.0",
+                    "id": call["id"],
+                    "error": { "code": -32000, "message": message },
+                }),
+            }
+        }
+    }
+
+    impl wiremock::Respond for CapabilityResponder {
+        fn respond(&self, request: &wiremock::Request) -> wiremock::ResponseTemplate {
+            let body: Value = serde_json::from_slice(&request.body).unwrap();
+            let response = match body.as_array() {
+                Some(calls) if calls.len() > self.max_batch => json!({
+                    "jsonrpc": "2.0",
+                    "id": null,
+                    "error": { "code": -32600, "message": "batch too large" },
+                }),
+                Some(calls) => Value::Array(calls.iter().map(|c| self.answer(c)).collect()),
+                None => self.answer(&body),
+            };
+            wiremock::ResponseTemplate::new(200).set_body_json(response)
+        }
+    }
+
+    async fn mock_node(
+        max_batch: usize,
+        max_range: Option<u64>,
+        state_override: bool,
+    ) -> wiremock::MockServer {
+        let server = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::method("POST"))
+            .respond_with(CapabilityResponder {
+                max_batch,
+                max_range,
+                state_override,
+            })
+            .mount(&server)
+            .await;
+        server
+    }
+
+    const TIMEOUT: Duration = Duration::from_secs(5);
+
+    #[tokio::test]
+    async fn test_probe_limited_endpoint() {
+        let server = mock_node(100, Some(5_000), true).await;
+        let caps = EndpointCapabilities::probe(&server.uri(), TIMEOUT)
+            .await
+            .unwrap();
+
+        assert_eq!(caps.supports_eth_get_logs, Some(true));
+        assert_eq!(caps.max_batch_size, Some(100));
+        assert_eq!(caps.max_block_range, Some(5_000));
+        assert_eq!(caps.supports_eth_call_state_override, Some(true));
+        assert!(!caps.supports_websocket);
+    }
+
+    #[tokio::test]
+    async fn test_probe_unlimited_endpoint() {
+        let server = mock_node(1_000, Some(u64::MAX), true).await;
+        let caps = EndpointCapabilities::probe(&server.uri(), TIMEOUT)
+            .await
+            .unwrap();
+
+        assert_eq!(caps.max_batch_size, Some(0));
+        assert_eq!(caps.max_block_range, Some(0));
+    }
+
+    #[tokio::test]
+    async fn test_probe_without_logs_or_batching() {
+        let server = mock_node(1, None, false).await;
+        let caps = EndpointCapabilities::probe(&server.uri(), TIMEOUT)
+            .await
+            .unwrap();
+
+        assert_eq!(caps.supports_eth_get_logs, Some(false));
+        assert_eq!(caps.max_batch_size, Some(1));
+        assert_eq!(caps.max_block_range, None);
+        assert_eq!(caps.supports_eth_call_state_override, Some(false));
+    }
+
+    #[tokio::test]
+    async fn test_probe_unreachable_endpoint() {
+        let result = EndpointCapabilities::probe("http://127.0.0.1:1", TIMEOUT).await;
+        assert!(matches!(result, Err(RpcPoolError::TransportError(_))));
+
+        let result = EndpointCapabilities::probe("not a url", TIMEOUT).await;
+        assert!(matches!(result, Err(RpcPoolError::InvalidUrl(_))));
+    }
+}
@@ -0,0 +1,70 @@
+//! Token-bucket rate limiting for individual endpoints.
+
+use std::time::{Duration, Instant};
+
+/// Token bucket allowing `rps` requests per second with a burst of `rps`.
+///
+/// Tokens are reserved rather than polled: a caller that finds the bucket
+/// empty takes a token on credit and is told how long to wait, so concurrent
+/// callers queue up in order instead of racing for the next refill.
+#[derive(Debug)]
+pub(crate) struct TokenBucket {
+    capacity: f64,
+    tokens: f64,
+    refill_per_sec: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    /// Create a full bucket for `rps` requests per second.
+    pub(crate) fn new(rps: u32) -> Self {
+        let rps = f64::from(rps.max(1));
+        Self {
+            capacity: rps,
+            tokens: rps,
+            refill_per_sec: rps,
+            last_refill: Instant::now(),
+        }
+    }
+
+    /// Take one token, returning how long the caller must wait before using it.
+    pub(crate) fn reserve(&mut self) -> Duration {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.last_refill).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.refill_per_sec).min(self.capacity);
+        self.last_refill = now;
+
+        self.tokens -= 1.0;
+        if self.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-self.tokens / self.refill_per_sec)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_burst_then_wait() {
+        let mut bucket = TokenBucket::new(2);
+
+        assert_eq!(bucket.reserve(), Duration::ZERO);
+        assert_eq!(bucket.reserve(), Duration::ZERO);
+
+        // Third and fourth requests queue behind each other
+        let third = bucket.reserve();
+        let fourth = bucket.reserve();
+        assert!(third > Duration::from_millis(400) && third <= Duration::from_millis(500));
+        assert!(fourth > Duration::from_millis(900) && fourth <= Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_zero_rps_treated_as_one() {
+        let mut bucket = TokenBucket::new(0);
+        assert_eq!(bucket.reserve(), Duration::ZERO);
+        assert!(bucket.reserve() > Duration::ZERO);
+    }
+}
@@ -0,0 +1,140 @@
+//! [`tower::Service`] integration.
+//!
+//! `Arc<RpcPool>` is a service from [`RpcRequest`] to the JSON result, so it
+//! can be wrapped in tower's `RateLimit`, `Timeout`, `Retry` or `Buffer`
+//! layers. Each call goes through [`RpcPool::execute_with_client`], keeping
+//! failover, endpoint headers and the proxy.
+//!
+//! ```rust,no_run
+//! use std::sync::Arc;
+//! use tower::Service;
+//! use web3_rpc_pool::{presets, RpcPool, RpcPoolConfig, RpcRequest};
+//!
+//! # async fn example() -> Result<(), web3_rpc_pool::RpcPoolError> {
+//! let mut pool = Arc::new(RpcPool::new(
+//!     RpcPoolConfig::new().with_endpoints(presets::ethereum_endpoints()),
+//! )?);
+//! std::future::poll_fn(|cx| pool.poll_ready(cx)).await?;
+//! let block = pool.call(RpcRequest::new("eth_blockNumber", serde_json::json!([]))).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::RpcPoolError;
+use crate::pool::RpcPool;
+
+use alloy::rpc::client::RpcClient;
+use futures_util::future::BoxFuture;
+use serde_json::Value;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+/// A JSON-RPC call sent through the pool's [`tower::Service`] implementation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RpcRequest {
+    /// JSON-RPC method name.
+    pub method: String,
+    /// Call parameters, usually an array.
+    pub params: Value,
+}
+
+impl RpcRequest {
+    /// Create a request for `method` with `params`.
+    pub fn new(method: impl Into<String>, params: Value) -> Self {
+        Self {
+            method: method.into(),
+            params,
+        }
+    }
+}
+
+impl tower::Service<RpcRequest> for Arc<RpcPool> {
+    type Response = Value;
+    type Error = RpcPoolError;
+    type Future = BoxFuture<'static, Result<Value, RpcPoolError>>;
+
+    /// Always ready until the pool is shut down, then fails with
+    /// [`RpcPoolError::PoolShutdown`].
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), RpcPoolError>> {
+        if self.is_shutdown() {
+            Poll::Ready(Err(RpcPoolError::PoolShutdown))
+        } else {
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    fn call(&mut self, request: RpcRequest) -> Self::Future {
+        let pool = Arc::clone(self);
+        Box::pin(async move {
+            pool.execute_with_client(|client, url| {
+                let request = request.clone();
+                async move {
+                    RpcClient::new_http_with_client(client, url)
+                        .request::<_, Value>(request.method, request.params)
+                        .await
+                }
+            })
+            .await
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::endpoint::RpcEndpoint;
+    use crate::pool::RpcPoolConfig;
+    use serde_json::json;
+    use std::future::poll_fn;
+    use tower::Service;
+
+    async fn mock_endpoint(result: Value) -> wiremock::MockServer {
+        let server = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::method("POST"))
+            .respond_with(move |request: &wiremock::Request| {
+                let body: Value = serde_json::from_slice(&request.body).unwrap();
+                wiremock::ResponseTemplate::new(200).set_body_json(json!({
+                    "jsonrpc": "2.0",
+                    "id": body["id"],
+                    "result": result,
+                }))
+            })
+            .mount(&server)
+            .await;
+        server
+    }
+
+    #[tokio::test]
+    async fn test_call_fails_over() {
+        let server = mock_endpoint(json!("0x10")).await;
+        let mut pool = Arc::new(
+            RpcPool::new(RpcPoolConfig::new().with_endpoints(vec![
+                RpcEndpoint::new("http://127.0.0.1:1").with_priority(0),
+                RpcEndpoint::new(server.uri()).with_priority(1),
+            ]))
+            .unwrap(),
+        );
+
+        poll_fn(|cx| pool.poll_ready(cx)).await.unwrap();
+        let result = pool
+            .call(RpcRequest::new("eth_blockNumber", json!([])))
+            .await
+            .unwrap();
+        assert_eq!(result, json!("0x10"));
+        assert_eq!(pool.metrics().failovers, 1);
+    }
+
+    #[tokio::test]
+    async fn test_not_ready_after_shutdown() {
+        let mut pool = Arc::new(
+            RpcPool::new(
+                RpcPoolConfig::new().with_endpoints(vec![RpcEndpoint::new("http://127.0.0.1:1")]),
+            )
+            .unwrap(),
+        );
+        pool.shutdown().await;
+
+        let ready = poll_fn(|cx| pool.poll_ready(cx)).await;
+        assert!(matches!(ready, Err(RpcPoolError::PoolShutdown)));
+    }
+}
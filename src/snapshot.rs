@@ -0,0 +1,123 @@
+//! Serializable snapshots of pool runtime state.
+//!
+//! A [`PoolSnapshot`] captures endpoint health and statistics so a restarted
+//! process can resume with warm stats via [`RpcPool::restore_from_snapshot`]
+//! instead of rediscovering failing endpoints.
+//!
+//! [`RpcPool::restore_from_snapshot`]: crate::RpcPool::restore_from_snapshot
+
+use crate::endpoint::{EndpointStats, LATENCY_BUCKETS_MS};
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+/// Runtime state of an [`RpcPool`](crate::RpcPool).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PoolSnapshot {
+    /// Total number of requests made through the pool.
+    pub total_requests: u64,
+
+    /// Number of times failover occurred.
+    pub failovers: u64,
+
+    /// State of each endpoint.
+    pub endpoints: Vec<EndpointSnapshot>,
+}
+
+/// Serializable form of [`EndpointStats`].
+///
+/// Timestamps are stored as ages relative to when the snapshot was taken.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EndpointSnapshot {
+    pub url: String,
+    pub name: String,
+    pub total_requests: u64,
+    pub successful_requests: u64,
+    pub failed_requests: u64,
+    pub avg_latency_ms: f64,
+    pub last_latency_ms: u64,
+    pub latency_histogram: [u64; LATENCY_BUCKETS_MS.len()],
+    pub latency_sum_ms: u64,
+    pub last_error: Option<String>,
+    /// Time between the most recent error and the snapshot.
+    pub last_error_age: Option<Duration>,
+    pub is_healthy: bool,
+    pub consecutive_errors: u32,
+    pub recovery_attempts: u32,
+    pub last_known_block: Option<u64>,
+}
+
+impl EndpointSnapshot {
+    /// Capture `stats`, measuring timestamps relative to `now`.
+    pub(crate) fn capture(stats: &EndpointStats, now: Instant) -> Self {
+        Self {
+            url: stats.url.clone(),
+            name: stats.name.clone(),
+            total_requests: stats.total_requests,
+            successful_requests: stats.successful_requests,
+            failed_requests: stats.failed_requests,
+            avg_latency_ms: stats.avg_latency_ms,
+            last_latency_ms: stats.last_latency_ms,
+            latency_histogram: stats.latency_histogram,
+            latency_sum_ms: stats.latency_sum_ms,
+            last_error: stats.last_error.clone(),
+            last_error_age: stats
+                .last_error_time
+                .map(|t| now.saturating_duration_since(t)),
+            is_healthy: stats.is_healthy,
+            consecutive_errors: stats.consecutive_errors,
+            recovery_attempts: stats.recovery_attempts,
+            last_known_block: stats.last_known_block,
+        }
+    }
+
+    /// Overwrite the runtime fields of `stats`, placing timestamps relative to `now`.
+    ///
+    /// The endpoint name is left as configured. An error older than the
+    /// monotonic clock can represent is dropped, which allows an immediate retry.
+    pub(crate) fn apply(&self, stats: &mut EndpointStats, now: Instant) {
+        stats.total_requests = self.total_requests;
+        stats.successful_requests = self.successful_requests;
+        stats.failed_requests = self.failed_requests;
+        stats.avg_latency_ms = self.avg_latency_ms;
+        stats.last_latency_ms = self.last_latency_ms;
+        stats.latency_histogram = self.latency_histogram;
+        stats.latency_sum_ms = self.latency_sum_ms;
+        stats.last_error = self.last_error.clone();
+        stats.last_error_time = self.last_error_age.and_then(|age| now.checked_sub(age));
+        stats.set_healthy(self.is_healthy);
+        stats.consecutive_errors = self.consecutive_errors;
+        stats.recovery_attempts = self.recovery_attempts;
+        stats.last_known_block = self.last_known_block;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::endpoint::RpcEndpoint;
+
+    #[test]
+    fn test_capture_and_apply_round_trip() {
+        let endpoint = RpcEndpoint::new("https://rpc.example.com");
+        let mut stats = EndpointStats::new(&endpoint);
+        stats.record_success(40);
+        stats.record_failure("boom".to_string(), 1);
+        stats.increment_recovery_attempts();
+        stats.last_known_block = Some(100);
+
+        let now = Instant::now();
+        let snapshot = EndpointSnapshot::capture(&stats, now);
+        let json = serde_json::to_string(&snapshot).unwrap();
+        let snapshot: EndpointSnapshot = serde_json::from_str(&json).unwrap();
+
+        let mut restored = EndpointStats::new(&endpoint);
+        snapshot.apply(&mut restored, now);
+        assert_eq!(restored.total_requests, 2);
+        assert_eq!(restored.latency_histogram, stats.latency_histogram);
+        assert_eq!(restored.last_error.as_deref(), Some("boom"));
+        assert_eq!(restored.last_error_time, stats.last_error_time);
+        assert!(!restored.is_healthy);
+        assert_eq!(restored.recovery_attempts, 1);
+        assert_eq!(restored.last_known_block, Some(100));
+    }
+}
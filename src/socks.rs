@@ -0,0 +1,121 @@
+//! WebSocket connections tunnelled through a SOCKS5 proxy.
+//!
+//! alloy's `WsConnect` always dials the endpoint directly. [`Socks5WsConnect`]
+//! opens the TCP connection through the proxy with `tokio-socks` instead, runs
+//! the TLS and WebSocket handshakes over the tunnel, and drives the connection
+//! with a backend task much like alloy's. Hostnames are resolved by the proxy,
+//! so DNS lookups do not leak either.
+
+use alloy::pubsub::{ConnectionHandle, ConnectionInterface, PubSubConnect};
+use alloy::transports::{TransportErrorKind, TransportResult};
+use futures_util::{SinkExt, StreamExt};
+use std::net::SocketAddr;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio_socks::tcp::Socks5Stream;
+use tokio_socks::TargetAddr;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::WebSocketStream;
+use tracing::{debug, warn};
+
+/// Connection details for a WebSocket endpoint reached through a SOCKS5 proxy.
+#[derive(Clone, Debug)]
+pub(crate) struct Socks5WsConnect {
+    url: String,
+    proxy: SocketAddr,
+}
+
+impl Socks5WsConnect {
+    /// Connect to `url` through the SOCKS5 proxy at `proxy`.
+    pub(crate) fn new(url: impl Into<String>, proxy: SocketAddr) -> Self {
+        Self {
+            url: url.into(),
+            proxy,
+        }
+    }
+
+    /// The endpoint address as sent to the proxy, leaving hostnames unresolved.
+    fn target(&self) -> TransportResult<TargetAddr<'static>> {
+        let url = url::Url::parse(&self.url).map_err(TransportErrorKind::custom)?;
+        let port = url
+            .port_or_known_default()
+            .ok_or_else(|| TransportErrorKind::custom_str("WebSocket URL has no port"))?;
+        match url.host() {
+            Some(url::Host::Domain(domain)) => {
+                Ok(TargetAddr::Domain(domain.to_string().into(), port))
+            }
+            Some(url::Host::Ipv4(ip)) => Ok(TargetAddr::Ip((ip, port).into())),
+            Some(url::Host::Ipv6(ip)) => Ok(TargetAddr::Ip((ip, port).into())),
+            None => Err(TransportErrorKind::custom_str("WebSocket URL has no host")),
+        }
+    }
+}
+
+impl PubSubConnect for Socks5WsConnect {
+    fn is_local(&self) -> bool {
+        alloy::transports::utils::guess_local_url(&self.url)
+    }
+
+    async fn connect(&self) -> TransportResult<ConnectionHandle> {
+        let stream = Socks5Stream::connect(self.proxy, self.target()?)
+            .await
+            .map_err(TransportErrorKind::custom)?;
+        let (socket, _) =
+            tokio_tungstenite::client_async_tls_with_config(self.url.as_str(), stream, None, None)
+                .await
+                .map_err(TransportErrorKind::custom)?;
+        debug!(url = %self.url, proxy = %self.proxy, "WebSocket connected through SOCKS5 proxy");
+
+        let (handle, interface) = ConnectionHandle::new();
+        tokio::spawn(run_backend(socket, interface));
+        Ok(handle)
+    }
+}
+
+/// Relay requests from the frontend to the socket and responses back until either side closes.
+async fn run_backend<S>(mut socket: WebSocketStream<S>, mut interface: ConnectionInterface)
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    loop {
+        tokio::select! {
+            biased;
+
+            request = interface.recv_from_frontend() => {
+                let Some(request) = request else {
+                    // The frontend shut down; nothing to report
+                    let _ = socket.close(None).await;
+                    return;
+                };
+                if let Err(e) = socket.send(Message::Text(request.get().to_owned().into())).await {
+                    warn!(error = %e, "SOCKS5 WebSocket send failed");
+                    break;
+                }
+            }
+
+            message = socket.next() => match message {
+                Some(Ok(Message::Text(text))) => match serde_json::from_str(&text) {
+                    Ok(item) => {
+                        if interface.send_to_frontend(item).is_err() {
+                            break;
+                        }
+                    }
+                    Err(e) => {
+                        warn!(error = %e, "Invalid message on SOCKS5 WebSocket");
+                        break;
+                    }
+                },
+                Some(Ok(Message::Close(_))) | None => {
+                    debug!("SOCKS5 WebSocket closed by server");
+                    break;
+                }
+                // Pings are answered by tungstenite
+                Some(Ok(_)) => {}
+                Some(Err(e)) => {
+                    warn!(error = %e, "SOCKS5 WebSocket connection error");
+                    break;
+                }
+            },
+        }
+    }
+    interface.close_with_error();
+}
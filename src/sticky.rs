@@ -0,0 +1,76 @@
+//! Key-to-endpoint bindings for sticky-session routing.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// Maps hashed session keys to the endpoint URL they are pinned to.
+///
+/// A binding expires once it has gone unused for longer than the TTL.
+#[derive(Debug)]
+pub(crate) struct StickySessionMap {
+    ttl: Duration,
+    bindings: HashMap<u64, (String, Instant)>,
+}
+
+impl StickySessionMap {
+    /// Create an empty map whose bindings live for `ttl` after their last use.
+    pub(crate) fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            bindings: HashMap::new(),
+        }
+    }
+
+    /// Get the URL bound to `key`, if the binding has not expired.
+    pub(crate) fn get(&self, key: u64) -> Option<&str> {
+        self.bindings
+            .get(&key)
+            .filter(|(_, last_used)| last_used.elapsed() < self.ttl)
+            .map(|(url, _)| url.as_str())
+    }
+
+    /// Bind `key` to `url`, refreshing its expiry and dropping expired bindings.
+    pub(crate) fn bind(&mut self, key: u64, url: String) {
+        let ttl = self.ttl;
+        self.bindings
+            .retain(|_, (_, last_used)| last_used.elapsed() < ttl);
+        self.bindings.insert(key, (url, Instant::now()));
+    }
+
+    /// Number of live (unexpired) bindings.
+    pub(crate) fn len(&self) -> usize {
+        self.bindings
+            .values()
+            .filter(|(_, last_used)| last_used.elapsed() < self.ttl)
+            .count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bind_and_get() {
+        let mut map = StickySessionMap::new(Duration::from_secs(60));
+        assert_eq!(map.get(1), None);
+
+        map.bind(1, "https://rpc1.example.com".to_string());
+        map.bind(2, "https://rpc2.example.com".to_string());
+        assert_eq!(map.get(1), Some("https://rpc1.example.com"));
+        assert_eq!(map.len(), 2);
+
+        map.bind(1, "https://rpc2.example.com".to_string());
+        assert_eq!(map.get(1), Some("https://rpc2.example.com"));
+    }
+
+    #[test]
+    fn test_binding_expires() {
+        let mut map = StickySessionMap::new(Duration::from_millis(20));
+        map.bind(1, "https://rpc1.example.com".to_string());
+
+        std::thread::sleep(Duration::from_millis(30));
+        assert_eq!(map.get(1), None);
+        assert_eq!(map.len(), 0);
+    }
+}
@@ -0,0 +1,256 @@
+//! Adaptive strategy - latency-based when the pool is healthy, failover when degraded.
+
+use super::{
+    FailoverStrategy, LatencyBasedStrategy, SelectionContext, SelectionStrategy, ThrottleRecorder,
+};
+use crate::endpoint::{EndpointStats, RpcEndpoint};
+use std::collections::{HashMap, HashSet};
+use tracing::info;
+
+/// Selection mode currently used by an [`AdaptiveStrategy`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AdaptiveMode {
+    /// Pool is healthy; the fastest endpoint is selected.
+    LatencyBased,
+    /// Pool is degraded; endpoints are selected in priority order.
+    Failover,
+}
+
+/// Adaptive selection strategy.
+///
+/// Selects by latency while enough of the pool is healthy, and falls back to
+/// failover once the percentage of healthy endpoints drops below `threshold`.
+/// Endpoints without stats count as healthy. Either mode's strategy can be
+/// replaced, e.g. by a [`ThrottleAwareStrategy`](super::ThrottleAwareStrategy).
+///
+/// Best for: Pools that should chase latency in normal operation but prefer
+/// the known-good primary when many endpoints are failing.
+pub struct AdaptiveStrategy {
+    /// Health percentage (0-100) below which failover mode is used.
+    threshold: f64,
+    mode: AdaptiveMode,
+    latency: Box<dyn SelectionStrategy>,
+    failover: Box<dyn SelectionStrategy>,
+}
+
+impl AdaptiveStrategy {
+    /// Create an adaptive strategy that switches to failover when fewer than
+    /// `threshold` percent (0-100) of the endpoints are healthy.
+    pub fn new(threshold: f64) -> Self {
+        Self {
+            threshold: threshold.clamp(0.0, 100.0),
+            mode: AdaptiveMode::LatencyBased,
+            latency: Box::new(LatencyBasedStrategy::new()),
+            failover: Box::new(FailoverStrategy),
+        }
+    }
+
+    /// Builder: select with `strategy` instead of by latency while the pool is healthy.
+    pub fn with_latency_strategy(mut self, strategy: Box<dyn SelectionStrategy>) -> Self {
+        self.latency = strategy;
+        self
+    }
+
+    /// Builder: select with `strategy` instead of by priority once the pool is degraded.
+    pub fn with_failover_strategy(mut self, strategy: Box<dyn SelectionStrategy>) -> Self {
+        self.failover = strategy;
+        self
+    }
+
+    /// Mode used by the most recent selection.
+    pub fn mode(&self) -> AdaptiveMode {
+        self.mode
+    }
+
+    /// Health percentage below which failover mode is used.
+    pub fn threshold(&self) -> f64 {
+        self.threshold
+    }
+
+    /// Pick the mode for the current pool health and log any change.
+    fn update_mode(&mut self, endpoints: &[RpcEndpoint], stats: &HashMap<String, EndpointStats>) {
+        if endpoints.is_empty() {
+            return;
+        }
+
+        let healthy = endpoints
+            .iter()
+            .filter(|e| stats.get(&e.url).map(|s| s.is_healthy).unwrap_or(true))
+            .count();
+        let health_percentage = (healthy as f64 / endpoints.len() as f64) * 100.0;
+
+        let mode = if health_percentage < self.threshold {
+            AdaptiveMode::Failover
+        } else {
+            AdaptiveMode::LatencyBased
+        };
+
+        if mode != self.mode {
+            info!(
+                from = ?self.mode,
+                to = ?mode,
+                healthy,
+                total = endpoints.len(),
+                threshold = self.threshold,
+                "Adaptive strategy switching mode"
+            );
+            self.mode = mode;
+        }
+    }
+}
+
+impl SelectionStrategy for AdaptiveStrategy {
+    fn select<'a>(
+        &mut self,
+        endpoints: &'a [RpcEndpoint],
+        stats: &HashMap<String, EndpointStats>,
+        exclude: &HashSet<String>,
+        ctx: &SelectionContext,
+    ) -> Option<&'a RpcEndpoint> {
+        self.update_mode(endpoints, stats);
+
+        match self.mode {
+            AdaptiveMode::LatencyBased => self.latency.select(endpoints, stats, exclude, ctx),
+            AdaptiveMode::Failover => self.failover.select(endpoints, stats, exclude, ctx),
+        }
+    }
+
+    fn peek<'a>(
+        &mut self,
+        endpoints: &'a [RpcEndpoint],
+        stats: &HashMap<String, EndpointStats>,
+        exclude: &HashSet<String>,
+        ctx: &SelectionContext,
+    ) -> Option<&'a RpcEndpoint> {
+        self.update_mode(endpoints, stats);
+
+        match self.mode {
+            AdaptiveMode::LatencyBased => self.latency.peek(endpoints, stats, exclude, ctx),
+            AdaptiveMode::Failover => self.failover.peek(endpoints, stats, exclude, ctx),
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "adaptive"
+    }
+
+    fn on_success(&mut self, url: &str, latency_ms: u64) {
+        self.latency.on_success(url, latency_ms);
+        self.failover.on_success(url, latency_ms);
+    }
+
+    fn on_failure(&mut self, url: &str, error: &str) {
+        self.latency.on_failure(url, error);
+        self.failover.on_failure(url, error);
+    }
+
+    fn as_throttle_recorder(&mut self) -> Option<&mut dyn ThrottleRecorder> {
+        match self.latency.as_throttle_recorder() {
+            Some(recorder) => Some(recorder),
+            None => self.failover.as_throttle_recorder(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::strategies::ThrottleAwareStrategy;
+    use std::time::Duration;
+
+    fn create_test_endpoints() -> Vec<RpcEndpoint> {
+        vec![
+            RpcEndpoint::new("https://primary.rpc").with_priority(10),
+            RpcEndpoint::new("https://secondary.rpc").with_priority(20),
+            RpcEndpoint::new("https://tertiary.rpc").with_priority(30),
+            RpcEndpoint::new("https://quaternary.rpc").with_priority(40),
+        ]
+    }
+
+    /// Stats where the primary is slowest, so the two modes disagree.
+    fn create_stats(endpoints: &[RpcEndpoint]) -> HashMap<String, EndpointStats> {
+        let latencies = [500.0, 200.0, 100.0, 50.0];
+        endpoints
+            .iter()
+            .zip(latencies)
+            .map(|(e, latency)| {
+                let mut stats = EndpointStats::new(e);
+                stats.avg_latency_ms = latency;
+                (e.url.clone(), stats)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_latency_mode_when_healthy() {
+        let mut strategy = AdaptiveStrategy::new(50.0);
+        let endpoints = create_test_endpoints();
+        let stats = create_stats(&endpoints);
+
+        let selected = strategy.select(
+            &endpoints,
+            &stats,
+            &HashSet::new(),
+            &SelectionContext::default(),
+        );
+        assert_eq!(selected.unwrap().url, "https://quaternary.rpc");
+        assert_eq!(strategy.mode(), AdaptiveMode::LatencyBased);
+    }
+
+    #[test]
+    fn test_switches_modes_at_threshold() {
+        let mut strategy = AdaptiveStrategy::new(75.0);
+        let endpoints = create_test_endpoints();
+        let mut stats = create_stats(&endpoints);
+        let exclude = HashSet::new();
+
+        // 3 of 4 healthy is exactly 75%: still latency-based
+        stats.get_mut("https://tertiary.rpc").unwrap().is_healthy = false;
+        let selected = strategy.select(&endpoints, &stats, &exclude, &SelectionContext::default());
+        assert_eq!(selected.unwrap().url, "https://quaternary.rpc");
+        assert_eq!(strategy.mode(), AdaptiveMode::LatencyBased);
+
+        // 2 of 4 healthy drops below the threshold: failover to the primary
+        stats.get_mut("https://secondary.rpc").unwrap().is_healthy = false;
+        let selected = strategy.select(&endpoints, &stats, &exclude, &SelectionContext::default());
+        assert_eq!(selected.unwrap().url, "https://primary.rpc");
+        assert_eq!(strategy.mode(), AdaptiveMode::Failover);
+
+        // Recovery switches back
+        for s in stats.values_mut() {
+            s.is_healthy = true;
+        }
+        let selected = strategy.select(&endpoints, &stats, &exclude, &SelectionContext::default());
+        assert_eq!(selected.unwrap().url, "https://quaternary.rpc");
+        assert_eq!(strategy.mode(), AdaptiveMode::LatencyBased);
+    }
+
+    #[test]
+    fn test_forwards_throttle_recorder() {
+        let mut strategy = AdaptiveStrategy::new(50.0)
+            .with_latency_strategy(Box::new(ThrottleAwareStrategy::new()));
+        let endpoints = create_test_endpoints();
+        let stats = create_stats(&endpoints);
+
+        strategy
+            .as_throttle_recorder()
+            .expect("inner throttle-aware strategy")
+            .record_throttle("https://primary.rpc", Duration::from_secs(60));
+
+        let selected = strategy.select(
+            &endpoints,
+            &stats,
+            &HashSet::new(),
+            &SelectionContext::default(),
+        );
+        assert_eq!(selected.unwrap().url, "https://secondary.rpc");
+
+        assert!(AdaptiveStrategy::new(50.0).as_throttle_recorder().is_none());
+    }
+
+    #[test]
+    fn test_threshold_is_clamped() {
+        assert_eq!(AdaptiveStrategy::new(150.0).threshold(), 100.0);
+        assert_eq!(AdaptiveStrategy::new(-5.0).threshold(), 0.0);
+    }
+}
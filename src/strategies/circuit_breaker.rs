@@ -0,0 +1,467 @@
+//! Circuit-breaker strategy - short-circuits endpoints with a high failure rate.
+//!
+//! Wraps any other [`SelectionStrategy`] and keeps a three-state circuit
+//! (Closed/Open/HalfOpen) per endpoint. Request outcomes are derived from the
+//! success/failure counters in [`EndpointStats`], so the wrapper works with the
+//! existing pool without any extra bookkeeping at the call site.
+
+use super::{SelectionContext, SelectionStrategy, ThrottleRecorder};
+use crate::endpoint::{EndpointStats, RpcEndpoint};
+use parking_lot::RwLock;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::time::{Duration, Instant};
+use tracing::{debug, info, warn};
+
+/// Rolling window used to compute the failure rate.
+const ROLLING_WINDOW_SECS: u64 = 60;
+
+/// Default minimum number of requests in the window before a circuit can open.
+const DEFAULT_MIN_REQUESTS: u64 = 5;
+
+/// State of a single endpoint circuit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitState {
+    /// Requests flow normally.
+    Closed,
+    /// Endpoint is short-circuited until the reset timeout elapses.
+    Open,
+    /// Reset timeout elapsed; a single probe request is allowed through.
+    HalfOpen,
+}
+
+/// Per-endpoint circuit bookkeeping.
+#[derive(Debug)]
+struct Circuit {
+    state: CircuitState,
+    /// Outcomes observed within the rolling window: (time, successes, failures).
+    outcomes: VecDeque<(Instant, u64, u64)>,
+    /// Last seen `successful_requests` counter.
+    seen_successes: u64,
+    /// Last seen `failed_requests` counter.
+    seen_failures: u64,
+    /// When the circuit was last opened.
+    opened_at: Option<Instant>,
+    /// When the half-open probe was handed out (if one is in flight).
+    probe_started: Option<Instant>,
+}
+
+impl Circuit {
+    fn new(stats: &EndpointStats) -> Self {
+        Self {
+            state: CircuitState::Closed,
+            outcomes: VecDeque::new(),
+            seen_successes: stats.successful_requests,
+            seen_failures: stats.failed_requests,
+            opened_at: None,
+            probe_started: None,
+        }
+    }
+
+    /// Move an expired Open circuit to HalfOpen, and release a stale probe.
+    fn apply_timeouts(&mut self, now: Instant, reset_timeout: Duration) {
+        match self.state {
+            CircuitState::Open => {
+                let expired = self
+                    .opened_at
+                    .map(|t| now.duration_since(t) >= reset_timeout)
+                    .unwrap_or(true);
+                if expired {
+                    self.state = CircuitState::HalfOpen;
+                    self.probe_started = None;
+                }
+            }
+            CircuitState::HalfOpen => {
+                // A probe that never reported back must not block the endpoint forever
+                if let Some(t) = self.probe_started {
+                    if now.duration_since(t) >= reset_timeout {
+                        self.probe_started = None;
+                    }
+                }
+            }
+            CircuitState::Closed => {}
+        }
+    }
+
+    fn open(&mut self, now: Instant) {
+        self.state = CircuitState::Open;
+        self.opened_at = Some(now);
+        self.probe_started = None;
+    }
+
+    fn close(&mut self) {
+        self.state = CircuitState::Closed;
+        self.outcomes.clear();
+        self.opened_at = None;
+        self.probe_started = None;
+    }
+
+    /// Returns true if the endpoint must not be handed to the inner strategy.
+    fn is_blocked(&self) -> bool {
+        match self.state {
+            CircuitState::Closed => false,
+            CircuitState::Open => true,
+            CircuitState::HalfOpen => self.probe_started.is_some(),
+        }
+    }
+}
+
+/// Circuit-breaker selection strategy.
+///
+/// Delegates selection to an inner strategy, but hides endpoints whose failure
+/// rate over a rolling 60-second window exceeds `threshold` percent. An open
+/// circuit moves to half-open after `reset_timeout`, allowing a single probe
+/// request; the circuit only closes again once that probe succeeds.
+///
+/// Best for: Pools with flaky endpoints that fail intermittently rather than
+/// going down outright.
+pub struct CircuitBreakerStrategy {
+    /// Strategy used to pick among endpoints with a non-blocking circuit.
+    inner: Box<dyn SelectionStrategy>,
+
+    /// Failure rate (percent) above which a circuit opens.
+    threshold: u32,
+
+    /// Time an open circuit waits before allowing a probe.
+    reset_timeout: Duration,
+
+    /// Minimum requests in the window before the failure rate is evaluated.
+    min_requests: u64,
+
+    /// Circuit state per endpoint URL.
+    circuits: RwLock<HashMap<String, Circuit>>,
+}
+
+impl CircuitBreakerStrategy {
+    /// Create a circuit breaker wrapping `inner`.
+    ///
+    /// `threshold` is a failure-rate percentage (0-100).
+    pub fn new(inner: Box<dyn SelectionStrategy>, threshold: u32, reset_timeout: Duration) -> Self {
+        Self {
+            inner,
+            threshold: threshold.min(100),
+            reset_timeout,
+            min_requests: DEFAULT_MIN_REQUESTS,
+            circuits: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Builder: set the minimum number of requests in the window before a circuit can open.
+    pub fn with_min_requests(mut self, min_requests: u64) -> Self {
+        self.min_requests = min_requests.max(1);
+        self
+    }
+
+    /// Get the current circuit state for an endpoint.
+    ///
+    /// Endpoints that have not been observed yet are reported as `Closed`.
+    pub fn get_circuit_state(&self, url: &str) -> CircuitState {
+        let mut circuits = self.circuits.write();
+        match circuits.get_mut(url) {
+            Some(circuit) => {
+                circuit.apply_timeouts(Instant::now(), self.reset_timeout);
+                circuit.state
+            }
+            None => CircuitState::Closed,
+        }
+    }
+
+    /// Fold new request outcomes from the stats snapshot into each circuit.
+    fn observe(&self, stats: &HashMap<String, EndpointStats>) {
+        let now = Instant::now();
+        let window = Duration::from_secs(ROLLING_WINDOW_SECS);
+        let mut circuits = self.circuits.write();
+
+        for (url, s) in stats {
+            let circuit = circuits
+                .entry(url.clone())
+                .or_insert_with(|| Circuit::new(s));
+
+            let successes = s.successful_requests.saturating_sub(circuit.seen_successes);
+            let failures = s.failed_requests.saturating_sub(circuit.seen_failures);
+            circuit.seen_successes = s.successful_requests;
+            circuit.seen_failures = s.failed_requests;
+
+            circuit.apply_timeouts(now, self.reset_timeout);
+
+            match circuit.state {
+                CircuitState::Closed => {
+                    if successes > 0 || failures > 0 {
+                        circuit.outcomes.push_back((now, successes, failures));
+                    }
+                    while let Some(&(t, _, _)) = circuit.outcomes.front() {
+                        if now.duration_since(t) > window {
+                            circuit.outcomes.pop_front();
+                        } else {
+                            break;
+                        }
+                    }
+
+                    let (ok, failed) = circuit
+                        .outcomes
+                        .iter()
+                        .fold((0u64, 0u64), |(a, b), &(_, s, f)| (a + s, b + f));
+                    let total = ok + failed;
+                    if total >= self.min_requests
+                        && failed * 100 > u64::from(self.threshold) * total
+                    {
+                        circuit.open(now);
+                        warn!(
+                            endpoint_url = %url,
+                            failed,
+                            total,
+                            threshold = self.threshold,
+                            "Circuit opened"
+                        );
+                    }
+                }
+                CircuitState::HalfOpen if circuit.probe_started.is_some() => {
+                    if failures > 0 {
+                        circuit.open(now);
+                        debug!(endpoint_url = %url, "Half-open probe failed, circuit reopened");
+                    } else if successes > 0 {
+                        circuit.close();
+                        info!(endpoint_url = %url, "Half-open probe succeeded, circuit closed");
+                    }
+                }
+                // Late outcomes from requests issued before the circuit opened are ignored
+                CircuitState::HalfOpen | CircuitState::Open => {}
+            }
+        }
+    }
+
+    /// `exclude` plus the endpoints whose circuit is open (or half-open with a probe in flight).
+    fn blocked(
+        &mut self,
+        stats: &HashMap<String, EndpointStats>,
+        exclude: &HashSet<String>,
+    ) -> HashSet<String> {
+        self.observe(stats);
+
+        let mut blocked = exclude.clone();
+        blocked.extend(
+            self.circuits
+                .read()
+                .iter()
+                .filter(|(_, c)| c.is_blocked())
+                .map(|(url, _)| url.clone()),
+        );
+        blocked
+    }
+
+    /// Select with `pick`, which is handed the inner strategy and the URLs to exclude.
+    fn select_closed<'a>(
+        &mut self,
+        stats: &HashMap<String, EndpointStats>,
+        exclude: &HashSet<String>,
+        pick: impl FnOnce(&mut dyn SelectionStrategy, &HashSet<String>) -> Option<&'a RpcEndpoint>,
+    ) -> Option<&'a RpcEndpoint> {
+        let blocked = self.blocked(stats, exclude);
+        let selected = match pick(self.inner.as_mut(), &blocked) {
+            Some(e) => e,
+            None => {
+                debug!("No endpoint with a closed circuit available");
+                return None;
+            }
+        };
+
+        if let Some(circuit) = self.circuits.write().get_mut(&selected.url) {
+            if circuit.state == CircuitState::HalfOpen {
+                circuit.probe_started = Some(Instant::now());
+                debug!(endpoint_url = %selected.url, "Sending half-open probe");
+            }
+        }
+
+        Some(selected)
+    }
+}
+
+impl SelectionStrategy for CircuitBreakerStrategy {
+    fn select<'a>(
+        &mut self,
+        endpoints: &'a [RpcEndpoint],
+        stats: &HashMap<String, EndpointStats>,
+        exclude: &HashSet<String>,
+        ctx: &SelectionContext,
+    ) -> Option<&'a RpcEndpoint> {
+        self.select_closed(stats, exclude, |inner, blocked| {
+            inner.select(endpoints, stats, blocked, ctx)
+        })
+    }
+
+    fn peek<'a>(
+        &mut self,
+        endpoints: &'a [RpcEndpoint],
+        stats: &HashMap<String, EndpointStats>,
+        exclude: &HashSet<String>,
+        ctx: &SelectionContext,
+    ) -> Option<&'a RpcEndpoint> {
+        // No half-open probe is started for a peek
+        let blocked = self.blocked(stats, exclude);
+        self.inner.peek(endpoints, stats, &blocked, ctx)
+    }
+
+    fn name(&self) -> &'static str {
+        "circuit-breaker"
+    }
+
+    fn on_success(&mut self, url: &str, latency_ms: u64) {
+        self.inner.on_success(url, latency_ms);
+    }
+
+    fn on_failure(&mut self, url: &str, error: &str) {
+        self.inner.on_failure(url, error);
+    }
+
+    fn as_throttle_recorder(&mut self) -> Option<&mut dyn ThrottleRecorder> {
+        self.inner.as_throttle_recorder()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::strategies::FailoverStrategy;
+    use std::thread::sleep;
+
+    const PRIMARY: &str = "https://primary.rpc";
+    const SECONDARY: &str = "https://secondary.rpc";
+
+    fn create_test_endpoints() -> Vec<RpcEndpoint> {
+        vec![
+            RpcEndpoint::new(PRIMARY).with_priority(10),
+            RpcEndpoint::new(SECONDARY).with_priority(50),
+        ]
+    }
+
+    fn create_stats(endpoints: &[RpcEndpoint]) -> HashMap<String, EndpointStats> {
+        endpoints
+            .iter()
+            .map(|e| (e.url.clone(), EndpointStats::new(e)))
+            .collect()
+    }
+
+    fn create_strategy(reset_timeout: Duration) -> CircuitBreakerStrategy {
+        CircuitBreakerStrategy::new(Box::new(FailoverStrategy), 50, reset_timeout)
+            .with_min_requests(4)
+    }
+
+    /// Record failures without tripping the pool's own health flag.
+    fn fail(stats: &mut HashMap<String, EndpointStats>, url: &str, count: u32) {
+        let s = stats.get_mut(url).unwrap();
+        for _ in 0..count {
+            s.record_failure("error".into(), u32::MAX);
+        }
+    }
+
+    #[test]
+    fn test_opens_when_failure_rate_exceeds_threshold() {
+        let mut strategy = create_strategy(Duration::from_secs(60));
+        let endpoints = create_test_endpoints();
+        let mut stats = create_stats(&endpoints);
+        let exclude = HashSet::new();
+
+        // Establish baseline counters
+        strategy.select(&endpoints, &stats, &exclude, &SelectionContext::default());
+
+        fail(&mut stats, PRIMARY, 4);
+        let selected = strategy
+            .select(&endpoints, &stats, &exclude, &SelectionContext::default())
+            .unwrap();
+        assert_eq!(selected.url, SECONDARY);
+        assert_eq!(strategy.get_circuit_state(PRIMARY), CircuitState::Open);
+        assert_eq!(strategy.get_circuit_state(SECONDARY), CircuitState::Closed);
+    }
+
+    #[test]
+    fn test_stays_closed_below_min_requests() {
+        let mut strategy = create_strategy(Duration::from_secs(60));
+        let endpoints = create_test_endpoints();
+        let mut stats = create_stats(&endpoints);
+        let exclude = HashSet::new();
+
+        strategy.select(&endpoints, &stats, &exclude, &SelectionContext::default());
+        fail(&mut stats, PRIMARY, 3);
+
+        let selected = strategy
+            .select(&endpoints, &stats, &exclude, &SelectionContext::default())
+            .unwrap();
+        assert_eq!(selected.url, PRIMARY);
+        assert_eq!(strategy.get_circuit_state(PRIMARY), CircuitState::Closed);
+    }
+
+    #[test]
+    fn test_half_open_allows_single_probe_and_closes_on_success() {
+        let mut strategy = create_strategy(Duration::from_millis(20));
+        let endpoints = create_test_endpoints();
+        let mut stats = create_stats(&endpoints);
+        let exclude = HashSet::new();
+
+        strategy.select(&endpoints, &stats, &exclude, &SelectionContext::default());
+        fail(&mut stats, PRIMARY, 4);
+        strategy.select(&endpoints, &stats, &exclude, &SelectionContext::default());
+        assert_eq!(strategy.get_circuit_state(PRIMARY), CircuitState::Open);
+
+        sleep(Duration::from_millis(30));
+        assert_eq!(strategy.get_circuit_state(PRIMARY), CircuitState::HalfOpen);
+
+        // First selection is the probe
+        let probe = strategy
+            .select(&endpoints, &stats, &exclude, &SelectionContext::default())
+            .unwrap();
+        assert_eq!(probe.url, PRIMARY);
+
+        // While the probe is in flight, the endpoint is skipped
+        let next = strategy
+            .select(&endpoints, &stats, &exclude, &SelectionContext::default())
+            .unwrap();
+        assert_eq!(next.url, SECONDARY);
+
+        stats.get_mut(PRIMARY).unwrap().record_success(50);
+        let selected = strategy
+            .select(&endpoints, &stats, &exclude, &SelectionContext::default())
+            .unwrap();
+        assert_eq!(selected.url, PRIMARY);
+        assert_eq!(strategy.get_circuit_state(PRIMARY), CircuitState::Closed);
+    }
+
+    #[test]
+    fn test_half_open_probe_failure_reopens() {
+        let mut strategy = create_strategy(Duration::from_millis(20));
+        let endpoints = create_test_endpoints();
+        let mut stats = create_stats(&endpoints);
+        let exclude = HashSet::new();
+
+        strategy.select(&endpoints, &stats, &exclude, &SelectionContext::default());
+        fail(&mut stats, PRIMARY, 4);
+        strategy.select(&endpoints, &stats, &exclude, &SelectionContext::default());
+
+        sleep(Duration::from_millis(30));
+        let probe = strategy
+            .select(&endpoints, &stats, &exclude, &SelectionContext::default())
+            .unwrap();
+        assert_eq!(probe.url, PRIMARY);
+
+        fail(&mut stats, PRIMARY, 1);
+        let selected = strategy
+            .select(&endpoints, &stats, &exclude, &SelectionContext::default())
+            .unwrap();
+        assert_eq!(selected.url, SECONDARY);
+        assert_eq!(strategy.get_circuit_state(PRIMARY), CircuitState::Open);
+    }
+
+    #[test]
+    fn test_returns_none_when_all_circuits_open() {
+        let mut strategy = create_strategy(Duration::from_secs(60));
+        let endpoints = create_test_endpoints();
+        let mut stats = create_stats(&endpoints);
+        let exclude = HashSet::new();
+
+        strategy.select(&endpoints, &stats, &exclude, &SelectionContext::default());
+        fail(&mut stats, PRIMARY, 4);
+        fail(&mut stats, SECONDARY, 4);
+
+        assert!(strategy
+            .select(&endpoints, &stats, &exclude, &SelectionContext::default())
+            .is_none());
+    }
+}
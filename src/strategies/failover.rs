@@ -1,6 +1,6 @@
 //! Failover strategy - uses highest priority healthy endpoint.
 
-use super::SelectionStrategy;
+use super::{SelectionContext, SelectionStrategy};
 use crate::endpoint::{EndpointStats, RpcEndpoint};
 use std::collections::{HashMap, HashSet};
 
@@ -19,6 +19,7 @@ impl SelectionStrategy for FailoverStrategy {
         endpoints: &'a [RpcEndpoint],
         stats: &HashMap<String, EndpointStats>,
         exclude: &HashSet<String>,
+        _ctx: &SelectionContext,
     ) -> Option<&'a RpcEndpoint> {
         // Try to find a healthy, non-excluded endpoint (endpoints are pre-sorted by priority)
         let healthy = endpoints
@@ -61,7 +62,7 @@ mod tests {
         let stats = create_stats(&endpoints);
         let exclude = HashSet::new();
 
-        let selected = strategy.select(&endpoints, &stats, &exclude);
+        let selected = strategy.select(&endpoints, &stats, &exclude, &SelectionContext::default());
         assert_eq!(selected.unwrap().url, "https://primary.rpc");
     }
 
@@ -75,7 +76,7 @@ mod tests {
         stats.get_mut("https://primary.rpc").unwrap().is_healthy = false;
 
         let exclude = HashSet::new();
-        let selected = strategy.select(&endpoints, &stats, &exclude);
+        let selected = strategy.select(&endpoints, &stats, &exclude, &SelectionContext::default());
         assert_eq!(selected.unwrap().url, "https://secondary.rpc");
     }
 
@@ -91,7 +92,7 @@ mod tests {
         }
 
         let exclude = HashSet::new();
-        let selected = strategy.select(&endpoints, &stats, &exclude);
+        let selected = strategy.select(&endpoints, &stats, &exclude, &SelectionContext::default());
         // Should still return something (graceful degradation)
         assert!(selected.is_some());
     }
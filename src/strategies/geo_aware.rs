@@ -0,0 +1,149 @@
+//! Geo-aware strategy - prefers endpoints in the client's region.
+
+use super::{SelectionContext, SelectionStrategy};
+use crate::endpoint::{EndpointStats, Region, RpcEndpoint};
+use std::collections::{HashMap, HashSet};
+
+/// Geo-aware selection strategy.
+///
+/// Selects the healthy, non-excluded endpoint tagged with the preferred
+/// [`Region`] that has the lowest priority number. When no such endpoint is
+/// left it falls back to healthy endpoints in other regions (or without a
+/// region tag), then to unhealthy ones, each by priority. Ties go to the
+/// endpoint listed first.
+///
+/// Best for: Clients far from most endpoints, e.g. a service in Singapore
+/// that should use an Asia-Pacific node before crossing the Pacific.
+#[derive(Debug, Clone)]
+pub struct GeoAwareStrategy {
+    preferred_region: Region,
+}
+
+impl GeoAwareStrategy {
+    /// Create a strategy preferring endpoints in `preferred_region`.
+    pub fn new(preferred_region: Region) -> Self {
+        Self { preferred_region }
+    }
+
+    /// The region this strategy prefers.
+    pub fn preferred_region(&self) -> Region {
+        self.preferred_region
+    }
+}
+
+impl SelectionStrategy for GeoAwareStrategy {
+    fn select<'a>(
+        &mut self,
+        endpoints: &'a [RpcEndpoint],
+        stats: &HashMap<String, EndpointStats>,
+        exclude: &HashSet<String>,
+        _ctx: &SelectionContext,
+    ) -> Option<&'a RpcEndpoint> {
+        let candidates = || endpoints.iter().filter(|e| !exclude.contains(&e.url));
+        let healthy =
+            || candidates().filter(|e| stats.get(&e.url).map(|s| s.is_healthy).unwrap_or(true));
+
+        // min_by_key keeps the first of equal keys, preserving list order on ties
+        healthy()
+            .filter(|e| e.region == Some(self.preferred_region))
+            .min_by_key(|e| e.priority)
+            .or_else(|| healthy().min_by_key(|e| e.priority))
+            .or_else(|| candidates().min_by_key(|e| e.priority))
+    }
+
+    fn name(&self) -> &'static str {
+        "geo-aware"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_endpoints() -> Vec<RpcEndpoint> {
+        vec![
+            RpcEndpoint::new("https://virginia.rpc")
+                .with_priority(1)
+                .with_region(Region::NorthAmerica),
+            RpcEndpoint::new("https://singapore.rpc")
+                .with_priority(2)
+                .with_region(Region::AsiaPacific),
+        ]
+    }
+
+    fn create_stats(endpoints: &[RpcEndpoint]) -> HashMap<String, EndpointStats> {
+        endpoints
+            .iter()
+            .map(|e| (e.url.clone(), EndpointStats::new(e)))
+            .collect()
+    }
+
+    #[test]
+    fn test_prefers_matching_region() {
+        let mut strategy = GeoAwareStrategy::new(Region::AsiaPacific);
+        let endpoints = create_test_endpoints();
+        let stats = create_stats(&endpoints);
+
+        let selected = strategy.select(
+            &endpoints,
+            &stats,
+            &HashSet::new(),
+            &SelectionContext::default(),
+        );
+        assert_eq!(selected.unwrap().url, "https://singapore.rpc");
+
+        // Other regions are used once the preferred one is exhausted
+        let exclude: HashSet<String> = ["https://singapore.rpc".to_string()].into();
+        let selected = strategy.select(&endpoints, &stats, &exclude, &SelectionContext::default());
+        assert_eq!(selected.unwrap().url, "https://virginia.rpc");
+    }
+
+    #[test]
+    fn test_falls_back_when_preferred_region_unhealthy() {
+        let mut strategy = GeoAwareStrategy::new(Region::AsiaPacific);
+        let endpoints = create_test_endpoints();
+        let mut stats = create_stats(&endpoints);
+        let exclude = HashSet::new();
+
+        stats.get_mut("https://singapore.rpc").unwrap().is_healthy = false;
+        let selected = strategy.select(&endpoints, &stats, &exclude, &SelectionContext::default());
+        assert_eq!(selected.unwrap().url, "https://virginia.rpc");
+
+        // With nothing healthy, the lowest priority number wins
+        stats.get_mut("https://virginia.rpc").unwrap().is_healthy = false;
+        let selected = strategy.select(&endpoints, &stats, &exclude, &SelectionContext::default());
+        assert_eq!(selected.unwrap().url, "https://virginia.rpc");
+    }
+
+    #[test]
+    fn test_fallback_sorted_by_priority() {
+        let mut strategy = GeoAwareStrategy::new(Region::Europe);
+        let endpoints = vec![
+            RpcEndpoint::new("https://untagged.rpc").with_priority(3),
+            RpcEndpoint::new("https://tokyo.rpc")
+                .with_priority(2)
+                .with_region(Region::AsiaPacific),
+            RpcEndpoint::new("https://frankfurt.rpc")
+                .with_priority(9)
+                .with_region(Region::Europe),
+        ];
+        let mut stats = create_stats(&endpoints);
+
+        let selected = strategy.select(
+            &endpoints,
+            &stats,
+            &HashSet::new(),
+            &SelectionContext::default(),
+        );
+        assert_eq!(selected.unwrap().url, "https://frankfurt.rpc");
+
+        stats.get_mut("https://frankfurt.rpc").unwrap().is_healthy = false;
+        let selected = strategy.select(
+            &endpoints,
+            &stats,
+            &HashSet::new(),
+            &SelectionContext::default(),
+        );
+        assert_eq!(selected.unwrap().url, "https://tokyo.rpc");
+    }
+}
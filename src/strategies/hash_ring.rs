@@ -0,0 +1,234 @@
+//! Hash ring strategy - consistent hashing of one-second time windows onto endpoints.
+
+use super::{SelectionContext, SelectionStrategy};
+use crate::endpoint::{EndpointStats, RpcEndpoint};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Consistent hashing selection strategy.
+///
+/// Places `virtual_nodes` replicas of every endpoint URL on a hash ring.
+/// Each selection hashes the current wall-clock second to a ring position
+/// and walks clockwise to the first healthy, non-excluded endpoint, falling
+/// back to the first non-excluded one if none is healthy. All requests within
+/// the same second go to the same endpoint, and adding or removing an
+/// endpoint only remaps the windows that land on its replicas, about 1/N of
+/// them. The ring is rebuilt whenever the endpoint list changes.
+///
+/// Best for: Caching layers behind the pool, which see better hit rates when
+/// requests stick to one endpoint per time window.
+#[derive(Debug, Clone)]
+pub struct HashRingStrategy {
+    virtual_nodes: usize,
+    /// Ring positions with the index of their endpoint, sorted by position.
+    ring: Vec<(u64, usize)>,
+    /// Endpoint URLs the ring was built for, in list order.
+    ring_urls: Vec<String>,
+}
+
+impl HashRingStrategy {
+    /// Create a strategy with `virtual_nodes` replicas per endpoint (at least 1).
+    ///
+    /// More replicas spread the time windows more evenly between endpoints.
+    pub fn new(virtual_nodes: usize) -> Self {
+        Self {
+            virtual_nodes: virtual_nodes.max(1),
+            ring: Vec::new(),
+            ring_urls: Vec::new(),
+        }
+    }
+
+    /// Number of replicas placed on the ring per endpoint.
+    pub fn virtual_nodes(&self) -> usize {
+        self.virtual_nodes
+    }
+
+    /// Rebuild the ring if the endpoint list differs from the one it was built for.
+    fn ensure_ring(&mut self, endpoints: &[RpcEndpoint]) {
+        if self.ring_urls.iter().eq(endpoints.iter().map(|e| &e.url)) {
+            return;
+        }
+        self.ring = endpoints
+            .iter()
+            .enumerate()
+            .flat_map(|(index, e)| {
+                (0..self.virtual_nodes).map(move |replica| (hash(&(&e.url, replica)), index))
+            })
+            .collect();
+        self.ring.sort_unstable();
+        self.ring_urls = endpoints.iter().map(|e| e.url.clone()).collect();
+    }
+
+    /// Select the endpoint for the time window `key`.
+    fn select_for_key<'a>(
+        &mut self,
+        endpoints: &'a [RpcEndpoint],
+        stats: &HashMap<String, EndpointStats>,
+        exclude: &HashSet<String>,
+        key: u64,
+    ) -> Option<&'a RpcEndpoint> {
+        self.ensure_ring(endpoints);
+        let position = hash(&key);
+        let start = self.ring.partition_point(|(node, _)| *node < position);
+
+        // Walk clockwise from the key's position, wrapping around once
+        let clockwise = || {
+            let (after, before) = self.ring.split_at(start);
+            before
+                .iter()
+                .chain(after)
+                .map(|(_, index)| &endpoints[*index])
+                .filter(|e| !exclude.contains(&e.url))
+        };
+        let is_healthy = |e: &&RpcEndpoint| stats.get(&e.url).map(|s| s.is_healthy).unwrap_or(true);
+
+        clockwise().find(is_healthy).or_else(|| clockwise().next())
+    }
+}
+
+/// Position of `value` on the ring.
+fn hash<T: Hash>(value: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+impl SelectionStrategy for HashRingStrategy {
+    fn select<'a>(
+        &mut self,
+        endpoints: &'a [RpcEndpoint],
+        stats: &HashMap<String, EndpointStats>,
+        exclude: &HashSet<String>,
+        _ctx: &SelectionContext,
+    ) -> Option<&'a RpcEndpoint> {
+        let window = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs())
+            .unwrap_or(0);
+        self.select_for_key(endpoints, stats, exclude, window)
+    }
+
+    fn name(&self) -> &'static str {
+        "hash-ring"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_endpoints() -> Vec<RpcEndpoint> {
+        vec![
+            RpcEndpoint::new("https://rpc1.example.com"),
+            RpcEndpoint::new("https://rpc2.example.com"),
+            RpcEndpoint::new("https://rpc3.example.com"),
+        ]
+    }
+
+    fn create_stats(endpoints: &[RpcEndpoint]) -> HashMap<String, EndpointStats> {
+        endpoints
+            .iter()
+            .map(|e| (e.url.clone(), EndpointStats::new(e)))
+            .collect()
+    }
+
+    /// Endpoint URL chosen for each of the first 1000 time windows.
+    fn assignments(strategy: &mut HashRingStrategy, endpoints: &[RpcEndpoint]) -> Vec<String> {
+        let stats = create_stats(endpoints);
+        (0..1000)
+            .map(|key| {
+                let selected = strategy.select_for_key(endpoints, &stats, &HashSet::new(), key);
+                selected.unwrap().url.clone()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_same_window_same_endpoint_and_spread() {
+        let mut strategy = HashRingStrategy::new(100);
+        let endpoints = create_test_endpoints();
+
+        let first = assignments(&mut strategy, &endpoints);
+        assert_eq!(assignments(&mut strategy, &endpoints), first);
+
+        for endpoint in &endpoints {
+            let share = first.iter().filter(|url| **url == endpoint.url).count();
+            assert!(
+                share > 150,
+                "{} got {} of 1000 windows",
+                endpoint.url,
+                share
+            );
+        }
+    }
+
+    #[test]
+    fn test_removing_endpoint_only_remaps_its_windows() {
+        let mut strategy = HashRingStrategy::new(100);
+        let mut endpoints = create_test_endpoints();
+        let before = assignments(&mut strategy, &endpoints);
+
+        let removed = endpoints.remove(1).url;
+        let after = assignments(&mut strategy, &endpoints);
+
+        for (old, new) in before.iter().zip(&after) {
+            if *old != removed {
+                assert_eq!(old, new);
+            }
+        }
+    }
+
+    #[test]
+    fn test_skips_unhealthy_and_excluded_clockwise() {
+        let mut strategy = HashRingStrategy::new(10);
+        let endpoints = create_test_endpoints();
+        let mut stats = create_stats(&endpoints);
+        let exclude = HashSet::new();
+
+        let owner = strategy
+            .select_for_key(&endpoints, &stats, &exclude, 42)
+            .unwrap()
+            .url
+            .clone();
+
+        stats.get_mut(&owner).unwrap().is_healthy = false;
+        let selected = strategy.select_for_key(&endpoints, &stats, &exclude, 42);
+        let next = selected.unwrap().url.clone();
+        assert_ne!(next, owner);
+
+        // Excluding the next endpoint as well leaves the last healthy one
+        let exclude: HashSet<String> = [next.clone()].into();
+        let selected = strategy.select_for_key(&endpoints, &stats, &exclude, 42);
+        let last = &selected.unwrap().url;
+        assert!(*last != owner && *last != next);
+
+        // With nothing healthy left, unhealthy endpoints are still used
+        for stat in stats.values_mut() {
+            stat.is_healthy = false;
+        }
+        let selected = strategy.select_for_key(&endpoints, &stats, &HashSet::new(), 42);
+        assert_eq!(selected.unwrap().url, owner);
+    }
+
+    #[test]
+    fn test_virtual_nodes_minimum() {
+        let mut strategy = HashRingStrategy::new(0);
+        assert_eq!(strategy.virtual_nodes(), 1);
+
+        let endpoints = create_test_endpoints();
+        let stats = create_stats(&endpoints);
+        assert!(strategy
+            .select(
+                &endpoints,
+                &stats,
+                &HashSet::new(),
+                &SelectionContext::default()
+            )
+            .is_some());
+        assert!(strategy
+            .select(&[], &stats, &HashSet::new(), &SelectionContext::default())
+            .is_none());
+    }
+}
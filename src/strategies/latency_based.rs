@@ -1,17 +1,111 @@
 //! Latency-based strategy - selects the fastest endpoint.
 
-use super::SelectionStrategy;
+use super::{SelectionContext, SelectionStrategy};
 use crate::endpoint::{EndpointStats, RpcEndpoint};
-use std::collections::{HashMap, HashSet};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::time::Duration;
+
+/// Percentile used when ranking endpoints in percentile mode.
+const PERCENTILE: f64 = 0.95;
 
 /// Latency-based selection strategy.
 ///
 /// Always selects the healthy endpoint with the lowest average latency.
 /// Uses exponential moving average (EMA) to smooth out latency measurements.
 ///
+/// In percentile mode (see [`LatencyBasedStrategy::with_percentile_window`]) the
+/// strategy instead ranks endpoints by the p95 of their most recent samples,
+/// which exposes tail latency that the EMA smooths away.
+///
+/// With [`LatencyBasedStrategy::with_success_rate_tiebreak`], endpoints whose
+/// latency is close to the fastest are ranked by their recent success rate, so
+/// a slightly faster but currently failing endpoint loses to a reliable one.
+///
+/// With [`LatencyBasedStrategy::with_min_samples`], endpoints that have served
+/// too few requests to have a meaningful latency are warmed up with a small
+/// share of traffic instead of winning every selection with their zero average.
+///
 /// Best for: Latency-sensitive applications where response time is critical.
 #[derive(Debug, Default, Clone)]
-pub struct LatencyBasedStrategy;
+pub struct LatencyBasedStrategy {
+    /// Per-endpoint ring buffers of recent latencies (percentile mode only).
+    samples: HashMap<String, VecDeque<u64>>,
+    /// Number of samples kept per endpoint; `None` selects by EMA.
+    window: Option<usize>,
+    /// Success rate window and latency tolerance (ms) for breaking near-ties.
+    tiebreak: Option<(Duration, f64)>,
+    /// Requests an endpoint needs before it is ranked by latency.
+    min_samples: Option<u32>,
+    /// Selections made while warming endpoints existed, for spreading their share.
+    warmup_selections: u64,
+}
+
+impl LatencyBasedStrategy {
+    /// Create a strategy that selects by EMA average latency.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a strategy that selects by p95 over the last `window` samples per endpoint.
+    pub fn with_percentile_window(window: usize) -> Self {
+        Self {
+            window: Some(window.max(1)),
+            ..Self::default()
+        }
+    }
+
+    /// Among endpoints within `tolerance_ms` of the fastest, prefer the highest
+    /// [`success_rate_windowed`](EndpointStats::success_rate_windowed) over `window`.
+    pub fn with_success_rate_tiebreak(mut self, window: Duration, tolerance_ms: f64) -> Self {
+        self.tiebreak = Some((window, tolerance_ms.max(0.0)));
+        self
+    }
+
+    /// Warm up endpoints with fewer than `n` requests before ranking them by latency.
+    ///
+    /// A new endpoint's average latency is 0, which would otherwise win every
+    /// selection. Instead, one in every `n` selections goes to a warming
+    /// endpoint, taking turns between them, and the rest are ranked by latency
+    /// among the established endpoints. If no endpoint is established yet, the
+    /// warming endpoints take turns on every selection. Requests are counted
+    /// by [`EndpointStats::total_requests`].
+    pub fn with_min_samples(mut self, n: u32) -> Self {
+        self.min_samples = Some(n.max(1));
+        self
+    }
+
+    /// p95 latency for an endpoint, or `None` if no samples are recorded.
+    pub fn p95_latency_ms(&self, url: &str) -> Option<u64> {
+        let samples = self.samples.get(url).filter(|s| !s.is_empty())?;
+        let mut sorted: Vec<u64> = samples.iter().copied().collect();
+        sorted.sort_unstable();
+        let rank = (sorted.len() as f64 * PERCENTILE).ceil() as usize;
+        Some(sorted[rank.saturating_sub(1)])
+    }
+
+    /// Record a request latency sample (ignored in EMA mode).
+    ///
+    /// The pool calls this through [`SelectionStrategy::on_success`].
+    pub fn record_latency(&mut self, url: &str, latency_ms: u64) {
+        let Some(window) = self.window else {
+            return;
+        };
+        let samples = self.samples.entry(url.to_string()).or_default();
+        if samples.len() == window {
+            samples.pop_front();
+        }
+        samples.push_back(latency_ms);
+    }
+
+    /// Ranking key for an endpoint in the active mode.
+    fn latency_key(&self, url: &str, stats: &HashMap<String, EndpointStats>) -> f64 {
+        match self.window {
+            // Endpoints without samples rank first so they get measured
+            Some(_) => self.p95_latency_ms(url).map(|p| p as f64).unwrap_or(0.0),
+            None => stats.get(url).map(|s| s.avg_latency_ms).unwrap_or(f64::MAX),
+        }
+    }
+}
 
 impl SelectionStrategy for LatencyBasedStrategy {
     fn select<'a>(
@@ -19,6 +113,7 @@ impl SelectionStrategy for LatencyBasedStrategy {
         endpoints: &'a [RpcEndpoint],
         stats: &HashMap<String, EndpointStats>,
         exclude: &HashSet<String>,
+        _ctx: &SelectionContext,
     ) -> Option<&'a RpcEndpoint> {
         // Collect healthy, non-excluded endpoints with their latencies
         let mut healthy: Vec<_> = endpoints
@@ -32,14 +127,69 @@ impl SelectionStrategy for LatencyBasedStrategy {
             return endpoints.iter().find(|e| !exclude.contains(&e.url));
         }
 
-        // Sort by average latency (ascending)
+        if let Some(min_samples) = self.min_samples {
+            let (warming, established): (Vec<_>, Vec<_>) = healthy.into_iter().partition(|e| {
+                stats.get(&e.url).map(|s| s.total_requests).unwrap_or(0) < u64::from(min_samples)
+            });
+            if !warming.is_empty() {
+                let turn = self.warmup_selections;
+                self.warmup_selections = self.warmup_selections.wrapping_add(1);
+                if established.is_empty() {
+                    return Some(warming[turn as usize % warming.len()]);
+                }
+                let n = u64::from(min_samples);
+                if turn % n == 0 {
+                    return Some(warming[(turn / n) as usize % warming.len()]);
+                }
+            }
+            healthy = established;
+        }
+
+        // Sort by latency (ascending)
         healthy.sort_by(|a, b| {
-            let lat_a = stats.get(&a.url).map(|s| s.avg_latency_ms).unwrap_or(f64::MAX);
-            let lat_b = stats.get(&b.url).map(|s| s.avg_latency_ms).unwrap_or(f64::MAX);
+            let lat_a = self.latency_key(&a.url, stats);
+            let lat_b = self.latency_key(&b.url, stats);
             lat_a.partial_cmp(&lat_b).unwrap_or(std::cmp::Ordering::Equal)
         });
 
-        healthy.first().copied()
+        let Some((window, tolerance_ms)) = self.tiebreak else {
+            return healthy.first().copied();
+        };
+        let fastest = self.latency_key(&healthy[0].url, stats);
+        let success_rate = |e: &RpcEndpoint| {
+            stats
+                .get(&e.url)
+                .map(|s| s.success_rate_windowed(window))
+                .unwrap_or(100.0)
+        };
+        // Keep the faster endpoint when success rates are equal
+        healthy
+            .into_iter()
+            .take_while(|e| self.latency_key(&e.url, stats) - fastest <= tolerance_ms)
+            .reduce(|best, e| {
+                if success_rate(e) > success_rate(best) {
+                    e
+                } else {
+                    best
+                }
+            })
+    }
+
+    fn peek<'a>(
+        &mut self,
+        endpoints: &'a [RpcEndpoint],
+        stats: &HashMap<String, EndpointStats>,
+        exclude: &HashSet<String>,
+        ctx: &SelectionContext,
+    ) -> Option<&'a RpcEndpoint> {
+        let warmup_selections = self.warmup_selections;
+        let selected = self.select(endpoints, stats, exclude, ctx);
+        self.warmup_selections = warmup_selections;
+        selected
+    }
+
+    fn on_success(&mut self, url: &str, latency_ms: u64) {
+        self.record_latency(url, latency_ms);
     }
 
     fn name(&self) -> &'static str {
@@ -61,7 +211,8 @@ mod tests {
 
     #[test]
     fn test_selects_lowest_latency() {
-        let mut strategy = LatencyBasedStrategy;
+        let ctx = SelectionContext::default();
+        let mut strategy = LatencyBasedStrategy::new();
         let endpoints = create_test_endpoints();
 
         let mut stats: HashMap<String, EndpointStats> = endpoints
@@ -75,13 +226,14 @@ mod tests {
         stats.get_mut("https://medium.rpc").unwrap().avg_latency_ms = 200.0;
 
         let exclude = HashSet::new();
-        let selected = strategy.select(&endpoints, &stats, &exclude);
+        let selected = strategy.select(&endpoints, &stats, &exclude, &ctx);
         assert_eq!(selected.unwrap().url, "https://fast.rpc");
     }
 
     #[test]
     fn test_prefers_no_data_over_high_latency() {
-        let mut strategy = LatencyBasedStrategy;
+        let ctx = SelectionContext::default();
+        let mut strategy = LatencyBasedStrategy::new();
         let endpoints = create_test_endpoints();
 
         let mut stats: HashMap<String, EndpointStats> = endpoints
@@ -94,8 +246,149 @@ mod tests {
         // Others have 0.0 (no data yet)
 
         let exclude = HashSet::new();
-        let selected = strategy.select(&endpoints, &stats, &exclude);
+        let selected = strategy.select(&endpoints, &stats, &exclude, &ctx);
         // Should not select the slow one
         assert_ne!(selected.unwrap().url, "https://slow.rpc");
     }
+
+    #[test]
+    fn test_percentile_mode_penalizes_tail_latency() {
+        let ctx = SelectionContext::default();
+        let mut strategy = LatencyBasedStrategy::with_percentile_window(20);
+        let endpoints = create_test_endpoints();
+
+        let mut stats: HashMap<String, EndpointStats> = endpoints
+            .iter()
+            .map(|e| (e.url.clone(), EndpointStats::new(e)))
+            .collect();
+
+        // "Fast" has the better average but a bursty tail
+        stats.get_mut("https://fast.rpc").unwrap().avg_latency_ms = 60.0;
+        stats.get_mut("https://medium.rpc").unwrap().avg_latency_ms = 100.0;
+        stats.get_mut("https://slow.rpc").unwrap().avg_latency_ms = 500.0;
+        for i in 0..20 {
+            strategy.record_latency("https://fast.rpc", if i % 5 == 0 { 900 } else { 20 });
+            strategy.record_latency("https://medium.rpc", 100);
+            strategy.record_latency("https://slow.rpc", 500);
+        }
+
+        assert_eq!(strategy.p95_latency_ms("https://fast.rpc"), Some(900));
+        assert_eq!(strategy.p95_latency_ms("https://medium.rpc"), Some(100));
+
+        let exclude = HashSet::new();
+        let selected = strategy.select(&endpoints, &stats, &exclude, &ctx);
+        assert_eq!(selected.unwrap().url, "https://medium.rpc");
+
+        // EMA mode still picks the lowest average
+        let selected = LatencyBasedStrategy::new().select(&endpoints, &stats, &exclude, &ctx);
+        assert_eq!(selected.unwrap().url, "https://fast.rpc");
+    }
+
+    #[test]
+    fn test_success_rate_tiebreak() {
+        let ctx = SelectionContext::default();
+        let endpoints = create_test_endpoints();
+        let mut stats: HashMap<String, EndpointStats> = endpoints
+            .iter()
+            .map(|e| (e.url.clone(), EndpointStats::new(e)))
+            .collect();
+
+        // "Fast" is marginally quicker but failing most recent requests
+        stats.get_mut("https://fast.rpc").unwrap().avg_latency_ms = 50.0;
+        stats.get_mut("https://medium.rpc").unwrap().avg_latency_ms = 55.0;
+        stats.get_mut("https://slow.rpc").unwrap().avg_latency_ms = 500.0;
+        let fast = stats.get_mut("https://fast.rpc").unwrap();
+        for _ in 0..3 {
+            fast.record_failure("timeout".to_string(), 10);
+        }
+
+        let exclude = HashSet::new();
+        let window = Duration::from_secs(60);
+        let selected = LatencyBasedStrategy::new()
+            .with_success_rate_tiebreak(window, 10.0)
+            .select(&endpoints, &stats, &exclude, &ctx);
+        assert_eq!(selected.unwrap().url, "https://medium.rpc");
+
+        // Outside the tolerance, latency still wins
+        let selected = LatencyBasedStrategy::new()
+            .with_success_rate_tiebreak(window, 1.0)
+            .select(&endpoints, &stats, &exclude, &ctx);
+        assert_eq!(selected.unwrap().url, "https://fast.rpc");
+
+        // Without a tie-break the failures are ignored
+        let selected = LatencyBasedStrategy::new().select(&endpoints, &stats, &exclude, &ctx);
+        assert_eq!(selected.unwrap().url, "https://fast.rpc");
+    }
+
+    #[test]
+    fn test_percentile_window_evicts_old_samples() {
+        let mut strategy = LatencyBasedStrategy::with_percentile_window(3);
+
+        for latency in [1000, 1000, 1000, 10, 20, 30] {
+            strategy.record_latency("https://fast.rpc", latency);
+        }
+        assert_eq!(strategy.p95_latency_ms("https://fast.rpc"), Some(30));
+        assert_eq!(strategy.p95_latency_ms("https://unknown.rpc"), None);
+
+        // EMA mode ignores recorded samples
+        let mut ema = LatencyBasedStrategy::new();
+        ema.record_latency("https://fast.rpc", 10);
+        assert_eq!(ema.p95_latency_ms("https://fast.rpc"), None);
+    }
+
+    #[test]
+    fn test_min_samples_warms_up_new_endpoints() {
+        let ctx = SelectionContext::default();
+        let mut strategy = LatencyBasedStrategy::new().with_min_samples(4);
+        let endpoints = create_test_endpoints();
+        let exclude = HashSet::new();
+
+        let mut stats: HashMap<String, EndpointStats> = endpoints
+            .iter()
+            .map(|e| (e.url.clone(), EndpointStats::new(e)))
+            .collect();
+        for (url, latency) in [("https://slow.rpc", 500.0), ("https://fast.rpc", 50.0)] {
+            let stat = stats.get_mut(url).unwrap();
+            stat.total_requests = 100;
+            stat.avg_latency_ms = latency;
+        }
+
+        // The new endpoint gets one selection in four instead of all of them
+        let mut counts: HashMap<&str, usize> = HashMap::new();
+        for _ in 0..8 {
+            let selected = strategy.select(&endpoints, &stats, &exclude, &ctx).unwrap();
+            *counts.entry(selected.url.as_str()).or_default() += 1;
+        }
+        assert_eq!(counts.get("https://medium.rpc"), Some(&2));
+        assert_eq!(counts.get("https://fast.rpc"), Some(&6));
+
+        // Once it has enough samples it is ranked by latency like the others
+        let medium = stats.get_mut("https://medium.rpc").unwrap();
+        medium.total_requests = 4;
+        medium.avg_latency_ms = 200.0;
+        for _ in 0..8 {
+            let selected = strategy.select(&endpoints, &stats, &exclude, &ctx);
+            assert_eq!(selected.unwrap().url, "https://fast.rpc");
+        }
+    }
+
+    #[test]
+    fn test_min_samples_rotates_when_nothing_established() {
+        let ctx = SelectionContext::default();
+        let mut strategy = LatencyBasedStrategy::new().with_min_samples(10);
+        let endpoints = create_test_endpoints();
+        let stats: HashMap<String, EndpointStats> = endpoints
+            .iter()
+            .map(|e| (e.url.clone(), EndpointStats::new(e)))
+            .collect();
+
+        // All endpoints are warming, so they take turns
+        let exclude = HashSet::new();
+        let mut selected = HashSet::new();
+        for _ in 0..3 {
+            let endpoint = strategy.select(&endpoints, &stats, &exclude, &ctx).unwrap();
+            selected.insert(endpoint.url.as_str());
+        }
+        assert_eq!(selected.len(), 3);
+    }
 }
@@ -9,19 +9,42 @@
 //! - [`RoundRobinStrategy`]: Cycles through endpoints evenly (good for load distribution)
 //! - [`LatencyBasedStrategy`]: Selects fastest endpoint (best for latency-sensitive ops)
 //! - [`RateAwareStrategy`]: Tracks usage per endpoint, selects least recently used (best for free tier)
+//! - [`CircuitBreakerStrategy`]: Wraps another strategy, short-circuits endpoints with high failure rates
+//! - [`AdaptiveStrategy`]: Latency-based while the pool is healthy, failover when it is degraded
+//! - [`StrictPriorityStrategy`]: Lowest priority number first, always starting from the top (best for fixed preference orders)
+//! - [`GeoAwareStrategy`]: Endpoints in a preferred region first, then the rest by priority (best for clients far from most endpoints)
+//! - [`HashRingStrategy`]: Consistent hashing of one-second time windows onto endpoints (best for caching layers behind the pool)
+//! - [`ThrottleAwareStrategy`]: Skips endpoints until the `Retry-After` of their last HTTP 429 has passed (best for free tier)
 
+mod adaptive;
+mod circuit_breaker;
 mod failover;
+mod geo_aware;
+mod hash_ring;
 mod latency_based;
 mod rate_aware;
 mod round_robin;
+mod strict_priority;
+mod throttle_aware;
 
+pub use adaptive::{AdaptiveMode, AdaptiveStrategy};
+pub use circuit_breaker::{CircuitBreakerStrategy, CircuitState};
 pub use failover::FailoverStrategy;
+pub use geo_aware::GeoAwareStrategy;
+pub use hash_ring::HashRingStrategy;
 pub use latency_based::LatencyBasedStrategy;
 pub use rate_aware::RateAwareStrategy;
 pub use round_robin::RoundRobinStrategy;
+pub use strict_priority::StrictPriorityStrategy;
+pub use throttle_aware::{parse_retry_after, ThrottleAwareStrategy};
 
 use crate::endpoint::{EndpointStats, RpcEndpoint};
+use serde::{Deserialize, Serialize};
+use std::any::Any;
 use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
 
 /// Trait for endpoint selection strategies.
 ///
@@ -34,6 +57,11 @@ pub trait SelectionStrategy: Send + Sync {
     /// * `endpoints` - All configured endpoints (sorted by priority).
     /// * `stats` - Current statistics for each endpoint.
     /// * `exclude` - URLs to exclude (already tried in current request).
+    /// * `ctx` - Request-level hints from the call site; all-false unless the
+    ///   request was made with
+    ///   [`RpcPool::execute_with_context_hint`](crate::RpcPool::execute_with_context_hint)
+    ///   or [`RpcPool::execute_with_context`](crate::RpcPool::execute_with_context).
+    ///   Strategies that wrap another one pass it on unchanged.
     ///
     /// # Returns
     ///
@@ -43,8 +71,139 @@ pub trait SelectionStrategy: Send + Sync {
         endpoints: &'a [RpcEndpoint],
         stats: &HashMap<String, EndpointStats>,
         exclude: &HashSet<String>,
+        ctx: &SelectionContext,
     ) -> Option<&'a RpcEndpoint>;
 
+    /// The endpoint [`select`](Self::select) would pick, without side effects.
+    ///
+    /// Used by [`RpcPool::execute_dry_run`](crate::RpcPool::execute_dry_run),
+    /// which must not change where the next real request goes. The default
+    /// calls `select`, which suits strategies without per-selection state;
+    /// strategies that record selections, and strategies that wrap another
+    /// one, override this.
+    fn peek<'a>(
+        &mut self,
+        endpoints: &'a [RpcEndpoint],
+        stats: &HashMap<String, EndpointStats>,
+        exclude: &HashSet<String>,
+        ctx: &SelectionContext,
+    ) -> Option<&'a RpcEndpoint> {
+        self.select(endpoints, stats, exclude, ctx)
+    }
+
     /// Name of this strategy for logging.
     fn name(&self) -> &'static str;
+
+    /// Observe a successful request.
+    ///
+    /// Called by the pool after the endpoint's stats have been updated.
+    /// Strategies that keep their own history override this; the default ignores it.
+    fn on_success(&mut self, _url: &str, _latency_ms: u64) {}
+
+    /// Observe a failed request.
+    ///
+    /// Called by the pool after the endpoint's stats have been updated.
+    /// The default ignores it.
+    fn on_failure(&mut self, _url: &str, _error: &str) {}
+
+    /// Access the strategy's [`ThrottleRecorder`], if it keeps throttle state.
+    ///
+    /// The pool calls this after a rate-limited failure that carries a
+    /// `Retry-After` value. The default returns `None`.
+    fn as_throttle_recorder(&mut self) -> Option<&mut dyn ThrottleRecorder> {
+        None
+    }
+}
+
+/// Request-level hints passed from the call site to [`SelectionStrategy::select`].
+///
+/// The built-in strategies ignore these hints; custom strategies can use them
+/// to route e.g. writes or archive queries to suitable endpoints.
+#[derive(Clone, Default)]
+pub struct SelectionContext {
+    /// The request changes chain state, e.g. `eth_sendRawTransaction`.
+    pub is_write: bool,
+
+    /// The request reads historical state only archive nodes keep.
+    pub requires_archive: bool,
+
+    /// Application-defined hints.
+    pub custom: HashMap<String, String>,
+
+    /// Typed application context, as passed to
+    /// [`RpcPool::execute_with_context`](crate::RpcPool::execute_with_context).
+    ///
+    /// Read it with [`extension`](Self::extension). Compared by identity.
+    pub extension: Option<Arc<dyn Any + Send + Sync>>,
+}
+
+impl SelectionContext {
+    /// The typed application context, if one was given and it is a `T`.
+    pub fn extension<T: Any>(&self) -> Option<&T> {
+        self.extension.as_deref()?.downcast_ref()
+    }
+}
+
+impl fmt::Debug for SelectionContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SelectionContext")
+            .field("is_write", &self.is_write)
+            .field("requires_archive", &self.requires_archive)
+            .field("custom", &self.custom)
+            .field("extension", &self.extension.is_some())
+            .finish()
+    }
+}
+
+impl PartialEq for SelectionContext {
+    fn eq(&self, other: &Self) -> bool {
+        let same_extension = match (&self.extension, &other.extension) {
+            (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+            (a, b) => a.is_none() && b.is_none(),
+        };
+        self.is_write == other.is_write
+            && self.requires_archive == other.requires_archive
+            && self.custom == other.custom
+            && same_extension
+    }
+}
+
+impl Eq for SelectionContext {}
+
+/// Strategies that can be told an endpoint asked the client to back off.
+pub trait ThrottleRecorder {
+    /// Record that `url` must not be used for `retry_after`.
+    fn record_throttle(&mut self, url: &str, retry_after: Duration);
+}
+
+/// Built-in strategies, addressable by name in configuration files.
+///
+/// Names match [`SelectionStrategy::name`] of the corresponding strategy.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum StrategyKind {
+    /// [`FailoverStrategy`].
+    #[default]
+    Failover,
+    /// [`RoundRobinStrategy`].
+    RoundRobin,
+    /// [`LatencyBasedStrategy`].
+    LatencyBased,
+    /// [`RateAwareStrategy`] with its default interval.
+    RateAware,
+    /// [`StrictPriorityStrategy`].
+    StrictPriority,
+}
+
+impl StrategyKind {
+    /// Instantiate the strategy with its default settings.
+    pub fn build(self) -> Box<dyn SelectionStrategy> {
+        match self {
+            StrategyKind::Failover => Box::new(FailoverStrategy),
+            StrategyKind::RoundRobin => Box::new(RoundRobinStrategy::new()),
+            StrategyKind::LatencyBased => Box::new(LatencyBasedStrategy::new()),
+            StrategyKind::RateAware => Box::new(RateAwareStrategy::new()),
+            StrategyKind::StrictPriority => Box::new(StrictPriorityStrategy),
+        }
+    }
 }
@@ -1,10 +1,10 @@
 //! Rate-aware strategy - distributes load while respecting rate limits.
 //!
 //! This strategy tracks the last request time for each endpoint and
-//! selects the endpoint that has been idle the longest, naturally
-//! distributing load across all available endpoints.
+//! selects the endpoint that has been idle the longest relative to its
+//! rate limit, naturally distributing load across all available endpoints.
 
-use super::SelectionStrategy;
+use super::{SelectionContext, SelectionStrategy};
 use crate::endpoint::{EndpointStats, RpcEndpoint};
 use parking_lot::RwLock;
 use std::collections::{HashMap, HashSet};
@@ -13,8 +13,12 @@ use std::time::{Duration, Instant};
 /// Rate-aware selection strategy.
 ///
 /// Tracks last request time per endpoint and selects the one that
-/// has been idle longest. This naturally distributes load across
-/// all endpoints and helps stay within rate limits.
+/// has been idle longest, measured in multiples of its minimum interval.
+/// An endpoint with a known `rate_limit_rps` in its
+/// [`EndpointCapabilities`](crate::EndpointCapabilities) gets an interval of
+/// `1 / rate_limit_rps` seconds; others use the strategy's default interval.
+/// This naturally distributes load across all endpoints in proportion to
+/// their limits and helps stay within them.
 ///
 /// Best for: Free tier RPCs where you want to maximize throughput
 /// while staying within each provider's rate limits.
@@ -22,7 +26,7 @@ pub struct RateAwareStrategy {
     /// Last request time for each endpoint URL.
     last_request: RwLock<HashMap<String, Instant>>,
 
-    /// Minimum interval between requests to the same endpoint.
+    /// Minimum interval between requests to an endpoint without a known rate limit.
     /// Default: 1 second (allows 1 req/s per endpoint).
     min_interval: Duration,
 }
@@ -44,8 +48,9 @@ impl RateAwareStrategy {
 
     /// Create with custom minimum interval between requests to same endpoint.
     ///
-    /// For example, if you have 10 endpoints and want 10 req/s total,
-    /// set interval to 1 second (each endpoint gets 1 req/s).
+    /// The interval applies to endpoints without a `rate_limit_rps`. For
+    /// example, if you have 10 endpoints and want 10 req/s total, set
+    /// interval to 1 second (each endpoint gets 1 req/s).
     pub fn with_min_interval(min_interval: Duration) -> Self {
         Self {
             last_request: RwLock::new(HashMap::new()),
@@ -70,36 +75,74 @@ impl RateAwareStrategy {
     }
 
     /// Check if endpoint is ready (enough time passed since last request).
+    ///
+    /// Uses the default interval; see [`is_endpoint_ready`](Self::is_endpoint_ready)
+    /// to honour the endpoint's own rate limit.
+    #[allow(dead_code)]
     fn is_ready(&self, url: &str) -> bool {
         self.time_since_last(url) >= self.min_interval
     }
-}
 
-impl SelectionStrategy for RateAwareStrategy {
-    fn select<'a>(
-        &mut self,
+    /// Minimum interval between requests to `endpoint`.
+    ///
+    /// `1 / rate_limit_rps` seconds if the endpoint's capabilities give a
+    /// nonzero rate limit, otherwise the default interval.
+    pub fn min_interval_for(&self, endpoint: &RpcEndpoint) -> Duration {
+        match endpoint.capabilities.rate_limit_rps {
+            Some(rps) if rps > 0 => Duration::from_secs_f64(1.0 / f64::from(rps)),
+            _ => self.min_interval,
+        }
+    }
+
+    /// Check if enough time passed since the last request to `endpoint`,
+    /// according to its own rate limit.
+    pub fn is_endpoint_ready(&self, endpoint: &RpcEndpoint) -> bool {
+        self.time_since_last(&endpoint.url) >= self.min_interval_for(endpoint)
+    }
+
+    /// Idle time of `endpoint` in multiples of its minimum interval.
+    fn readiness(&self, endpoint: &RpcEndpoint) -> f64 {
+        let Some(last) = self.last_request.read().get(&endpoint.url).copied() else {
+            return f64::INFINITY; // Never used = maximum idle time
+        };
+        // A zero interval still ranks by idle time
+        let interval = self.min_interval_for(endpoint).as_secs_f64().max(1e-9);
+        last.elapsed().as_secs_f64() / interval
+    }
+
+    /// The healthy, non-excluded endpoint that has been idle longest, without recording it.
+    fn most_rested<'a>(
+        &self,
         endpoints: &'a [RpcEndpoint],
         stats: &HashMap<String, EndpointStats>,
         exclude: &HashSet<String>,
     ) -> Option<&'a RpcEndpoint> {
-        // Collect healthy, non-excluded endpoints with their idle time
+        // Collect healthy, non-excluded endpoints with their idle time relative to their interval
         let mut candidates: Vec<_> = endpoints
             .iter()
             .filter(|e| !exclude.contains(&e.url))
             .filter(|e| stats.get(&e.url).map(|s| s.is_healthy).unwrap_or(true))
-            .map(|e| (e, self.time_since_last(&e.url)))
+            .map(|e| (e, self.readiness(e)))
             .collect();
 
-        if candidates.is_empty() {
+        // Sort by relative idle time descending (most rested first); ties keep list order
+        candidates.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        candidates.first().map(|(e, _)| *e)
+    }
+}
+
+impl SelectionStrategy for RateAwareStrategy {
+    fn select<'a>(
+        &mut self,
+        endpoints: &'a [RpcEndpoint],
+        stats: &HashMap<String, EndpointStats>,
+        exclude: &HashSet<String>,
+        _ctx: &SelectionContext,
+    ) -> Option<&'a RpcEndpoint> {
+        let Some(selected) = self.most_rested(endpoints, stats, exclude) else {
             // Fallback: any non-excluded endpoint
             return endpoints.iter().find(|e| !exclude.contains(&e.url));
-        }
-
-        // Sort by idle time descending (longest idle first)
-        candidates.sort_by(|a, b| b.1.cmp(&a.1));
-
-        // Select the endpoint that has been idle longest
-        let selected = candidates.first().map(|(e, _)| *e)?;
+        };
 
         // Record this selection
         self.record_request(&selected.url);
@@ -107,6 +150,17 @@ impl SelectionStrategy for RateAwareStrategy {
         Some(selected)
     }
 
+    fn peek<'a>(
+        &mut self,
+        endpoints: &'a [RpcEndpoint],
+        stats: &HashMap<String, EndpointStats>,
+        exclude: &HashSet<String>,
+        _ctx: &SelectionContext,
+    ) -> Option<&'a RpcEndpoint> {
+        self.most_rested(endpoints, stats, exclude)
+            .or_else(|| endpoints.iter().find(|e| !exclude.contains(&e.url)))
+    }
+
     fn name(&self) -> &'static str {
         "rate-aware"
     }
@@ -140,15 +194,21 @@ mod tests {
         let exclude = HashSet::new();
 
         // First selection - any endpoint (all have max idle time)
-        let first = strategy.select(&endpoints, &stats, &exclude).unwrap();
+        let first = strategy
+            .select(&endpoints, &stats, &exclude, &SelectionContext::default())
+            .unwrap();
         let first_url = first.url.clone();
 
         // Second selection should pick a different endpoint (first one just used)
-        let second = strategy.select(&endpoints, &stats, &exclude).unwrap();
+        let second = strategy
+            .select(&endpoints, &stats, &exclude, &SelectionContext::default())
+            .unwrap();
         assert_ne!(first_url, second.url);
 
         // Third selection should pick the remaining endpoint
-        let third = strategy.select(&endpoints, &stats, &exclude).unwrap();
+        let third = strategy
+            .select(&endpoints, &stats, &exclude, &SelectionContext::default())
+            .unwrap();
         assert_ne!(first_url, third.url);
         assert_ne!(second.url, third.url);
     }
@@ -161,7 +221,7 @@ mod tests {
         let exclude = HashSet::new();
 
         // First request
-        strategy.select(&endpoints, &stats, &exclude);
+        strategy.select(&endpoints, &stats, &exclude, &SelectionContext::default());
 
         // Check readiness
         assert!(!strategy.is_ready("https://rpc1.example.com"));
@@ -172,6 +232,24 @@ mod tests {
         assert!(strategy.is_ready("https://rpc1.example.com"));
     }
 
+    #[test]
+    fn test_peek_does_not_record() {
+        let mut strategy = RateAwareStrategy::with_min_interval(Duration::from_secs(60));
+        let endpoints = create_test_endpoints();
+        let stats = create_stats(&endpoints);
+        let exclude = HashSet::new();
+        let ctx = SelectionContext::default();
+
+        let peeked = strategy.peek(&endpoints, &stats, &exclude, &ctx).unwrap();
+        assert_eq!(peeked.url, "https://rpc1.example.com");
+        assert!(strategy.is_ready("https://rpc1.example.com"));
+
+        // The next real selection is the endpoint the peek reported
+        let selected = strategy.select(&endpoints, &stats, &exclude, &ctx).unwrap();
+        assert_eq!(selected.url, peeked.url);
+        assert!(!strategy.is_ready("https://rpc1.example.com"));
+    }
+
     #[test]
     fn test_cycles_through_all_endpoints() {
         let mut strategy = RateAwareStrategy::with_min_interval(Duration::from_millis(1));
@@ -183,7 +261,9 @@ mod tests {
 
         // Make 3 selections
         for _ in 0..3 {
-            let selected = strategy.select(&endpoints, &stats, &exclude).unwrap();
+            let selected = strategy
+                .select(&endpoints, &stats, &exclude, &SelectionContext::default())
+                .unwrap();
             seen.insert(selected.url.clone());
             sleep(Duration::from_millis(2)); // Small delay to ensure ordering
         }
@@ -191,4 +271,49 @@ mod tests {
         // Should have used all 3 endpoints
         assert_eq!(seen.len(), 3);
     }
+
+    #[test]
+    fn test_per_endpoint_rate_limit() {
+        let mut strategy = RateAwareStrategy::new();
+        let fast = RpcEndpoint::new("https://fast.example.com").with_capabilities(
+            crate::EndpointCapabilities {
+                rate_limit_rps: Some(100),
+                ..Default::default()
+            },
+        );
+        let endpoints = vec![RpcEndpoint::new("https://default.example.com"), fast];
+        let stats = create_stats(&endpoints);
+        let exclude = HashSet::new();
+
+        assert_eq!(
+            strategy.min_interval_for(&endpoints[0]),
+            Duration::from_secs(1)
+        );
+        assert_eq!(
+            strategy.min_interval_for(&endpoints[1]),
+            Duration::from_millis(10)
+        );
+
+        // Both endpoints get their first request in list order
+        let first = strategy
+            .select(&endpoints, &stats, &exclude, &SelectionContext::default())
+            .unwrap();
+        assert_eq!(first.url, "https://default.example.com");
+        let second = strategy
+            .select(&endpoints, &stats, &exclude, &SelectionContext::default())
+            .unwrap();
+        assert_eq!(second.url, "https://fast.example.com");
+
+        // After 20ms the 100 req/s endpoint is rested, the 1 req/s one is not
+        sleep(Duration::from_millis(20));
+        assert!(strategy.is_endpoint_ready(&endpoints[1]));
+        assert!(!strategy.is_endpoint_ready(&endpoints[0]));
+        for _ in 0..3 {
+            let selected = strategy
+                .select(&endpoints, &stats, &exclude, &SelectionContext::default())
+                .unwrap();
+            assert_eq!(selected.url, "https://fast.example.com");
+            sleep(Duration::from_millis(20));
+        }
+    }
 }
@@ -1,27 +1,29 @@
 //! Round-robin strategy - cycles through endpoints evenly.
 
-use super::SelectionStrategy;
+use super::{SelectionContext, SelectionStrategy};
 use crate::endpoint::{EndpointStats, RpcEndpoint};
 use std::collections::{HashMap, HashSet};
-use std::sync::atomic::{AtomicUsize, Ordering};
 
 /// Round-robin selection strategy.
 ///
 /// Cycles through healthy endpoints to distribute load evenly.
 /// Skips unhealthy endpoints.
 ///
+/// The position is remembered across requests: each selection starts just
+/// after the previously selected endpoint, so failover attempts within one
+/// request advance the rotation instead of resetting it.
+///
 /// Best for: Load balancing across multiple equivalent endpoints.
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub struct RoundRobinStrategy {
-    current_index: AtomicUsize,
+    /// Index into the endpoint list where the next scan starts.
+    next_index: usize,
 }
 
 impl RoundRobinStrategy {
     /// Create a new round-robin strategy.
     pub fn new() -> Self {
-        Self {
-            current_index: AtomicUsize::new(0),
-        }
+        Self::default()
     }
 }
 
@@ -31,22 +33,44 @@ impl SelectionStrategy for RoundRobinStrategy {
         endpoints: &'a [RpcEndpoint],
         stats: &HashMap<String, EndpointStats>,
         exclude: &HashSet<String>,
+        _ctx: &SelectionContext,
     ) -> Option<&'a RpcEndpoint> {
-        // Collect healthy, non-excluded endpoints
-        let healthy: Vec<_> = endpoints
-            .iter()
-            .filter(|e| !exclude.contains(&e.url))
-            .filter(|e| stats.get(&e.url).map(|s| s.is_healthy).unwrap_or(true))
-            .collect();
-
-        if healthy.is_empty() {
-            // Fallback: any non-excluded endpoint
-            return endpoints.iter().find(|e| !exclude.contains(&e.url));
+        if endpoints.is_empty() {
+            return None;
         }
 
-        // Get and increment index atomically
-        let idx = self.current_index.fetch_add(1, Ordering::Relaxed) % healthy.len();
-        Some(healthy[idx])
+        // Scan from the remembered position, wrapping around the list
+        let start = self.next_index % endpoints.len();
+        let rotation = || (0..endpoints.len()).map(|offset| (start + offset) % endpoints.len());
+        let available = |i: &usize| !exclude.contains(&endpoints[*i].url);
+        let healthy = |i: &usize| {
+            stats
+                .get(&endpoints[*i].url)
+                .map(|s| s.is_healthy)
+                .unwrap_or(true)
+        };
+
+        // Fallback: any non-excluded endpoint
+        let idx = rotation()
+            .filter(available)
+            .find(healthy)
+            .or_else(|| rotation().find(available))?;
+
+        self.next_index = idx + 1;
+        Some(&endpoints[idx])
+    }
+
+    fn peek<'a>(
+        &mut self,
+        endpoints: &'a [RpcEndpoint],
+        stats: &HashMap<String, EndpointStats>,
+        exclude: &HashSet<String>,
+        ctx: &SelectionContext,
+    ) -> Option<&'a RpcEndpoint> {
+        let next_index = self.next_index;
+        let selected = self.select(endpoints, stats, exclude, ctx);
+        self.next_index = next_index;
+        selected
     }
 
     fn name(&self) -> &'static str {
@@ -75,16 +99,17 @@ mod tests {
 
     #[test]
     fn test_cycles_through_endpoints() {
+        let ctx = SelectionContext::default();
         let mut strategy = RoundRobinStrategy::new();
         let endpoints = create_test_endpoints();
         let stats = create_stats(&endpoints);
         let exclude = HashSet::new();
 
         // Should cycle through all endpoints
-        let first = strategy.select(&endpoints, &stats, &exclude).unwrap().url.clone();
-        let second = strategy.select(&endpoints, &stats, &exclude).unwrap().url.clone();
-        let third = strategy.select(&endpoints, &stats, &exclude).unwrap().url.clone();
-        let fourth = strategy.select(&endpoints, &stats, &exclude).unwrap().url.clone();
+        let first = strategy.select(&endpoints, &stats, &exclude, &ctx).unwrap().url.clone();
+        let second = strategy.select(&endpoints, &stats, &exclude, &ctx).unwrap().url.clone();
+        let third = strategy.select(&endpoints, &stats, &exclude, &ctx).unwrap().url.clone();
+        let fourth = strategy.select(&endpoints, &stats, &exclude, &ctx).unwrap().url.clone();
 
         // Fourth should wrap around to first
         assert_eq!(first, fourth);
@@ -92,4 +117,64 @@ mod tests {
         assert_ne!(first, second);
         assert_ne!(second, third);
     }
+
+    #[test]
+    fn test_skips_unhealthy_endpoints() {
+        let ctx = SelectionContext::default();
+        let mut strategy = RoundRobinStrategy::new();
+        let endpoints = create_test_endpoints();
+        let mut stats = create_stats(&endpoints);
+        stats.get_mut("https://rpc1.example.com").unwrap().is_healthy = false;
+        let exclude = HashSet::new();
+
+        let selected: Vec<_> = (0..4)
+            .map(|_| strategy.select(&endpoints, &stats, &exclude, &ctx).unwrap().url.clone())
+            .collect();
+        assert_eq!(
+            selected,
+            vec![
+                "https://rpc2.example.com",
+                "https://rpc3.example.com",
+                "https://rpc2.example.com",
+                "https://rpc3.example.com",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_resumes_position_after_failover() {
+        let ctx = SelectionContext::default();
+        let mut strategy = RoundRobinStrategy::new();
+        let endpoints = create_test_endpoints();
+        let stats = create_stats(&endpoints);
+
+        // First request fails over from rpc1 to rpc2
+        let mut exclude = HashSet::new();
+        let first = strategy.select(&endpoints, &stats, &exclude, &ctx).unwrap();
+        exclude.insert(first.url.clone());
+        let retry = strategy.select(&endpoints, &stats, &exclude, &ctx).unwrap();
+        assert_eq!(retry.url, "https://rpc2.example.com");
+
+        // Next request continues the rotation rather than restarting at rpc1
+        let next = strategy.select(&endpoints, &stats, &HashSet::new(), &ctx).unwrap();
+        assert_eq!(next.url, "https://rpc3.example.com");
+    }
+
+    #[test]
+    fn test_all_unhealthy_falls_back_to_rotation() {
+        let ctx = SelectionContext::default();
+        let mut strategy = RoundRobinStrategy::new();
+        let endpoints = create_test_endpoints();
+        let mut stats = create_stats(&endpoints);
+        for s in stats.values_mut() {
+            s.is_healthy = false;
+        }
+        let exclude = HashSet::new();
+
+        let first = strategy.select(&endpoints, &stats, &exclude, &ctx).unwrap().url.clone();
+        let second = strategy.select(&endpoints, &stats, &exclude, &ctx).unwrap().url.clone();
+        assert_eq!(first, "https://rpc1.example.com");
+        assert_eq!(second, "https://rpc2.example.com");
+        assert!(strategy.select(&[], &stats, &exclude, &ctx).is_none());
+    }
 }
@@ -0,0 +1,117 @@
+//! Strict priority strategy - ranks endpoints by their priority number alone.
+
+use super::{SelectionContext, SelectionStrategy};
+use crate::endpoint::{EndpointStats, RpcEndpoint};
+use std::collections::{HashMap, HashSet};
+
+/// Strict priority selection strategy.
+///
+/// Treats [`RpcEndpoint::priority`] as an absolute ranking: selects the healthy,
+/// non-excluded endpoint with the lowest priority number, ties going to the
+/// endpoint listed first. It keeps no state, so every request starts again
+/// from the top-ranked endpoint, and it does not rely on the endpoint list
+/// being sorted. Falls back to the lowest priority number among unhealthy
+/// endpoints if no healthy one is left.
+///
+/// Best for: Setups where priority numbers encode a fixed preference order,
+/// e.g. an own node at 1, a paid provider at 2 and public endpoints after.
+#[derive(Debug, Default, Clone)]
+pub struct StrictPriorityStrategy;
+
+impl SelectionStrategy for StrictPriorityStrategy {
+    fn select<'a>(
+        &mut self,
+        endpoints: &'a [RpcEndpoint],
+        stats: &HashMap<String, EndpointStats>,
+        exclude: &HashSet<String>,
+        _ctx: &SelectionContext,
+    ) -> Option<&'a RpcEndpoint> {
+        let candidates = || endpoints.iter().filter(|e| !exclude.contains(&e.url));
+
+        // min_by_key keeps the first of equal keys, preserving list order on ties
+        candidates()
+            .filter(|e| stats.get(&e.url).map(|s| s.is_healthy).unwrap_or(true))
+            .min_by_key(|e| e.priority)
+            .or_else(|| candidates().min_by_key(|e| e.priority))
+    }
+
+    fn name(&self) -> &'static str {
+        "strict-priority"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Endpoints deliberately out of priority order.
+    fn create_test_endpoints() -> Vec<RpcEndpoint> {
+        vec![
+            RpcEndpoint::new("https://third.rpc").with_priority(3),
+            RpcEndpoint::new("https://first.rpc").with_priority(1),
+            RpcEndpoint::new("https://second.rpc").with_priority(2),
+        ]
+    }
+
+    fn create_stats(endpoints: &[RpcEndpoint]) -> HashMap<String, EndpointStats> {
+        endpoints
+            .iter()
+            .map(|e| (e.url.clone(), EndpointStats::new(e)))
+            .collect()
+    }
+
+    #[test]
+    fn test_always_selects_lowest_priority_number() {
+        let mut strategy = StrictPriorityStrategy;
+        let endpoints = create_test_endpoints();
+        let stats = create_stats(&endpoints);
+        let exclude = HashSet::new();
+
+        for _ in 0..3 {
+            let selected =
+                strategy.select(&endpoints, &stats, &exclude, &SelectionContext::default());
+            assert_eq!(selected.unwrap().url, "https://first.rpc");
+        }
+
+        // Within a request, excluded endpoints give way to the next rank
+        let exclude: HashSet<String> = ["https://first.rpc".to_string()].into();
+        let selected = strategy.select(&endpoints, &stats, &exclude, &SelectionContext::default());
+        assert_eq!(selected.unwrap().url, "https://second.rpc");
+    }
+
+    #[test]
+    fn test_skips_unhealthy_and_falls_back() {
+        let mut strategy = StrictPriorityStrategy;
+        let endpoints = create_test_endpoints();
+        let mut stats = create_stats(&endpoints);
+        let exclude = HashSet::new();
+
+        stats.get_mut("https://first.rpc").unwrap().is_healthy = false;
+        let selected = strategy.select(&endpoints, &stats, &exclude, &SelectionContext::default());
+        assert_eq!(selected.unwrap().url, "https://second.rpc");
+
+        for stat in stats.values_mut() {
+            stat.is_healthy = false;
+        }
+        let selected = strategy.select(&endpoints, &stats, &exclude, &SelectionContext::default());
+        assert_eq!(selected.unwrap().url, "https://first.rpc");
+    }
+
+    #[test]
+    fn test_ties_keep_list_order() {
+        let mut strategy = StrictPriorityStrategy;
+        let endpoints = vec![
+            RpcEndpoint::new("https://a.rpc").with_priority(5),
+            RpcEndpoint::new("https://b.rpc").with_priority(5),
+        ];
+        let stats = create_stats(&endpoints);
+
+        let selected = strategy.select(
+            &endpoints,
+            &stats,
+            &HashSet::new(),
+            &SelectionContext::default(),
+        );
+        assert_eq!(selected.unwrap().url, "https://a.rpc");
+    }
+}
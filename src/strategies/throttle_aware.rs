@@ -0,0 +1,291 @@
+//! Throttle-aware strategy - skips endpoints that asked the client to back off.
+//!
+//! When an endpoint answers HTTP 429 with a `Retry-After` header, the pool
+//! passes the requested delay to [`ThrottleRecorder::record_throttle`]. The
+//! strategy hides the endpoint from selection until that time has passed and
+//! delegates the choice among the remaining endpoints to an inner strategy.
+
+use super::{FailoverStrategy, SelectionContext, SelectionStrategy, ThrottleRecorder};
+use crate::endpoint::{EndpointStats, RpcEndpoint};
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tracing::debug;
+
+/// Throttle-aware selection strategy.
+///
+/// Keeps a throttle-until time per endpoint, set from `Retry-After` values of
+/// rate-limited responses, and never selects an endpoint before its time has
+/// passed. Other endpoints are chosen by the inner strategy, failover by default.
+///
+/// Best for: Free-tier endpoints that announce how long to back off.
+pub struct ThrottleAwareStrategy {
+    /// Strategy used to pick among endpoints that are not throttled.
+    inner: Box<dyn SelectionStrategy>,
+
+    /// Time until which each throttled endpoint must not be used, keyed by URL.
+    throttled_until: HashMap<String, Instant>,
+}
+
+impl ThrottleAwareStrategy {
+    /// Create a throttle-aware strategy selecting by priority.
+    pub fn new() -> Self {
+        Self {
+            inner: Box::new(FailoverStrategy),
+            throttled_until: HashMap::new(),
+        }
+    }
+
+    /// Builder: pick among unthrottled endpoints with `inner` instead of by priority.
+    pub fn with_inner(mut self, inner: Box<dyn SelectionStrategy>) -> Self {
+        self.inner = inner;
+        self
+    }
+
+    /// Throttle `url` for `retry_after`, extending any longer throttle already recorded.
+    pub fn record_throttle(&mut self, url: &str, retry_after: Duration) {
+        let until = Instant::now() + retry_after;
+        let entry = self.throttled_until.entry(url.to_string()).or_insert(until);
+        *entry = (*entry).max(until);
+        debug!(
+            endpoint_url = %url,
+            retry_after_ms = retry_after.as_millis() as u64,
+            "Endpoint throttled"
+        );
+    }
+
+    /// Whether `url` is throttled right now.
+    pub fn is_throttled(&self, url: &str) -> bool {
+        self.throttled_until
+            .get(url)
+            .is_some_and(|&until| until > Instant::now())
+    }
+
+    /// Select with `pick`, which is handed the inner strategy and the URLs to exclude.
+    fn select_unthrottled<'a>(
+        &mut self,
+        exclude: &HashSet<String>,
+        pick: impl FnOnce(&mut dyn SelectionStrategy, &HashSet<String>) -> Option<&'a RpcEndpoint>,
+    ) -> Option<&'a RpcEndpoint> {
+        let now = Instant::now();
+        self.throttled_until.retain(|_, until| *until > now);
+
+        let mut blocked = exclude.clone();
+        blocked.extend(self.throttled_until.keys().cloned());
+
+        let selected = pick(self.inner.as_mut(), &blocked);
+        if selected.is_none() && !self.throttled_until.is_empty() {
+            debug!(
+                throttled = self.throttled_until.len(),
+                "No unthrottled endpoint available"
+            );
+        }
+        selected
+    }
+}
+
+impl Default for ThrottleAwareStrategy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ThrottleRecorder for ThrottleAwareStrategy {
+    fn record_throttle(&mut self, url: &str, retry_after: Duration) {
+        ThrottleAwareStrategy::record_throttle(self, url, retry_after);
+    }
+}
+
+impl SelectionStrategy for ThrottleAwareStrategy {
+    fn select<'a>(
+        &mut self,
+        endpoints: &'a [RpcEndpoint],
+        stats: &HashMap<String, EndpointStats>,
+        exclude: &HashSet<String>,
+        ctx: &SelectionContext,
+    ) -> Option<&'a RpcEndpoint> {
+        self.select_unthrottled(exclude, |inner, blocked| {
+            inner.select(endpoints, stats, blocked, ctx)
+        })
+    }
+
+    fn peek<'a>(
+        &mut self,
+        endpoints: &'a [RpcEndpoint],
+        stats: &HashMap<String, EndpointStats>,
+        exclude: &HashSet<String>,
+        ctx: &SelectionContext,
+    ) -> Option<&'a RpcEndpoint> {
+        self.select_unthrottled(exclude, |inner, blocked| {
+            inner.peek(endpoints, stats, blocked, ctx)
+        })
+    }
+
+    fn name(&self) -> &'static str {
+        "throttle-aware"
+    }
+
+    fn on_success(&mut self, url: &str, latency_ms: u64) {
+        self.inner.on_success(url, latency_ms);
+    }
+
+    fn on_failure(&mut self, url: &str, error: &str) {
+        self.inner.on_failure(url, error);
+    }
+
+    fn as_throttle_recorder(&mut self) -> Option<&mut dyn ThrottleRecorder> {
+        Some(self)
+    }
+}
+
+/// Extract the delay requested by a `Retry-After` value in an error message.
+///
+/// Looks for `retry-after` (any case) followed by `:` or `=` and either a
+/// number of seconds or an HTTP date such as `Wed, 21 Oct 2015 07:28:00 GMT`.
+/// A date in the past yields a zero delay.
+pub fn parse_retry_after(message: &str) -> Option<Duration> {
+    let lower = message.to_ascii_lowercase();
+    let start = lower.find("retry-after")? + "retry-after".len();
+    let value = message[start..]
+        .trim_start_matches(['"', '\''])
+        .trim_start();
+    let value = value.strip_prefix([':', '='])?.trim_start();
+    let value = value.trim_start_matches(['"', '\'']);
+
+    let digits: &str = &value[..value.find(|c: char| !c.is_ascii_digit()).unwrap_or(value.len())];
+    if !digits.is_empty() {
+        return digits.parse().ok().map(Duration::from_secs);
+    }
+
+    let until = parse_http_date(value)?;
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).ok()?.as_secs();
+    Some(Duration::from_secs(until.saturating_sub(now)))
+}
+
+/// Parse an IMF-fixdate (`Wed, 21 Oct 2015 07:28:00 GMT`) into Unix seconds.
+fn parse_http_date(value: &str) -> Option<u64> {
+    const MONTHS: [&str; 12] = [
+        "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+    ];
+
+    let mut parts = value.split_whitespace();
+    let _weekday = parts.next()?.strip_suffix(',')?;
+    let day: u64 = parts.next()?.parse().ok()?;
+    let month = parts.next()?.to_ascii_lowercase();
+    let month = MONTHS.iter().position(|&m| m == month)? as u64 + 1;
+    let year: u64 = parts.next()?.parse().ok()?;
+    let mut time = parts.next()?.split(':').map(|p| p.parse::<u64>().ok());
+    let (hour, minute, second) = (time.next()??, time.next()??, time.next()??);
+    if !parts.next()?.starts_with("GMT") || !(1..=31).contains(&day) || year < 1970 {
+        return None;
+    }
+
+    // Days since the epoch of a proleptic Gregorian date (Howard Hinnant's algorithm)
+    let (y, m) = if month <= 2 { (year - 1, month + 9) } else { (year, month - 3) };
+    let era = y / 400;
+    let yoe = y % 400;
+    let doy = (153 * m + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    let days = (era * 146_097 + doe).checked_sub(719_468)?;
+
+    Some(days * 86_400 + hour * 3_600 + minute * 60 + second)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PRIMARY: &str = "https://primary.rpc";
+    const SECONDARY: &str = "https://secondary.rpc";
+
+    fn create_test_endpoints() -> Vec<RpcEndpoint> {
+        vec![
+            RpcEndpoint::new(PRIMARY).with_priority(10),
+            RpcEndpoint::new(SECONDARY).with_priority(50),
+        ]
+    }
+
+    fn create_stats(endpoints: &[RpcEndpoint]) -> HashMap<String, EndpointStats> {
+        endpoints
+            .iter()
+            .map(|e| (e.url.clone(), EndpointStats::new(e)))
+            .collect()
+    }
+
+    #[test]
+    fn test_skips_throttled_endpoint_until_expiry() {
+        let mut strategy = ThrottleAwareStrategy::new();
+        let endpoints = create_test_endpoints();
+        let stats = create_stats(&endpoints);
+        let exclude = HashSet::new();
+
+        strategy.record_throttle(PRIMARY, Duration::from_millis(50));
+        assert!(strategy.is_throttled(PRIMARY));
+        let selected = strategy.select(&endpoints, &stats, &exclude, &SelectionContext::default());
+        assert_eq!(selected.unwrap().url, SECONDARY);
+
+        std::thread::sleep(Duration::from_millis(60));
+        assert!(!strategy.is_throttled(PRIMARY));
+        let selected = strategy.select(&endpoints, &stats, &exclude, &SelectionContext::default());
+        assert_eq!(selected.unwrap().url, PRIMARY);
+    }
+
+    #[test]
+    fn test_all_throttled_selects_nothing() {
+        let mut strategy = ThrottleAwareStrategy::new();
+        let endpoints = create_test_endpoints();
+        let stats = create_stats(&endpoints);
+
+        strategy.record_throttle(PRIMARY, Duration::from_secs(60));
+        strategy.record_throttle(SECONDARY, Duration::from_secs(60));
+        let selected = strategy.select(
+            &endpoints,
+            &stats,
+            &HashSet::new(),
+            &SelectionContext::default(),
+        );
+        assert!(selected.is_none());
+    }
+
+    #[test]
+    fn test_shorter_throttle_does_not_shorten_existing() {
+        let mut strategy = ThrottleAwareStrategy::new();
+        strategy.record_throttle(PRIMARY, Duration::from_secs(60));
+        strategy.record_throttle(PRIMARY, Duration::ZERO);
+        assert!(strategy.is_throttled(PRIMARY));
+    }
+
+    #[test]
+    fn test_parse_retry_after_seconds() {
+        assert_eq!(
+            parse_retry_after("HTTP 429 Too Many Requests, Retry-After: 12"),
+            Some(Duration::from_secs(12))
+        );
+        assert_eq!(
+            parse_retry_after("rate limited (retry-after=3s)"),
+            Some(Duration::from_secs(3))
+        );
+        assert_eq!(
+            parse_retry_after(r#"{"retry-after": "7"}"#),
+            Some(Duration::from_secs(7))
+        );
+        assert_eq!(parse_retry_after("HTTP 429 Too Many Requests"), None);
+    }
+
+    #[test]
+    fn test_parse_retry_after_http_date() {
+        assert_eq!(
+            parse_http_date("Wed, 21 Oct 2015 07:28:00 GMT"),
+            Some(1_445_412_480)
+        );
+        assert_eq!(parse_http_date("Thu, 01 Jan 1970 00:00:00 GMT"), Some(0));
+        assert_eq!(parse_http_date("21 Oct 2015"), None);
+
+        // Dates in the past mean "retry now"
+        assert_eq!(
+            parse_retry_after("Retry-After: Wed, 21 Oct 2015 07:28:00 GMT"),
+            Some(Duration::ZERO)
+        );
+        let delay = parse_retry_after("Retry-After: Fri, 01 Jan 2100 00:00:00 GMT").unwrap();
+        assert!(delay > Duration::from_secs(86_400 * 365));
+    }
+}
@@ -6,20 +6,30 @@
 //! - Using free public RPCs for non-urgent batch operations (historical sync)
 //! - Managing RPC costs by routing low-priority requests to free tiers
 
-use crate::endpoint::RpcEndpoint;
+use crate::config::{TieredEndpointFileConfig, TieredPoolConfigFile};
+use crate::endpoint::{CapabilityFilter, RpcEndpoint};
 use crate::error::RpcPoolError;
-use crate::pool::{RpcPool, RpcPoolConfig};
+use crate::metrics::RpcPoolMetrics;
+use crate::pool::{HealthSummary, RpcPool, RpcPoolConfig, WarmUpReport};
 use crate::presets;
 use crate::strategies::{FailoverStrategy, RateAwareStrategy, SelectionStrategy};
 
 use std::collections::{HashMap, HashSet};
 use std::future::Future;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
-use std::time::Duration;
-use tracing::{debug, info, warn};
+use std::time::{Duration, Instant};
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, info, warn, Instrument, Span};
+
+/// Default maximum number of shadow requests in flight.
+pub const DEFAULT_SHADOW_CONCURRENCY: usize = 32;
 
 /// Request priority levels.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum RequestPriority {
     /// Critical operations: liquidation execution, transaction submission.
     /// Uses premium tier first, falls back to standard.
@@ -27,6 +37,7 @@ pub enum RequestPriority {
 
     /// Normal operations: real-time health checks, price queries.
     /// Uses standard tier first, falls back to free.
+    #[default]
     Normal,
 
     /// Low priority: historical sync, batch queries, background tasks.
@@ -34,29 +45,26 @@ pub enum RequestPriority {
     Low,
 }
 
-impl Default for RequestPriority {
-    fn default() -> Self {
-        Self::Normal
-    }
-}
-
 /// Endpoint tier classification.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+///
+/// Non-exhaustive: `match`es outside this crate need a wildcard arm.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[non_exhaustive]
 pub enum EndpointTier {
     /// Premium tier: paid RPCs with high rate limits (Alchemy, Infura, QuickNode).
     Premium,
 
     /// Standard tier: reliable public RPCs with moderate limits.
+    #[default]
     Standard,
 
     /// Free tier: public RPCs with low rate limits, best for batch operations.
     Free,
-}
 
-impl Default for EndpointTier {
-    fn default() -> Self {
-        Self::Standard
-    }
+    /// Shadow tier: receives a fire-and-forget copy of every
+    /// [`TieredPool::execute_with_shadow`] request, e.g. to evaluate a new
+    /// provider. Its responses are discarded and it never serves as a fallback.
+    Shadow,
 }
 
 /// Extended endpoint configuration with tier information.
@@ -69,6 +77,8 @@ pub struct TieredEndpoint {
     pub tier: EndpointTier,
 
     /// Rate limit (requests per second), 0 = unlimited.
+    ///
+    /// Enforced by the tier's pool with a token bucket.
     pub rate_limit: u32,
 }
 
@@ -108,6 +118,11 @@ impl TieredEndpoint {
 }
 
 /// Configuration for the tiered RPC pool.
+///
+/// Non-exhaustive so new settings can be added without breaking callers:
+/// start from [`TieredPoolConfig::default`] and assign fields, or use
+/// [`TieredPoolBuilder`].
+#[non_exhaustive]
 pub struct TieredPoolConfig {
     /// Endpoints grouped by tier.
     pub endpoints: Vec<TieredEndpoint>,
@@ -126,6 +141,20 @@ pub struct TieredPoolConfig {
 
     /// Whether to allow fallback to higher tiers for low priority requests (not recommended).
     pub allow_low_escalation: bool,
+
+    /// Selection strategies overriding the tier defaults
+    /// (failover for premium and standard, rate-aware for free).
+    pub strategies: HashMap<EndpointTier, Box<dyn SelectionStrategy>>,
+
+    /// Tier receiving a fraction (0.0-1.0) of [`TieredPool::execute`] requests.
+    ///
+    /// Canary requests use only that tier and never fall back to the others.
+    pub canary_tier: Option<(EndpointTier, f64)>,
+
+    /// Maximum number of shadow requests in flight.
+    ///
+    /// Copies arriving while the limit is reached are dropped rather than queued.
+    pub shadow_concurrency: usize,
 }
 
 impl Default for TieredPoolConfig {
@@ -137,6 +166,9 @@ impl Default for TieredPoolConfig {
             retry_delay: Duration::from_secs(5),
             allow_critical_fallback: true,
             allow_low_escalation: false,
+            strategies: HashMap::new(),
+            canary_tier: None,
+            shadow_concurrency: DEFAULT_SHADOW_CONCURRENCY,
         }
     }
 }
@@ -149,15 +181,39 @@ pub struct TieredPool {
     /// Fallback configuration.
     allow_critical_fallback: bool,
     allow_low_escalation: bool,
+
+    /// Canary tier and the fraction of requests routed to it.
+    canary_tier: Option<(EndpointTier, f64)>,
+    canary_errors: AtomicU64,
+
+    /// Limits the shadow requests in flight; shared with the spawned tasks.
+    shadow_permits: Arc<Semaphore>,
+    shadow_errors: Arc<AtomicU64>,
+
+    /// Whether `execute` creates tracing spans.
+    tracing_enabled: bool,
+
+    /// Settings the tier pools were created with, for [`save_config`](Self::save_config).
+    health_check_interval: Duration,
+    max_consecutive_errors: u32,
+    retry_delay: Duration,
+    rate_limits: HashMap<String, u32>,
 }
 
 impl TieredPool {
     /// Create a new tiered pool from configuration.
-    pub fn new(config: TieredPoolConfig) -> Result<Self, RpcPoolError> {
+    pub fn new(mut config: TieredPoolConfig) -> Result<Self, RpcPoolError> {
         let mut tier_endpoints: HashMap<EndpointTier, Vec<RpcEndpoint>> = HashMap::new();
+        let mut tier_rate_limits: HashMap<EndpointTier, HashMap<String, u32>> = HashMap::new();
 
         // Group endpoints by tier
         for te in config.endpoints {
+            if te.rate_limit > 0 {
+                tier_rate_limits
+                    .entry(te.tier)
+                    .or_default()
+                    .insert(te.endpoint.url.clone(), te.rate_limit);
+            }
             tier_endpoints
                 .entry(te.tier)
                 .or_default()
@@ -167,6 +223,10 @@ impl TieredPool {
         if tier_endpoints.is_empty() {
             return Err(RpcPoolError::NoEndpointsConfigured);
         }
+        let rate_limits: HashMap<String, u32> = tier_rate_limits
+            .values()
+            .flat_map(|limits| limits.iter().map(|(url, &rps)| (url.clone(), rps)))
+            .collect();
 
         // Create a pool for each tier
         let mut pools = HashMap::new();
@@ -176,36 +236,125 @@ impl TieredPool {
                 continue;
             }
 
-            let strategy: Box<dyn SelectionStrategy> = match tier {
-                // Premium: use failover to maximize success rate
-                EndpointTier::Premium => Box::new(FailoverStrategy),
-                // Standard: use failover (paid RPCs, prefer reliability)
-                EndpointTier::Standard => Box::new(FailoverStrategy),
-                // Free: use rate-aware to distribute load across all providers
-                // This tracks last request time per endpoint and selects the
-                // one that has been idle longest, naturally staying within rate limits
-                EndpointTier::Free => Box::new(RateAwareStrategy::new()),
-            };
+            let strategy = config.strategies.remove(&tier).unwrap_or_else(|| {
+                let strategy: Box<dyn SelectionStrategy> = match tier {
+                    // Premium: use failover to maximize success rate
+                    EndpointTier::Premium => Box::new(FailoverStrategy),
+                    // Standard: use failover (paid RPCs, prefer reliability)
+                    EndpointTier::Standard => Box::new(FailoverStrategy),
+                    // Free: use rate-aware to distribute load across all providers
+                    // This tracks last request time per endpoint and selects the
+                    // one that has been idle longest, naturally staying within rate limits
+                    EndpointTier::Free => Box::new(RateAwareStrategy::new()),
+                    // Shadow: use failover, only the first working endpoint needs a copy
+                    EndpointTier::Shadow => Box::new(FailoverStrategy),
+                };
+                strategy
+            });
 
             let pool_config = RpcPoolConfig::new()
                 .with_endpoints(endpoints)
                 .with_strategy(strategy)
                 .with_health_check_interval(config.health_check_interval)
                 .with_max_consecutive_errors(config.max_consecutive_errors)
-                .with_retry_delay(config.retry_delay);
+                .with_retry_delay(config.retry_delay)
+                .with_rate_limits(tier_rate_limits.remove(&tier).unwrap_or_default());
 
             let pool = RpcPool::new(pool_config)?;
-            info!(tier = ?tier, "Created RPC pool for tier");
+            info!(tier = ?tier, strategy = %pool.strategy_name(), "Created RPC pool for tier");
             pools.insert(tier, Arc::new(pool));
         }
 
+        let canary_tier = config
+            .canary_tier
+            .map(|(tier, fraction)| (tier, fraction.clamp(0.0, 1.0)));
+        if let Some((tier, fraction)) = canary_tier {
+            if pools.contains_key(&tier) {
+                info!(tier = ?tier, fraction, "Canary traffic splitting enabled");
+            } else {
+                warn!(tier = ?tier, "Canary tier has no endpoints, canary disabled");
+            }
+        }
+        if pools.contains_key(&EndpointTier::Shadow) {
+            info!(
+                max_in_flight = config.shadow_concurrency,
+                "Shadow traffic enabled"
+            );
+        }
+
         Ok(Self {
             pools,
             allow_critical_fallback: config.allow_critical_fallback,
             allow_low_escalation: config.allow_low_escalation,
+            canary_tier,
+            canary_errors: AtomicU64::new(0),
+            shadow_permits: Arc::new(Semaphore::new(config.shadow_concurrency)),
+            shadow_errors: Arc::new(AtomicU64::new(0)),
+            tracing_enabled: true,
+            health_check_interval: config.health_check_interval,
+            max_consecutive_errors: config.max_consecutive_errors,
+            retry_delay: config.retry_delay,
+            rate_limits,
         })
     }
 
+    /// Enable or disable the tracing spans created by [`execute`](Self::execute) (enabled by default).
+    ///
+    /// Disabling skips span creation entirely, for hot loops where even
+    /// filtered-out spans are too costly.
+    pub fn with_tracing_enabled(mut self, enabled: bool) -> Self {
+        self.tracing_enabled = enabled;
+        self
+    }
+
+    /// Pick the canary pool for this request, if the random draw selects it.
+    fn canary_pool(&self) -> Option<(EndpointTier, &Arc<RpcPool>)> {
+        let (tier, fraction) = self.canary_tier?;
+        let pool = self.pools.get(&tier)?;
+        (rand::random::<f64>() < fraction).then_some((tier, pool))
+    }
+
+    /// Number of canary requests that failed.
+    pub fn canary_error_count(&self) -> u64 {
+        self.canary_errors.load(Ordering::Relaxed)
+    }
+
+    /// Send a copy of a request to the shadow tier in a detached task, if configured.
+    ///
+    /// The copy is dropped when the shadow concurrency limit is reached.
+    fn spawn_shadow<T, E, F, Fut>(&self, f: &F)
+    where
+        F: Fn(url::Url) -> Fut + Clone + Send + Sync + 'static,
+        Fut: Future<Output = Result<T, E>> + Send + 'static,
+        T: Send + 'static,
+        E: std::error::Error + Send + 'static,
+    {
+        let Some(pool) = self.pools.get(&EndpointTier::Shadow) else {
+            return;
+        };
+        let Ok(permit) = Arc::clone(&self.shadow_permits).try_acquire_owned() else {
+            debug!("Shadow concurrency limit reached, dropping shadow request");
+            return;
+        };
+
+        let pool = Arc::clone(pool);
+        let errors = Arc::clone(&self.shadow_errors);
+        let f = f.clone();
+        tokio::spawn(async move {
+            // Latency and errors still land in the shadow pool's own stats
+            if let Err(e) = pool.execute(f).await {
+                errors.fetch_add(1, Ordering::Relaxed);
+                debug!(error = %e, "Shadow request failed");
+            }
+            drop(permit);
+        });
+    }
+
+    /// Number of shadow requests that failed.
+    pub fn shadow_error_count(&self) -> u64 {
+        self.shadow_errors.load(Ordering::Relaxed)
+    }
+
     /// Get the tier order for a given priority.
     fn tier_order(&self, priority: RequestPriority) -> Vec<EndpointTier> {
         match priority {
@@ -238,6 +387,18 @@ impl TieredPool {
     }
 
     /// Execute a request with the specified priority.
+    ///
+    /// If a canary tier is configured, the configured fraction of requests is
+    /// sent only to the canary tier regardless of priority; their failures are
+    /// counted in [`canary_error_count`](Self::canary_error_count).
+    ///
+    /// The shadow tier is not used; see [`execute_with_shadow`](Self::execute_with_shadow).
+    ///
+    /// Runs in a `tiered_pool.execute` span carrying `priority`, plus
+    /// `tier_used` on success, `error.type = "AllTiersFailed"` when every
+    /// tier fails, or `error.type = "CanaryFailed"` when a canary request fails. Each tier tried gets a `tiered_pool.tier_attempt` child span
+    /// with `tier`, `attempt_number` and the `endpoint_name` last sent to.
+    /// See [`with_tracing_enabled`](Self::with_tracing_enabled).
     pub async fn execute<T, E, F, Fut>(
         &self,
         priority: RequestPriority,
@@ -248,6 +409,97 @@ impl TieredPool {
         Fut: Future<Output = Result<T, E>>,
         E: std::error::Error,
     {
+        self.execute_instrumented(priority, f, |_, _, _| {}).await
+    }
+
+    /// Execute a request like [`execute`](Self::execute), also sending a copy to the shadow tier.
+    ///
+    /// If a shadow tier is configured, the request is sent to it in a detached
+    /// task, which is why `f` and its future must be `Send + 'static`. The
+    /// shadow response never reaches the caller; its failures are counted in
+    /// [`shadow_error_count`](Self::shadow_error_count).
+    pub async fn execute_with_shadow<T, E, F, Fut>(
+        &self,
+        priority: RequestPriority,
+        f: F,
+    ) -> Result<T, RpcPoolError>
+    where
+        F: Fn(url::Url) -> Fut + Clone + Send + Sync + 'static,
+        Fut: Future<Output = Result<T, E>> + Send + 'static,
+        T: Send + 'static,
+        E: std::error::Error + Send + 'static,
+    {
+        self.spawn_shadow(&f);
+        self.execute(priority, f).await
+    }
+
+    /// Execute a request like [`execute`](Self::execute), reporting each tier attempt.
+    ///
+    /// `on_tier_result` is called after every tier tried, including a canary
+    /// tier, with the tier, the time spent on it (across all of its endpoints)
+    /// and whether it succeeded. This makes per-tier timing observable from a
+    /// metrics client outside the pool without adding it as a dependency.
+    /// The shadow tier is not used.
+    pub async fn execute_instrumented<F, Fut, T, E, M>(
+        &self,
+        priority: RequestPriority,
+        f: F,
+        on_tier_result: M,
+    ) -> Result<T, RpcPoolError>
+    where
+        F: Fn(url::Url) -> Fut + Clone,
+        Fut: Future<Output = Result<T, E>>,
+        E: std::error::Error,
+        M: Fn(EndpointTier, Duration, bool),
+    {
+        let span = if self.tracing_enabled {
+            tracing::info_span!(
+                "tiered_pool.execute",
+                priority = ?priority,
+                tier_used = tracing::field::Empty,
+                error.type = tracing::field::Empty,
+            )
+        } else {
+            Span::none()
+        };
+        self.execute_in_span(priority, &span, f, &on_tier_result)
+            .instrument(span.clone())
+            .await
+    }
+
+    /// Body of [`execute`](Self::execute), recording into `span` and reporting to `on_tier_result`.
+    async fn execute_in_span<T, E, F, Fut, M>(
+        &self,
+        priority: RequestPriority,
+        span: &Span,
+        f: F,
+        on_tier_result: &M,
+    ) -> Result<T, RpcPoolError>
+    where
+        F: Fn(url::Url) -> Fut + Clone,
+        Fut: Future<Output = Result<T, E>>,
+        E: std::error::Error,
+        M: Fn(EndpointTier, Duration, bool),
+    {
+        if let Some((tier, pool)) = self.canary_pool() {
+            debug!(priority = ?priority, tier = ?tier, "Routing request to canary tier");
+            let start = Instant::now();
+            let result = Self::execute_tier_attempt(span, tier, 1, pool, f).await;
+            on_tier_result(tier, start.elapsed(), result.is_ok());
+            return match result {
+                Ok(result) => {
+                    span.record("tier_used", tracing::field::debug(tier));
+                    Ok(result)
+                }
+                Err(e) => {
+                    self.canary_errors.fetch_add(1, Ordering::Relaxed);
+                    warn!(tier = ?tier, error = %e, "Canary request failed");
+                    span.record("error.type", "CanaryFailed");
+                    Err(e)
+                }
+            };
+        }
+
         let tiers = self.tier_order(priority);
         let mut last_error = None;
         let mut tried_tiers = Vec::new();
@@ -257,8 +509,16 @@ impl TieredPool {
                 debug!(priority = ?priority, tier = ?tier, "Attempting tier");
                 tried_tiers.push(*tier);
 
-                match pool.execute(f.clone()).await {
-                    Ok(result) => return Ok(result),
+                let attempt_number = tried_tiers.len();
+                let start = Instant::now();
+                let result =
+                    Self::execute_tier_attempt(span, *tier, attempt_number, pool, f.clone()).await;
+                on_tier_result(*tier, start.elapsed(), result.is_ok());
+                match result {
+                    Ok(result) => {
+                        span.record("tier_used", tracing::field::debug(tier));
+                        return Ok(result);
+                    }
                     Err(e) => {
                         warn!(tier = ?tier, error = %e, "Tier failed, falling back to next tier");
                         last_error = Some(e);
@@ -276,9 +536,45 @@ impl TieredPool {
             error = ?last_error,
             "All tiers failed"
         );
+        span.record("error.type", "AllTiersFailed");
         Err(last_error.unwrap_or(RpcPoolError::NoEndpointsConfigured))
     }
 
+    /// Execute on one tier's pool inside a `tiered_pool.tier_attempt` child of `parent`.
+    async fn execute_tier_attempt<T, E, F, Fut>(
+        parent: &Span,
+        tier: EndpointTier,
+        attempt_number: usize,
+        pool: &RpcPool,
+        f: F,
+    ) -> Result<T, RpcPoolError>
+    where
+        F: Fn(url::Url) -> Fut + Clone,
+        Fut: Future<Output = Result<T, E>>,
+        E: std::error::Error,
+    {
+        if parent.is_disabled() {
+            return pool.execute(f).await;
+        }
+        let span = tracing::info_span!(
+            parent: parent,
+            "tiered_pool.tier_attempt",
+            tier = ?tier,
+            attempt_number,
+            endpoint_name = tracing::field::Empty,
+        );
+        let traced = {
+            let span = span.clone();
+            move |url: url::Url| {
+                if let Some(name) = pool.endpoint_name(&url) {
+                    span.record("endpoint_name", name.as_str());
+                }
+                f(url)
+            }
+        };
+        pool.execute(traced).instrument(span).await
+    }
+
     /// Execute with URL string instead of parsed URL.
     pub async fn execute_with_url<T, E, F, Fut>(
         &self,
@@ -321,6 +617,144 @@ impl TieredPool {
         Err(last_error.unwrap_or(RpcPoolError::NoEndpointsConfigured))
     }
 
+    /// Execute a request, skipping endpoints that do not satisfy `required_caps`.
+    ///
+    /// Endpoints are filtered before the tier's strategy runs, so structurally
+    /// incapable endpoints (e.g. too small a `max_block_range` for a large
+    /// `eth_getLogs` query) are never tried. If no endpoint in a tier qualifies,
+    /// the next tier is used as usual.
+    pub async fn execute_with_capability_filter<T, E, F, Fut>(
+        &self,
+        priority: RequestPriority,
+        required_caps: CapabilityFilter,
+        f: F,
+    ) -> Result<T, RpcPoolError>
+    where
+        F: Fn(url::Url) -> Fut + Clone,
+        Fut: Future<Output = Result<T, E>>,
+        E: std::error::Error,
+    {
+        let tiers = self.tier_order(priority);
+        let mut last_error = None;
+        let mut tried_tiers = Vec::new();
+
+        for tier in &tiers {
+            if let Some(pool) = self.pools.get(tier) {
+                debug!(
+                    priority = ?priority,
+                    tier = ?tier,
+                    required_caps = ?required_caps,
+                    "Attempting tier with capability filter"
+                );
+                tried_tiers.push(*tier);
+
+                match pool
+                    .execute_matching(|e| required_caps.matches(&e.capabilities), f.clone())
+                    .await
+                {
+                    Ok(result) => return Ok(result),
+                    Err(RpcPoolError::NoMatchingEndpoints) => {
+                        debug!(tier = ?tier, "No capable endpoints in tier, skipping");
+                        last_error = Some(RpcPoolError::NoMatchingEndpoints);
+                    }
+                    Err(e) => {
+                        warn!(tier = ?tier, error = %e, "Tier failed, falling back to next tier");
+                        last_error = Some(e);
+                    }
+                }
+            } else {
+                debug!(tier = ?tier, "Tier not configured, skipping");
+            }
+        }
+
+        warn!(
+            priority = ?priority,
+            tried_tiers = ?tried_tiers,
+            available_tiers = ?tiers,
+            error = ?last_error,
+            "All tiers failed"
+        );
+        Err(last_error.unwrap_or(RpcPoolError::NoEndpointsConfigured))
+    }
+
+    /// Execute a request on every tier at once and return the first success.
+    ///
+    /// Spawns one task per configured tier (except [`EndpointTier::Shadow`]),
+    /// each failing over within its tier as usual. The first `Ok` wins and a
+    /// shared [`CancellationToken`] stops the other tiers, dropping their
+    /// in-flight attempts. This is the tiered counterpart of
+    /// [`RpcPool::execute_hedged`], for operations such as liquidations where
+    /// latency matters more than quota. Dropping the returned future cancels
+    /// every tier as well.
+    pub async fn execute_all_tiers<F, Fut, T, E>(&self, f: F) -> Result<T, RpcPoolError>
+    where
+        F: Fn(url::Url) -> Fut + Clone + Send + Sync + 'static,
+        Fut: Future<Output = Result<T, E>> + Send + 'static,
+        T: Send + 'static,
+        E: std::error::Error + Send + 'static,
+    {
+        let token = CancellationToken::new();
+        let _cancel_on_drop = token.clone().drop_guard();
+
+        let mut tasks = JoinSet::new();
+        for (&tier, pool) in &self.pools {
+            if tier == EndpointTier::Shadow {
+                continue;
+            }
+            let pool = Arc::clone(pool);
+            let token = token.clone();
+            let f = f.clone();
+            tasks.spawn(async move {
+                let result = tokio::select! {
+                    _ = token.cancelled() => Err(RpcPoolError::Cancelled),
+                    result = pool.execute(f) => result,
+                };
+                (tier, result)
+            });
+        }
+        if tasks.is_empty() {
+            return Err(RpcPoolError::NoEndpointsConfigured);
+        }
+        debug!(tiers = tasks.len(), "Broadcasting request to all tiers");
+
+        let mut last_error = None;
+        while let Some(joined) = tasks.join_next().await {
+            match joined {
+                Ok((tier, Ok(result))) => {
+                    token.cancel();
+                    debug!(tier = ?tier, cancelled = tasks.len(), "Tier won broadcast request");
+                    return Ok(result);
+                }
+                Ok((tier, Err(e))) => {
+                    warn!(tier = ?tier, error = %e, "Tier failed in broadcast request");
+                    last_error = Some(e);
+                }
+                Err(e) => {
+                    warn!(error = %e, "Broadcast tier task panicked");
+                    last_error = Some(RpcPoolError::AllEndpointsFailed(e.to_string()));
+                }
+            }
+        }
+
+        warn!(error = ?last_error, "All tiers failed in broadcast request");
+        Err(last_error.unwrap_or(RpcPoolError::NoEndpointsConfigured))
+    }
+
+    /// URL of the endpoint a request with `priority` would try first, without sending it.
+    ///
+    /// Walks the tiers in the order [`execute`](Self::execute) would, defaulting
+    /// to [`RequestPriority::Normal`], and returns the first tier's
+    /// [`RpcPool::execute_dry_run`] result. Canary routing is random per
+    /// request and is not simulated. Returns `None` if no tier would accept
+    /// the request.
+    pub fn execute_dry_run(&self, priority: Option<RequestPriority>) -> Option<String> {
+        let priority = priority.unwrap_or_default();
+        self.tier_order(priority)
+            .iter()
+            .filter_map(|tier| self.pools.get(tier))
+            .find_map(|pool| pool.execute_dry_run(Some(priority)))
+    }
+
     /// Get pool for a specific tier.
     pub fn get_tier_pool(&self, tier: EndpointTier) -> Option<&Arc<RpcPool>> {
         self.pools.get(&tier)
@@ -354,10 +788,137 @@ impl TieredPool {
             .collect()
     }
 
+    /// Get the health summary of each configured tier.
+    pub fn health_summary_by_tier(&self) -> HashMap<EndpointTier, HealthSummary> {
+        self.pools
+            .iter()
+            .map(|(tier, pool)| (*tier, pool.health_summary()))
+            .collect()
+    }
+
+    /// Get the metrics of each configured tier's pool.
+    pub fn get_tier_metrics(&self) -> HashMap<EndpointTier, RpcPoolMetrics> {
+        self.pools
+            .iter()
+            .map(|(tier, pool)| (*tier, pool.metrics()))
+            .collect()
+    }
+
+    /// Failovers per request for `tier`, or `None` if the tier is not configured.
+    ///
+    /// A request that tried three endpoints counts two failovers, so the rate
+    /// can exceed 1. Returns 0 before the tier has served any request.
+    pub fn tier_failover_rate(&self, tier: EndpointTier) -> Option<f64> {
+        let metrics = self.pools.get(&tier)?.metrics();
+        if metrics.total_requests == 0 {
+            return Some(0.0);
+        }
+        Some(metrics.failovers as f64 / metrics.total_requests as f64)
+    }
+
+    /// Get the health summary across all tiers.
+    ///
+    /// Each field is the sum of the per-tier values from
+    /// [`health_summary_by_tier`](Self::health_summary_by_tier), so
+    /// `overall.total` equals the sum of every tier's `total`.
+    pub fn overall_health_summary(&self) -> HealthSummary {
+        self.pools.values().map(|pool| pool.health_summary()).fold(
+            HealthSummary {
+                healthy: 0,
+                degraded: 0,
+                unhealthy: 0,
+                total: 0,
+                read_healthy: 0,
+                write_healthy: 0,
+            },
+            |acc, tier| HealthSummary {
+                healthy: acc.healthy + tier.healthy,
+                degraded: acc.degraded + tier.degraded,
+                unhealthy: acc.unhealthy + tier.unhealthy,
+                total: acc.total + tier.total,
+                read_healthy: acc.read_healthy + tier.read_healthy,
+                write_healthy: acc.write_healthy + tier.write_healthy,
+            },
+        )
+    }
+
+    /// Probe every endpoint of every tier before serving traffic.
+    ///
+    /// Runs [`RpcPool::warm_up`] on each tier in turn, with up to
+    /// `concurrency` probes in flight per tier, and sums the reports.
+    pub async fn warm_up(&self, concurrency: usize) -> Result<WarmUpReport, RpcPoolError> {
+        let mut report = WarmUpReport::default();
+        for (tier, pool) in &self.pools {
+            let tier_report = pool.warm_up(concurrency).await?;
+            debug!(
+                tier = ?tier,
+                healthy = tier_report.healthy,
+                unhealthy = tier_report.unhealthy,
+                "Tier warmed up"
+            );
+            report.merge(tier_report);
+        }
+        Ok(report)
+    }
+
+    /// Current endpoints and settings in config file form, without runtime stats.
+    ///
+    /// Endpoints added to a tier's pool at runtime are included. Strategy
+    /// overrides and the canary tier are not part of the file format.
+    /// `allow_insecure_premium` is set if a premium endpoint does not use
+    /// `https://`, so the result loads again.
+    pub fn to_config_file(&self) -> TieredPoolConfigFile {
+        let tier_entries = |tier: EndpointTier| -> Vec<TieredEndpointFileConfig> {
+            let Some(pool) = self.pools.get(&tier) else {
+                return vec![];
+            };
+            pool.get_all_endpoints()
+                .into_iter()
+                .map(|endpoint| TieredEndpointFileConfig {
+                    rate_limit: self.rate_limits.get(&endpoint.url).copied().unwrap_or(0),
+                    endpoint,
+                })
+                .collect()
+        };
+
+        let premium = tier_entries(EndpointTier::Premium);
+        TieredPoolConfigFile {
+            health_check_interval_ms: self.health_check_interval.as_millis() as u64,
+            max_consecutive_errors: self.max_consecutive_errors,
+            retry_delay_ms: self.retry_delay.as_millis() as u64,
+            allow_critical_fallback: self.allow_critical_fallback,
+            allow_low_escalation: self.allow_low_escalation,
+            allow_insecure_premium: premium
+                .iter()
+                .any(|e| !e.endpoint.url.starts_with("https://")),
+            premium,
+            standard: tier_entries(EndpointTier::Standard),
+            free: tier_entries(EndpointTier::Free),
+            shadow: tier_entries(EndpointTier::Shadow),
+        }
+    }
+
+    /// Save the current endpoint configuration to a TOML or JSON file.
+    ///
+    /// Files ending in `.toml` are written as TOML; anything else as JSON.
+    /// The file can be loaded with [`TieredPoolBuilder::from_config_file`];
+    /// see [`to_config_file`](Self::to_config_file) for what it contains.
+    pub fn save_config(&self, path: impl AsRef<Path>) -> Result<(), RpcPoolError> {
+        let path = path.as_ref();
+        self.to_config_file().to_file(path)?;
+        info!(path = %path.display(), "Saved tiered pool config");
+        Ok(())
+    }
+
     /// Log current tier configuration for debugging.
     pub fn log_tier_info(&self) {
         let counts = self.tier_endpoint_counts();
-        for tier in [EndpointTier::Premium, EndpointTier::Standard, EndpointTier::Free] {
+        for tier in [
+            EndpointTier::Premium,
+            EndpointTier::Standard,
+            EndpointTier::Free,
+            EndpointTier::Shadow,
+        ] {
             if let Some(count) = counts.get(&tier) {
                 info!(tier = ?tier, endpoint_count = count, "Tier configuration");
             } else {
@@ -375,6 +936,10 @@ pub struct TieredPoolBuilder {
     retry_delay: Duration,
     allow_critical_fallback: bool,
     allow_low_escalation: bool,
+    strategies: HashMap<EndpointTier, Box<dyn SelectionStrategy>>,
+    canary_tier: Option<(EndpointTier, f64)>,
+    shadow_concurrency: usize,
+    allow_insecure_premium: bool,
 }
 
 impl Default for TieredPoolBuilder {
@@ -393,9 +958,23 @@ impl TieredPoolBuilder {
             retry_delay: Duration::from_secs(5),
             allow_critical_fallback: true,
             allow_low_escalation: false,
+            strategies: HashMap::new(),
+            canary_tier: None,
+            shadow_concurrency: DEFAULT_SHADOW_CONCURRENCY,
+            allow_insecure_premium: false,
         }
     }
 
+    /// Build a tiered pool from a TOML or JSON file.
+    ///
+    /// Files ending in `.toml` are parsed as TOML; anything else as JSON.
+    /// See [`TieredPoolConfigFile`] for the accepted fields. Fails like
+    /// [`build`](Self::build), or with [`RpcPoolError::ConfigParseError`] if the
+    /// file cannot be read or parsed.
+    pub fn from_config_file(path: impl AsRef<Path>) -> Result<TieredPool, RpcPoolError> {
+        Self::from(TieredPoolConfigFile::from_file(path)?).build()
+    }
+
     /// Add a premium endpoint.
     pub fn add_premium(mut self, url: impl Into<String>, name: impl Into<String>) -> Self {
         self.endpoints.push(
@@ -406,6 +985,24 @@ impl TieredPoolBuilder {
         self
     }
 
+    /// Add a premium endpoint limited to `rps` requests per second.
+    ///
+    /// Requests beyond the limit wait for a token rather than failing.
+    pub fn add_premium_with_rate_limit(
+        mut self,
+        url: impl Into<String>,
+        name: impl Into<String>,
+        rps: u32,
+    ) -> Self {
+        self.endpoints.push(
+            TieredEndpoint::new(url, EndpointTier::Premium)
+                .with_name(name)
+                .with_priority(10)
+                .with_rate_limit(rps),
+        );
+        self
+    }
+
     /// Add a standard endpoint.
     pub fn add_standard(mut self, url: impl Into<String>, name: impl Into<String>) -> Self {
         self.endpoints.push(
@@ -426,6 +1023,19 @@ impl TieredPoolBuilder {
         self
     }
 
+    /// Add a shadow endpoint.
+    ///
+    /// Receives a copy of every [`TieredPool::execute_with_shadow`] request; see
+    /// [`EndpointTier::Shadow`].
+    pub fn add_shadow(mut self, url: impl Into<String>, name: impl Into<String>) -> Self {
+        self.endpoints.push(
+            TieredEndpoint::new(url, EndpointTier::Shadow)
+                .with_name(name)
+                .with_priority(50),
+        );
+        self
+    }
+
     /// Add a custom tiered endpoint.
     pub fn add_endpoint(mut self, endpoint: TieredEndpoint) -> Self {
         self.endpoints.push(endpoint);
@@ -489,6 +1099,30 @@ impl TieredPoolBuilder {
         self.add_free_endpoints(endpoints)
     }
 
+    /// Add premium endpoints from `PREMIUM_RPC_ENDPOINT_{chain_id}_0`,
+    /// `PREMIUM_RPC_ENDPOINT_{chain_id}_1`, ... environment variables.
+    ///
+    /// Numbering stops at the first missing index; see [`presets::from_env`].
+    pub fn with_env_premium_endpoints(mut self, chain_id: u64) -> Self {
+        let prefix = format!("PREMIUM_RPC_ENDPOINT_{}", chain_id);
+        let endpoints = presets::endpoints_from_env(&prefix, chain_id);
+        if endpoints.is_empty() {
+            warn!(chain_id, "No premium endpoints found in environment");
+        } else {
+            info!(
+                chain_id,
+                endpoint_count = endpoints.len(),
+                "Loading premium endpoints from environment"
+            );
+        }
+        self.endpoints.extend(endpoints.into_iter().map(|endpoint| TieredEndpoint {
+            endpoint,
+            tier: EndpointTier::Premium,
+            rate_limit: 0,
+        }));
+        self
+    }
+
     /// Automatically load built-in free endpoints for multiple chains.
     pub fn with_default_free_endpoints_for_chains(mut self, chain_ids: &[u64]) -> Self {
         for &chain_id in chain_ids {
@@ -515,8 +1149,69 @@ impl TieredPoolBuilder {
         self
     }
 
+    /// Use `strategy` for `tier` instead of the tier's default.
+    ///
+    /// Premium and standard tiers default to failover and the free tier to
+    /// rate-aware selection. A later call for the same tier replaces the earlier one.
+    pub fn with_strategy_for_tier(
+        mut self,
+        tier: EndpointTier,
+        strategy: Box<dyn SelectionStrategy>,
+    ) -> Self {
+        self.strategies.insert(tier, strategy);
+        self
+    }
+
+    /// Route `fraction` (0.0-1.0) of [`TieredPool::execute`] requests to `tier` only.
+    ///
+    /// Useful for shadowing a share of traffic to a new provider before
+    /// migrating. Canary requests do not fall back to other tiers.
+    pub fn with_canary_tier(mut self, tier: EndpointTier, fraction: f64) -> Self {
+        self.canary_tier = Some((tier, fraction));
+        self
+    }
+
+    /// Set the maximum number of shadow requests in flight (default 32).
+    ///
+    /// Shadow copies arriving while the limit is reached are dropped, so a slow
+    /// shadow provider cannot pile up tasks.
+    pub fn with_shadow_concurrency(mut self, max_in_flight: usize) -> Self {
+        self.shadow_concurrency = max_in_flight;
+        self
+    }
+
+    /// Allow premium endpoints over plain `http://` (e.g. a node on a private network).
+    ///
+    /// Premium URLs usually carry API keys, so [`build`](Self::build) rejects
+    /// them unless they use `https://`.
+    pub fn allow_insecure_premium(mut self, allow: bool) -> Self {
+        self.allow_insecure_premium = allow;
+        self
+    }
+
     /// Build the tiered pool.
+    ///
+    /// Fails with [`RpcPoolError::InvalidUrl`] if an endpoint URL does not parse,
+    /// and with [`RpcPoolError::InsecureEndpoint`] for a premium endpoint that
+    /// does not use `https://`, unless [`allow_insecure_premium`](Self::allow_insecure_premium)
+    /// is set. Plain `http://` endpoints in any tier are logged as warnings.
     pub fn build(self) -> Result<TieredPool, RpcPoolError> {
+        for ep in &self.endpoints {
+            let url = url::Url::parse(&ep.endpoint.url)
+                .map_err(|e| RpcPoolError::InvalidUrl(format!("{}: {}", ep.endpoint.url, e)))?;
+            let is_https = url.scheme() == "https";
+            if ep.tier == EndpointTier::Premium && !is_https && !self.allow_insecure_premium {
+                return Err(RpcPoolError::InsecureEndpoint(ep.endpoint.url.clone()));
+            }
+            if url.scheme() == "http" {
+                warn!(
+                    name = %ep.endpoint.name,
+                    tier = ?ep.tier,
+                    "Endpoint does not use TLS; requests and any API key are sent in plaintext"
+                );
+            }
+        }
+
         // Deduplicate endpoints by URL, keeping the first occurrence (higher tier / earlier added wins)
         let mut seen = HashSet::new();
         let mut deduped = Vec::with_capacity(self.endpoints.len());
@@ -540,8 +1235,56 @@ impl TieredPoolBuilder {
             retry_delay: self.retry_delay,
             allow_critical_fallback: self.allow_critical_fallback,
             allow_low_escalation: self.allow_low_escalation,
+            strategies: self.strategies,
+            canary_tier: self.canary_tier,
+            shadow_concurrency: self.shadow_concurrency,
         })
     }
+
+    /// Build the tiered pool and [`warm it up`](TieredPool::warm_up) before returning it.
+    ///
+    /// Unreachable endpoints are already marked unhealthy when the pool is
+    /// returned, so the first requests go straight to working endpoints.
+    pub async fn build_and_warm_up(self, concurrency: usize) -> Result<TieredPool, RpcPoolError> {
+        let pool = self.build()?;
+        let report = pool.warm_up(concurrency).await?;
+        if report.healthy == 0 {
+            warn!(
+                unhealthy = report.unhealthy,
+                "No endpoint answered the warm-up probe"
+            );
+        }
+        Ok(pool)
+    }
+}
+
+impl From<TieredPoolConfigFile> for TieredPoolBuilder {
+    fn from(file: TieredPoolConfigFile) -> Self {
+        let mut builder = TieredPoolBuilder::new()
+            .health_check_interval(Duration::from_millis(file.health_check_interval_ms))
+            .allow_critical_fallback(file.allow_critical_fallback)
+            .allow_low_escalation(file.allow_low_escalation)
+            .allow_insecure_premium(file.allow_insecure_premium);
+        builder.max_consecutive_errors = file.max_consecutive_errors;
+        builder.retry_delay = Duration::from_millis(file.retry_delay_ms);
+
+        let tiers = [
+            (EndpointTier::Premium, file.premium),
+            (EndpointTier::Standard, file.standard),
+            (EndpointTier::Free, file.free),
+            (EndpointTier::Shadow, file.shadow),
+        ];
+        for (tier, entries) in tiers {
+            for entry in entries {
+                builder = builder.add_endpoint(TieredEndpoint {
+                    endpoint: entry.endpoint,
+                    tier,
+                    rate_limit: entry.rate_limit,
+                });
+            }
+        }
+        builder
+    }
 }
 
 #[cfg(test)]
@@ -573,6 +1316,26 @@ mod tests {
         assert_eq!(tiers[0], EndpointTier::Free);
     }
 
+    #[test]
+    fn test_execute_dry_run_follows_tier_order() {
+        let pool = TieredPoolBuilder::new()
+            .add_premium("https://premium.example.com", "Premium")
+            .add_free("https://free.example.com", "Free")
+            .build()
+            .unwrap();
+
+        let selected = pool.execute_dry_run(Some(RequestPriority::Critical));
+        assert_eq!(selected.as_deref(), Some("https://premium.example.com"));
+        // Normal requests skip the missing standard tier
+        let selected = pool.execute_dry_run(None);
+        assert_eq!(selected.as_deref(), Some("https://free.example.com"));
+
+        pool.get_tier_pool(EndpointTier::Free).unwrap().pause();
+        assert_eq!(pool.execute_dry_run(Some(RequestPriority::Low)), None);
+        let metrics = pool.get_tier_metrics();
+        assert!(metrics.values().all(|m| m.total_requests == 0));
+    }
+
     #[test]
     fn test_builder() {
         let pool = TieredPoolBuilder::new()
@@ -609,6 +1372,177 @@ mod tests {
         assert_eq!(counts.get(&EndpointTier::Free), Some(&3));
     }
 
+    #[test]
+    fn test_health_summary_by_tier() {
+        let pool = TieredPoolBuilder::new()
+            .add_premium("https://premium1.example.com", "Premium1")
+            .add_standard("https://standard1.example.com", "Standard1")
+            .add_free("https://free1.example.com", "Free1")
+            .add_free("https://free2.example.com", "Free2")
+            .build()
+            .unwrap();
+
+        pool.get_tier_pool(EndpointTier::Premium)
+            .unwrap()
+            .mark_unhealthy("https://premium1.example.com");
+
+        let by_tier = pool.health_summary_by_tier();
+        assert_eq!(by_tier.len(), 3);
+        assert!(by_tier[&EndpointTier::Premium].all_unhealthy());
+        assert_eq!(by_tier[&EndpointTier::Standard].healthy, 1);
+        assert_eq!(by_tier[&EndpointTier::Free].healthy, 2);
+
+        let overall = pool.overall_health_summary();
+        assert_eq!(overall.total, by_tier.values().map(|s| s.total).sum::<usize>());
+        assert_eq!(overall.total, 4);
+        assert_eq!(overall.healthy, 3);
+        assert_eq!(overall.unhealthy, 1);
+    }
+
+    #[tokio::test]
+    async fn test_tier_metrics_track_failovers_per_tier() {
+        let pool = TieredPoolBuilder::new()
+            .add_premium("https://premium-down.example.com", "PremiumDown")
+            .add_premium("https://premium-up.example.com", "PremiumUp")
+            .add_standard("https://standard.example.com", "Standard")
+            .build()
+            .unwrap();
+
+        let call = |url: url::Url| async move {
+            if url.as_str().contains("down") {
+                return Err(std::io::Error::other("boom"));
+            }
+            Ok(url.to_string())
+        };
+        for _ in 0..2 {
+            pool.execute(RequestPriority::Critical, call).await.unwrap();
+            pool.execute(RequestPriority::Normal, call).await.unwrap();
+        }
+
+        let metrics = pool.get_tier_metrics();
+        assert_eq!(metrics.len(), 2);
+        assert_eq!(metrics[&EndpointTier::Premium].total_requests, 2);
+        assert!(metrics[&EndpointTier::Premium].failovers > 0);
+        assert_eq!(metrics[&EndpointTier::Standard].total_requests, 2);
+        assert_eq!(metrics[&EndpointTier::Standard].failovers, 0);
+
+        let premium_rate = pool.tier_failover_rate(EndpointTier::Premium).unwrap();
+        assert!(premium_rate > 0.0);
+        assert_eq!(pool.tier_failover_rate(EndpointTier::Standard), Some(0.0));
+        assert_eq!(pool.tier_failover_rate(EndpointTier::Free), None);
+    }
+
+    #[tokio::test]
+    async fn test_execute_instrumented_reports_each_tier() {
+        let pool = TieredPoolBuilder::new()
+            .add_premium("https://premium-down.example.com", "PremiumDown")
+            .add_standard("https://standard.example.com", "Standard")
+            .build()
+            .unwrap();
+        let results = parking_lot::Mutex::new(Vec::new());
+
+        let start = Instant::now();
+        pool.execute_instrumented(
+            RequestPriority::Critical,
+            |url: url::Url| async move {
+                if url.as_str().contains("down") {
+                    tokio::time::sleep(Duration::from_millis(50)).await;
+                    return Err(std::io::Error::other("boom"));
+                }
+                Ok(url.to_string())
+            },
+            |tier, elapsed, ok| results.lock().push((tier, elapsed, ok)),
+        )
+        .await
+        .unwrap();
+        let total = start.elapsed();
+
+        let results = results.into_inner();
+        let expected = vec![
+            (EndpointTier::Premium, false),
+            (EndpointTier::Standard, true),
+        ];
+        let tiers: Vec<_> = results.iter().map(|(tier, _, ok)| (*tier, *ok)).collect();
+        assert_eq!(tiers, expected);
+        assert!(results[0].1 >= Duration::from_millis(50));
+        assert!(results[1].1 < total);
+    }
+
+    #[tokio::test]
+    async fn test_build_and_warm_up_marks_unreachable_endpoints() {
+        let server = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::method("POST"))
+            .respond_with(
+                wiremock::ResponseTemplate::new(200).set_body_json(
+                    serde_json::json!({"jsonrpc": "2.0", "id": 0, "result": "0x10"}),
+                ),
+            )
+            .mount(&server)
+            .await;
+
+        let pool = TieredPoolBuilder::new()
+            .add_standard(server.uri(), "Standard")
+            .add_free("http://127.0.0.1:1", "FreeDown")
+            .build_and_warm_up(4)
+            .await
+            .unwrap();
+
+        let by_tier = pool.health_summary_by_tier();
+        assert_eq!(by_tier[&EndpointTier::Standard].healthy, 1);
+        assert_eq!(by_tier[&EndpointTier::Free].unhealthy, 1);
+
+        let report = pool.warm_up(4).await.unwrap();
+        assert_eq!((report.healthy, report.unhealthy), (1, 1));
+        assert!(report.latencies.contains_key(&server.uri()));
+    }
+
+    #[test]
+    fn test_build_validates_endpoint_urls() {
+        let result = TieredPoolBuilder::new()
+            .add_premium("http://premium.example.com/v2/key", "Premium")
+            .build();
+        match result {
+            Err(RpcPoolError::InsecureEndpoint(url)) => assert!(url.starts_with("http://premium")),
+            other => panic!("unexpected result: {:?}", other.map(|_| ())),
+        }
+
+        let result = TieredPoolBuilder::new().add_free("not a url", "Broken").build();
+        assert!(matches!(result, Err(RpcPoolError::InvalidUrl(_))));
+
+        // Plain HTTP is only a warning outside the premium tier, or when explicitly allowed
+        assert!(TieredPoolBuilder::new()
+            .add_premium("https://premium.example.com", "Premium")
+            .add_standard("http://standard.example.com", "Standard")
+            .build()
+            .is_ok());
+        assert!(TieredPoolBuilder::new()
+            .add_premium("http://10.0.0.5:8545", "Private node")
+            .allow_insecure_premium(true)
+            .build()
+            .is_ok());
+    }
+
+    #[test]
+    fn test_strategy_for_tier() {
+        use crate::strategies::{LatencyBasedStrategy, RoundRobinStrategy};
+
+        let pool = TieredPoolBuilder::new()
+            .add_premium("https://premium1.example.com", "Premium1")
+            .add_premium("https://premium2.example.com", "Premium2")
+            .add_standard("https://standard1.example.com", "Standard1")
+            .add_free("https://free1.example.com", "Free1")
+            .with_strategy_for_tier(EndpointTier::Premium, Box::new(LatencyBasedStrategy::new()))
+            .with_strategy_for_tier(EndpointTier::Free, Box::new(RoundRobinStrategy::new()))
+            .build()
+            .unwrap();
+
+        let strategy = |tier| pool.get_tier_pool(tier).unwrap().strategy_name();
+        assert_eq!(strategy(EndpointTier::Premium), "latency-based");
+        assert_eq!(strategy(EndpointTier::Free), "round-robin");
+        // Tiers without an override keep their default
+        assert_eq!(strategy(EndpointTier::Standard), "failover");
+    }
+
     #[test]
     fn test_normal_priority_includes_standard_and_free() {
         // Build pool with 2 Standard and 19 Free endpoints (similar to user's case)
@@ -642,6 +1576,267 @@ mod tests {
         assert!(pool.has_tier(EndpointTier::Free));
     }
 
+    #[tokio::test]
+    async fn test_premium_rate_limit_delays_requests() {
+        let pool = TieredPoolBuilder::new()
+            .add_premium_with_rate_limit("https://premium.example.com", "Premium", 1)
+            .build()
+            .unwrap();
+
+        let start = std::time::Instant::now();
+        for _ in 0..2 {
+            let used = pool
+                .execute(RequestPriority::Critical, |url: url::Url| async move {
+                    Ok::<_, std::io::Error>(url.to_string())
+                })
+                .await
+                .unwrap();
+            assert_eq!(used, "https://premium.example.com/");
+        }
+        assert!(start.elapsed() >= Duration::from_millis(900));
+    }
+
+    fn canary_pool(fraction: f64) -> TieredPool {
+        TieredPoolBuilder::new()
+            .add_premium("https://canary.example.com", "Canary")
+            .add_standard("https://standard.example.com", "Standard")
+            .with_canary_tier(EndpointTier::Premium, fraction)
+            .build()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_canary_does_not_fall_back() {
+        let pool = canary_pool(1.0);
+
+        let result = pool
+            .execute(RequestPriority::Normal, |url: url::Url| async move {
+                if url.as_str().starts_with("https://canary") {
+                    return Err(std::io::Error::other("canary down"));
+                }
+                Ok(url.to_string())
+            })
+            .await;
+
+        assert!(result.is_err());
+        assert_eq!(pool.canary_error_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_canary_traffic_fraction() {
+        let count_canary = |pool: TieredPool| async move {
+            let mut canary = 0;
+            for _ in 0..1000 {
+                let url = pool
+                    .execute(RequestPriority::Normal, |url: url::Url| async move {
+                        Ok::<_, std::io::Error>(url.to_string())
+                    })
+                    .await
+                    .unwrap();
+                if url.starts_with("https://canary") {
+                    canary += 1;
+                }
+            }
+            assert_eq!(pool.canary_error_count(), 0);
+            canary
+        };
+
+        assert_eq!(count_canary(canary_pool(0.0)).await, 0);
+        assert_eq!(count_canary(canary_pool(1.0)).await, 1000);
+        let canary = count_canary(canary_pool(0.2)).await;
+        assert!((100..300).contains(&canary), "canary requests: {}", canary);
+    }
+
+    /// Wait until `done` holds, polling for up to a second.
+    async fn wait_for(done: impl Fn() -> bool) {
+        for _ in 0..100 {
+            if done() {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        panic!("condition not reached");
+    }
+
+    #[tokio::test]
+    async fn test_shadow_failure_does_not_affect_response() {
+        let pool = TieredPoolBuilder::new()
+            .add_standard("https://standard.example.com", "Standard")
+            .add_shadow("https://shadow.example.com", "Shadow")
+            .build()
+            .unwrap();
+
+        let used = pool
+            .execute_with_shadow(RequestPriority::Normal, |url: url::Url| async move {
+                if url.as_str().starts_with("https://shadow") {
+                    return Err(std::io::Error::other("shadow down"));
+                }
+                Ok(url.to_string())
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(used, "https://standard.example.com/");
+        wait_for(|| pool.shadow_error_count() == 1).await;
+    }
+
+    #[tokio::test]
+    async fn test_shadow_success_recorded_in_shadow_pool() {
+        let pool = TieredPoolBuilder::new()
+            .add_standard("https://standard.example.com", "Standard")
+            .add_shadow("https://shadow.example.com", "Shadow")
+            .build()
+            .unwrap();
+        let shadow = pool.get_tier_pool(EndpointTier::Shadow).unwrap().clone();
+
+        pool.execute_with_shadow(RequestPriority::Normal, |url: url::Url| async move {
+            if url.as_str().starts_with("https://shadow") {
+                tokio::time::sleep(Duration::from_millis(20)).await;
+            }
+            Ok::<_, std::io::Error>(url.to_string())
+        })
+        .await
+        .unwrap();
+
+        let stats = || shadow.get_endpoint_stats_by_name("Shadow").unwrap();
+        wait_for(|| stats().successful_requests == 1).await;
+        assert!(stats().last_latency_ms >= 20);
+        assert_eq!(pool.shadow_error_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_shadow_never_used_as_fallback() {
+        let pool = TieredPoolBuilder::new()
+            .add_standard("https://standard.example.com", "Standard")
+            .add_shadow("https://shadow.example.com", "Shadow")
+            .build()
+            .unwrap();
+
+        let result = pool
+            .execute_with_shadow(RequestPriority::Normal, |url: url::Url| async move {
+                if url.as_str().starts_with("https://standard") {
+                    return Err(std::io::Error::other("standard down"));
+                }
+                Ok(url.to_string())
+            })
+            .await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_execute_skips_shadow_and_accepts_borrowing_closure() {
+        let pool = TieredPoolBuilder::new()
+            .add_standard("https://standard.example.com", "Standard")
+            .add_shadow("https://shadow.example.com", "Shadow")
+            .build()
+            .unwrap();
+        let shadow = pool.get_tier_pool(EndpointTier::Shadow).unwrap().clone();
+
+        // Neither the closure nor its future is 'static
+        let expected = String::from("https://standard.example.com/");
+        let matched = pool
+            .execute(RequestPriority::Normal, |url: url::Url| {
+                let expected = &expected;
+                async move { Ok::<_, std::io::Error>(url.as_str() == expected) }
+            })
+            .await
+            .unwrap();
+        assert!(matched);
+
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        let stats = shadow.get_endpoint_stats_by_name("Shadow").unwrap();
+        assert_eq!(stats.total_requests, 0);
+    }
+
+    #[tokio::test]
+    async fn test_shadow_concurrency_limit_drops_copies() {
+        use std::sync::atomic::AtomicUsize;
+
+        let pool = TieredPoolBuilder::new()
+            .add_standard("https://standard.example.com", "Standard")
+            .add_shadow("https://shadow.example.com", "Shadow")
+            .with_shadow_concurrency(1)
+            .build()
+            .unwrap();
+        let shadow_calls = Arc::new(AtomicUsize::new(0));
+
+        for _ in 0..3 {
+            let shadow_calls = shadow_calls.clone();
+            pool.execute_with_shadow(RequestPriority::Normal, move |url: url::Url| {
+                let shadow_calls = shadow_calls.clone();
+                async move {
+                    if url.as_str().starts_with("https://shadow") {
+                        shadow_calls.fetch_add(1, Ordering::SeqCst);
+                        tokio::time::sleep(Duration::from_millis(200)).await;
+                    }
+                    Ok::<_, std::io::Error>(())
+                }
+            })
+            .await
+            .unwrap();
+        }
+
+        wait_for(|| shadow_calls.load(Ordering::SeqCst) > 0).await;
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(shadow_calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_execute_with_capability_filter_skips_incapable_tier() {
+        use crate::endpoint::EndpointCapabilities;
+
+        let capable = RpcEndpoint::new("https://capable.example.com")
+            .with_name("Capable")
+            .with_capabilities(EndpointCapabilities {
+                supports_eth_get_logs: Some(true),
+                max_block_range: Some(0),
+                ..Default::default()
+            });
+        let limited = RpcEndpoint::new("https://limited.example.com")
+            .with_name("Limited")
+            .with_capabilities(EndpointCapabilities {
+                supports_eth_get_logs: Some(true),
+                max_block_range: Some(100),
+                ..Default::default()
+            });
+
+        let pool = TieredPoolBuilder::new()
+            .add_premium("https://premium.example.com", "Premium")
+            .add_free_endpoints(vec![limited, capable])
+            .build()
+            .unwrap();
+
+        let filter = CapabilityFilter::new()
+            .with_eth_get_logs()
+            .with_min_block_range(10_000);
+
+        let used = pool
+            .execute_with_capability_filter(RequestPriority::Critical, filter, |url: url::Url| async move {
+                Ok::<_, std::io::Error>(url.to_string())
+            })
+            .await
+            .unwrap();
+        assert_eq!(used, "https://capable.example.com/");
+
+        // No tier qualifies at all
+        let strict = CapabilityFilter::new().with_min_batch_size(1_000);
+        let no_match = TieredPoolBuilder::new()
+            .add_free_endpoints(vec![RpcEndpoint::new("https://small.example.com")
+                .with_capabilities(EndpointCapabilities {
+                    max_batch_size: Some(10),
+                    ..Default::default()
+                })])
+            .build()
+            .unwrap();
+        let result = no_match
+            .execute_with_capability_filter(RequestPriority::Low, strict, |url: url::Url| async move {
+                Ok::<_, std::io::Error>(url.to_string())
+            })
+            .await;
+        assert!(matches!(result, Err(RpcPoolError::NoMatchingEndpoints)));
+    }
+
     #[test]
     fn test_with_default_free_endpoints() {
         use crate::presets::chain_id;
@@ -683,4 +1878,147 @@ mod tests {
             + crate::presets::arbitrum_endpoints().len();
         assert_eq!(*free_count, expected_count);
     }
+
+    #[test]
+    fn test_with_env_premium_endpoints() {
+        std::env::set_var("PREMIUM_RPC_ENDPOINT_990002_0", "https://premium-a.example.com");
+        std::env::set_var("PREMIUM_RPC_ENDPOINT_990002_1", "https://premium-b.example.com");
+        let pool = TieredPoolBuilder::new()
+            .with_env_premium_endpoints(990_002)
+            .add_free("https://free.example.com", "Free")
+            .build()
+            .unwrap();
+        std::env::remove_var("PREMIUM_RPC_ENDPOINT_990002_0");
+        std::env::remove_var("PREMIUM_RPC_ENDPOINT_990002_1");
+
+        let premium = pool.get_tier_pool(EndpointTier::Premium).unwrap();
+        assert_eq!(
+            premium.get_all_urls(),
+            vec![
+                "https://premium-a.example.com".to_string(),
+                "https://premium-b.example.com".to_string(),
+            ]
+        );
+    }
+
+    fn config_roundtrip(file_name: &str) {
+        let pool = TieredPoolBuilder::new()
+            .add_premium_with_rate_limit("https://premium.example.com", "Premium", 25)
+            .add_standard("https://standard.example.com", "Standard")
+            .add_free("http://free.example.com", "Free")
+            .add_shadow("https://shadow.example.com", "Shadow")
+            .health_check_interval(Duration::from_secs(30))
+            .allow_low_escalation(true)
+            .build()
+            .unwrap();
+        let free = pool.get_tier_pool(EndpointTier::Free).unwrap();
+        free.add_endpoint(RpcEndpoint::new("https://added.example.com"))
+            .unwrap();
+
+        let path = std::env::temp_dir().join(format!(
+            "web3-rpc-pool-{}-{}",
+            std::process::id(),
+            file_name
+        ));
+        pool.save_config(&path).unwrap();
+        let loaded = TieredPoolBuilder::from_config_file(&path);
+        std::fs::remove_file(&path).unwrap();
+        let loaded = loaded.unwrap();
+
+        let saved = pool.to_config_file();
+        assert_eq!(loaded.to_config_file(), saved);
+        assert_eq!(saved.premium[0].rate_limit, 25);
+        assert_eq!(saved.free.len(), 2);
+        assert_eq!(saved.shadow[0].endpoint.name, "Shadow");
+        assert_eq!(saved.health_check_interval_ms, 30_000);
+        assert!(saved.allow_low_escalation);
+        assert!(!saved.allow_insecure_premium);
+    }
+
+    #[test]
+    fn test_save_config_toml_roundtrip() {
+        config_roundtrip("tiered.toml");
+    }
+
+    #[test]
+    fn test_save_config_json_roundtrip() {
+        config_roundtrip("tiered.json");
+    }
+
+    /// Counts the futures dropped before completing.
+    struct DropCounter(Arc<AtomicU64>);
+
+    impl Drop for DropCounter {
+        fn drop(&mut self) {
+            self.0.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_execute_all_tiers_fastest_tier_wins() {
+        let pool = TieredPoolBuilder::new()
+            .add_premium("https://premium.example.com", "Premium")
+            .add_standard("https://standard.example.com", "Standard")
+            .add_free("https://free.example.com", "Free")
+            .build()
+            .unwrap();
+        let cancelled = Arc::new(AtomicU64::new(0));
+
+        let counter = Arc::clone(&cancelled);
+        let start = Instant::now();
+        let used = pool
+            .execute_all_tiers(move |url: url::Url| {
+                let counter = Arc::clone(&counter);
+                async move {
+                    let delay = match url.host_str() {
+                        Some("standard.example.com") => 20,
+                        Some("premium.example.com") => 2_000,
+                        _ => 3_000,
+                    };
+                    let guard = DropCounter(counter);
+                    tokio::time::sleep(Duration::from_millis(delay)).await;
+                    std::mem::forget(guard);
+                    Ok::<_, std::io::Error>(url.to_string())
+                }
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(used, "https://standard.example.com/");
+        assert!(start.elapsed() < Duration::from_millis(1_000));
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert_eq!(cancelled.load(Ordering::Relaxed), 2);
+    }
+
+    #[tokio::test]
+    async fn test_execute_all_tiers_skips_failed_tiers() {
+        let pool = TieredPoolBuilder::new()
+            .add_premium("https://premium.example.com", "Premium")
+            .add_free("https://free.example.com", "Free")
+            .add_shadow("https://shadow.example.com", "Shadow")
+            .build()
+            .unwrap();
+
+        let used = pool
+            .execute_all_tiers(|url: url::Url| async move {
+                if url.as_str().starts_with("https://premium") {
+                    return Err(std::io::Error::other("premium down"));
+                }
+                if url.as_str().starts_with("https://free") {
+                    tokio::time::sleep(Duration::from_millis(20)).await;
+                }
+                Ok(url.to_string())
+            })
+            .await
+            .unwrap();
+        // The shadow tier answers instantly but never serves requests
+        assert_eq!(used, "https://free.example.com/");
+
+        let result = pool
+            .execute_all_tiers(|url: url::Url| async move {
+                Err::<String, _>(std::io::Error::other(format!("{} down", url)))
+            })
+            .await;
+        assert!(result.is_err());
+    }
 }
@@ -1,7 +1,8 @@
 //! WebSocket subscription pool with automatic failover and reconnection.
 //!
 //! Provides subscription-based streaming over WebSocket connections with
-//! automatic endpoint failover when connections drop.
+//! automatic endpoint failover when connections drop, and plain JSON-RPC
+//! requests over cached connections with [`WsPool::execute_rpc`].
 //!
 //! # Example
 //!
@@ -25,47 +26,105 @@
 use crate::endpoint::RpcEndpoint;
 use crate::error::RpcPoolError;
 
-use alloy::providers::{Provider, ProviderBuilder};
+use alloy::providers::{DynProvider, Provider, ProviderBuilder};
 use alloy::rpc::types::{Filter, Header, Log};
 use alloy::primitives::B256;
 use alloy::transports::ws::WsConnect;
-use futures_util::stream::Stream;
+use futures_util::stream::{Stream, StreamExt};
+use parking_lot::Mutex;
+use std::collections::{BTreeSet, HashSet};
+#[cfg(feature = "socks5")]
+use std::net::SocketAddr;
 use std::pin::Pin;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::task::{Context, Poll};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
+use tokio::sync::{mpsc, Notify};
 use tracing::{debug, info, warn};
 
 /// Default connection timeout for WebSocket endpoints.
 const DEFAULT_WS_CONNECT_TIMEOUT_SECS: u64 = 15;
 
+/// Default timeout for a request sent with [`WsPool::execute_rpc`].
+const DEFAULT_WS_REQUEST_TIMEOUT_SECS: u64 = 30;
+
 /// Default delay between reconnection attempts.
 const DEFAULT_RECONNECT_DELAY_MS: u64 = 1000;
 
 /// Maximum reconnection delay (with exponential backoff).
 const MAX_RECONNECT_DELAY_MS: u64 = 30_000;
 
+/// Number of items buffered between a resilient subscription task and its stream.
+const RESILIENT_STREAM_BUFFER: usize = 64;
+
+/// Default number of concurrent subscriptions for multi-endpoint streams.
+const DEFAULT_MULTI_STREAM_COUNT: usize = 2;
+
+/// How many blocks behind the newest seen header are remembered for deduplication.
+const MULTI_STREAM_DEDUP_WINDOW: u64 = 128;
+
+/// Default number of log subscriptions sharing one connection.
+const DEFAULT_MAX_SUBSCRIPTIONS_PER_CONNECTION: usize = 10;
+
+/// Default number of upstream subscriptions a pool keeps open at once.
+const DEFAULT_MAX_SUBSCRIPTIONS: usize = 100;
+
 /// Configuration for the WebSocket pool.
 #[derive(Clone)]
 pub struct WsPoolConfig {
     /// Connection timeout for WebSocket endpoints.
     pub connect_timeout: Duration,
+    /// Timeout for a single request sent with [`WsPool::execute_rpc`].
+    pub request_timeout: Duration,
     /// Base delay between reconnection attempts.
     pub reconnect_delay: Duration,
     /// Maximum reconnection delay (exponential backoff cap).
     pub max_reconnect_delay: Duration,
+    /// Default number of endpoints [`WsPool::subscribe_new_heads_multi`] subscribes to.
+    pub multi_stream_count: usize,
+    /// Most subscriptions [`WsPool::subscribe_logs_multi`] opens over one connection.
+    pub max_subscriptions_per_connection: usize,
+    /// Most upstream subscriptions open at once across the pool and its clones.
+    ///
+    /// [`WsPool::subscribe_new_heads_multi`] counts one per endpoint it
+    /// subscribes on, [`WsPool::subscribe_logs_multi`] one per filter, and the
+    /// other `subscribe_*` methods one per stream. They count until the stream
+    /// is dropped; calls beyond the limit fail with
+    /// [`RpcPoolError::TooManySubscriptions`].
+    pub max_subscriptions: usize,
+    /// SOCKS5 proxy that all WebSocket connections are tunnelled through.
+    #[cfg(feature = "socks5")]
+    pub socks5_proxy: Option<SocketAddr>,
 }
 
 impl Default for WsPoolConfig {
     fn default() -> Self {
         Self {
             connect_timeout: Duration::from_secs(DEFAULT_WS_CONNECT_TIMEOUT_SECS),
+            request_timeout: Duration::from_secs(DEFAULT_WS_REQUEST_TIMEOUT_SECS),
             reconnect_delay: Duration::from_millis(DEFAULT_RECONNECT_DELAY_MS),
             max_reconnect_delay: Duration::from_millis(MAX_RECONNECT_DELAY_MS),
+            multi_stream_count: DEFAULT_MULTI_STREAM_COUNT,
+            max_subscriptions_per_connection: DEFAULT_MAX_SUBSCRIPTIONS_PER_CONNECTION,
+            max_subscriptions: DEFAULT_MAX_SUBSCRIPTIONS,
+            #[cfg(feature = "socks5")]
+            socks5_proxy: None,
         }
     }
 }
 
+impl WsPoolConfig {
+    /// Builder: tunnel every WebSocket connection through the SOCKS5 proxy at `addr`.
+    ///
+    /// Endpoint hostnames are resolved by the proxy. Requires the `socks5` feature.
+    #[cfg(feature = "socks5")]
+    pub fn with_socks5_proxy(mut self, addr: SocketAddr) -> Self {
+        self.socks5_proxy = Some(addr);
+        self
+    }
+}
+
 /// A boxed stream type for subscription items.
 pub type BoxSubscriptionStream<T> = Pin<Box<dyn Stream<Item = T> + Send>>;
 
@@ -73,13 +132,26 @@ pub type BoxSubscriptionStream<T> = Pin<Box<dyn Stream<Item = T> + Send>>;
 ///
 /// Manages WebSocket connections to multiple RPC endpoints and provides
 /// subscription streams that automatically reconnect and failover on errors.
+///
+/// Cloning is cheap: clones share the endpoint list, request connections,
+/// subscription count and shutdown state, so shutting down any clone shuts
+/// down all of them.
+/// Subscriptions opened through different clones are independent streams
+/// with their own connections.
+#[derive(Clone)]
 pub struct WsPool {
     /// Endpoints sorted by priority (only those with ws_url).
-    endpoints: Vec<RpcEndpoint>,
+    endpoints: Arc<Vec<RpcEndpoint>>,
+    /// Connection per endpoint (same order) reused by [`WsPool::execute_rpc`].
+    providers: Arc<Vec<Mutex<Option<DynProvider>>>>,
     /// Configuration.
     config: WsPoolConfig,
     /// Shutdown flag.
     shutdown: Arc<AtomicBool>,
+    /// Wakes resilient subscription tasks on shutdown.
+    shutdown_notify: Arc<Notify>,
+    /// Subscription streams currently open.
+    active_subscriptions: Arc<AtomicUsize>,
 }
 
 impl WsPool {
@@ -118,10 +190,14 @@ impl WsPool {
             );
         }
 
+        let providers = endpoints.iter().map(|_| Mutex::new(None)).collect();
         Ok(Self {
-            endpoints,
+            endpoints: Arc::new(endpoints),
+            providers: Arc::new(providers),
             config,
             shutdown: Arc::new(AtomicBool::new(false)),
+            shutdown_notify: Arc::new(Notify::new()),
+            active_subscriptions: Arc::new(AtomicUsize::new(0)),
         })
     }
 
@@ -138,6 +214,153 @@ impl WsPool {
             .collect()
     }
 
+    /// Number of upstream subscriptions currently open, counted as for
+    /// [`WsPoolConfig::max_subscriptions`].
+    pub fn active_subscriptions(&self) -> usize {
+        self.active_subscriptions.load(Ordering::Acquire)
+    }
+
+    /// Reserve `count` subscription slots for a call about to subscribe.
+    ///
+    /// Fails with [`RpcPoolError::TooManySubscriptions`] if that would exceed
+    /// [`WsPoolConfig::max_subscriptions`].
+    fn reserve_subscriptions(&self, count: usize) -> Result<SubscriptionSlots, RpcPoolError> {
+        let max = self.config.max_subscriptions;
+        self.active_subscriptions
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |current| {
+                current.checked_add(count).filter(|&total| total <= max)
+            })
+            .map_err(|current| {
+                warn!(current, max, requested = count, "Subscription limit reached");
+                RpcPoolError::TooManySubscriptions { current, max }
+            })?;
+
+        Ok(SubscriptionSlots {
+            active: Arc::clone(&self.active_subscriptions),
+            count,
+        })
+    }
+
+    /// Send a JSON-RPC request over WebSocket with automatic failover.
+    ///
+    /// Tries each endpoint in priority order, like [`RpcPool::execute`](crate::RpcPool::execute),
+    /// and returns the first successful result. Each endpoint's connection is
+    /// opened on first use and kept for later requests; a connection that
+    /// fails or times out is dropped and reopened next time, while an error
+    /// response from the node leaves it open. Each request is bounded by
+    /// [`WsPoolConfig::request_timeout`].
+    ///
+    /// Fails with [`RpcPoolError::AllEndpointsFailed`] carrying the last
+    /// error if no endpoint answered, or [`RpcPoolError::PoolShutdown`] after
+    /// [`WsPool::shutdown`].
+    pub async fn execute_rpc(
+        &self,
+        method: &str,
+        params: serde_json::Value,
+    ) -> Result<serde_json::Value, RpcPoolError> {
+        let mut last_error = None;
+
+        for (endpoint, cached) in self.endpoints.iter().zip(self.providers.iter()) {
+            if self.is_shutdown() {
+                return Err(RpcPoolError::PoolShutdown);
+            }
+            let Some(ws_url) = &endpoint.ws_url else {
+                continue;
+            };
+
+            let existing = cached.lock().clone();
+            let provider = match existing {
+                Some(provider) => provider,
+                None => match connect_ws_with_timeout(ws_url, &self.config).await {
+                    Ok(provider) => {
+                        let provider = provider.erased();
+                        // Keep a connection another request opened meanwhile
+                        cached.lock().get_or_insert(provider).clone()
+                    }
+                    Err(e) => {
+                        warn!(name = %endpoint.name, error = %e, "WS connect failed");
+                        last_error = Some(e.to_string());
+                        continue;
+                    }
+                },
+            };
+
+            let request = provider
+                .raw_request::<_, serde_json::Value>(method.to_string().into(), params.clone());
+            let (error, reconnect) =
+                match tokio::time::timeout(self.config.request_timeout, request).await {
+                    Ok(Ok(result)) => {
+                        debug!(name = %endpoint.name, method, "WS request succeeded");
+                        return Ok(result);
+                    }
+                    Ok(Err(e)) => (e.to_string(), e.as_error_resp().is_none()),
+                    Err(_) => (
+                        format!(
+                            "Request timeout after {}ms",
+                            self.config.request_timeout.as_millis()
+                        ),
+                        true,
+                    ),
+                };
+
+            warn!(name = %endpoint.name, method, error = %error, "WS request failed");
+            if reconnect {
+                cached.lock().take();
+            }
+            last_error = Some(format!("{} on {}: {}", method, endpoint.name, error));
+        }
+
+        Err(RpcPoolError::AllEndpointsFailed(
+            last_error.unwrap_or_else(|| "Unknown error".to_string()),
+        ))
+    }
+
+    /// Probe every endpoint with `eth_blockNumber` over its request connection.
+    ///
+    /// Opens connections that are not cached yet, so later
+    /// [`execute_rpc`](Self::execute_rpc) calls find them ready, and drops
+    /// connections that fail to answer within the request timeout. Returns
+    /// the number of endpoints that answered.
+    pub(crate) async fn check_health(&self) -> usize {
+        let mut healthy = 0;
+
+        for (endpoint, cached) in self.endpoints.iter().zip(self.providers.iter()) {
+            if self.is_shutdown() {
+                break;
+            }
+            let Some(ws_url) = &endpoint.ws_url else {
+                continue;
+            };
+
+            let existing = cached.lock().clone();
+            let provider = match existing {
+                Some(provider) => provider,
+                None => match connect_ws_with_timeout(ws_url, &self.config).await {
+                    Ok(provider) => cached.lock().get_or_insert(provider.erased()).clone(),
+                    Err(e) => {
+                        debug!(name = %endpoint.name, error = %e, "WS health probe could not connect");
+                        continue;
+                    }
+                },
+            };
+
+            let probe = provider.get_block_number();
+            match tokio::time::timeout(self.config.request_timeout, probe).await {
+                Ok(Ok(_)) => healthy += 1,
+                Ok(Err(e)) => {
+                    debug!(name = %endpoint.name, error = %e, "WS health probe failed");
+                    cached.lock().take();
+                }
+                Err(_) => {
+                    debug!(name = %endpoint.name, "WS health probe timed out");
+                    cached.lock().take();
+                }
+            }
+        }
+
+        healthy
+    }
+
     /// Subscribe to new block headers with automatic failover.
     ///
     /// Tries each WebSocket endpoint in priority order until one connects
@@ -145,18 +368,19 @@ impl WsPool {
     pub async fn subscribe_new_heads(
         &self,
     ) -> Result<BoxSubscriptionStream<Header>, RpcPoolError> {
+        let slot = self.reserve_subscriptions(1)?;
         let mut last_error = None;
 
-        for endpoint in &self.endpoints {
+        for endpoint in self.endpoints.iter() {
             if let Some(ws_url) = &endpoint.ws_url {
                 debug!(name = %endpoint.name, ws_url = %ws_url, "Connecting for newHeads subscription");
 
-                match connect_ws_with_timeout(ws_url, self.config.connect_timeout).await {
+                match connect_ws_with_timeout(ws_url, &self.config).await {
                     Ok(provider) => {
                         match provider.subscribe_blocks().await {
                             Ok(sub) => {
                                 info!(name = %endpoint.name, "Subscribed to newHeads");
-                                return Ok(Box::pin(sub.into_stream()));
+                                return Ok(slot.guard(Box::pin(sub.into_stream())));
                             }
                             Err(e) => {
                                 warn!(name = %endpoint.name, error = %e, "Subscribe failed");
@@ -177,6 +401,230 @@ impl WsPool {
         Err(last_error.unwrap_or(RpcPoolError::NoWebSocketEndpoints))
     }
 
+    /// Subscribe to new block headers with transparent reconnection.
+    ///
+    /// Spawns a background task that keeps a `newHeads` subscription alive.
+    /// When the underlying stream ends or an endpoint fails to connect, the task
+    /// waits `reconnect_delay` (doubling up to `max_reconnect_delay`), yields
+    /// [`RpcPoolError::WebSocketReconnecting`] and moves on to the next endpoint
+    /// in priority order. The backoff resets once a header is received.
+    ///
+    /// The stream ends when [`WsPool::shutdown`] is called or the stream is dropped.
+    /// Fails only with [`RpcPoolError::TooManySubscriptions`].
+    pub fn subscribe_new_heads_resilient(
+        self: Arc<Self>,
+    ) -> Result<BoxSubscriptionStream<Result<Header, RpcPoolError>>, RpcPoolError> {
+        let slot = self.reserve_subscriptions(1)?;
+        let (tx, rx) = mpsc::channel(RESILIENT_STREAM_BUFFER);
+        tokio::spawn(self.run_resilient_new_heads(tx));
+
+        Ok(slot.guard(Box::pin(futures_util::stream::unfold(rx, |mut rx| async move {
+            rx.recv().await.map(|item| (item, rx))
+        }))))
+    }
+
+    /// Background loop behind [`WsPool::subscribe_new_heads_resilient`].
+    async fn run_resilient_new_heads(
+        self: Arc<Self>,
+        tx: mpsc::Sender<Result<Header, RpcPoolError>>,
+    ) {
+        let mut index = 0;
+        let mut delay = self.config.reconnect_delay;
+        let mut reconnecting = false;
+
+        loop {
+            let endpoint = &self.endpoints[index];
+            let ws_url = endpoint.ws_url.as_deref().unwrap_or_default();
+
+            if reconnecting {
+                tokio::select! {
+                    biased;
+
+                    _ = self.shutdown_signalled() => return,
+                    _ = tx.closed() => return,
+                    _ = tokio::time::sleep(delay) => {}
+                }
+                delay = (delay * 2).min(self.config.max_reconnect_delay);
+
+                info!(name = %endpoint.name, ws_url = %ws_url, "Reconnecting newHeads subscription");
+                if tx
+                    .send(Err(RpcPoolError::WebSocketReconnecting(endpoint.name.clone())))
+                    .await
+                    .is_err()
+                {
+                    return;
+                }
+            }
+            reconnecting = true;
+
+            let subscribed = tokio::select! {
+                biased;
+
+                _ = self.shutdown_signalled() => return,
+                result = subscribe_blocks_with_timeout(ws_url, &self.config) => result,
+            };
+
+            let mut stream = match subscribed {
+                Ok(stream) => {
+                    info!(name = %endpoint.name, "Subscribed to newHeads");
+                    stream
+                }
+                Err(e) => {
+                    warn!(name = %endpoint.name, error = %e, "Resilient newHeads subscribe failed");
+                    index = (index + 1) % self.endpoints.len();
+                    continue;
+                }
+            };
+
+            loop {
+                let header = tokio::select! {
+                    biased;
+
+                    _ = self.shutdown_signalled() => return,
+                    _ = tx.closed() => return,
+                    header = stream.next() => header,
+                };
+
+                match header {
+                    Some(header) => {
+                        delay = self.config.reconnect_delay;
+                        if tx.send(Ok(header)).await.is_err() {
+                            return;
+                        }
+                    }
+                    None => break,
+                }
+            }
+
+            warn!(name = %endpoint.name, "newHeads stream ended, failing over");
+            index = (index + 1) % self.endpoints.len();
+        }
+    }
+
+    /// Subscribe to new block headers on several endpoints at once.
+    ///
+    /// Keeps `n` subscriptions open on distinct endpoints (`0` uses
+    /// [`WsPoolConfig::multi_stream_count`]; capped at the number of endpoints)
+    /// and merges them into one stream that yields each block once, so a
+    /// dropped connection does not leave a gap while it reconnects. Blocks are
+    /// told apart by number and hash: a reorged header at a height already
+    /// seen is still yielded, as it would be on a single subscription. When a
+    /// subscription ends, its slot moves to the next endpoint not already in use,
+    /// with the same backoff as [`WsPool::subscribe_new_heads_resilient`].
+    ///
+    /// The stream ends when [`WsPool::shutdown`] is called or the stream is dropped.
+    /// It counts as `n` subscriptions (after capping) towards
+    /// [`WsPoolConfig::max_subscriptions`] and fails only with
+    /// [`RpcPoolError::TooManySubscriptions`].
+    pub fn subscribe_new_heads_multi(
+        self: Arc<Self>,
+        n: usize,
+    ) -> Result<BoxSubscriptionStream<Header>, RpcPoolError> {
+        let n = match n {
+            0 => self.config.multi_stream_count,
+            n => n,
+        }
+        .clamp(1, self.endpoints.len());
+        let slots = self.reserve_subscriptions(n)?;
+
+        let (tx, rx) = mpsc::channel(RESILIENT_STREAM_BUFFER);
+        let active = Arc::new(Mutex::new(HashSet::new()));
+        for slot_index in 0..n {
+            let slot_task = Arc::clone(&self).run_multi_new_heads_slot(
+                slot_index,
+                Arc::clone(&active),
+                tx.clone(),
+            );
+            tokio::spawn(slot_task);
+        }
+        info!(streams = n, "Started multi-endpoint newHeads subscription");
+
+        Ok(slots.guard(Box::pin(futures_util::stream::unfold(
+            (rx, SeenBlocks::default()),
+            |(mut rx, mut seen)| async move {
+                loop {
+                    let header: Header = rx.recv().await?;
+                    if seen.insert(header.number, header.hash) {
+                        return Some((header, (rx, seen)));
+                    }
+                }
+            },
+        ))))
+    }
+
+    /// One subscription slot behind [`WsPool::subscribe_new_heads_multi`].
+    ///
+    /// `active` holds the endpoint indices currently claimed by any slot.
+    async fn run_multi_new_heads_slot(
+        self: Arc<Self>,
+        slot: usize,
+        active: Arc<Mutex<HashSet<usize>>>,
+        tx: mpsc::Sender<Header>,
+    ) {
+        let mut index = slot;
+        let mut delay = self.config.reconnect_delay;
+        let mut reconnecting = false;
+
+        loop {
+            if reconnecting {
+                tokio::select! {
+                    biased;
+
+                    _ = self.shutdown_signalled() => return,
+                    _ = tx.closed() => return,
+                    _ = tokio::time::sleep(delay) => {}
+                }
+                delay = (delay * 2).min(self.config.max_reconnect_delay);
+            }
+            reconnecting = true;
+
+            let Some(claimed) = claim_endpoint(&active, index, self.endpoints.len()) else {
+                continue;
+            };
+            let endpoint = &self.endpoints[claimed];
+            let ws_url = endpoint.ws_url.as_deref().unwrap_or_default();
+            index = claimed + 1;
+
+            let subscribed = tokio::select! {
+                biased;
+
+                _ = self.shutdown_signalled() => return,
+                result = subscribe_blocks_with_timeout(ws_url, &self.config) => result,
+            };
+
+            match subscribed {
+                Ok(mut stream) => {
+                    info!(slot, name = %endpoint.name, "Subscribed to newHeads");
+                    loop {
+                        let header = tokio::select! {
+                            biased;
+
+                            _ = self.shutdown_signalled() => return,
+                            _ = tx.closed() => return,
+                            header = stream.next() => header,
+                        };
+
+                        match header {
+                            Some(header) => {
+                                delay = self.config.reconnect_delay;
+                                if tx.send(header).await.is_err() {
+                                    return;
+                                }
+                            }
+                            None => break,
+                        }
+                    }
+                    warn!(slot, name = %endpoint.name, "newHeads stream ended, replacing endpoint");
+                }
+                Err(e) => {
+                    warn!(slot, name = %endpoint.name, error = %e, "Multi newHeads subscribe failed");
+                }
+            }
+
+            active.lock().remove(&claimed);
+        }
+    }
+
     /// Subscribe to pending transaction hashes with automatic failover.
     ///
     /// Tries each WebSocket endpoint in priority order until one connects
@@ -184,18 +632,19 @@ impl WsPool {
     pub async fn subscribe_pending_transactions(
         &self,
     ) -> Result<BoxSubscriptionStream<B256>, RpcPoolError> {
+        let slot = self.reserve_subscriptions(1)?;
         let mut last_error = None;
 
-        for endpoint in &self.endpoints {
+        for endpoint in self.endpoints.iter() {
             if let Some(ws_url) = &endpoint.ws_url {
                 debug!(name = %endpoint.name, ws_url = %ws_url, "Connecting for pendingTransactions subscription");
 
-                match connect_ws_with_timeout(ws_url, self.config.connect_timeout).await {
+                match connect_ws_with_timeout(ws_url, &self.config).await {
                     Ok(provider) => {
                         match provider.subscribe_pending_transactions().await {
                             Ok(sub) => {
                                 info!(name = %endpoint.name, "Subscribed to pendingTransactions");
-                                return Ok(Box::pin(sub.into_stream()));
+                                return Ok(slot.guard(Box::pin(sub.into_stream())));
                             }
                             Err(e) => {
                                 warn!(name = %endpoint.name, error = %e, "Subscribe failed");
@@ -224,18 +673,19 @@ impl WsPool {
         &self,
         filter: &Filter,
     ) -> Result<BoxSubscriptionStream<Log>, RpcPoolError> {
+        let slot = self.reserve_subscriptions(1)?;
         let mut last_error = None;
 
-        for endpoint in &self.endpoints {
+        for endpoint in self.endpoints.iter() {
             if let Some(ws_url) = &endpoint.ws_url {
                 debug!(name = %endpoint.name, ws_url = %ws_url, "Connecting for logs subscription");
 
-                match connect_ws_with_timeout(ws_url, self.config.connect_timeout).await {
+                match connect_ws_with_timeout(ws_url, &self.config).await {
                     Ok(provider) => {
                         match provider.subscribe_logs(filter).await {
                             Ok(sub) => {
                                 info!(name = %endpoint.name, "Subscribed to logs");
-                                return Ok(Box::pin(sub.into_stream()));
+                                return Ok(slot.guard(Box::pin(sub.into_stream())));
                             }
                             Err(e) => {
                                 warn!(name = %endpoint.name, error = %e, "Subscribe failed");
@@ -256,28 +706,284 @@ impl WsPool {
         Err(last_error.unwrap_or(RpcPoolError::NoWebSocketEndpoints))
     }
 
+    /// Subscribe to several log filters over shared connections.
+    ///
+    /// Returns one stream per filter, in order. Filters are grouped into
+    /// connections of up to [`WsPoolConfig::max_subscriptions_per_connection`]
+    /// subscriptions, each opened on the highest-priority endpoint that
+    /// accepts all of its subscriptions. When a connection drops (any of its
+    /// subscriptions ends), every subscription in the group is re-created
+    /// together on the next endpoint, with the same backoff as
+    /// [`WsPool::subscribe_new_heads_resilient`]. Logs emitted while
+    /// reconnecting are missed.
+    ///
+    /// Each stream buffers up to 64 logs. A stream that is not polled fast
+    /// enough loses the logs that arrive while its buffer is full (a warning
+    /// is logged for each), so it cannot hold up the other streams sharing
+    /// its connection.
+    ///
+    /// The streams end when [`WsPool::shutdown`] is called; a group's
+    /// connection is closed once all of its streams are dropped. Each stream
+    /// counts towards [`WsPoolConfig::max_subscriptions`]; if there is no room
+    /// for all of them, none are opened and the call fails with
+    /// [`RpcPoolError::TooManySubscriptions`].
+    pub fn subscribe_logs_multi(
+        self: Arc<Self>,
+        filters: Vec<Filter>,
+    ) -> Result<Vec<BoxSubscriptionStream<Log>>, RpcPoolError> {
+        let mut slots = self.reserve_subscriptions(filters.len())?.split();
+        let per_connection = self.config.max_subscriptions_per_connection.max(1);
+        let mut streams = Vec::with_capacity(filters.len());
+
+        for group in filters.chunks(per_connection) {
+            let mut senders = Vec::with_capacity(group.len());
+            for _ in group {
+                let (tx, rx) = mpsc::channel(RESILIENT_STREAM_BUFFER);
+                senders.push(tx);
+                let slot = slots.next().expect("one slot reserved per filter");
+                streams.push(slot.guard(Box::pin(futures_util::stream::unfold(
+                    rx,
+                    |mut rx| async move { rx.recv().await.map(|log| (log, rx)) },
+                ))));
+            }
+            tokio::spawn(Arc::clone(&self).run_shared_logs_connection(group.to_vec(), senders));
+        }
+        info!(
+            filters = filters.len(),
+            connections = filters.len().div_ceil(per_connection),
+            "Started multiplexed logs subscriptions"
+        );
+
+        Ok(streams)
+    }
+
+    /// One shared connection behind [`WsPool::subscribe_logs_multi`].
+    ///
+    /// `senders[i]` receives the logs matching `filters[i]`.
+    async fn run_shared_logs_connection(
+        self: Arc<Self>,
+        filters: Vec<Filter>,
+        senders: Vec<mpsc::Sender<Log>>,
+    ) {
+        let mut index = 0;
+        let mut delay = self.config.reconnect_delay;
+        let mut reconnecting = false;
+        let all_closed = || futures_util::future::join_all(senders.iter().map(|tx| tx.closed()));
+
+        loop {
+            let endpoint = &self.endpoints[index];
+            let ws_url = endpoint.ws_url.as_deref().unwrap_or_default();
+            index = (index + 1) % self.endpoints.len();
+
+            if reconnecting {
+                tokio::select! {
+                    biased;
+
+                    _ = self.shutdown_signalled() => return,
+                    _ = all_closed() => return,
+                    _ = tokio::time::sleep(delay) => {}
+                }
+                delay = (delay * 2).min(self.config.max_reconnect_delay);
+            }
+            reconnecting = true;
+
+            let subscribed = tokio::select! {
+                biased;
+
+                _ = self.shutdown_signalled() => return,
+                result = subscribe_logs_group(ws_url, &filters, &self.config) => result,
+            };
+
+            let (_provider, subscriptions) = match subscribed {
+                Ok(subscribed) => {
+                    info!(
+                        name = %endpoint.name,
+                        subscriptions = filters.len(),
+                        "Subscribed to logs over shared connection"
+                    );
+                    subscribed
+                }
+                Err(e) => {
+                    warn!(name = %endpoint.name, error = %e, "Shared logs subscribe failed");
+                    continue;
+                }
+            };
+
+            // Tag each log with its filter; a `None` marks a subscription that ended
+            let mut merged = futures_util::stream::select_all(
+                subscriptions.into_iter().enumerate().map(|(i, stream)| {
+                    stream
+                        .map(move |log| (i, Some(log)))
+                        .chain(futures_util::stream::once(async move { (i, None) }))
+                        .boxed()
+                }),
+            );
+
+            loop {
+                let item = tokio::select! {
+                    biased;
+
+                    _ = self.shutdown_signalled() => return,
+                    _ = all_closed() => return,
+                    item = merged.next() => item,
+                };
+
+                match item {
+                    Some((i, Some(log))) => {
+                        delay = self.config.reconnect_delay;
+                        // Never wait on one consumer: that would stall every filter on the connection
+                        match senders[i].try_send(log) {
+                            Err(mpsc::error::TrySendError::Full(_)) => {
+                                warn!(
+                                    name = %endpoint.name,
+                                    filter = i,
+                                    "Log stream buffer full, dropping log"
+                                );
+                            }
+                            // A dropped stream only stops its own filter's delivery
+                            Ok(()) | Err(mpsc::error::TrySendError::Closed(_)) => {}
+                        }
+                    }
+                    Some((_, None)) | None => break,
+                }
+            }
+
+            warn!(name = %endpoint.name, "Shared logs connection ended, resubscribing all filters");
+        }
+    }
+
     /// Shutdown the WebSocket pool.
     ///
     /// Signals all active subscription streams to stop reconnecting.
     pub fn shutdown(&self) {
         info!("WebSocket pool shutting down");
         self.shutdown.store(true, Ordering::Release);
+        self.shutdown_notify.notify_waiters();
     }
 
     /// Check if the pool has been shut down.
     pub fn is_shutdown(&self) -> bool {
         self.shutdown.load(Ordering::Acquire)
     }
+
+    /// Whether `other` is a clone of this pool (sharing its shutdown state)
+    /// rather than a separately created pool.
+    pub fn is_same_pool(&self, other: &WsPool) -> bool {
+        Arc::ptr_eq(&self.shutdown, &other.shutdown)
+    }
+
+    /// Wait until shutdown is signalled.
+    async fn shutdown_signalled(&self) {
+        loop {
+            let notified = self.shutdown_notify.notified();
+            if self.is_shutdown() {
+                return;
+            }
+            notified.await;
+        }
+    }
+}
+
+/// Subscriptions counted by [`WsPool::active_subscriptions`] for one stream.
+///
+/// Releases its `count` slots when dropped.
+struct SubscriptionSlots {
+    active: Arc<AtomicUsize>,
+    count: usize,
+}
+
+impl SubscriptionSlots {
+    /// Tie these slots to `stream`, releasing them when the stream is dropped.
+    fn guard<T: 'static>(self, stream: BoxSubscriptionStream<T>) -> BoxSubscriptionStream<T> {
+        Box::pin(SubscriptionGuard { stream, _slots: self })
+    }
+
+    /// Split into `count` single slots, one per stream.
+    fn split(mut self) -> impl Iterator<Item = SubscriptionSlots> {
+        let count = std::mem::take(&mut self.count);
+        (0..count).map(move |_| SubscriptionSlots {
+            active: Arc::clone(&self.active),
+            count: 1,
+        })
+    }
+}
+
+impl Drop for SubscriptionSlots {
+    fn drop(&mut self) {
+        self.active.fetch_sub(self.count, Ordering::AcqRel);
+    }
+}
+
+/// A subscription stream holding its [`SubscriptionSlots`].
+struct SubscriptionGuard<T> {
+    stream: BoxSubscriptionStream<T>,
+    _slots: SubscriptionSlots,
+}
+
+impl<T> Stream for SubscriptionGuard<T> {
+    type Item = T;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        self.stream.as_mut().poll_next(cx)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.stream.size_hint()
+    }
+}
+
+/// Claim the first endpoint index at or after `start` (wrapping) that no slot is using.
+fn claim_endpoint(active: &Mutex<HashSet<usize>>, start: usize, len: usize) -> Option<usize> {
+    let mut active = active.lock();
+    let index = (0..len)
+        .map(|offset| (start + offset) % len)
+        .find(|index| !active.contains(index))?;
+    active.insert(index);
+    Some(index)
+}
+
+/// Blocks (number and hash) already emitted by a multi-endpoint stream.
+///
+/// Blocks more than [`MULTI_STREAM_DEDUP_WINDOW`] behind the newest one are
+/// forgotten; a header that old is treated as already seen.
+#[derive(Debug, Default)]
+struct SeenBlocks {
+    blocks: BTreeSet<(u64, B256)>,
+}
+
+impl SeenBlocks {
+    /// Record a block, returning `true` if it has not been seen before.
+    ///
+    /// A different hash at a known number (a reorg) counts as a new block.
+    fn insert(&mut self, number: u64, hash: B256) -> bool {
+        let newest = self.blocks.last().map_or(number, |&(n, _)| n).max(number);
+        let floor = newest.saturating_sub(MULTI_STREAM_DEDUP_WINDOW);
+        if number < floor || !self.blocks.insert((number, hash)) {
+            return false;
+        }
+        self.blocks = self.blocks.split_off(&(floor, B256::ZERO));
+        true
+    }
 }
 
-/// Connect to a WebSocket endpoint with timeout.
+/// Connect to a WebSocket endpoint with the configured timeout, through the SOCKS5 proxy if one is set.
 async fn connect_ws_with_timeout(
     ws_url: &str,
-    timeout: Duration,
+    config: &WsPoolConfig,
 ) -> Result<impl Provider, RpcPoolError> {
-    let connect = WsConnect::new(ws_url.to_string());
+    let timeout = config.connect_timeout;
+    let connect = async {
+        #[cfg(feature = "socks5")]
+        if let Some(proxy) = config.socks5_proxy {
+            let connect = crate::socks::Socks5WsConnect::new(ws_url, proxy);
+            return ProviderBuilder::new().connect_pubsub_with(connect).await;
+        }
+        ProviderBuilder::new()
+            .connect_ws(WsConnect::new(ws_url.to_string()))
+            .await
+    };
 
-    let provider = tokio::time::timeout(timeout, ProviderBuilder::new().connect_ws(connect))
+    let provider = tokio::time::timeout(timeout, connect)
         .await
         .map_err(|_| {
             RpcPoolError::WebSocketError(format!(
@@ -293,6 +999,42 @@ async fn connect_ws_with_timeout(
     Ok(provider)
 }
 
+/// Connect to a WebSocket endpoint with timeout and subscribe to block headers.
+async fn subscribe_blocks_with_timeout(
+    ws_url: &str,
+    config: &WsPoolConfig,
+) -> Result<BoxSubscriptionStream<Header>, RpcPoolError> {
+    let provider = connect_ws_with_timeout(ws_url, config).await?;
+
+    let sub = provider.subscribe_blocks().await.map_err(|e| {
+        RpcPoolError::WebSocketError(format!("Subscribe failed on {}: {}", ws_url, e))
+    })?;
+
+    Ok(Box::pin(sub.into_stream()))
+}
+
+/// Connect to a WebSocket endpoint and subscribe to every filter over that connection.
+///
+/// Fails if any subscription fails. The provider must be kept alive for the
+/// streams to keep receiving logs.
+async fn subscribe_logs_group(
+    ws_url: &str,
+    filters: &[Filter],
+    config: &WsPoolConfig,
+) -> Result<(impl Provider, Vec<BoxSubscriptionStream<Log>>), RpcPoolError> {
+    let provider = connect_ws_with_timeout(ws_url, config).await?;
+
+    let mut streams = Vec::with_capacity(filters.len());
+    for filter in filters {
+        let sub = provider.subscribe_logs(filter).await.map_err(|e| {
+            RpcPoolError::WebSocketError(format!("Subscribe failed on {}: {}", ws_url, e))
+        })?;
+        streams.push(Box::pin(sub.into_stream()) as BoxSubscriptionStream<Log>);
+    }
+
+    Ok((provider, streams))
+}
+
 /// Connect to a WebSocket endpoint and create a block header subscription.
 ///
 /// Standalone helper for creating a single subscription without the pool.
@@ -416,6 +1158,22 @@ mod tests {
         assert_eq!(urls[1], "wss://ws1.example.com");
     }
 
+    #[test]
+    fn test_ws_pool_clone_shares_state() {
+        let pool = WsPool::new(create_ws_endpoints()).unwrap();
+        let clone = pool.clone();
+        assert!(pool.is_same_pool(&clone));
+        assert_eq!(clone.ws_urls(), pool.ws_urls());
+
+        // A pool built from the same endpoints is a different pool
+        let other = WsPool::new(create_ws_endpoints()).unwrap();
+        assert!(!pool.is_same_pool(&other));
+
+        clone.shutdown();
+        assert!(pool.is_shutdown());
+        assert!(!other.is_shutdown());
+    }
+
     #[test]
     fn test_ws_pool_shutdown() {
         let pool = WsPool::new(create_ws_endpoints()).unwrap();
@@ -424,12 +1182,233 @@ mod tests {
         assert!(pool.is_shutdown());
     }
 
+    fn unreachable_ws_endpoints() -> Vec<RpcEndpoint> {
+        vec![
+            RpcEndpoint::new("http://127.0.0.1:1")
+                .with_name("Dead1")
+                .with_ws_url("ws://127.0.0.1:1")
+                .with_priority(10),
+            RpcEndpoint::new("http://127.0.0.1:2")
+                .with_name("Dead2")
+                .with_ws_url("ws://127.0.0.1:2")
+                .with_priority(20),
+        ]
+    }
+
+    // `..Default::default()` fills in fields behind optional features
+    #[allow(clippy::needless_update)]
+    fn fast_reconnect_config() -> WsPoolConfig {
+        WsPoolConfig {
+            connect_timeout: Duration::from_secs(1),
+            reconnect_delay: Duration::from_millis(10),
+            max_reconnect_delay: Duration::from_millis(40),
+            multi_stream_count: 2,
+            max_subscriptions_per_connection: 2,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_resilient_stream_reconnects_through_endpoints() {
+        let pool =
+            Arc::new(WsPool::with_config(unreachable_ws_endpoints(), fast_reconnect_config()).unwrap());
+        let mut stream = pool.clone().subscribe_new_heads_resilient().unwrap();
+
+        let mut targets = Vec::new();
+        for _ in 0..3 {
+            let item = tokio::time::timeout(Duration::from_secs(5), stream.next())
+                .await
+                .expect("reconnect event")
+                .expect("stream open");
+            match item {
+                Err(RpcPoolError::WebSocketReconnecting(name)) => targets.push(name),
+                other => panic!("unexpected item: {:?}", other.map(|h| h.number)),
+            }
+        }
+
+        // First endpoint failed on the initial attempt, so reconnects rotate from the second
+        assert_eq!(targets, vec!["Dead2", "Dead1", "Dead2"]);
+        pool.shutdown();
+    }
+
+    #[tokio::test]
+    async fn test_resilient_stream_ends_on_shutdown() {
+        let config = WsPoolConfig {
+            reconnect_delay: Duration::from_secs(60),
+            max_reconnect_delay: Duration::from_secs(60),
+            ..fast_reconnect_config()
+        };
+        let pool = Arc::new(WsPool::with_config(unreachable_ws_endpoints(), config).unwrap());
+        let mut stream = pool.clone().subscribe_new_heads_resilient().unwrap();
+
+        // Let the task fail its first attempt and park in the backoff sleep
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        pool.shutdown();
+
+        let next = tokio::time::timeout(Duration::from_secs(5), stream.next())
+            .await
+            .expect("stream should end promptly");
+        assert!(next.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_execute_rpc_fails_over_all_endpoints() {
+        let pool =
+            WsPool::with_config(unreachable_ws_endpoints(), fast_reconnect_config()).unwrap();
+        let params = serde_json::json!([]);
+
+        let result = pool.execute_rpc("eth_blockNumber", params.clone()).await;
+        match result {
+            Err(RpcPoolError::AllEndpointsFailed(msg)) => assert!(msg.contains("127.0.0.1:2")),
+            other => panic!("unexpected result: {:?}", other),
+        }
+
+        pool.shutdown();
+        let result = pool.execute_rpc("eth_blockNumber", params).await;
+        assert!(matches!(result, Err(RpcPoolError::PoolShutdown)));
+    }
+
+    #[test]
+    fn test_seen_blocks_deduplicates() {
+        let (a, b) = (B256::repeat_byte(0xaa), B256::repeat_byte(0xbb));
+        let mut seen = SeenBlocks::default();
+        assert!(seen.insert(100, a));
+        assert!(!seen.insert(100, a));
+        assert!(seen.insert(102, a));
+        assert!(seen.insert(101, a));
+        assert!(!seen.insert(101, a));
+
+        // A reorged header at a known height is new
+        assert!(seen.insert(101, b));
+        assert!(!seen.insert(101, b));
+
+        // Far behind the newest block: dropped, and old entries are pruned
+        assert!(seen.insert(100 + MULTI_STREAM_DEDUP_WINDOW + 10, a));
+        assert!(!seen.insert(50, b));
+        assert!(!seen.blocks.contains(&(100, a)));
+    }
+
+    #[test]
+    fn test_claim_endpoint_skips_active() {
+        let active = Mutex::new(HashSet::new());
+        assert_eq!(claim_endpoint(&active, 0, 3), Some(0));
+        assert_eq!(claim_endpoint(&active, 0, 3), Some(1));
+        assert_eq!(claim_endpoint(&active, 2, 3), Some(2));
+        assert_eq!(claim_endpoint(&active, 0, 3), None);
+
+        active.lock().remove(&1);
+        assert_eq!(claim_endpoint(&active, 2, 3), Some(1));
+    }
+
+    #[tokio::test]
+    async fn test_multi_stream_ends_on_shutdown() {
+        let pool =
+            Arc::new(WsPool::with_config(unreachable_ws_endpoints(), fast_reconnect_config()).unwrap());
+        let mut stream = pool.clone().subscribe_new_heads_multi(0).unwrap();
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        pool.shutdown();
+
+        let next = tokio::time::timeout(Duration::from_secs(5), stream.next())
+            .await
+            .expect("stream should end promptly");
+        assert!(next.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_logs_multi_streams_end_on_shutdown() {
+        let pool =
+            Arc::new(WsPool::with_config(unreachable_ws_endpoints(), fast_reconnect_config()).unwrap());
+        assert!(pool.clone().subscribe_logs_multi(vec![]).unwrap().is_empty());
+
+        // Three filters over connections of two subscriptions each
+        let filters = (0..3).map(|i| Filter::new().from_block(i)).collect();
+        let streams = pool.clone().subscribe_logs_multi(filters).unwrap();
+        assert_eq!(streams.len(), 3);
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        pool.shutdown();
+
+        for mut stream in streams {
+            let next = tokio::time::timeout(Duration::from_secs(5), stream.next())
+                .await
+                .expect("stream should end promptly");
+            assert!(next.is_none());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_subscription_limit_enforced() {
+        let config = WsPoolConfig {
+            max_subscriptions: 2,
+            ..fast_reconnect_config()
+        };
+        let pool = Arc::new(WsPool::with_config(unreachable_ws_endpoints(), config).unwrap());
+        let clone = Arc::new(pool.as_ref().clone());
+
+        // A multi-endpoint stream counts once per endpoint it subscribes on
+        let multi = clone.clone().subscribe_new_heads_multi(2).unwrap();
+        assert_eq!(pool.active_subscriptions(), 2);
+        drop(multi);
+        assert_eq!(pool.active_subscriptions(), 0);
+
+        let resilient = pool.clone().subscribe_new_heads_resilient().unwrap();
+        let multi = clone.clone().subscribe_new_heads_multi(1).unwrap();
+        assert_eq!(pool.active_subscriptions(), 2);
+
+        // Clones share the limit, and a failed call reserves nothing
+        let result = clone.clone().subscribe_new_heads_resilient();
+        assert!(matches!(
+            result,
+            Err(RpcPoolError::TooManySubscriptions { current: 2, max: 2 })
+        ));
+        let result = pool.subscribe_logs(&Filter::new()).await;
+        assert!(matches!(result, Err(RpcPoolError::TooManySubscriptions { .. })));
+        assert_eq!(pool.active_subscriptions(), 2);
+
+        drop(resilient);
+        let filters = vec![Filter::new(), Filter::new()];
+        let result = pool.clone().subscribe_logs_multi(filters);
+        assert!(matches!(
+            result,
+            Err(RpcPoolError::TooManySubscriptions { current: 1, max: 2 })
+        ));
+        let streams = pool.clone().subscribe_logs_multi(vec![Filter::new()]).unwrap();
+        assert_eq!(clone.active_subscriptions(), 2);
+
+        drop(multi);
+        drop(streams);
+        assert_eq!(pool.active_subscriptions(), 0);
+        pool.shutdown();
+    }
+
+    #[tokio::test]
+    async fn test_failed_subscribe_releases_slot() {
+        let config = WsPoolConfig {
+            max_subscriptions: 1,
+            ..fast_reconnect_config()
+        };
+        let pool = WsPool::with_config(unreachable_ws_endpoints(), config).unwrap();
+
+        let result = pool.subscribe_new_heads().await;
+        assert!(matches!(result, Err(RpcPoolError::WebSocketError(_))));
+        assert_eq!(pool.active_subscriptions(), 0);
+
+        let result = pool.subscribe_pending_transactions().await;
+        assert!(matches!(result, Err(RpcPoolError::WebSocketError(_))));
+        assert_eq!(pool.active_subscriptions(), 0);
+    }
+
     #[test]
+    #[allow(clippy::needless_update)]
     fn test_ws_pool_config() {
         let config = WsPoolConfig {
             connect_timeout: Duration::from_secs(5),
             reconnect_delay: Duration::from_millis(500),
             max_reconnect_delay: Duration::from_secs(10),
+            multi_stream_count: 3,
+            max_subscriptions_per_connection: 5,
+            ..Default::default()
         };
 
         let pool = WsPool::with_config(create_ws_endpoints(), config).unwrap();
@@ -103,8 +103,10 @@ fn test_serialization_roundtrip() {
         max_batch_size: Some(100),
         max_block_range: Some(10_000),
         supports_debug_trace: Some(false),
+        supports_eth_call_state_override: Some(true),
         supports_websocket: true,
         rate_limit_rps: Some(25),
+        is_starknet: false,
     };
     let endpoint = RpcEndpoint::new("https://rpc.example.com")
         .with_name("Test")
@@ -121,6 +123,11 @@ fn test_serialization_roundtrip() {
     assert!(deserialized.capabilities.supports_websocket);
     assert_eq!(deserialized.capabilities.rate_limit_rps, Some(25));
     assert_eq!(deserialized.capabilities.supports_debug_trace, Some(false));
+    assert_eq!(
+        deserialized.capabilities.supports_eth_call_state_override,
+        Some(true)
+    );
+    assert!(json.contains("\"supports_eth_call_state_override\":true"));
 }
 
 #[test]
@@ -198,6 +205,7 @@ fn test_priority_adjustment_values() {
         ..Default::default()
     };
     assert_eq!(a.priority_adjustment(), -20);
+    assert_eq!(a.weight_adjustment(), 3);
 
     // Grade B: -10
     let b = EndpointCapabilities {
@@ -207,6 +215,7 @@ fn test_priority_adjustment_values() {
         ..Default::default()
     };
     assert_eq!(b.priority_adjustment(), -10);
+    assert_eq!(b.weight_adjustment(), 2);
 
     // Grade C: 0
     let c = EndpointCapabilities {
@@ -216,6 +225,7 @@ fn test_priority_adjustment_values() {
         ..Default::default()
     };
     assert_eq!(c.priority_adjustment(), 0);
+    assert_eq!(c.weight_adjustment(), 1);
 
     // Grade D (tested): +10
     let d = EndpointCapabilities {
@@ -223,6 +233,7 @@ fn test_priority_adjustment_values() {
         ..Default::default()
     };
     assert_eq!(d.priority_adjustment(), 10);
+    assert_eq!(d.weight_adjustment(), 0);
 
     // Grade D (unknown): 0
     let unknown = EndpointCapabilities::default();
@@ -276,3 +287,81 @@ fn test_no_duplicate_urls_per_chain() {
         }
     }
 }
+
+#[test]
+fn test_ranked_endpoints_meet_grade() {
+    let all = presets::default_endpoints(chain_id::ARBITRUM_ONE);
+    let ranked = presets::ranked_endpoints(chain_id::ARBITRUM_ONE, EndpointGrade::B);
+
+    assert!(!ranked.is_empty());
+    assert!(ranked.len() < all.len());
+    assert!(ranked
+        .iter()
+        .all(|e| e.capabilities.grade() >= EndpointGrade::B));
+
+    // The lowest grade keeps everything, in preset order
+    assert_eq!(
+        presets::ranked_endpoints(chain_id::ARBITRUM_ONE, EndpointGrade::F),
+        all
+    );
+}
+
+#[test]
+fn test_grade_distribution() {
+    let distribution = presets::grade_distribution(chain_id::ARBITRUM_ONE);
+    let total: usize = distribution.values().sum();
+    assert_eq!(total, presets::default_endpoints(chain_id::ARBITRUM_ONE).len());
+
+    let at_least_b = distribution
+        .iter()
+        .filter(|(grade, _)| **grade >= EndpointGrade::B)
+        .map(|(_, count)| count)
+        .sum::<usize>();
+    assert_eq!(
+        at_least_b,
+        presets::ranked_endpoints(chain_id::ARBITRUM_ONE, EndpointGrade::B).len()
+    );
+
+    assert!(presets::grade_distribution(0).is_empty());
+}
+
+#[test]
+fn test_ws_and_http_only_endpoints() {
+    let ws = presets::endpoints_with_ws_support(chain_id::ARBITRUM_ONE);
+    let http_only = presets::endpoints_http_only(chain_id::ARBITRUM_ONE);
+
+    assert!(!ws.is_empty());
+    assert!(ws.iter().all(|e| e.ws_url.is_some()));
+    assert!(http_only.iter().all(|e| e.ws_url.is_none()));
+    assert_eq!(
+        ws.len() + http_only.len(),
+        presets::default_endpoints(chain_id::ARBITRUM_ONE).len()
+    );
+    assert!(ws.windows(2).all(|w| w[0].priority <= w[1].priority));
+    assert!(http_only.windows(2).all(|w| w[0].priority <= w[1].priority));
+}
+
+#[test]
+fn test_snapshot_roundtrip() {
+    let original = presets::default_endpoints(chain_id::ARBITRUM_ONE);
+    let json = presets::snapshot_to_json(chain_id::ARBITRUM_ONE);
+    let loaded = presets::load_endpoints_from_json(&json).unwrap();
+
+    assert_eq!(loaded.len(), original.len());
+    for (loaded, original) in loaded.iter().zip(&original) {
+        assert_eq!(loaded.url, original.url);
+        assert_eq!(loaded.priority, original.priority);
+        assert_eq!(loaded.capabilities, original.capabilities);
+    }
+    assert_eq!(loaded, original);
+
+    assert_eq!(presets::snapshot_to_json(0), "[]");
+    assert!(presets::load_endpoints_from_json("{\"url\": 1}").is_err());
+}
+
+#[test]
+fn test_validate_all_presets() {
+    let errors = presets::validate_all();
+    let report: Vec<String> = errors.iter().map(ToString::to_string).collect();
+    assert!(errors.is_empty(), "Invalid presets:\n{}", report.join("\n"));
+}
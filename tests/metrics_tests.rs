@@ -0,0 +1,144 @@
+//! Integration tests for Prometheus metrics export.
+//!
+//! Run with: `cargo test --features metrics --test metrics_tests`
+
+#![cfg(feature = "metrics")]
+
+use std::collections::{HashMap, HashSet};
+use web3_rpc_pool::{RpcEndpoint, RpcPool, RpcPoolConfig, LATENCY_BUCKETS_MS};
+
+/// Parse sample lines into (metric name, labels, value).
+fn parse_samples(text: &str) -> Vec<(String, HashMap<String, String>, f64)> {
+    text.lines()
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            let (series, value) = line.rsplit_once(' ').expect("sample has a value");
+            let value: f64 = value.parse().expect("numeric sample value");
+            let (name, labels) = match series.split_once('{') {
+                Some((name, rest)) => {
+                    let labels = rest
+                        .trim_end_matches('}')
+                        .split(',')
+                        .filter_map(|pair| pair.split_once('='))
+                        .map(|(k, v)| (k.to_string(), v.trim_matches('"').to_string()))
+                        .collect();
+                    (name.to_string(), labels)
+                }
+                None => (series.to_string(), HashMap::new()),
+            };
+            (name, labels, value)
+        })
+        .collect()
+}
+
+#[test]
+fn test_prometheus_text_contains_all_endpoints() {
+    let urls = [
+        "https://rpc1.example.com",
+        "https://rpc2.example.com",
+        "https://rpc3.example.com",
+    ];
+    let pool = RpcPool::new(
+        RpcPoolConfig::new().with_endpoints(
+            urls.iter()
+                .enumerate()
+                .map(|(i, url)| RpcEndpoint::new(*url).with_name(format!("RPC {}", i)))
+                .collect(),
+        ),
+    )
+    .unwrap();
+    pool.mark_unhealthy("https://rpc2.example.com");
+
+    let text = pool.metrics().to_prometheus_text("rpc_pool");
+
+    for name in [
+        "rpc_pool_total_requests_total",
+        "rpc_pool_failovers_total",
+        "rpc_pool_cross_pool_failovers_total",
+        "rpc_pool_latency_ms",
+        "rpc_pool_success_rate",
+        "rpc_pool_is_healthy",
+    ] {
+        assert!(text.contains(&format!("# HELP {} ", name)), "missing HELP for {}", name);
+        assert!(text.contains(&format!("# TYPE {} ", name)), "missing TYPE for {}", name);
+    }
+
+    let samples = parse_samples(&text);
+    for gauge in ["rpc_pool_latency_ms", "rpc_pool_success_rate", "rpc_pool_is_healthy"] {
+        let seen: HashSet<&str> = samples
+            .iter()
+            .filter(|(name, _, _)| name == gauge)
+            .map(|(_, labels, _)| labels["url"].as_str())
+            .collect();
+        assert_eq!(seen, urls.iter().copied().collect(), "endpoints for {}", gauge);
+    }
+
+    let unhealthy = samples
+        .iter()
+        .find(|(name, labels, _)| {
+            name == "rpc_pool_is_healthy" && labels["url"] == "https://rpc2.example.com"
+        })
+        .unwrap();
+    assert_eq!(unhealthy.1["endpoint"], "RPC 1");
+    assert_eq!(unhealthy.2, 0.0);
+}
+
+#[tokio::test]
+async fn test_prometheus_latency_histogram() {
+    let pool = RpcPool::new(RpcPoolConfig::new().with_endpoints(vec![
+        RpcEndpoint::new("https://rpc.example.com").with_name("RPC"),
+    ]))
+    .unwrap();
+
+    for _ in 0..100 {
+        pool.execute_with_url(|_| async { Ok::<_, std::io::Error>(()) })
+            .await
+            .unwrap();
+    }
+
+    let metrics = pool.metrics();
+    let histogram = &metrics.endpoints[0].latency_histogram;
+    assert!(histogram.keys().all(|le| LATENCY_BUCKETS_MS.contains(le)));
+    assert_eq!(histogram.values().sum::<u64>(), 100);
+
+    let text = metrics.to_prometheus_text("rpc_pool");
+    assert!(text.contains("# TYPE rpc_pool_request_latency_ms histogram"));
+
+    let samples = parse_samples(&text);
+    let sample = |name: &str, le: Option<&str>| {
+        samples
+            .iter()
+            .find(|(n, labels, _)| n == name && labels.get("le").map(String::as_str) == le)
+            .map(|(_, labels, value)| {
+                assert_eq!(labels["endpoint"], "RPC");
+                *value
+            })
+            .unwrap_or_else(|| panic!("missing {} le={:?}", name, le))
+    };
+
+    // Buckets are cumulative, ending with +Inf at the total count
+    let mut previous = 0.0;
+    for le in LATENCY_BUCKETS_MS {
+        let count = sample("rpc_pool_request_latency_ms_bucket", Some(&le.to_string()));
+        assert!(count >= previous);
+        previous = count;
+    }
+    assert_eq!(
+        sample("rpc_pool_request_latency_ms_bucket", Some("+Inf")),
+        100.0
+    );
+    assert_eq!(sample("rpc_pool_request_latency_ms_count", None), 100.0);
+    assert!(sample("rpc_pool_request_latency_ms_sum", None) >= 0.0);
+}
+
+#[test]
+fn test_prometheus_text_without_namespace() {
+    let pool = RpcPool::new(
+        RpcPoolConfig::new().with_endpoints(vec![RpcEndpoint::new("https://rpc.example.com")]),
+    )
+    .unwrap();
+
+    let text = pool.metrics().to_prometheus_text("");
+    assert!(text.contains("# TYPE total_requests_total counter"));
+    assert!(text.contains("# TYPE is_healthy gauge"));
+}
@@ -0,0 +1,102 @@
+//! Integration tests for OpenTelemetry span integration.
+//!
+//! Run with: `cargo test --features otel --test otel_tests`
+
+#![cfg(feature = "otel")]
+
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::Subscriber;
+use tracing_subscriber::layer::{Context, SubscriberExt};
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::Layer;
+use web3_rpc_pool::{RpcEndpoint, RpcPool, RpcPoolConfig, RpcPoolError};
+
+type Fields = Arc<Mutex<HashMap<String, String>>>;
+
+/// Captures the latest value of every field recorded on `rpc_pool.execute` spans.
+struct CaptureLayer(Fields);
+
+struct FieldVisitor<'a>(&'a mut HashMap<String, String>);
+
+impl Visit for FieldVisitor<'_> {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.0.insert(
+            field.name().to_string(),
+            format!("{:?}", value).trim_matches('"').to_string(),
+        );
+    }
+}
+
+impl<S: Subscriber + for<'a> LookupSpan<'a>> Layer<S> for CaptureLayer {
+    fn on_new_span(&self, attrs: &Attributes<'_>, _id: &Id, _ctx: Context<'_, S>) {
+        if attrs.metadata().name() == "rpc_pool.execute" {
+            attrs.record(&mut FieldVisitor(&mut self.0.lock()));
+        }
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        if ctx.span(id).is_some_and(|s| s.name() == "rpc_pool.execute") {
+            values.record(&mut FieldVisitor(&mut self.0.lock()));
+        }
+    }
+}
+
+fn capture() -> (Fields, tracing::subscriber::DefaultGuard) {
+    let fields = Fields::default();
+    let subscriber = tracing_subscriber::registry()
+        .with(tracing_opentelemetry::layer())
+        .with(CaptureLayer(fields.clone()));
+    (fields, tracing::subscriber::set_default(subscriber))
+}
+
+fn create_pool() -> RpcPool {
+    RpcPool::new(RpcPoolConfig::new().with_endpoints(vec![
+        RpcEndpoint::new("https://rpc1.example.com").with_priority(10),
+        RpcEndpoint::new("https://rpc2.example.com").with_priority(20),
+    ]))
+    .unwrap()
+}
+
+#[tokio::test]
+async fn test_traced_execute_records_attempt_attributes() {
+    let (fields, _guard) = capture();
+    let pool = create_pool();
+
+    let result = pool
+        .execute_with_url_traced(&opentelemetry::Context::new(), |url: String| async move {
+            if url.contains("rpc1") {
+                Err(std::io::Error::other("down"))
+            } else {
+                Ok(url)
+            }
+        })
+        .await;
+
+    assert_eq!(result.unwrap(), "https://rpc2.example.com");
+    let fields = fields.lock();
+    assert_eq!(fields["rpc.strategy"], "failover");
+    assert_eq!(fields["rpc.endpoint"], "https://rpc2.example.com");
+    assert_eq!(fields["rpc.attempt"], "2");
+    assert!(!fields.contains_key("otel.status_code"));
+}
+
+#[tokio::test]
+async fn test_traced_execute_sets_error_status() {
+    let (fields, _guard) = capture();
+    let pool = create_pool();
+
+    let result = pool
+        .execute_with_url_traced(&opentelemetry::Context::new(), |_url: String| async move {
+            Err::<(), _>(std::io::Error::other("down"))
+        })
+        .await;
+
+    assert!(matches!(result, Err(RpcPoolError::AllEndpointsFailed(_))));
+    let fields = fields.lock();
+    assert_eq!(fields["otel.status_code"], "ERROR");
+    assert!(fields["otel.status_message"].contains("down"));
+}
@@ -13,7 +13,7 @@ use std::time::{Duration, Instant};
 use web3_rpc_pool::endpoint::{EndpointStats, RpcEndpoint};
 use web3_rpc_pool::presets::chain_id;
 use web3_rpc_pool::strategies::{
-    FailoverStrategy, LatencyBasedStrategy, RoundRobinStrategy, SelectionStrategy,
+    FailoverStrategy, LatencyBasedStrategy, RoundRobinStrategy, SelectionContext, SelectionStrategy,
 };
 use web3_rpc_pool::{RpcPool, RpcPoolConfig};
 
@@ -35,11 +35,7 @@ impl PerfResult {
         let total_ns: u64 = durations_ns.iter().sum();
         let min_ns = *durations_ns.iter().min().unwrap_or(&0);
         let max_ns = *durations_ns.iter().max().unwrap_or(&0);
-        let avg_ns = if iterations > 0 {
-            total_ns / iterations
-        } else {
-            0
-        };
+        let avg_ns = total_ns.checked_div(iterations).unwrap_or(0);
         let throughput = if total_ns > 0 {
             (iterations as f64 * 1_000_000_000.0) / total_ns as f64
         } else {
@@ -75,8 +71,8 @@ impl PerfResult {
 fn create_test_endpoints(count: usize) -> Vec<RpcEndpoint> {
     (0..count)
         .map(|i| {
-            RpcEndpoint::new(&format!("https://rpc{}.example.com", i))
-                .with_name(&format!("RPC {}", i))
+            RpcEndpoint::new(format!("https://rpc{}.example.com", i))
+                .with_name(format!("RPC {}", i))
                 .with_priority((i * 10) as u32)
                 .with_chain_id(chain_id::ETHEREUM)
         })
@@ -110,7 +106,7 @@ fn test_strategy_selection_performance() {
     let mut strategy = FailoverStrategy;
     for _ in 0..ITERATIONS {
         let start = Instant::now();
-        let _ = strategy.select(&endpoints, &stats, &tried);
+        let _ = strategy.select(&endpoints, &stats, &tried, &SelectionContext::default());
         durations.push(start.elapsed().as_nanos() as u64);
     }
     let result = PerfResult::new("Failover Strategy Selection", durations);
@@ -126,7 +122,7 @@ fn test_strategy_selection_performance() {
     let mut strategy = RoundRobinStrategy::new();
     for _ in 0..ITERATIONS {
         let start = Instant::now();
-        let _ = strategy.select(&endpoints, &stats, &tried);
+        let _ = strategy.select(&endpoints, &stats, &tried, &SelectionContext::default());
         durations.push(start.elapsed().as_nanos() as u64);
     }
     let result = PerfResult::new("Round Robin Strategy Selection", durations);
@@ -139,10 +135,10 @@ fn test_strategy_selection_performance() {
 
     // Test Latency Based Strategy
     let mut durations = Vec::with_capacity(ITERATIONS);
-    let mut strategy = LatencyBasedStrategy;
+    let mut strategy = LatencyBasedStrategy::new();
     for _ in 0..ITERATIONS {
         let start = Instant::now();
-        let _ = strategy.select(&endpoints, &stats, &tried);
+        let _ = strategy.select(&endpoints, &stats, &tried, &SelectionContext::default());
         durations.push(start.elapsed().as_nanos() as u64);
     }
     let result = PerfResult::new("Latency Based Strategy Selection", durations);
@@ -0,0 +1,137 @@
+//! Integration tests for SOCKS5 proxying of WebSocket and HTTP traffic.
+//!
+//! Run with: `cargo test --features socks5 --test socks_tests`
+
+#![cfg(feature = "socks5")]
+
+use futures_util::{SinkExt, StreamExt};
+use parking_lot::Mutex;
+use serde_json::{json, Value};
+use std::net::{Ipv4Addr, SocketAddr};
+use std::sync::Arc;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio_tungstenite::tungstenite::Message;
+use web3_rpc_pool::ws::{WsPool, WsPoolConfig};
+use web3_rpc_pool::{RpcEndpoint, RpcPool, RpcPoolConfig};
+
+/// Targets the mock proxy was asked to connect to, as `host:port`.
+type Targets = Arc<Mutex<Vec<String>>>;
+
+/// Start a minimal no-auth SOCKS5 proxy that records each CONNECT target.
+async fn socks5_proxy() -> (SocketAddr, Targets) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let targets = Targets::default();
+
+    let recorded = Arc::clone(&targets);
+    tokio::spawn(async move {
+        while let Ok((mut client, _)) = listener.accept().await {
+            let recorded = Arc::clone(&recorded);
+            tokio::spawn(async move {
+                // Greeting: version, method count, methods; accept "no auth"
+                let mut greeting = [0u8; 2];
+                client.read_exact(&mut greeting).await.unwrap();
+                let mut methods = vec![0u8; greeting[1] as usize];
+                client.read_exact(&mut methods).await.unwrap();
+                client.write_all(&[5, 0]).await.unwrap();
+
+                // Request: version, CONNECT, reserved, address type, address, port
+                let mut request = [0u8; 4];
+                client.read_exact(&mut request).await.unwrap();
+                let host = match request[3] {
+                    1 => {
+                        let mut ip = [0u8; 4];
+                        client.read_exact(&mut ip).await.unwrap();
+                        Ipv4Addr::from(ip).to_string()
+                    }
+                    3 => {
+                        let len = client.read_u8().await.unwrap();
+                        let mut name = vec![0u8; len as usize];
+                        client.read_exact(&mut name).await.unwrap();
+                        String::from_utf8(name).unwrap()
+                    }
+                    other => panic!("unsupported address type {}", other),
+                };
+                let port = client.read_u16().await.unwrap();
+                let target = format!("{}:{}", host, port);
+                recorded.lock().push(target.clone());
+
+                let mut upstream = TcpStream::connect(&target).await.unwrap();
+                client
+                    .write_all(&[5, 0, 0, 1, 0, 0, 0, 0, 0, 0])
+                    .await
+                    .unwrap();
+                let _ = tokio::io::copy_bidirectional(&mut client, &mut upstream).await;
+            });
+        }
+    });
+
+    (addr, targets)
+}
+
+/// Start a WebSocket node that answers every request with subscription id `0x1`.
+async fn ws_node() -> SocketAddr {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    tokio::spawn(async move {
+        while let Ok((stream, _)) = listener.accept().await {
+            tokio::spawn(async move {
+                let mut socket = tokio_tungstenite::accept_async(stream).await.unwrap();
+                while let Some(Ok(Message::Text(text))) = socket.next().await {
+                    let request: Value = serde_json::from_str(&text).unwrap();
+                    let response = json!({"jsonrpc": "2.0", "id": request["id"], "result": "0x1"});
+                    socket
+                        .send(Message::Text(response.to_string().into()))
+                        .await
+                        .unwrap();
+                }
+            });
+        }
+    });
+
+    addr
+}
+
+#[tokio::test]
+async fn test_ws_subscription_goes_through_proxy() {
+    let (proxy, targets) = socks5_proxy().await;
+    let node = ws_node().await;
+
+    // A hostname, which the proxy rather than the client must resolve
+    let ws_url = format!("ws://localhost:{}", node.port());
+    let pool = WsPool::with_config(
+        vec![RpcEndpoint::new("https://unused.example.com").with_ws_url(ws_url)],
+        WsPoolConfig::default().with_socks5_proxy(proxy),
+    )
+    .unwrap();
+
+    // The subscription id only comes back if the tunnel relays both ways
+    assert!(pool.subscribe_pending_transactions().await.is_ok());
+    assert_eq!(*targets.lock(), vec![format!("localhost:{}", node.port())]);
+}
+
+#[tokio::test]
+async fn test_http_health_probe_goes_through_proxy() {
+    let (proxy, targets) = socks5_proxy().await;
+    let server = wiremock::MockServer::start().await;
+    wiremock::Mock::given(wiremock::matchers::method("POST"))
+        .respond_with(
+            wiremock::ResponseTemplate::new(200)
+                .set_body_json(json!({"jsonrpc": "2.0", "id": 0, "result": "0x10"})),
+        )
+        .mount(&server)
+        .await;
+
+    let pool = RpcPool::new(
+        RpcPoolConfig::new()
+            .with_endpoints(vec![RpcEndpoint::new(server.uri())])
+            .with_socks5_proxy(proxy),
+    )
+    .unwrap();
+
+    let report = pool.warm_up(1).await.unwrap();
+    assert_eq!(report.healthy, 1);
+    assert_eq!(*targets.lock(), vec![server.address().to_string()]);
+}
@@ -0,0 +1,152 @@
+//! Integration tests for the tracing spans of `TieredPool::execute`.
+
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::Subscriber;
+use tracing_subscriber::layer::{Context, SubscriberExt};
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::Layer;
+use web3_rpc_pool::{EndpointTier, RequestPriority, TieredPoolBuilder};
+
+/// Recorded spans in creation order: span name and latest field values.
+type Spans = Arc<Mutex<Vec<(String, HashMap<String, String>)>>>;
+
+/// Captures the fields of every span, keyed by creation order.
+struct CaptureLayer(Spans);
+
+struct FieldVisitor<'a>(&'a mut HashMap<String, String>);
+
+impl Visit for FieldVisitor<'_> {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.0.insert(
+            field.name().to_string(),
+            format!("{:?}", value).trim_matches('"').to_string(),
+        );
+    }
+}
+
+/// Index of a span in the captured list, stored in the span's extensions.
+struct SpanIndex(usize);
+
+impl<S: Subscriber + for<'a> LookupSpan<'a>> Layer<S> for CaptureLayer {
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let mut fields = HashMap::new();
+        attrs.record(&mut FieldVisitor(&mut fields));
+        let mut spans = self.0.lock();
+        spans.push((attrs.metadata().name().to_string(), fields));
+        if let Some(span) = ctx.span(id) {
+            span.extensions_mut().insert(SpanIndex(spans.len() - 1));
+        }
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let index = span.extensions().get::<SpanIndex>().map(|index| index.0);
+        if let Some(index) = index {
+            values.record(&mut FieldVisitor(&mut self.0.lock()[index].1));
+        }
+    }
+}
+
+fn capture() -> (Spans, tracing::subscriber::DefaultGuard) {
+    let spans = Spans::default();
+    let subscriber = tracing_subscriber::registry().with(CaptureLayer(spans.clone()));
+    (spans, tracing::subscriber::set_default(subscriber))
+}
+
+fn spans_named(spans: &Spans, name: &str) -> Vec<HashMap<String, String>> {
+    spans
+        .lock()
+        .iter()
+        .filter(|(span_name, _)| span_name == name)
+        .map(|(_, fields)| fields.clone())
+        .collect()
+}
+
+async fn call(url: url::Url) -> Result<String, std::io::Error> {
+    if url.as_str().contains("down") {
+        return Err(std::io::Error::other("boom"));
+    }
+    Ok(url.to_string())
+}
+
+#[tokio::test]
+async fn test_execute_records_tier_attempt_spans() {
+    let (spans, _guard) = capture();
+    let pool = TieredPoolBuilder::new()
+        .add_premium("https://premium-down.example.com", "PremiumDown")
+        .add_standard("https://standard.example.com", "Standard")
+        .build()
+        .unwrap();
+
+    pool.execute(RequestPriority::Critical, call).await.unwrap();
+
+    let execute = spans_named(&spans, "tiered_pool.execute");
+    assert_eq!(execute.len(), 1);
+    assert_eq!(execute[0]["priority"], "Critical");
+    assert_eq!(execute[0]["tier_used"], "Standard");
+    assert!(!execute[0].contains_key("error.type"));
+
+    let attempts = spans_named(&spans, "tiered_pool.tier_attempt");
+    assert_eq!(attempts.len(), 2);
+    assert_eq!(attempts[0]["tier"], "Premium");
+    assert_eq!(attempts[0]["attempt_number"], "1");
+    assert_eq!(attempts[0]["endpoint_name"], "PremiumDown");
+    assert_eq!(attempts[1]["tier"], "Standard");
+    assert_eq!(attempts[1]["attempt_number"], "2");
+    assert_eq!(attempts[1]["endpoint_name"], "Standard");
+}
+
+#[tokio::test]
+async fn test_execute_records_all_tiers_failed() {
+    let (spans, _guard) = capture();
+    let pool = TieredPoolBuilder::new()
+        .add_premium("https://premium-down.example.com", "PremiumDown")
+        .add_standard("https://standard-down.example.com", "StandardDown")
+        .build()
+        .unwrap();
+
+    assert!(pool.execute(RequestPriority::Critical, call).await.is_err());
+
+    let execute = spans_named(&spans, "tiered_pool.execute");
+    assert_eq!(execute[0]["error.type"], "AllTiersFailed");
+    assert!(!execute[0].contains_key("tier_used"));
+    assert_eq!(spans_named(&spans, "tiered_pool.tier_attempt").len(), 2);
+}
+
+#[tokio::test]
+async fn test_execute_records_canary_failed() {
+    let (spans, _guard) = capture();
+    let pool = TieredPoolBuilder::new()
+        .add_standard("https://standard.example.com", "Standard")
+        .add_free("https://free-down.example.com", "FreeDown")
+        .with_canary_tier(EndpointTier::Free, 1.0)
+        .build()
+        .unwrap();
+
+    assert!(pool.execute(RequestPriority::Normal, call).await.is_err());
+
+    let execute = spans_named(&spans, "tiered_pool.execute");
+    assert_eq!(execute[0]["error.type"], "CanaryFailed");
+    assert_eq!(spans_named(&spans, "tiered_pool.tier_attempt").len(), 1);
+}
+
+#[tokio::test]
+async fn test_tracing_disabled_creates_no_spans() {
+    let (spans, _guard) = capture();
+    let pool = TieredPoolBuilder::new()
+        .add_premium("https://premium.example.com", "Premium")
+        .build()
+        .unwrap()
+        .with_tracing_enabled(false);
+
+    pool.execute(RequestPriority::Critical, call).await.unwrap();
+
+    assert!(spans_named(&spans, "tiered_pool.execute").is_empty());
+    assert!(spans_named(&spans, "tiered_pool.tier_attempt").is_empty());
+}
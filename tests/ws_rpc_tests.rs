@@ -0,0 +1,214 @@
+//! Integration tests for JSON-RPC requests over `WsPool` connections,
+//! including the `FullNodePool` health check that shares them, and for log
+//! subscriptions multiplexed over one connection.
+
+use alloy::rpc::types::Filter;
+use futures_util::{SinkExt, StreamExt};
+use serde_json::{json, Value};
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tokio_tungstenite::tungstenite::Message;
+use web3_rpc_pool::ws::WsPool;
+use web3_rpc_pool::{FullNodePoolBuilder, RpcEndpoint, RpcPoolConfig, RpcPoolError};
+
+/// Start a WebSocket node answering `eth_blockNumber` with `0x10` and anything
+/// else with a JSON-RPC error. Returns its address and a connection counter.
+async fn ws_node() -> (SocketAddr, Arc<AtomicUsize>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let connections = Arc::new(AtomicUsize::new(0));
+
+    let accepted = Arc::clone(&connections);
+    tokio::spawn(async move {
+        while let Ok((stream, _)) = listener.accept().await {
+            accepted.fetch_add(1, Ordering::SeqCst);
+            tokio::spawn(async move {
+                let mut socket = tokio_tungstenite::accept_async(stream).await.unwrap();
+                while let Some(Ok(Message::Text(text))) = socket.next().await {
+                    let request: Value = serde_json::from_str(&text).unwrap();
+                    let response = match request["method"].as_str() {
+                        Some("eth_blockNumber") => {
+                            json!({"jsonrpc": "2.0", "id": request["id"], "result": "0x10"})
+                        }
+                        _ => json!({
+                            "jsonrpc": "2.0",
+                            "id": request["id"],
+                            "error": {"code": -32601, "message": "method not found"}
+                        }),
+                    };
+                    socket
+                        .send(Message::Text(response.to_string().into()))
+                        .await
+                        .unwrap();
+                }
+            });
+        }
+    });
+
+    (addr, connections)
+}
+
+/// Start a WebSocket node that answers two `eth_subscribe` calls and then
+/// pushes `logs` notifications to each subscription in turn.
+async fn log_node(logs: u64) -> SocketAddr {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    tokio::spawn(async move {
+        while let Ok((stream, _)) = listener.accept().await {
+            tokio::spawn(async move {
+                let mut socket = tokio_tungstenite::accept_async(stream).await.unwrap();
+                let mut subscriptions = Vec::new();
+                while subscriptions.len() < 2 {
+                    let Some(Ok(Message::Text(text))) = socket.next().await else {
+                        return;
+                    };
+                    let request: Value = serde_json::from_str(&text).unwrap();
+                    let id = format!("0x{:x}", subscriptions.len() + 1);
+                    let response = json!({"jsonrpc": "2.0", "id": request["id"], "result": id});
+                    socket
+                        .send(Message::Text(response.to_string().into()))
+                        .await
+                        .unwrap();
+                    subscriptions.push(id);
+                }
+
+                for i in 0..logs {
+                    for subscription in &subscriptions {
+                        let log = json!({
+                            "address": "0x0000000000000000000000000000000000000001",
+                            "topics": [],
+                            "data": "0x",
+                            "blockHash": format!("0x{:064x}", i + 1),
+                            "blockNumber": format!("0x{:x}", i + 1),
+                            "transactionHash": format!("0x{:064x}", i + 1),
+                            "transactionIndex": "0x0",
+                            "logIndex": "0x0",
+                            "removed": false,
+                        });
+                        let notification = json!({
+                            "jsonrpc": "2.0",
+                            "method": "eth_subscription",
+                            "params": {"subscription": subscription, "result": log},
+                        });
+                        socket
+                            .send(Message::Text(notification.to_string().into()))
+                            .await
+                            .unwrap();
+                    }
+                    tokio::time::sleep(Duration::from_millis(1)).await;
+                }
+                // Keep the connection open until the client goes away
+                while let Some(Ok(_)) = socket.next().await {}
+            });
+        }
+    });
+
+    addr
+}
+
+fn endpoints(live: SocketAddr) -> Vec<RpcEndpoint> {
+    vec![
+        RpcEndpoint::new("http://127.0.0.1:1")
+            .with_name("Dead")
+            .with_ws_url("ws://127.0.0.1:1")
+            .with_priority(1),
+        RpcEndpoint::new(format!("http://{}", live))
+            .with_name("Live")
+            .with_ws_url(format!("ws://{}", live))
+            .with_priority(2),
+    ]
+}
+
+#[tokio::test]
+async fn test_execute_rpc_fails_over_and_reuses_connection() {
+    let (node, connections) = ws_node().await;
+    let pool = WsPool::new(endpoints(node)).unwrap();
+
+    for _ in 0..3 {
+        let result = pool
+            .execute_rpc("eth_blockNumber", json!([]))
+            .await
+            .unwrap();
+        assert_eq!(result, json!("0x10"));
+    }
+    assert_eq!(connections.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn test_execute_rpc_error_response_keeps_connection() {
+    let (node, connections) = ws_node().await;
+    let pool = WsPool::new(endpoints(node)).unwrap();
+
+    let result = pool.execute_rpc("eth_unknown", json!([])).await;
+    match result {
+        Err(RpcPoolError::AllEndpointsFailed(msg)) => assert!(msg.contains("method not found")),
+        other => panic!("unexpected result: {:?}", other),
+    }
+
+    // The node answered, so its connection is reused
+    pool.execute_rpc("eth_blockNumber", json!([]))
+        .await
+        .unwrap();
+    assert_eq!(connections.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn test_full_node_health_check_probes_websocket_endpoints() {
+    let (node, connections) = ws_node().await;
+    let pool = FullNodePoolBuilder::new(endpoints(node))
+        .with_config(RpcPoolConfig::new().with_health_check_interval(Duration::from_millis(50)))
+        .build()
+        .unwrap();
+    assert_eq!(pool.ws_healthy_endpoints(), 0);
+
+    let task = pool.start_health_check();
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    assert_eq!(pool.ws_healthy_endpoints(), 1);
+
+    // The health check opened the connection once and keeps reusing it
+    let result = pool
+        .ws_pool()
+        .execute_rpc("eth_blockNumber", json!([]))
+        .await
+        .unwrap();
+    assert_eq!(result, json!("0x10"));
+    assert_eq!(connections.load(Ordering::SeqCst), 1);
+
+    pool.shutdown().await;
+    assert!(pool.ws_pool().is_shutdown());
+    tokio::time::timeout(Duration::from_secs(1), task)
+        .await
+        .unwrap()
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_logs_multi_slow_stream_does_not_block_others() {
+    let node = log_node(100).await;
+    let endpoint =
+        RpcEndpoint::new(format!("http://{}", node)).with_ws_url(format!("ws://{}", node));
+    let pool = Arc::new(WsPool::new(vec![endpoint]).unwrap());
+
+    // Both filters share one connection; the first stream is never polled
+    let filters = vec![Filter::new(), Filter::new()];
+    let mut streams = pool.clone().subscribe_logs_multi(filters).unwrap();
+    let mut fast = streams.pop().unwrap();
+
+    // The fast stream keeps receiving after the slow one's buffer fills up
+    let last = async {
+        while let Some(log) = fast.next().await {
+            if log.block_number == Some(100) {
+                return;
+            }
+        }
+        panic!("stream ended");
+    };
+    tokio::time::timeout(Duration::from_secs(5), last)
+        .await
+        .expect("a full buffer on another stream must not stall delivery");
+    pool.shutdown();
+}